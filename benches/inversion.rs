@@ -0,0 +1,70 @@
+//! Timings of the `gf2` inversion strategies behind the table in `gf2::inversion`.
+//!
+//! Run with `cargo bench --bench inversion`. Builds with the `std` feature detect the carry-less multiply
+//! instruction at runtime, so the multiplications of `ITOH_TSUJII` and `FERMAT` use it where the CPU has one.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use galois_field_2pm::gf2::{self, inversion};
+use galois_field_2pm::GaloisField;
+
+// Inverts a fixed set of nonzero elements, so data dependent loop counts average out
+fn bench<GF: GaloisField>(c: &mut Criterion, name: &str, m: u128) {
+    let elements: Vec<GF> = (1..=64u128)
+        .map(|i| GF::from_u128((i * 0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835) % GF::NUM_ELEM))
+        .filter(|&e| e != GF::ZERO)
+        .collect();
+    let mut group = c.benchmark_group(format!("inverse/M={}", m));
+    group.bench_function(BenchmarkId::from_parameter(name), |b| {
+        b.iter(|| {
+            for &e in elements.iter() {
+                black_box(black_box(e).inverse());
+            }
+        })
+    });
+    group.finish();
+}
+
+macro_rules! strategies {
+    ($c:ident, $type:ident, $poly:expr, [$($name:literal: $strategy:ident,)*]) => {
+        $(
+            bench::<gf2::$type<$poly, { inversion::$strategy }>>(
+                $c,
+                $name,
+                gf2::$type::<$poly>::M,
+            );
+        )*
+    };
+}
+
+fn inversion(c: &mut Criterion) {
+    strategies!(c, GFu8, 0x11B, [
+        "euclid": EUCLID,
+        "itoh_tsujii": ITOH_TSUJII,
+        "fermat": FERMAT,
+        "lut": LUT,
+    ]);
+    strategies!(c, GFu16, 0x1_002D, [
+        "euclid": EUCLID,
+        "itoh_tsujii": ITOH_TSUJII,
+        "fermat": FERMAT,
+        "lut": LUT,
+    ]);
+    strategies!(c, GFu32, 0x1_0000_008D, [
+        "euclid": EUCLID,
+        "itoh_tsujii": ITOH_TSUJII,
+        "fermat": FERMAT,
+    ]);
+    strategies!(c, GFu64, 0x1_0000_0000_0000_001B, [
+        "euclid": EUCLID,
+        "itoh_tsujii": ITOH_TSUJII,
+        "fermat": FERMAT,
+    ]);
+    strategies!(c, GFu128, 0x8000_0000_0000_0000_0000_0000_0000_0003, [
+        "euclid": EUCLID,
+        "itoh_tsujii": ITOH_TSUJII,
+        "fermat": FERMAT,
+    ]);
+}
+
+criterion_group!(benches, inversion);
+criterion_main!(benches);
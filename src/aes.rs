@@ -0,0 +1,163 @@
+//! The GF(2<sup>8</sup>) arithmetic of AES.
+//!
+//! AES computes in GF(2)\[x\] / (x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1). The S-box is the field
+//! inverse followed by an affine transform over GF(2), and MixColumns multiplies each column of the state by the
+//! fixed polynomial 03 x<sup>3</sup> + 01 x<sup>2</sup> + 01 x + 02 modulo x<sup>4</sup> + 1.
+//!
+//! ```
+//! use galois_field_2pm::aes;
+//!
+//! assert_eq!(aes::sbox(0x53), 0xED);
+//! assert_eq!(aes::inv_sbox(0xED), 0x53);
+//! assert_eq!(aes::mix_column([0xDB, 0x13, 0x53, 0x45]), [0x8E, 0x4D, 0xA1, 0xBC]);
+//! ```
+
+use crate::bitmatrix::BitMatrix;
+use crate::{gf2, GaloisField};
+
+/// GF(2<sup>8</sup>) with the AES polynomial x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1
+pub type AesField = gf2::GFu8<0x11B>;
+
+/// The constant c = 0x63 added by the S-box affine transform
+pub const AFFINE_CONSTANT: u8 = 0x63;
+
+/// The MixColumns coefficients 02, 03, 01, 01
+pub const MIX_COLUMNS: [u8; 4] = [0x02, 0x03, 0x01, 0x01];
+
+/// The InvMixColumns coefficients 0E, 0B, 0D, 09
+pub const INV_MIX_COLUMNS: [u8; 4] = [0x0E, 0x0B, 0x0D, 0x09];
+
+/// Multiplies a byte by x in the AES field
+pub fn xtime(b: u8) -> u8 {
+    (b << 1) ^ if b & 0x80 != 0 { 0x1B } else { 0 }
+}
+
+/// The 8 × 8 GF(2) matrix A of the S-box affine transform b ↦ A b + c. Bit i of a byte is entry i of the vector,
+/// and row i sums bits i, i + 4, i + 5, i + 6 and i + 7 modulo 8
+pub fn affine_matrix() -> BitMatrix {
+    let mut matrix = BitMatrix::new(8, 8);
+    for i in 0..8 {
+        for j in [0, 4, 5, 6, 7] {
+            matrix.set(i, (i + j) % 8, true);
+        }
+    }
+    matrix
+}
+
+/// The S-box affine transform A b + c
+pub fn affine(b: u8) -> u8 {
+    b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ AFFINE_CONSTANT
+}
+
+/// The inverse affine transform A<sup>-1</sup> (b + c)
+pub fn inv_affine(b: u8) -> u8 {
+    b.rotate_left(1) ^ b.rotate_left(3) ^ b.rotate_left(6) ^ 0x05
+}
+
+/// The AES S-box: the field inverse, with 0 mapped to 0, followed by the affine transform
+pub fn sbox(b: u8) -> u8 {
+    let inv = if b == 0 {
+        0
+    } else {
+        AesField::new(b).inverse().value
+    };
+    affine(inv)
+}
+
+/// The inverse AES S-box
+pub fn inv_sbox(b: u8) -> u8 {
+    match inv_affine(b) {
+        0 => 0,
+        a => AesField::new(a).inverse().value,
+    }
+}
+
+/// Multiplies a column by the circulant matrix of coeffs, as MixColumns does with `MIX_COLUMNS`. Output byte i is
+/// the sum over j of coeffs\[(j - i) mod 4\] * column\[j\]
+pub fn mul_column(coeffs: [u8; 4], column: [u8; 4]) -> [u8; 4] {
+    let mut out = [0u8; 4];
+    for (i, o) in out.iter_mut().enumerate() {
+        *o = (0..4)
+            .map(|j| AesField::new(coeffs[(j + 4 - i) % 4]) * AesField::new(column[j]))
+            .fold(AesField::ZERO, |acc, p| acc + p)
+            .value;
+    }
+    out
+}
+
+/// MixColumns of one column
+pub fn mix_column(column: [u8; 4]) -> [u8; 4] {
+    mul_column(MIX_COLUMNS, column)
+}
+
+/// InvMixColumns of one column
+pub fn inv_mix_column(column: [u8; 4]) -> [u8; 4] {
+    mul_column(INV_MIX_COLUMNS, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_bits(b: u8) -> Vec<bool> {
+        (0..8).map(|i| (b >> i) & 1 == 1).collect()
+    }
+
+    fn from_bits(bits: &[bool]) -> u8 {
+        bits.iter()
+            .enumerate()
+            .fold(0, |acc, (i, &bit)| acc | ((bit as u8) << i))
+    }
+
+    #[test]
+    fn xtime_is_mul_x() {
+        for b in 0..=255u8 {
+            assert_eq!(xtime(b), AesField::new(b).mul_x().value);
+        }
+        assert_eq!(xtime(0x57), 0xAE);
+        assert_eq!(xtime(0xAE), 0x47);
+    }
+
+    #[test]
+    fn affine_transform() {
+        let a = affine_matrix();
+        let inv = a.inverse().unwrap();
+        for b in 0..=255u8 {
+            assert_eq!(
+                affine(b),
+                from_bits(&a.mul_vec(&to_bits(b))) ^ AFFINE_CONSTANT
+            );
+            assert_eq!(
+                inv_affine(b),
+                from_bits(&inv.mul_vec(&to_bits(b ^ AFFINE_CONSTANT)))
+            );
+            assert_eq!(inv_affine(affine(b)), b);
+        }
+    }
+
+    #[test]
+    fn sbox_table() {
+        assert_eq!(sbox(0x00), 0x63);
+        assert_eq!(sbox(0x01), 0x7C);
+        assert_eq!(sbox(0x53), 0xED);
+        assert_eq!(sbox(0xFF), 0x16);
+        for b in 0..=255u8 {
+            assert_eq!(inv_sbox(sbox(b)), b);
+        }
+    }
+
+    #[test]
+    fn mix_columns() {
+        // Test columns from the FIPS-197 literature
+        let columns = [
+            ([0xDB, 0x13, 0x53, 0x45], [0x8E, 0x4D, 0xA1, 0xBC]),
+            ([0xF2, 0x0A, 0x22, 0x5C], [0x9F, 0xDC, 0x58, 0x9D]),
+            ([0x01, 0x01, 0x01, 0x01], [0x01, 0x01, 0x01, 0x01]),
+            ([0xD4, 0xD4, 0xD4, 0xD5], [0xD5, 0xD5, 0xD7, 0xD6]),
+        ];
+        for (column, mixed) in columns {
+            assert_eq!(mix_column(column), mixed);
+            assert_eq!(inv_mix_column(mixed), column);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Alternative bases of GF(2<sup>M</sup>) over GF(2).
+//!
+//! Elements are normally stored in the polynomial basis 1, x, ..., x<sup>M-1</sup>. Any M linearly independent
+//! elements form a `Basis` with its own coordinates. A normal basis
+//! β, β<sup>2</sup>, β<sup>4</sup>, ..., β<sup>2<sup>M-1</sup></sup> makes squaring a cyclic rotation of the coordinates.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::basis::NormalBasis;
+//!
+//! type GF = gf2::GFu8<0x11b>;
+//! let nb = NormalBasis::<GF>::new();
+//! let a = GF::new(0x53);
+//!
+//! let mut coords = nb.to_normal_basis(a);
+//! coords.rotate_right(1);
+//! assert_eq!(nb.from_normal_basis(&coords), a.square());
+//! ```
+
+use crate::bitmatrix::BitMatrix;
+use crate::GaloisField;
+
+/// Returns the trace dual basis γ<sub>0</sub>, ..., γ<sub>M-1</sub> of basis, the unique basis with
+/// Tr(β<sub>i</sub> γ<sub>j</sub>) = 1 if i = j and 0 otherwise. Coordinates in the dual basis are then
+/// a<sub>j</sub> = Tr(a β<sub>j</sub>), which is how the Berlekamp (dual basis) representation of CCSDS Reed-Solomon is defined.
+/// Panics unless basis has M linearly independent elements
+pub fn dual_basis<GF: GaloisField>(basis: &[GF]) -> Vec<GF> {
+    let m = GF::M as usize;
+    if basis.len() != m {
+        panic!("A basis must have M elements");
+    }
+
+    // With T[i][k] = Tr(β_i β_k), γ_j = Σ_k T^-1[k][j] β_k
+    let mut gram = BitMatrix::new(m, m);
+    for (i, &bi) in basis.iter().enumerate() {
+        for (k, &bk) in basis.iter().enumerate() {
+            gram.set(i, k, (bi * bk).trace());
+        }
+    }
+    let gram_inv = gram.inverse().expect("Elements do not form a basis");
+
+    (0..m)
+        .map(|j| {
+            basis
+                .iter()
+                .enumerate()
+                .filter(|&(k, _)| gram_inv.get(k, j))
+                .fold(GF::ZERO, |acc, (_, &bk)| acc + bk)
+        })
+        .collect()
+}
+
+/// A basis of GF(2<sup>M</sup>) over GF(2) together with the matrices converting to and from the polynomial basis
+#[derive(Clone, Debug)]
+pub struct Basis<GF: GaloisField> {
+    elements: Vec<GF>,
+    // Column i holds the polynomial basis coefficients of element i
+    from_basis: BitMatrix,
+    to_basis: BitMatrix,
+}
+
+impl<GF: GaloisField> Basis<GF> {
+    /// Constructs a basis from its elements, or None unless there are M linearly independent elements
+    pub fn new(elements: &[GF]) -> Option<Self> {
+        let m = GF::M as usize;
+        if elements.len() != m {
+            return None;
+        }
+
+        let mut from_basis = BitMatrix::new(m, m);
+        for (c, e) in elements.iter().enumerate() {
+            for (r, bit) in e.to_coefficients().into_iter().enumerate() {
+                from_basis.set(r, c, bit);
+            }
+        }
+        let to_basis = from_basis.inverse()?;
+        Some(Self {
+            elements: elements.to_vec(),
+            from_basis,
+            to_basis,
+        })
+    }
+
+    /// The basis elements
+    pub fn elements(&self) -> &[GF] {
+        &self.elements
+    }
+
+    /// The coordinates of value with respect to the basis, so value = Σ coords\[i\] * elements()\[i\]
+    pub fn coordinates(&self, value: GF) -> Vec<bool> {
+        self.to_basis.mul_vec(&value.to_coefficients())
+    }
+
+    /// Reconstructs an element from its coordinates. Panics unless there are M coordinates
+    pub fn from_coordinates(&self, coords: &[bool]) -> GF {
+        GF::from_coefficients(&self.from_basis.mul_vec(coords))
+    }
+
+    /// The M × M change of basis matrix taking polynomial basis coefficients to coordinates
+    pub fn to_basis_matrix(&self) -> &BitMatrix {
+        &self.to_basis
+    }
+
+    /// The M × M change of basis matrix taking coordinates to polynomial basis coefficients
+    pub fn from_basis_matrix(&self) -> &BitMatrix {
+        &self.from_basis
+    }
+}
+
+/// A normal basis together with the matrices converting to and from the polynomial basis
+#[derive(Clone, Debug)]
+pub struct NormalBasis<GF: GaloisField> {
+    element: GF,
+    basis: Basis<GF>,
+}
+
+impl<GF: GaloisField> NormalBasis<GF> {
+    /// Constructs the normal basis generated by the first normal element in order of value
+    pub fn new() -> Self {
+        // Normal elements make up a large fraction of the field so the search ends quickly
+        (1..GF::NUM_ELEM)
+            .find_map(|v| Self::with_element(GF::from_u128(v)))
+            .unwrap()
+    }
+
+    /// Constructs the normal basis generated by element, or None if its conjugates are linearly dependent
+    pub fn with_element(element: GF) -> Option<Self> {
+        let mut conjugates = Vec::with_capacity(GF::M as usize);
+        let mut conjugate = element;
+        for _ in 0..GF::M {
+            conjugates.push(conjugate);
+            conjugate = conjugate.square();
+        }
+
+        Some(Self {
+            element,
+            basis: Basis::new(&conjugates)?,
+        })
+    }
+
+    /// The normal element β generating the basis
+    pub fn element(&self) -> GF {
+        self.element
+    }
+
+    /// The basis β<sup>2<sup>i</sup></sup> as a general `Basis`
+    pub fn basis(&self) -> &Basis<GF> {
+        &self.basis
+    }
+
+    /// The coordinates of value in the basis β<sup>2<sup>i</sup></sup>, index i first
+    pub fn to_normal_basis(&self, value: GF) -> Vec<bool> {
+        self.basis.coordinates(value)
+    }
+
+    /// Reconstructs an element from its normal basis coordinates. Panics unless there are M coordinates
+    pub fn from_normal_basis(&self, coords: &[bool]) -> GF {
+        self.basis.from_coordinates(coords)
+    }
+
+    /// The M × M change of basis matrix taking polynomial basis coefficients to normal basis coordinates
+    pub fn to_normal_matrix(&self) -> &BitMatrix {
+        self.basis.to_basis_matrix()
+    }
+
+    /// The M × M change of basis matrix taking normal basis coordinates to polynomial basis coefficients
+    pub fn from_normal_matrix(&self) -> &BitMatrix {
+        self.basis.from_basis_matrix()
+    }
+}
+
+impl<GF: GaloisField> Default for NormalBasis<GF> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::Rng;
+
+    #[test]
+    fn normal_basis() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        let nb = NormalBasis::<GF>::new();
+        assert_eq!(
+            nb.to_normal_matrix() * nb.from_normal_matrix(),
+            BitMatrix::identity(8)
+        );
+
+        for i in 0..=255 {
+            let a = GF::new(i);
+            let coords = nb.to_normal_basis(a);
+            assert_eq!(nb.from_normal_basis(&coords), a);
+
+            // Squaring rotates the coordinates
+            let mut rotated = coords.clone();
+            rotated.rotate_right(1);
+            assert_eq!(nb.to_normal_basis(a.square()), rotated);
+        }
+
+        // β itself is the first basis vector
+        let mut first = vec![false; 8];
+        first[0] = true;
+        assert_eq!(nb.to_normal_basis(nb.element()), first);
+    }
+
+    #[test]
+    fn not_normal() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        // 1 and 0 are their own conjugates
+        assert!(NormalBasis::with_element(GF::ONE).is_none());
+        assert!(NormalBasis::with_element(GF::ZERO).is_none());
+    }
+
+    fn assert_dual<GF: GaloisField>(basis: &[GF], dual: &[GF]) {
+        for (i, &b) in basis.iter().enumerate() {
+            for (j, &d) in dual.iter().enumerate() {
+                assert_eq!((b * d).trace(), i == j);
+            }
+        }
+    }
+
+    #[test]
+    fn dual_of_polynomial_basis() {
+        // CCSDS Reed-Solomon field
+        type GF = gf2::GFu8<0x187>;
+        let basis: Vec<GF> = (0..8).map(|i| GF::ONE.mul_x().pow(i)).collect();
+        let dual = dual_basis(&basis);
+        assert_dual(&basis, &dual);
+        assert_eq!(dual_basis(&dual), basis);
+
+        // Dual basis coordinates are traces
+        for i in 0..=255 {
+            let a = GF::new(i);
+            let coords: Vec<bool> = basis.iter().map(|&b| (a * b).trace()).collect();
+            let rebuilt = dual
+                .iter()
+                .zip(coords.iter())
+                .filter(|(_, &c)| c)
+                .fold(GF::ZERO, |acc, (&d, _)| acc + d);
+            assert_eq!(rebuilt, a);
+        }
+    }
+
+    #[test]
+    fn dual_of_random_basis() {
+        type GF = gf2::GFu64<0x2_0000_2001>;
+        let basis = loop {
+            let candidate: Vec<GF> = (0..33)
+                .map(|_| GF::new(rand::thread_rng().gen_range(0..(1u64 << 33))))
+                .collect();
+            if Basis::new(&candidate).is_some() {
+                break candidate;
+            }
+        };
+        let dual = dual_basis(&basis);
+        assert_dual(&basis, &dual);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dual_of_dependent_elements() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        let mut basis: Vec<GF> = (0..8).map(|i| GF::new(1 << i)).collect();
+        basis[7] = basis[0] + basis[1];
+        let _ = dual_basis(&basis);
+    }
+
+    #[test]
+    fn arbitrary_basis() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        // The polynomial basis gives the usual coefficients
+        let poly_basis: Vec<GF> = (0..8).map(|i| GF::new(1 << i)).collect();
+        let basis = Basis::new(&poly_basis).unwrap();
+        assert_eq!(basis.to_basis_matrix(), &BitMatrix::identity(8));
+
+        // A shuffled and mixed basis
+        let elements: Vec<GF> = [0x01, 0x03, 0x07, 0x0f, 0x1f, 0x3f, 0x7f, 0xff]
+            .iter()
+            .map(|&v| GF::new(v))
+            .collect();
+        let basis = Basis::new(&elements).unwrap();
+        for i in 0..=255 {
+            let a = GF::new(i);
+            assert_eq!(basis.coordinates(a), a.coordinates(&elements));
+            assert_eq!(basis.from_coordinates(&basis.coordinates(a)), a);
+            assert_eq!(
+                GF::from_coordinates(&a.coordinates(&elements), &elements),
+                a
+            );
+            assert_eq!(a.coordinates(&poly_basis), a.to_coefficients());
+
+            let rebuilt = elements
+                .iter()
+                .zip(basis.coordinates(a))
+                .filter(|(_, c)| *c)
+                .fold(GF::ZERO, |acc, (&e, _)| acc + e);
+            assert_eq!(rebuilt, a);
+        }
+
+        let mut dependent = elements.clone();
+        dependent[3] = dependent[0] + dependent[1];
+        assert!(Basis::new(&dependent).is_none());
+        assert!(Basis::new(&elements[1..]).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn coordinates_not_a_basis() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        let _ = GF::ONE.coordinates(&[GF::ONE; 8]);
+    }
+
+    #[test]
+    fn large_field() {
+        type GF = gf2::GFu64<0x2_0000_2001>;
+        let nb = NormalBasis::<GF>::new();
+        for _ in 0..20 {
+            let a = GF::new(rand::thread_rng().gen_range(0..(1u64 << 33)));
+            let mut coords = nb.to_normal_basis(a);
+            assert_eq!(nb.from_normal_basis(&coords), a);
+            coords.rotate_right(1);
+            assert_eq!(nb.from_normal_basis(&coords), a.square());
+        }
+    }
+}
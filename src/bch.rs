@@ -0,0 +1,443 @@
+//! Binary BCH codes over GF(2<sup>M</sup>).
+//!
+//! The primitive element α is taken to be x, so POLY must be a primitive polynomial.
+//!
+//! ```
+//! use galois_field_2pm::bch;
+//!
+//! // A 512 byte NAND sector protected against 8 bit errors
+//! let code = bch::nand_512(8);
+//! let mut data = vec![0x5a; 512];
+//! let mut parity = code.parity_bytes(&data);
+//! assert_eq!(parity.len(), 13);
+//!
+//! data[100] ^= 0x81;
+//! parity[3] ^= 0x10;
+//! assert_eq!(code.decode_bytes(&mut data, &mut parity), Some(3));
+//! assert_eq!(data, vec![0x5a; 512]);
+//! ```
+
+use crate::poly::Polynomial;
+use crate::rs::{berlekamp_massey, DecodeReport};
+use crate::GaloisField;
+
+mod dvb_s2;
+mod nand;
+
+pub use dvb_s2::{dvb_s2_normal, dvb_s2_short, DvbS2NormalField, DvbS2Rate, DvbS2ShortField};
+pub use nand::{nand_1024, nand_512, Nand1024Field, Nand512Field};
+
+/// Returns the generator polynomial of the binary BCH code with the given designed distance δ whose consecutive roots
+/// start at α<sup>first_root</sup>. This is the least common multiple of the minimal polynomials of
+/// α<sup>first_root</sup>, ..., α<sup>first_root + δ - 2</sup>. The coefficients are all 0 or 1
+pub fn generator_poly<GF: GaloisField>(
+    designed_distance: usize,
+    first_root: usize,
+) -> Polynomial<GF> {
+    let alpha = GF::ONE.mul_x();
+    let mut root = alpha.pow(first_root as u128);
+    let mut minimal_polys: Vec<u128> = Vec::new();
+    for _ in 1..designed_distance {
+        let m = root.minimal_poly();
+        if !minimal_polys.contains(&m) {
+            minimal_polys.push(m);
+        }
+        root *= alpha;
+    }
+
+    minimal_polys
+        .iter()
+        .fold(Polynomial::one(), |acc, &m| &acc * &Polynomial::from_gf2(m))
+}
+
+/// A binary narrow sense BCH code with roots α<sup>1</sup>, ..., α<sup>2t</sup>, shortened to k message bits.
+/// Codewords are stored highest degree first, the message followed by the n - k parity bits
+#[derive(Clone, Debug)]
+pub struct Bch<GF: GaloisField> {
+    n: usize,
+    k: usize,
+    t: usize,
+    generator: Polynomial<GF>,
+    // The coefficients of x^0, ..., x^(n-k-1) of the generator, 64 per word
+    generator_words: Vec<u64>,
+}
+
+impl<GF: GaloisField> Bch<GF> {
+    /// Constructs the code with k message bits correcting up to t bit errors. The codeword length n is k plus the
+    /// degree of the generator, at most M * t. Panics unless k > 0, t > 0 and n < 2<sup>M</sup>
+    pub fn new(k: usize, t: usize) -> Self {
+        if k == 0 || t == 0 {
+            panic!("BCH codes need k > 0 and t > 0");
+        }
+        let generator = generator_poly::<GF>(2 * t + 1, 1);
+        let parity_len = generator.degree() as usize;
+        let n = k + parity_len;
+        if n as u128 >= GF::NUM_ELEM {
+            panic!("BCH codes need n < 2^M");
+        }
+
+        let mut generator_words = vec![0; parity_len.div_ceil(64)];
+        for i in 0..parity_len {
+            if generator.coeff(i) == GF::ONE {
+                generator_words[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        Self {
+            n,
+            k,
+            t,
+            generator,
+            generator_words,
+        }
+    }
+
+    /// The codeword length n in bits
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The message length k in bits
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The number of bit errors that can be corrected
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// The number of parity bits n - k
+    pub fn parity_len(&self) -> usize {
+        self.n - self.k
+    }
+
+    /// The generator polynomial
+    pub fn generator_poly(&self) -> &Polynomial<GF> {
+        &self.generator
+    }
+
+    /// Computes the n - k parity bits of a message. Panics unless the message has k bits
+    pub fn parity(&self, message: &[bool]) -> Vec<bool> {
+        if message.len() != self.k {
+            panic!("Message length does not match k");
+        }
+
+        // The register holds m(x) * x^(n - k) mod g(x) for the bits shifted in so far
+        let r = self.parity_len();
+        let mut register = vec![0u64; self.generator_words.len()];
+        let top_mask = u64::MAX >> (64 * register.len() - r);
+        for &bit in message {
+            let feedback = bit ^ ((register[(r - 1) / 64] >> ((r - 1) % 64)) & 1 == 1);
+            for w in (1..register.len()).rev() {
+                register[w] = (register[w] << 1) | (register[w - 1] >> 63);
+            }
+            register[0] <<= 1;
+            register[(r - 1) / 64] &= top_mask;
+            if feedback {
+                for (reg, &g) in register.iter_mut().zip(self.generator_words.iter()) {
+                    *reg ^= g;
+                }
+            }
+        }
+        (0..r)
+            .rev()
+            .map(|i| (register[i / 64] >> (i % 64)) & 1 == 1)
+            .collect()
+    }
+
+    /// Encodes a message into a codeword: the message followed by its parity. Panics unless the message has k bits
+    pub fn encode(&self, message: &[bool]) -> Vec<bool> {
+        let mut codeword = message.to_vec();
+        codeword.extend(self.parity(message));
+        codeword
+    }
+
+    /// Computes the 2t syndromes r(α<sup>1</sup>), ..., r(α<sup>2t</sup>). All are zero exactly when received is a
+    /// codeword. Panics unless received has n bits
+    pub fn syndromes(&self, received: &[bool]) -> Vec<GF> {
+        if received.len() != self.n {
+            panic!("Received word length does not match n");
+        }
+
+        // The received word has binary coefficients so S_2j = S_j^2
+        let alpha = GF::ONE.mul_x();
+        let mut syndromes = vec![GF::ZERO; 2 * self.t];
+        for j in (1..=2 * self.t).step_by(2) {
+            let root = alpha.pow(j as u128);
+            syndromes[j - 1] = received.iter().fold(GF::ZERO, |acc, &bit| {
+                if bit {
+                    acc * root + GF::ONE
+                } else {
+                    acc * root
+                }
+            });
+        }
+        for j in (2..=2 * self.t).step_by(2) {
+            syndromes[j - 1] = syndromes[j / 2 - 1].square();
+        }
+        syndromes
+    }
+
+    /// Whether received is a codeword
+    pub fn is_codeword(&self, received: &[bool]) -> bool {
+        self.syndromes(received).iter().all(|&s| s == GF::ZERO)
+    }
+
+    /// Corrects up to t bit errors in place using Berlekamp-Massey and Chien search. Returns the number of
+    /// corrected bits, or None if the errors could not be corrected, in which case received is left unchanged.
+    /// Panics unless received has n bits
+    pub fn decode(&self, received: &mut [bool]) -> Option<usize> {
+        self.decode_report(received).num_corrected()
+    }
+
+    /// Corrects up to t bit errors in place like `decode`, and reports the flipped bit positions. Their error values
+    /// are all one. Panics unless received has n bits
+    pub fn decode_report(&self, received: &mut [bool]) -> DecodeReport<GF> {
+        let correction = self
+            .correct(received)
+            .map(|positions| positions.into_iter().map(|p| (p, GF::ONE)).collect());
+        DecodeReport::from_correction(correction)
+    }
+
+    // Returns the flipped positions, or None if received is uncorrectable
+    fn correct(&self, received: &mut [bool]) -> Option<Vec<usize>> {
+        let syndromes = self.syndromes(received);
+        if syndromes.iter().all(|&s| s == GF::ZERO) {
+            return Some(Vec::new());
+        }
+
+        let locator = berlekamp_massey(&syndromes);
+        let num_errors = locator.degree() as usize;
+        if num_errors > self.t {
+            return None;
+        }
+
+        // The error at index p has locator α^(n - 1 - p), a root of Λ(α^-(n - 1 - p))
+        let alpha_inv = GF::ONE.mul_x().inverse();
+        let mut x = GF::ONE;
+        let mut positions = Vec::new();
+        for power in 0..self.n {
+            if locator.eval(x) == GF::ZERO {
+                positions.push(self.n - 1 - power);
+            }
+            x *= alpha_inv;
+        }
+        if positions.len() != num_errors {
+            return None;
+        }
+
+        let mut corrected = received.to_vec();
+        for &p in positions.iter() {
+            corrected[p] = !corrected[p];
+        }
+        if !self.is_codeword(&corrected) {
+            return None;
+        }
+        received.copy_from_slice(&corrected);
+        Some(positions)
+    }
+
+    /// Computes the parity of a message of k / 8 bytes. Bits are taken most significant first, and the n - k parity
+    /// bits are packed the same way with the unused low bits of the last byte zero. Panics unless k = 8 * data.len()
+    pub fn parity_bytes(&self, data: &[u8]) -> Vec<u8> {
+        if data.len() * 8 != self.k {
+            panic!("Message length does not match k");
+        }
+        to_bytes(&self.parity(&to_bits(data, self.k)))
+    }
+
+    /// Corrects up to t bit errors in data and its parity, laid out as in parity_bytes. Returns the number of
+    /// corrected bits, or None if the errors could not be corrected, in which case both are left unchanged.
+    /// Panics unless k = 8 * data.len() and parity has (n - k) / 8 bytes rounded up
+    pub fn decode_bytes(&self, data: &mut [u8], parity: &mut [u8]) -> Option<usize> {
+        if data.len() * 8 != self.k || parity.len() != self.parity_len().div_ceil(8) {
+            panic!("Data or parity length does not match the code");
+        }
+
+        let mut received = to_bits(data, self.k);
+        received.extend(to_bits(parity, self.parity_len()));
+        let corrected = self.decode(&mut received)?;
+        if corrected > 0 {
+            data.copy_from_slice(&to_bytes(&received[..self.k]));
+            // Keep whatever is in the padding bits
+            let padding = parity.len() * 8 - self.parity_len();
+            let mask = (1u8 << padding).wrapping_sub(1);
+            let last = parity.len() - 1;
+            let kept = parity[last] & mask;
+            parity.copy_from_slice(&to_bytes(&received[self.k..]));
+            parity[last] |= kept;
+        }
+        Some(corrected)
+    }
+}
+
+// The first count bits of bytes, most significant first
+fn to_bits(bytes: &[u8], count: usize) -> Vec<bool> {
+    (0..count)
+        .map(|i| (bytes[i / 8] >> (7 - i % 8)) & 1 == 1)
+        .collect()
+}
+
+// Packs bits most significant first, padding the last byte with zeros
+fn to_bytes(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, &bit)| acc | ((bit as u8) << (7 - i)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    fn random_bits(count: usize) -> Vec<bool> {
+        (0..count).map(|_| rand::thread_rng().gen()).collect()
+    }
+
+    fn flip(word: &mut [bool], count: usize) {
+        let mut positions: Vec<usize> = (0..word.len()).collect();
+        positions.shuffle(&mut rand::thread_rng());
+        for &p in positions[..count].iter() {
+            word[p] = !word[p];
+        }
+    }
+
+    #[test]
+    fn minimal_polys() {
+        type GF = gf2_lut::GFu8<0x13>;
+        let alpha = GF::new(2);
+        assert_eq!(alpha.minimal_poly(), 0x13);
+        assert_eq!(alpha.pow(3).minimal_poly(), 0x1F);
+        assert_eq!(alpha.pow(5).minimal_poly(), 0x7);
+        assert_eq!(alpha.pow(7).minimal_poly(), 0x19);
+        assert_eq!(GF::ONE.minimal_poly(), 0x3);
+        assert_eq!(GF::ZERO.minimal_poly(), 0x2);
+
+        for i in 0..16 {
+            let a = GF::new(i);
+            let m = a.minimal_poly();
+            assert!(crate::polys::is_irreducible_gf2(m));
+            // The characteristic polynomial is a power of the minimal polynomial
+            let mut p = Polynomial::<GF>::one();
+            let deg = crate::calc_degree(m) as u128;
+            for _ in 0..(GF::M / deg) {
+                p *= Polynomial::from_gf2(m);
+            }
+            assert_eq!(p, Polynomial::from_gf2(a.char_poly()));
+        }
+    }
+
+    #[test]
+    fn bch_15() {
+        type GF = gf2_lut::GFu8<0x13>;
+        // (15, 11) t = 1, (15, 7) t = 2 and (15, 5) t = 3 narrow sense codes
+        assert_eq!(generator_poly::<GF>(3, 1), Polynomial::from_gf2(0x13));
+        assert_eq!(generator_poly::<GF>(5, 1), Polynomial::from_gf2(0x1D1));
+        assert_eq!(generator_poly::<GF>(7, 1), Polynomial::from_gf2(0x537));
+        assert_eq!(generator_poly::<GF>(1, 1), Polynomial::one());
+    }
+
+    #[test]
+    fn roots() {
+        type GF = gf2::GFu16<0x201B>;
+        for first_root in [0, 1, 5] {
+            let g = generator_poly::<GF>(9, first_root);
+            let alpha = GF::new(2);
+            for i in 0..8 {
+                assert_eq!(g.eval(alpha.pow((first_root + i) as u128)), GF::ZERO);
+            }
+            assert!(g.coeffs().iter().all(|&c| c == GF::ZERO || c == GF::ONE));
+        }
+    }
+
+    #[test]
+    fn encode() {
+        type GF = gf2_lut::GFu8<0x13>;
+        // The (15, 5) t = 3 code
+        let code = Bch::<GF>::new(5, 3);
+        assert_eq!(code.n(), 15);
+        assert_eq!(code.parity_len(), 10);
+        assert_eq!(code.generator_poly(), &Polynomial::from_gf2(0x537));
+
+        for m in 0..32 {
+            let message: Vec<bool> = (0..5).map(|i| (m >> (4 - i)) & 1 == 1).collect();
+            let codeword = code.encode(&message);
+            assert_eq!(codeword[..5], message[..]);
+            assert!(code.is_codeword(&codeword));
+
+            // The codeword polynomial is a multiple of the generator
+            let c = codeword
+                .iter()
+                .fold(0u128, |acc, &bit| (acc << 1) | bit as u128);
+            assert!(Polynomial::<GF>::from_gf2(c)
+                .rem(code.generator_poly())
+                .is_zero());
+        }
+    }
+
+    #[test]
+    fn correct_errors() {
+        type GF = gf2::GFu16<0x201B>;
+        for (k, t) in [(1, 1), (100, 1), (64, 5), (1000, 12), (8000, 14)] {
+            let code = Bch::<GF>::new(k, t);
+            for errors in [0, 1, t / 2, t] {
+                let codeword = code.encode(&random_bits(k));
+                let mut received = codeword.clone();
+                flip(&mut received, errors);
+                assert_eq!(code.decode(&mut received), Some(errors));
+                assert_eq!(received, codeword);
+            }
+        }
+    }
+
+    #[test]
+    fn report() {
+        type GF = gf2::GFu16<0x201B>;
+        let code = Bch::<GF>::new(300, 6);
+        let codeword = code.encode(&random_bits(300));
+        let mut received = codeword.clone();
+        for p in [310, 3, 150] {
+            received[p] = !received[p];
+        }
+        let report = code.decode_report(&mut received);
+        assert_eq!(report.status, crate::rs::DecodeStatus::Corrected);
+        assert_eq!(report.positions, vec![3, 150, 310]);
+        assert_eq!(report.magnitudes, vec![GF::ONE; 3]);
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn too_many_errors() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        let code = Bch::<GF>::new(200, 4);
+        let codeword = code.encode(&random_bits(200));
+        for _ in 0..20 {
+            let mut received = codeword.clone();
+            flip(&mut received, 9);
+            let before = received.clone();
+            match code.decode(&mut received) {
+                // Miscorrection to another codeword
+                Some(count) => {
+                    assert!(count <= 4);
+                    assert!(code.is_codeword(&received));
+                }
+                None => assert_eq!(received, before),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_long() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        let _ = Bch::<GF>::new(240, 2);
+    }
+}
@@ -0,0 +1,154 @@
+use super::Bch;
+use crate::gf2;
+
+/// GF(2<sup>16</sup>) with the primitive polynomial g<sub>1</sub>(x) = x<sup>16</sup> + x<sup>5</sup> + x<sup>3</sup> + x<sup>2</sup> + 1
+/// of the DVB-S2 normal FECFRAME BCH code
+pub type DvbS2NormalField = gf2::GFu16<0x1002D>;
+
+/// GF(2<sup>14</sup>) with the primitive polynomial g<sub>1</sub>(x) = x<sup>14</sup> + x<sup>5</sup> + x<sup>3</sup> + x + 1
+/// of the DVB-S2 short FECFRAME BCH code
+pub type DvbS2ShortField = gf2::GFu16<0x402B>;
+
+/// The LDPC code rates of DVB-S2 (EN 302 307), which fix the BCH message length
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DvbS2Rate {
+    R1_4,
+    R1_3,
+    R2_5,
+    R1_2,
+    R3_5,
+    R2_3,
+    R3_4,
+    R4_5,
+    R5_6,
+    R8_9,
+    R9_10,
+}
+
+/// The outer BCH code of a normal (64800 bit) FECFRAME. The message is the K<sub>bch</sub> bit BBFRAME and the
+/// generator is g<sub>1</sub>(x)...g<sub>t</sub>(x) with t = 8, 10 or 12 depending on the rate
+pub fn dvb_s2_normal(rate: DvbS2Rate) -> Bch<DvbS2NormalField> {
+    let (k, t) = match rate {
+        DvbS2Rate::R1_4 => (16008, 12),
+        DvbS2Rate::R1_3 => (21408, 12),
+        DvbS2Rate::R2_5 => (25728, 12),
+        DvbS2Rate::R1_2 => (32208, 12),
+        DvbS2Rate::R3_5 => (38688, 12),
+        DvbS2Rate::R2_3 => (43040, 10),
+        DvbS2Rate::R3_4 => (48408, 12),
+        DvbS2Rate::R4_5 => (51648, 12),
+        DvbS2Rate::R5_6 => (53840, 10),
+        DvbS2Rate::R8_9 => (57472, 8),
+        DvbS2Rate::R9_10 => (58192, 8),
+    };
+    Bch::new(k, t)
+}
+
+/// The outer BCH code of a short (16200 bit) FECFRAME, always with t = 12. Panics for rate 9/10, which has no short
+/// FECFRAME
+pub fn dvb_s2_short(rate: DvbS2Rate) -> Bch<DvbS2ShortField> {
+    let k = match rate {
+        DvbS2Rate::R1_4 => 3072,
+        DvbS2Rate::R1_3 => 5232,
+        DvbS2Rate::R2_5 => 6312,
+        DvbS2Rate::R1_2 => 7032,
+        DvbS2Rate::R3_5 => 9552,
+        DvbS2Rate::R2_3 => 10632,
+        DvbS2Rate::R3_4 => 11712,
+        DvbS2Rate::R4_5 => 12432,
+        DvbS2Rate::R5_6 => 13152,
+        DvbS2Rate::R8_9 => 14232,
+        DvbS2Rate::R9_10 => panic!("Rate 9/10 has no short FECFRAME"),
+    };
+    Bch::new(k, 12)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::Polynomial;
+    use crate::GaloisField;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    const RATES: [DvbS2Rate; 11] = [
+        DvbS2Rate::R1_4,
+        DvbS2Rate::R1_3,
+        DvbS2Rate::R2_5,
+        DvbS2Rate::R1_2,
+        DvbS2Rate::R3_5,
+        DvbS2Rate::R2_3,
+        DvbS2Rate::R3_4,
+        DvbS2Rate::R4_5,
+        DvbS2Rate::R5_6,
+        DvbS2Rate::R8_9,
+        DvbS2Rate::R9_10,
+    ];
+
+    #[test]
+    fn frame_lengths() {
+        // N_bch from tables 5a and 5b of EN 302 307
+        let normal = [
+            16200, 21600, 25920, 32400, 38880, 43200, 48600, 51840, 54000, 57600, 58320,
+        ];
+        for (&rate, &n) in RATES.iter().zip(normal.iter()) {
+            assert_eq!(dvb_s2_normal(rate).n(), n);
+        }
+
+        let short = [
+            3240, 5400, 6480, 7200, 9720, 10800, 11880, 12600, 13320, 14400,
+        ];
+        for (&rate, &n) in RATES.iter().zip(short.iter()) {
+            let code = dvb_s2_short(rate);
+            assert_eq!(code.n(), n);
+            assert_eq!(code.t(), 12);
+        }
+    }
+
+    #[test]
+    fn generator_factors() {
+        // g_2 and g_3 of tables 6a and 6b divide the generators
+        let g2 = Polynomial::<DvbS2NormalField>::from_gf2(0x10173);
+        let g3 = Polynomial::<DvbS2NormalField>::from_gf2(0x10FBD);
+        let g = dvb_s2_normal(DvbS2Rate::R8_9).generator_poly().clone();
+        assert!(g.rem(&g2).is_zero());
+        assert!(g.rem(&g3).is_zero());
+
+        let g2 = Polynomial::<DvbS2ShortField>::from_gf2(0x4941);
+        let g = dvb_s2_short(DvbS2Rate::R1_2).generator_poly().clone();
+        assert!(g.rem(&g2).is_zero());
+        assert_eq!(g.degree(), 168);
+    }
+
+    #[test]
+    fn correct_frame() {
+        for (rate, errors) in [(DvbS2Rate::R1_4, 12), (DvbS2Rate::R9_10, 8)] {
+            let code = dvb_s2_normal(rate);
+            let message: Vec<bool> = (0..code.k()).map(|_| rand::thread_rng().gen()).collect();
+            let codeword = code.encode(&message);
+
+            let mut received = codeword.clone();
+            let mut positions: Vec<usize> = (0..code.n()).collect();
+            positions.shuffle(&mut rand::thread_rng());
+            for &p in positions[..errors].iter() {
+                received[p] = !received[p];
+            }
+            assert_eq!(code.decode(&mut received), Some(errors));
+            assert_eq!(received, codeword);
+        }
+
+        let code = dvb_s2_short(DvbS2Rate::R3_5);
+        let mut received = code.encode(&vec![false; code.k()]);
+        received[0] = true;
+        received[9719] = true;
+        assert_eq!(code.decode(&mut received), Some(2));
+        assert!(received.iter().all(|&bit| !bit));
+        assert_eq!(DvbS2ShortField::M, 14);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_short_9_10() {
+        let _ = dvb_s2_short(DvbS2Rate::R9_10);
+    }
+}
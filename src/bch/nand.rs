@@ -0,0 +1,99 @@
+use super::Bch;
+use crate::gf2;
+
+/// GF(2<sup>13</sup>) with the primitive polynomial x<sup>13</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1,
+/// the field of BCH codes over 512 byte NAND sectors
+pub type Nand512Field = gf2::GFu16<0x201B>;
+
+/// GF(2<sup>14</sup>) with the primitive polynomial x<sup>14</sup> + x<sup>5</sup> + x<sup>3</sup> + x + 1,
+/// the field of BCH codes over 1024 byte NAND sectors
+pub type Nand1024Field = gf2::GFu16<0x402B>;
+
+/// The BCH code correcting t bit errors in a 512 byte sector, with 13 * t parity bits.
+/// Controllers commonly use t from 4 to 40. Use it through `Bch::parity_bytes` and `Bch::decode_bytes`
+pub fn nand_512(t: usize) -> Bch<Nand512Field> {
+    Bch::new(512 * 8, t)
+}
+
+/// The BCH code correcting t bit errors in a 1024 byte sector, with 14 * t parity bits.
+/// Controllers commonly use t from 4 to 40. Use it through `Bch::parity_bytes` and `Bch::decode_bytes`
+pub fn nand_1024(t: usize) -> Bch<Nand1024Field> {
+    Bch::new(1024 * 8, t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    // Flips count distinct bits across data and parity
+    fn flip_bits(data: &mut [u8], parity: &mut [u8], parity_bits: usize, count: usize) {
+        let mut positions: Vec<usize> = (0..data.len() * 8 + parity_bits).collect();
+        positions.shuffle(&mut rand::thread_rng());
+        for &p in positions[..count].iter() {
+            if p < data.len() * 8 {
+                data[p / 8] ^= 0x80 >> (p % 8);
+            } else {
+                let q = p - data.len() * 8;
+                parity[q / 8] ^= 0x80 >> (q % 8);
+            }
+        }
+    }
+
+    #[test]
+    fn parameters() {
+        for t in [4, 8, 16, 24, 40] {
+            let code = nand_512(t);
+            assert_eq!(code.parity_len(), 13 * t);
+            assert_eq!(code.n(), 4096 + 13 * t);
+
+            let code = nand_1024(t);
+            assert_eq!(code.parity_len(), 14 * t);
+            assert_eq!(code.n(), 8192 + 14 * t);
+        }
+    }
+
+    #[test]
+    fn correct_sector() {
+        for (t, errors) in [(4, 4), (8, 5), (24, 24)] {
+            let code = nand_512(t);
+            let data: Vec<u8> = (0..512).map(|_| rand::thread_rng().gen()).collect();
+            let parity = code.parity_bytes(&data);
+            assert_eq!(parity.len(), (13 * t).div_ceil(8));
+
+            let (mut received, mut received_parity) = (data.clone(), parity.clone());
+            flip_bits(&mut received, &mut received_parity, 13 * t, errors);
+            assert_eq!(
+                code.decode_bytes(&mut received, &mut received_parity),
+                Some(errors)
+            );
+            assert_eq!(received, data);
+            assert_eq!(received_parity, parity);
+        }
+
+        let code = nand_1024(40);
+        let data: Vec<u8> = (0..1024).map(|_| rand::thread_rng().gen()).collect();
+        let parity = code.parity_bytes(&data);
+        assert_eq!(parity.len(), 70);
+        let (mut received, mut received_parity) = (data.clone(), parity.clone());
+        flip_bits(&mut received, &mut received_parity, 560, 40);
+        assert_eq!(
+            code.decode_bytes(&mut received, &mut received_parity),
+            Some(40)
+        );
+        assert_eq!(received, data);
+    }
+
+    #[test]
+    fn erased_page() {
+        // A blank page of 0xff is not a codeword, but correcting it must not panic
+        let code = nand_512(4);
+        let mut data = vec![0xff; 512];
+        let mut parity = vec![0xff; 7];
+        let before = (data.clone(), parity.clone());
+        if code.decode_bytes(&mut data, &mut parity).is_none() {
+            assert_eq!((data, parity), before);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! Dense matrices over GF(2).
+//!
+//! Every element c of GF(2<sup>M</sup>) defines a GF(2) linear map x ↦ c * x on the coefficient vectors of the field.
+//! `GaloisField::mul_matrix` returns that map as an M × M BitMatrix.
+
+use core::fmt;
+use core::ops::Mul;
+
+/// A dense matrix with entries in GF(2), stored row major
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<bool>,
+}
+
+impl BitMatrix {
+    /// Constructs an all zero matrix
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![false; rows * cols],
+        }
+    }
+
+    /// Constructs the n × n identity matrix
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::new(n, n);
+        for i in 0..n {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    /// Constructs a matrix from its rows. Panics if the rows differ in length
+    pub fn from_rows(rows: &[Vec<bool>]) -> Self {
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            panic!("Rows must have the same length");
+        }
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.concat(),
+        }
+    }
+
+    /// The number of rows
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the entry at (row, col). Panics if out of range
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.data[self.index(row, col)]
+    }
+
+    /// Sets the entry at (row, col). Panics if out of range
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        let idx = self.index(row, col);
+        self.data[idx] = value;
+    }
+
+    /// Returns a row
+    pub fn row(&self, row: usize) -> &[bool] {
+        &self.data[self.index(row, 0)..self.index(row, 0) + self.cols]
+    }
+
+    /// Returns the transpose
+    pub fn transpose(&self) -> Self {
+        let mut t = Self::new(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                t.set(c, r, self.get(r, c));
+            }
+        }
+        t
+    }
+
+    /// Computes self * v. Panics if v does not have num_cols entries
+    pub fn mul_vec(&self, v: &[bool]) -> Vec<bool> {
+        if v.len() != self.cols {
+            panic!("Vector length does not match the number of columns");
+        }
+        (0..self.rows)
+            .map(|r| {
+                self.row(r)
+                    .iter()
+                    .zip(v.iter())
+                    .fold(false, |acc, (&a, &b)| acc ^ (a & b))
+            })
+            .collect()
+    }
+
+    /// The rank over GF(2)
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce()
+    }
+
+    /// Returns the inverse of a square matrix, or None if it is singular. Panics if the matrix is not square
+    pub fn inverse(&self) -> Option<Self> {
+        if self.rows != self.cols {
+            panic!("Only square matrices have an inverse");
+        }
+
+        // Reduce [self | I] to [I | self^-1]
+        let n = self.rows;
+        let mut augmented = Self::new(n, 2 * n);
+        for r in 0..n {
+            for c in 0..n {
+                augmented.set(r, c, self.get(r, c));
+            }
+            augmented.set(r, n + r, true);
+        }
+        augmented.row_reduce();
+        if (0..n).any(|i| !augmented.get(i, i)) {
+            return None;
+        }
+
+        let mut inverse = Self::new(n, n);
+        for r in 0..n {
+            for c in 0..n {
+                inverse.set(r, c, augmented.get(r, n + c));
+            }
+        }
+        Some(inverse)
+    }
+
+    // Gauss-Jordan elimination to reduced row echelon form. Returns the rank
+    pub(crate) fn row_reduce(&mut self) -> usize {
+        let mut rank = 0;
+        for col in 0..self.cols {
+            let Some(pivot) = (rank..self.rows).find(|&r| self.get(r, col)) else {
+                continue;
+            };
+            for c in 0..self.cols {
+                self.data.swap(rank * self.cols + c, pivot * self.cols + c);
+            }
+            for r in 0..self.rows {
+                if r != rank && self.get(r, col) {
+                    for c in 0..self.cols {
+                        self.data[r * self.cols + c] ^= self.data[rank * self.cols + c];
+                    }
+                }
+            }
+            rank += 1;
+            if rank == self.rows {
+                break;
+            }
+        }
+        rank
+    }
+
+    /// Packs each row into a u128 with column c in bit c. For a `mul_matrix` this gives the bitsliced form of the
+    /// multiplication: bit r of (c * x).to_u128() is the parity of row_masks()\[r\] & x.to_u128().
+    /// Panics if there are more than 128 columns
+    pub fn row_masks(&self) -> Vec<u128> {
+        if self.cols > 128 {
+            panic!("Rows with more than 128 columns do not fit in a u128");
+        }
+        (0..self.rows)
+            .map(|r| {
+                self.row(r)
+                    .iter()
+                    .enumerate()
+                    .fold(0u128, |acc, (c, &b)| acc | ((b as u128) << c))
+            })
+            .collect()
+    }
+
+    /// The number of two input XOR gates needed to compute self * v directly, one row at a time without sharing
+    /// terms between rows. An upper bound for the size of an XOR network implementing the map
+    pub fn xor_count(&self) -> usize {
+        (0..self.rows)
+            .map(|r| self.row(r).iter().filter(|&&b| b).count().saturating_sub(1))
+            .sum()
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        if row >= self.rows || col >= self.cols {
+            panic!("Matrix index out of range");
+        }
+        row * self.cols + col
+    }
+}
+
+// Serialized as nested arrays of rows
+#[cfg(feature = "serde")]
+impl serde::Serialize for BitMatrix {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.rows).map(|r| self.row(r)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BitMatrix {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<bool>>::deserialize(deserializer)?;
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            return Err(serde::de::Error::custom("rows must have the same length"));
+        }
+        Ok(Self::from_rows(&rows))
+    }
+}
+
+impl Mul<&BitMatrix> for &BitMatrix {
+    type Output = BitMatrix;
+
+    fn mul(self, other: &BitMatrix) -> BitMatrix {
+        if self.cols != other.rows {
+            panic!("Matrix dimensions do not match");
+        }
+        let mut output = BitMatrix::new(self.rows, other.cols);
+        for r in 0..self.rows {
+            for k in 0..self.cols {
+                if !self.get(r, k) {
+                    continue;
+                }
+                for c in 0..other.cols {
+                    let idx = output.index(r, c);
+                    output.data[idx] ^= other.get(k, c);
+                }
+            }
+        }
+        output
+    }
+}
+
+impl Mul<BitMatrix> for BitMatrix {
+    type Output = BitMatrix;
+
+    fn mul(self, other: BitMatrix) -> BitMatrix {
+        &self * &other
+    }
+}
+
+impl fmt::Display for BitMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in 0..self.rows {
+            let line: String = self
+                .row(r)
+                .iter()
+                .map(|&b| if b { '1' } else { '0' })
+                .collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut, GaloisField};
+    use rand::Rng;
+
+    #[test]
+    fn basics() {
+        let a = BitMatrix::from_rows(&[vec![true, false, true], vec![false, true, true]]);
+        assert_eq!(a.num_rows(), 2);
+        assert_eq!(a.num_cols(), 3);
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(&a * &BitMatrix::identity(3), a);
+        assert_eq!(&BitMatrix::identity(2) * &a, a);
+        assert_eq!(a.mul_vec(&[true, true, true]), vec![false, false]);
+        assert_eq!(format!("{}", a), "101\n011\n");
+
+        let ata = &a * &a.transpose();
+        assert_eq!(
+            ata,
+            BitMatrix::from_rows(&[vec![false, true], vec![true, false]])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = BitMatrix::from_rows(&[vec![true, false, true], vec![false, true, true]]);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, "[[true,false,true],[false,true,true]]");
+        assert_eq!(serde_json::from_str::<BitMatrix>(&json).unwrap(), a);
+        assert!(serde_json::from_str::<BitMatrix>("[[true],[false,true]]").is_err());
+    }
+
+    #[test]
+    fn rank_and_inverse() {
+        let a = BitMatrix::from_rows(&[
+            vec![true, true, false],
+            vec![false, true, true],
+            vec![true, false, true],
+        ]);
+        // The rows sum to zero
+        assert_eq!(a.rank(), 2);
+        assert_eq!(a.inverse(), None);
+
+        let b = BitMatrix::from_rows(&[
+            vec![true, true, false],
+            vec![false, true, true],
+            vec![false, false, true],
+        ]);
+        assert_eq!(b.rank(), 3);
+        let b_inv = b.inverse().unwrap();
+        assert_eq!(&b * &b_inv, BitMatrix::identity(3));
+        assert_eq!(&b_inv * &b, BitMatrix::identity(3));
+
+        // Multiplication by a non zero element is invertible, with the inverse element's matrix as inverse
+        type GF = gf2_lut::GFu8<0x11d>;
+        for i in 1..=255 {
+            let c = GF::new(i);
+            assert_eq!(c.mul_matrix().inverse(), Some(c.inverse().mul_matrix()));
+        }
+        assert_eq!(GF::ZERO.mul_matrix().rank(), 0);
+    }
+
+    #[test]
+    fn mul_matrix_matches_field() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        for i in 0..=255 {
+            let c = GF::new(i);
+            let m = c.mul_matrix();
+            for j in 0..=255 {
+                let x = GF::new(j);
+                assert_eq!(
+                    GF::from_coefficients(&m.mul_vec(&x.to_coefficients())),
+                    c * x
+                );
+            }
+        }
+
+        type GF2 = gf2::GFu64<0x2_0000_2001>;
+        let a = GF2::new(rand::thread_rng().gen_range(0..(1u64 << 33)));
+        let b = GF2::new(rand::thread_rng().gen_range(0..(1u64 << 33)));
+        assert_eq!(&a.mul_matrix() * &b.mul_matrix(), (a * b).mul_matrix());
+    }
+
+    #[test]
+    fn bitsliced_export() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        for _ in 0..100 {
+            let c = GF::new(rand::thread_rng().gen_range(0..=255));
+            let x = GF::new(rand::thread_rng().gen_range(0..=255));
+            let masks = c.mul_matrix().row_masks();
+            let y = masks.iter().enumerate().fold(0u128, |acc, (r, &mask)| {
+                acc | (((mask & x.to_u128()).count_ones() as u128 & 0x1) << r)
+            });
+            assert_eq!(y, (c * x).to_u128());
+        }
+
+        // Multiplying by 1 needs no gates, by x one gate per non zero tap of x^8 mod POLY
+        assert_eq!(GF::ONE.mul_matrix().xor_count(), 0);
+        assert_eq!(GF::new(2).mul_matrix().xor_count(), 3);
+        assert_eq!(
+            GF::new(2).mul_matrix().row_masks(),
+            vec![0x80, 0x01, 0x82, 0x84, 0x88, 0x10, 0x20, 0x40]
+        );
+    }
+
+    #[test]
+    fn char_poly() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        // x is a root of the defining polynomial
+        assert_eq!(GF::new(2).char_poly(), 0x11d);
+        // 1 has characteristic polynomial (x + 1)^8
+        assert_eq!(GF::ONE.char_poly(), 0x101);
+        assert_eq!(GF::ZERO.char_poly(), 0x100);
+
+        // Every element is a root of its characteristic polynomial
+        for i in 0..=255 {
+            let a = GF::new(i);
+            let p = a.char_poly();
+            let value = (0..=8).fold(GF::ZERO, |acc, k| {
+                if ((p >> k) & 0x1) > 0 {
+                    acc + a.pow(k)
+                } else {
+                    acc
+                }
+            });
+            assert_eq!(value, GF::ZERO);
+        }
+
+        type GF2 = gf2::GFu128<0x8000_0000_0000_0000_0000_0000_0000_0003>;
+        assert_eq!(
+            GF2::new(2).char_poly(),
+            0x8000_0000_0000_0000_0000_0000_0000_0003
+        );
+    }
+}
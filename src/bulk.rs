@@ -0,0 +1,154 @@
+//! Element-wise operations on slices of any field.
+//!
+//! `add_assign_slice`, `scale_slice` and `mul_add_slice` compute dst += src, dst = c * dst and dst += c * src, the
+//! inner loops of Reed-Solomon encoding and of Gaussian elimination. Fields with M ≤ 8 go through the kernels of
+//! `simd` a block at a time, and larger fields use plain loops that the compiler can vectorize.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::bulk;
+//!
+//! type GF = gf2::GFu16<0x1_002B>;
+//! let c = GF::new(0x1234);
+//! let src = [GF::new(1), GF::new(2), GF::new(3)];
+//! let mut dst = [GF::new(7); 3];
+//! bulk::mul_add_slice(c, &mut dst, &src);
+//! assert_eq!(dst[2], GF::new(7) + c * GF::new(3));
+//! ```
+
+use crate::simd::{self, NibbleTables};
+use crate::GaloisField;
+
+// Elements converted to bytes at a time for the simd kernels
+const BLOCK: usize = 256;
+
+/// dst += src element by element. Panics if the lengths differ
+pub fn add_assign_slice<GF: GaloisField>(dst: &mut [GF], src: &[GF]) {
+    assert_eq!(dst.len(), src.len(), "Slices must have the same length");
+    for (d, &s) in dst.iter_mut().zip(src.iter()) {
+        *d += s;
+    }
+}
+
+/// Multiplies every element of dst by c in place
+pub fn scale_slice<GF: GaloisField>(c: GF, dst: &mut [GF]) {
+    if c == GF::ONE {
+        return;
+    }
+    if c == GF::ZERO {
+        dst.fill(GF::ZERO);
+    } else if GF::M <= 8 {
+        let tables = NibbleTables::new(c);
+        let mut bytes = [0u8; BLOCK];
+        for block in dst.chunks_mut(BLOCK) {
+            let bytes = &mut bytes[..block.len()];
+            to_bytes(block, bytes);
+            simd::mul_slice_tables(&tables, bytes);
+            for (d, &b) in block.iter_mut().zip(bytes.iter()) {
+                *d = GF::from_u128(b as u128);
+            }
+        }
+    } else {
+        for d in dst.iter_mut() {
+            *d *= c;
+        }
+    }
+}
+
+/// dst += c * src element by element. Panics if the lengths differ
+pub fn mul_add_slice<GF: GaloisField>(c: GF, dst: &mut [GF], src: &[GF]) {
+    assert_eq!(dst.len(), src.len(), "Slices must have the same length");
+    if c == GF::ZERO {
+        return;
+    }
+    if c == GF::ONE {
+        add_assign_slice(dst, src);
+    } else if GF::M <= 8 {
+        let tables = NibbleTables::new(c);
+        let (mut dst_bytes, mut src_bytes) = ([0u8; BLOCK], [0u8; BLOCK]);
+        for (block, src) in dst.chunks_mut(BLOCK).zip(src.chunks(BLOCK)) {
+            let dst_bytes = &mut dst_bytes[..block.len()];
+            let src_bytes = &mut src_bytes[..block.len()];
+            to_bytes(block, dst_bytes);
+            to_bytes(src, src_bytes);
+            simd::mul_add_slice_tables(&tables, dst_bytes, src_bytes);
+            for (d, &b) in block.iter_mut().zip(dst_bytes.iter()) {
+                *d = GF::from_u128(b as u128);
+            }
+        }
+    } else {
+        for (d, &s) in dst.iter_mut().zip(src.iter()) {
+            *d += c * s;
+        }
+    }
+}
+
+fn to_bytes<GF: GaloisField>(elements: &[GF], bytes: &mut [u8]) {
+    for (b, e) in bytes.iter_mut().zip(elements.iter()) {
+        *b = e.to_u128() as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut, gf2_nibble, gf2_rev};
+    use rand::Rng;
+
+    fn check<GF: GaloisField>() {
+        for len in [0, 1, 33, 255, 256, 257, 1000] {
+            let random = || -> Vec<GF> {
+                (0..len)
+                    .map(|_| GF::from_u128(rand::thread_rng().gen::<u128>() % GF::NUM_ELEM))
+                    .collect()
+            };
+            let (dst, src) = (random(), random());
+            let mut c = random().first().copied().unwrap_or(GF::ONE);
+            if c == GF::ZERO {
+                c = GF::ONE;
+            }
+
+            let mut sum = dst.clone();
+            add_assign_slice(&mut sum, &src);
+            let mut scaled = dst.clone();
+            scale_slice(c, &mut scaled);
+            let mut mul_added = dst.clone();
+            mul_add_slice(c, &mut mul_added, &src);
+            for i in 0..len {
+                assert_eq!(sum[i], dst[i] + src[i]);
+                assert_eq!(scaled[i], c * dst[i]);
+                assert_eq!(mul_added[i], dst[i] + c * src[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_elements() {
+        check::<gf2::GFu8<0x11D>>();
+        check::<gf2::GFu8<0x13>>();
+        check::<gf2::GFu16<0x11B>>();
+        check::<gf2_lut::GFu8<0x11D>>();
+        check::<gf2_nibble::GFu8<0x11B>>();
+        check::<gf2_rev::GFu8<0x11B>>();
+        check::<gf2::GFu16<0x1_002B>>();
+        check::<gf2::GFu64<0x1_0000_0000_0000_001B>>();
+    }
+
+    #[test]
+    fn trivial_constants() {
+        type GF = gf2::GFu8<0x11D>;
+        let src = [GF::new(3), GF::new(4)];
+        let mut dst = [GF::new(5), GF::new(6)];
+        mul_add_slice(GF::ZERO, &mut dst, &src);
+        assert_eq!(dst, [GF::new(5), GF::new(6)]);
+        scale_slice(GF::ZERO, &mut dst);
+        assert_eq!(dst, [GF::ZERO; 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lengths() {
+        type GF = gf2::GFu8<0x11D>;
+        add_assign_slice(&mut [GF::ONE; 2], &[GF::ONE]);
+    }
+}
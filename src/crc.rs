@@ -0,0 +1,497 @@
+//! Cyclic redundancy checks.
+//!
+//! A CRC of width w is the remainder of the message polynomial times x<sup>w</sup> modulo a degree w polynomial
+//! over GF(2). `CrcParams` describes a CRC in the usual parameter model (width, polynomial, initial value, input and
+//! output reflection, final XOR) and `Crc` computes it with tables of byte remainders, eight input bytes at a time.
+//! When carry-less multiplication is done in hardware, long inputs are instead folded 8 bytes at a time with
+//! carry-less multiplications and reduced with a single Barrett reduction.
+//!
+//! Common CRCs are provided as constants such as `CRC_32`.
+//!
+//! ```
+//! use galois_field_2pm::crc::{self, Crc, CrcParams};
+//!
+//! // CRC-32 as used by zip and Ethernet
+//! let params = CrcParams {
+//!     width: 32,
+//!     poly: 0x04C1_1DB7u32,
+//!     init: 0xFFFF_FFFF,
+//!     refin: true,
+//!     refout: true,
+//!     xorout: 0xFFFF_FFFF,
+//! };
+//! assert_eq!(params, crc::CRC_32);
+//!
+//! let crc = Crc::new(params);
+//! assert_eq!(crc.checksum(b"123456789"), 0xCBF4_3926);
+//!
+//! let mut digest = crc.digest();
+//! digest.update(b"1234");
+//! digest.update(b"56789");
+//! assert_eq!(digest.finalize(), 0xCBF4_3926);
+//! ```
+
+use core::fmt::Debug;
+
+use crate::gf2::clmul;
+use crate::polys::rem_gf2;
+use fold::FoldConstants;
+
+mod fold;
+mod presets;
+
+pub use presets::{
+    CRC_16_CCITT_FALSE, CRC_16_IBM, CRC_16_KERMIT, CRC_16_XMODEM, CRC_32, CRC_32C, CRC_64_ECMA,
+    CRC_64_XZ, CRC_8_ATM,
+};
+
+/// The unsigned integer types a CRC value can be stored in
+pub trait CrcWidth: Copy + Debug + Eq {
+    /// The number of bits of the type, the largest supported CRC width
+    const BITS: u32;
+
+    /// Widens the value to a u64
+    fn to_u64(self) -> u64;
+
+    /// Truncates a u64 to the type
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! crc_width_impl {
+    ($($type:ty,)*) => {
+    $(
+        impl CrcWidth for $type {
+            const BITS: u32 = <$type>::BITS;
+
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            fn from_u64(value: u64) -> Self {
+                value as $type
+            }
+        }
+    )*
+    };
+}
+
+crc_width_impl! {
+    u8,
+    u16,
+    u32,
+    u64,
+}
+
+/// The parameters of a CRC
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrcParams<W: CrcWidth> {
+    /// The degree w of the polynomial, at most the number of bits of W
+    pub width: u32,
+    /// The polynomial without its x<sup>w</sup> term, most significant bit the coefficient of x<sup>w-1</sup>
+    pub poly: W,
+    /// The register value before the first byte, unreflected
+    pub init: W,
+    /// Whether the bits of each input byte are processed least significant first
+    pub refin: bool,
+    /// Whether the register is reflected before the final XOR
+    pub refout: bool,
+    /// The value XORed into the result
+    pub xorout: W,
+}
+
+/// A table driven CRC using slicing-by-8
+#[derive(Clone, Debug)]
+pub struct Crc<W: CrcWidth> {
+    params: CrcParams<W>,
+    // Table i holds the remainders of b(x) * x^(w + 8i), the effect of byte b followed by i zero bytes.
+    // Unreflected entries are aligned to the top of the u64, reflected ones to the bottom
+    tables: Box<[[u64; 256]; 8]>,
+    fold: FoldConstants,
+}
+
+impl<W: CrcWidth> Crc<W> {
+    /// Builds the table for the CRC. Panics unless 0 < width ≤ W::BITS and the polynomial and values fit in width bits
+    pub fn new(params: CrcParams<W>) -> Self {
+        let width = params.width;
+        if width == 0 || width > W::BITS {
+            panic!("CRC width must be between 1 and the number of bits of the type");
+        }
+        let mask = width_mask(width);
+        if [params.poly, params.init, params.xorout]
+            .iter()
+            .any(|v| v.to_u64() & !mask != 0)
+        {
+            panic!("CRC parameters do not fit in width bits");
+        }
+
+        let full_poly = (1u128 << width) | params.poly.to_u64() as u128;
+        let mut tables = Box::new([[0; 256]; 8]);
+        for (b, entry) in tables[0].iter_mut().enumerate() {
+            if params.refin {
+                let rem = rem_gf2(((b as u8).reverse_bits() as u128) << width, full_poly) as u64;
+                *entry = reflect(rem, width);
+            } else {
+                let rem = rem_gf2((b as u128) << width, full_poly) as u64;
+                *entry = rem << (64 - width);
+            }
+        }
+
+        // Appending a zero byte to b shifts its remainder by one byte and reduces the overflow through table 0
+        for i in 1..8 {
+            for b in 0..256 {
+                let prev = tables[i - 1][b];
+                tables[i][b] = if params.refin {
+                    (prev >> 8) ^ tables[0][(prev & 0xFF) as usize]
+                } else {
+                    (prev << 8) ^ tables[0][(prev >> 56) as usize]
+                };
+            }
+        }
+
+        Self {
+            params,
+            tables,
+            fold: FoldConstants::new(width, params.poly.to_u64()),
+        }
+    }
+
+    /// The parameters of the CRC
+    pub fn params(&self) -> &CrcParams<W> {
+        &self.params
+    }
+
+    /// Computes the CRC of data
+    pub fn checksum(&self, data: &[u8]) -> W {
+        let mut digest = self.digest();
+        digest.update(data);
+        digest.finalize()
+    }
+
+    /// Starts an incremental CRC computation
+    pub fn digest(&self) -> Digest<'_, W> {
+        Digest {
+            crc: self,
+            register: self.initial_register(),
+        }
+    }
+
+    fn initial_register(&self) -> u64 {
+        let init = self.params.init.to_u64();
+        if self.params.refin {
+            reflect(init, self.params.width)
+        } else {
+            init << (64 - self.params.width)
+        }
+    }
+
+    fn update_register(&self, register: u64, data: &[u8]) -> u64 {
+        if clmul::hardware() && data.len() >= FOLD_MIN_LEN {
+            self.update_folded(register, data)
+        } else {
+            self.update_sliced(register, data)
+        }
+    }
+
+    fn update_folded(&self, register: u64, data: &[u8]) -> u64 {
+        let width = self.params.width;
+        let (body, tail) = data.split_at(data.len() - data.len() % 8);
+        let register = if self.params.refin {
+            reflect(self.fold.fold(reflect(register, width), body, true), width)
+        } else {
+            self.fold.fold(register >> (64 - width), body, false) << (64 - width)
+        };
+        self.update_bytewise(register, tail)
+    }
+
+    fn update_sliced(&self, mut register: u64, data: &[u8]) -> u64 {
+        // The register is at most 8 bytes wide, so after XORing in the next 8 bytes each byte is independently
+        // followed by the zero bytes after it
+        let t = &self.tables;
+        let mut chunks = data.chunks_exact(8);
+        if self.params.refin {
+            for chunk in chunks.by_ref() {
+                let x = register ^ u64::from_le_bytes(chunk.try_into().unwrap());
+                register = t[7][(x & 0xFF) as usize]
+                    ^ t[6][((x >> 8) & 0xFF) as usize]
+                    ^ t[5][((x >> 16) & 0xFF) as usize]
+                    ^ t[4][((x >> 24) & 0xFF) as usize]
+                    ^ t[3][((x >> 32) & 0xFF) as usize]
+                    ^ t[2][((x >> 40) & 0xFF) as usize]
+                    ^ t[1][((x >> 48) & 0xFF) as usize]
+                    ^ t[0][(x >> 56) as usize];
+            }
+        } else {
+            for chunk in chunks.by_ref() {
+                let x = register ^ u64::from_be_bytes(chunk.try_into().unwrap());
+                register = t[7][(x >> 56) as usize]
+                    ^ t[6][((x >> 48) & 0xFF) as usize]
+                    ^ t[5][((x >> 40) & 0xFF) as usize]
+                    ^ t[4][((x >> 32) & 0xFF) as usize]
+                    ^ t[3][((x >> 24) & 0xFF) as usize]
+                    ^ t[2][((x >> 16) & 0xFF) as usize]
+                    ^ t[1][((x >> 8) & 0xFF) as usize]
+                    ^ t[0][(x & 0xFF) as usize];
+            }
+        }
+        self.update_bytewise(register, chunks.remainder())
+    }
+
+    fn update_bytewise(&self, mut register: u64, data: &[u8]) -> u64 {
+        let table = &self.tables[0];
+        if self.params.refin {
+            for &byte in data {
+                register = (register >> 8) ^ table[((register ^ byte as u64) & 0xFF) as usize];
+            }
+        } else {
+            for &byte in data {
+                register = (register << 8) ^ table[((register >> 56) ^ byte as u64) as usize];
+            }
+        }
+        register
+    }
+
+    fn finalize_register(&self, register: u64) -> W {
+        let width = self.params.width;
+        let mut value = if self.params.refin {
+            register
+        } else {
+            register >> (64 - width)
+        };
+        if self.params.refin != self.params.refout {
+            value = reflect(value, width);
+        }
+        W::from_u64((value ^ self.params.xorout.to_u64()) & width_mask(width))
+    }
+}
+
+// Below this length the setup of folding is not worth it
+const FOLD_MIN_LEN: usize = 64;
+
+/// An incremental CRC computation over data given in pieces
+#[derive(Clone, Debug)]
+pub struct Digest<'a, W: CrcWidth> {
+    crc: &'a Crc<W>,
+    register: u64,
+}
+
+impl<W: CrcWidth> Digest<'_, W> {
+    /// Appends data to the message
+    pub fn update(&mut self, data: &[u8]) {
+        self.register = self.crc.update_register(self.register, data);
+    }
+
+    /// The CRC of the data so far
+    pub fn finalize(&self) -> W {
+        self.crc.finalize_register(self.register)
+    }
+
+    /// Starts over with an empty message
+    pub fn reset(&mut self) {
+        self.register = self.crc.initial_register();
+    }
+}
+
+// Reverses the low width bits of value
+fn reflect(value: u64, width: u32) -> u64 {
+    value.reverse_bits() >> (64 - width)
+}
+
+fn width_mask(width: u32) -> u64 {
+    u64::MAX >> (64 - width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    const CHECK: &[u8] = b"123456789";
+
+    fn params<W: CrcWidth>(
+        width: u32,
+        poly: W,
+        init: W,
+        refin: bool,
+        refout: bool,
+        xorout: W,
+    ) -> CrcParams<W> {
+        CrcParams {
+            width,
+            poly,
+            init,
+            refin,
+            refout,
+            xorout,
+        }
+    }
+
+    // The CRC computed one bit at a time straight from the definition
+    fn bitwise(p: &CrcParams<u64>, data: &[u8]) -> u64 {
+        let width = p.width;
+        let top = 1u64 << (width - 1);
+        let mask = width_mask(width);
+        let mut register = p.init;
+        for &byte in data {
+            let byte = if p.refin { byte.reverse_bits() } else { byte };
+            for i in (0..8).rev() {
+                let feedback = ((register & top) != 0) ^ ((byte >> i) & 1 == 1);
+                register = (register << 1) & mask;
+                if feedback {
+                    register ^= p.poly;
+                }
+            }
+        }
+        if p.refout {
+            register = reflect(register, width);
+        }
+        register ^ p.xorout
+    }
+
+    #[test]
+    fn known_answers() {
+        // Check values of the catalogue of parametrised CRC algorithms
+        let crc = Crc::new(params(
+            32,
+            0x04C1_1DB7u32,
+            0xFFFF_FFFF,
+            true,
+            true,
+            0xFFFF_FFFF,
+        ));
+        assert_eq!(crc.checksum(CHECK), 0xCBF4_3926);
+
+        // CRC-16/XMODEM
+        let crc = Crc::new(params(16, 0x1021u16, 0, false, false, 0));
+        assert_eq!(crc.checksum(CHECK), 0x31C3);
+
+        // CRC-8/SMBUS
+        let crc = Crc::new(params(8, 0x07u8, 0, false, false, 0));
+        assert_eq!(crc.checksum(CHECK), 0xF4);
+
+        // CRC-5/USB, narrower than a byte
+        let crc = Crc::new(params(5, 0x05u8, 0x1F, true, true, 0x1F));
+        assert_eq!(crc.checksum(CHECK), 0x19);
+
+        // CRC-3/GSM
+        let crc = Crc::new(params(3, 0x3u8, 0, false, false, 0x7));
+        assert_eq!(crc.checksum(CHECK), 0x4);
+
+        // CRC-12/UMTS reflects only the output
+        let crc = Crc::new(params(12, 0x80Fu16, 0, false, true, 0));
+        assert_eq!(crc.checksum(CHECK), 0xDAF);
+
+        // CRC-24/OPENPGP in a u32
+        let crc = Crc::new(params(24, 0x86_4CFBu32, 0xB7_04CE, false, false, 0));
+        assert_eq!(crc.checksum(CHECK), 0x21_CF02);
+
+        // CRC-64/XZ
+        let crc = Crc::new(params(
+            64,
+            0x42F0_E1EB_A9EA_3693u64,
+            u64::MAX,
+            true,
+            true,
+            u64::MAX,
+        ));
+        assert_eq!(crc.checksum(CHECK), 0x995D_C9BB_DF19_39FA);
+    }
+
+    #[test]
+    fn matches_bitwise() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let width = rng.gen_range(1..=64);
+            let mask = width_mask(width);
+            let p = params(
+                width,
+                rng.gen::<u64>() & mask,
+                rng.gen::<u64>() & mask,
+                rng.gen(),
+                rng.gen(),
+                rng.gen::<u64>() & mask,
+            );
+            let data: Vec<u8> = (0..rng.gen_range(0..50)).map(|_| rng.gen()).collect();
+            assert_eq!(Crc::new(p).checksum(&data), bitwise(&p, &data));
+        }
+    }
+
+    #[test]
+    fn slicing() {
+        let mut rng = rand::thread_rng();
+        let data: Vec<u8> = (0..300).map(|_| rng.gen()).collect();
+        for width in [3, 8, 12, 16, 31, 32, 57, 64] {
+            let mask = width_mask(width);
+            for refin in [false, true] {
+                let crc = Crc::new(params(
+                    width,
+                    rng.gen::<u64>() & mask,
+                    rng.gen::<u64>() & mask,
+                    refin,
+                    rng.gen(),
+                    0,
+                ));
+                for (start, end) in [(0, 300), (1, 300), (3, 250), (5, 13), (7, 8)] {
+                    let chunk = &data[start..end];
+                    let sliced = crc.update_sliced(crc.initial_register(), chunk);
+                    let bytewise = crc.update_bytewise(crc.initial_register(), chunk);
+                    assert_eq!(sliced, bytewise);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn folding() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let width = rng.gen_range(1..=64);
+            let mask = width_mask(width);
+            let p = params(
+                width,
+                rng.gen::<u64>() & mask,
+                rng.gen::<u64>() & mask,
+                rng.gen(),
+                rng.gen(),
+                rng.gen::<u64>() & mask,
+            );
+            let crc = Crc::new(p);
+            let data: Vec<u8> = (0..rng.gen_range(0..200)).map(|_| rng.gen()).collect();
+            let folded = crc.update_folded(crc.initial_register(), &data);
+            assert_eq!(crc.finalize_register(folded), bitwise(&p, &data));
+        }
+
+        let crc = Crc::new(CRC_32);
+        let folded = crc.update_folded(crc.initial_register(), b"123456789");
+        assert_eq!(crc.finalize_register(folded), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn digest() {
+        let crc = Crc::new(params(16, 0x8005u16, 0, true, true, 0));
+        let data: Vec<u8> = (0..100).map(|_| rand::thread_rng().gen()).collect();
+
+        let mut digest = crc.digest();
+        assert_eq!(digest.finalize(), crc.checksum(&[]));
+        for chunk in data.chunks(7) {
+            digest.update(chunk);
+        }
+        assert_eq!(digest.finalize(), crc.checksum(&data));
+
+        digest.reset();
+        digest.update(CHECK);
+        assert_eq!(digest.finalize(), 0xBB3D);
+        assert_eq!(crc.params().width, 16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_wide() {
+        let _ = Crc::new(params(9, 0x07u8, 0, false, false, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn poly_too_large() {
+        let _ = Crc::new(params(12, 0x180Fu16, 0, false, false, 0));
+    }
+}
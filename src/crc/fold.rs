@@ -0,0 +1,80 @@
+use crate::gf2::clmul::CarryLessMultiply;
+use crate::polys::pow_x_mod;
+
+// Constants for folding 8 bytes at a time with carry-less multiplication and a final Barrett reduction.
+// Everything is in the unreflected domain with the CRC register as a polynomial of degree less than w
+#[derive(Clone, Debug)]
+pub(super) struct FoldConstants {
+    width: u32,
+    // The polynomial without its x^w term
+    poly: u64,
+    // x^128 mod P
+    k128: u64,
+    // floor(x^128 / P)
+    mu: u128,
+}
+
+impl FoldConstants {
+    pub(super) fn new(width: u32, poly: u64) -> Self {
+        let full_poly = (1u128 << width) | poly as u128;
+
+        // x^128 / P = 2 * (x^127 / P) + (2 * (x^127 mod P)) / P, where the last quotient is 0 or 1
+        let (q, r) = div_gf2(1 << 127, full_poly);
+        let mu = (q << 1) | ((r >> (width - 1)) & 0x1);
+
+        Self {
+            width,
+            poly,
+            k128: pow_x_mod(128, full_poly) as u64,
+            mu,
+        }
+    }
+
+    // Returns (r * x^(8 len) + D(x) * x^w) mod P for the bytes of data, most significant bit first
+    pub(super) fn fold(&self, register: u64, data: &[u8], refin: bool) -> u64 {
+        // Invariant: acc ≡ the register after the chunks so far (mod P). With acc = hi * x^64 + lo,
+        // acc * x^64 + c * x^w ≡ hi * (x^128 mod P) + lo * x^64 + c * x^w
+        let mut acc = register as u128;
+        for chunk in data.chunks_exact(8) {
+            let c = if refin {
+                u64::from_le_bytes(chunk.try_into().unwrap()).reverse_bits()
+            } else {
+                u64::from_be_bytes(chunk.try_into().unwrap())
+            };
+            let (hi, lo) = ((acc >> 64) as u64, acc as u64);
+            acc = clmul_64(hi, self.k128) ^ ((lo as u128) << 64) ^ ((c as u128) << self.width);
+        }
+        self.reduce(acc)
+    }
+
+    // Barrett reduction of a mod P: the quotient is floor(floor(a / x^w) * μ / x^(128 - w))
+    fn reduce(&self, a: u128) -> u64 {
+        let w = self.width;
+        let a_hi = a >> w;
+        let (prod_hi, prod_lo) = (a_hi.clmul_high(self.mu), a_hi.clmul_low(self.mu));
+        let q = (prod_hi << w) | (prod_lo >> (128 - w));
+
+        // Only the low w bits of q * P are needed, and the x^w term of P does not reach them
+        let mask = u64::MAX >> (64 - w);
+        ((a ^ q.clmul_low(self.poly as u128)) as u64) & mask
+    }
+}
+
+// The full 128 bit product of a and b
+fn clmul_64(a: u64, b: u64) -> u128 {
+    ((a.clmul_high(b) as u128) << 64) | a.clmul_low(b) as u128
+}
+
+// Returns (a / b, a mod b) over GF(2). b must be non zero
+fn div_gf2(a: u128, b: u128) -> (u128, u128) {
+    let deg_b = crate::calc_degree(b);
+    let mut q = 0;
+    let mut r = a;
+    let mut deg_r = crate::calc_degree(r);
+    while deg_r >= deg_b {
+        q |= 1 << (deg_r - deg_b);
+        r ^= b << (deg_r - deg_b);
+        deg_r = crate::calc_degree(r);
+    }
+    (q, r)
+}
@@ -0,0 +1,129 @@
+use super::CrcParams;
+
+/// CRC-8/I-432-1, the header error control of ATM cells
+pub const CRC_8_ATM: CrcParams<u8> = CrcParams {
+    width: 8,
+    poly: 0x07,
+    init: 0x00,
+    refin: false,
+    refout: false,
+    xorout: 0x55,
+};
+
+/// CRC-16/IBM-3740, often called CRC-16-CCITT or CRC-16/CCITT-FALSE
+pub const CRC_16_CCITT_FALSE: CrcParams<u16> = CrcParams {
+    width: 16,
+    poly: 0x1021,
+    init: 0xFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+};
+
+/// CRC-16/KERMIT, the reflected CRC-16-CCITT of HDLC and Bluetooth
+pub const CRC_16_KERMIT: CrcParams<u16> = CrcParams {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+};
+
+/// CRC-16/XMODEM, the CRC-16-CCITT of XMODEM and many radio protocols
+pub const CRC_16_XMODEM: CrcParams<u16> = CrcParams {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+};
+
+/// CRC-16/ARC, also known as CRC-16-IBM or plain CRC-16
+pub const CRC_16_IBM: CrcParams<u16> = CrcParams {
+    width: 16,
+    poly: 0x8005,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+};
+
+/// CRC-32/ISO-HDLC, the CRC-32 of Ethernet, zip, gzip and PNG
+pub const CRC_32: CrcParams<u32> = CrcParams {
+    width: 32,
+    poly: 0x04C1_1DB7,
+    init: 0xFFFF_FFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFF_FFFF,
+};
+
+/// CRC-32/ISCSI, the Castagnoli CRC-32C of iSCSI, SCTP, ext4 and the SSE4.2 crc32 instruction
+pub const CRC_32C: CrcParams<u32> = CrcParams {
+    width: 32,
+    poly: 0x1EDC_6F41,
+    init: 0xFFFF_FFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFF_FFFF,
+};
+
+/// CRC-64/ECMA-182, as used by DLT tapes
+pub const CRC_64_ECMA: CrcParams<u64> = CrcParams {
+    width: 64,
+    poly: 0x42F0_E1EB_A9EA_3693,
+    init: 0,
+    refin: false,
+    refout: false,
+    xorout: 0,
+};
+
+/// CRC-64/XZ, the reflected ECMA-182 CRC of xz and 7-Zip
+pub const CRC_64_XZ: CrcParams<u64> = CrcParams {
+    width: 64,
+    poly: 0x42F0_E1EB_A9EA_3693,
+    init: u64::MAX,
+    refin: true,
+    refout: true,
+    xorout: u64::MAX,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crc::Crc;
+
+    // The check values of the catalogue of parametrised CRC algorithms are the CRCs of "123456789"
+    const CHECK: &[u8] = b"123456789";
+
+    #[test]
+    fn check_values() {
+        assert_eq!(Crc::new(CRC_8_ATM).checksum(CHECK), 0xA1);
+        assert_eq!(Crc::new(CRC_16_CCITT_FALSE).checksum(CHECK), 0x29B1);
+        assert_eq!(Crc::new(CRC_16_KERMIT).checksum(CHECK), 0x2189);
+        assert_eq!(Crc::new(CRC_16_XMODEM).checksum(CHECK), 0x31C3);
+        assert_eq!(Crc::new(CRC_16_IBM).checksum(CHECK), 0xBB3D);
+        assert_eq!(Crc::new(CRC_32).checksum(CHECK), 0xCBF4_3926);
+        assert_eq!(Crc::new(CRC_32C).checksum(CHECK), 0xE306_9283);
+        assert_eq!(Crc::new(CRC_64_ECMA).checksum(CHECK), 0x6C40_DF5F_0B49_7347);
+        assert_eq!(Crc::new(CRC_64_XZ).checksum(CHECK), 0x995D_C9BB_DF19_39FA);
+    }
+
+    #[test]
+    fn other_known_answers() {
+        // The empty message leaves init ^ xorout, after reflection
+        assert_eq!(Crc::new(CRC_32).checksum(&[]), 0);
+        assert_eq!(Crc::new(CRC_16_CCITT_FALSE).checksum(&[]), 0xFFFF);
+
+        // CRC-32 of the pangram used in many test suites
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(Crc::new(CRC_32).checksum(fox), 0x414F_A339);
+        assert_eq!(Crc::new(CRC_32C).checksum(fox), 0x2262_0404);
+
+        // 32 bytes of zeros from RFC 3720 appendix B.4
+        assert_eq!(Crc::new(CRC_32C).checksum(&[0; 32]), 0x8A91_36AA);
+        assert_eq!(Crc::new(CRC_32C).checksum(&[0xFF; 32]), 0x62A8_AB43);
+    }
+}
@@ -0,0 +1,392 @@
+//! Cyclic codes over GF(2<sup>M</sup>) defined by a generator polynomial.
+//!
+//! The codewords of length n are the multiples of the generator g(x). The code is cyclic when g(x) divides
+//! x<sup>n</sup> - 1, otherwise it is a shortened cyclic code such as a CRC. Codewords are stored highest degree
+//! first, and encoding is systematic: the k message symbols followed by the n - k parity symbols.
+//!
+//! Decoding is delegated to a `CyclicDecoder`. `DetectOnly` only detects errors, `ErrorTrapping` corrects error
+//! patterns that fit in n - k cyclically consecutive symbols, and `ReedSolomon` and `Bch` decode as they do on their
+//! own.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::cyclic::{CyclicCode, ErrorTrapping};
+//! use galois_field_2pm::poly::Polynomial;
+//!
+//! // The (7, 4) Hamming code is cyclic with g(x) = x^3 + x + 1
+//! type GF = gf2::GFu8<0x3>;
+//! let g = Polynomial::from_gf2(0xB);
+//! let code = CyclicCode::<GF>::new(g, 7).with_decoder(ErrorTrapping::new(1));
+//! assert!(code.is_cyclic());
+//!
+//! let message = [GF::ONE, GF::ZERO, GF::ONE, GF::ONE];
+//! let mut received = code.encode(&message);
+//! received[2] += GF::ONE;
+//! assert_eq!(code.decode(&mut received), Some(1));
+//! assert_eq!(received[..4], message[..]);
+//! ```
+
+use crate::bch::Bch;
+use crate::poly::{PolyRemainderState, Polynomial};
+use crate::rs::ReedSolomon;
+use crate::GaloisField;
+
+/// Corrects received words of a cyclic code
+pub trait CyclicDecoder<GF: GaloisField> {
+    /// Corrects received in place. Returns the number of corrected symbols, or None if the errors could not be
+    /// corrected, in which case received is left unchanged
+    fn decode(&self, code: &CyclicCode<GF, Self>, received: &mut [GF]) -> Option<usize>
+    where
+        Self: Sized;
+}
+
+/// A decoder that corrects nothing: codewords decode with no errors and everything else fails, as for a CRC
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DetectOnly;
+
+impl<GF: GaloisField> CyclicDecoder<GF> for DetectOnly {
+    fn decode(&self, code: &CyclicCode<GF, Self>, received: &mut [GF]) -> Option<usize> {
+        code.is_codeword(received).then_some(0)
+    }
+}
+
+/// Error trapping decoding of a cyclic code. The received word is shifted cyclically until its syndrome is a
+/// correctable error pattern confined to the parity positions. This corrects error patterns of weight up to t that
+/// fall in n - k cyclically consecutive positions, or every burst up to a given length, as long as the code can
+/// correct them at all
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorTrapping {
+    max_weight: usize,
+    max_burst: usize,
+}
+
+impl ErrorTrapping {
+    /// Traps error patterns of weight up to t
+    pub fn new(t: usize) -> Self {
+        Self {
+            max_weight: t,
+            max_burst: usize::MAX,
+        }
+    }
+
+    /// Traps cyclic bursts of length up to len, that is error patterns whose nonzero symbols lie within len
+    /// cyclically consecutive positions
+    pub fn burst(len: usize) -> Self {
+        Self {
+            max_weight: usize::MAX,
+            max_burst: len,
+        }
+    }
+
+    // A shifted syndrome is trapped when it is light enough and its nonzero coefficients lie in degrees below
+    // max_burst
+    fn traps<GF: GaloisField>(&self, syndrome: &Polynomial<GF>) -> bool {
+        let weight = syndrome.coeffs().iter().filter(|&&c| c != GF::ZERO).count();
+        let span = syndrome.degree() + 1;
+        weight <= self.max_weight && (span as usize) <= self.max_burst
+    }
+}
+
+impl<GF: GaloisField> CyclicDecoder<GF> for ErrorTrapping {
+    /// Panics unless the code is cyclic and received has n symbols
+    fn decode(&self, code: &CyclicCode<GF, Self>, received: &mut [GF]) -> Option<usize> {
+        if !code.is_cyclic() {
+            panic!("Error trapping needs a cyclic code");
+        }
+        let n = code.n();
+
+        // The syndrome of x^j r(x) mod (x^n - 1) is x^j s(x) mod g(x). For the right j it is the shifted error
+        // pattern itself. Coefficient i of it is then the error at degree i - j mod n
+        let x = Polynomial::monomial(GF::ONE, 1);
+        let mut syndrome = code.syndrome(received);
+        for j in 0..n {
+            if self.traps(&syndrome) {
+                let mut corrected = received.to_vec();
+                let mut count = 0;
+                for (i, &e) in syndrome.coeffs().iter().enumerate() {
+                    if e != GF::ZERO {
+                        let degree = (i + n - j) % n;
+                        corrected[n - 1 - degree] -= e;
+                        count += 1;
+                    }
+                }
+                if !code.is_codeword(&corrected) {
+                    return None;
+                }
+                received.copy_from_slice(&corrected);
+                return Some(count);
+            }
+            syndrome = (&syndrome * &x).rem(code.generator_poly());
+        }
+        None
+    }
+}
+
+impl<GF: GaloisField> CyclicDecoder<GF> for ReedSolomon<GF> {
+    /// Panics unless the Reed-Solomon code has the same length and generator
+    fn decode(&self, code: &CyclicCode<GF, Self>, received: &mut [GF]) -> Option<usize> {
+        if self.n() != code.n() || self.generator_poly() != code.generator_poly() {
+            panic!("The Reed-Solomon code does not match the cyclic code");
+        }
+        ReedSolomon::decode(self, received)
+    }
+}
+
+/// A code of length n whose codewords are the multiples of a generator polynomial, decoded by D
+#[derive(Clone, Debug)]
+pub struct CyclicCode<GF: GaloisField, D = DetectOnly> {
+    n: usize,
+    generator: Polynomial<GF>,
+    decoder: D,
+}
+
+impl<GF: GaloisField> CyclicCode<GF> {
+    /// Constructs the code of length n generated by generator, which only detects errors. Panics unless the
+    /// generator has degree at least 1 and less than n
+    pub fn new(generator: Polynomial<GF>, n: usize) -> Self {
+        let degree = generator.degree();
+        if degree < 1 || degree as usize >= n {
+            panic!("The generator must have degree at least 1 and less than n");
+        }
+        Self {
+            n,
+            generator,
+            decoder: DetectOnly,
+        }
+    }
+}
+
+impl<GF: GaloisField, D> CyclicCode<GF, D> {
+    /// Replaces the decoder
+    pub fn with_decoder<E: CyclicDecoder<GF>>(self, decoder: E) -> CyclicCode<GF, E> {
+        CyclicCode {
+            n: self.n,
+            generator: self.generator,
+            decoder,
+        }
+    }
+
+    /// The decoder
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// The codeword length n
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The message length k
+    pub fn k(&self) -> usize {
+        self.n - self.parity_len()
+    }
+
+    /// The number of parity symbols n - k, the degree of the generator
+    pub fn parity_len(&self) -> usize {
+        self.generator.degree() as usize
+    }
+
+    /// The generator polynomial
+    pub fn generator_poly(&self) -> &Polynomial<GF> {
+        &self.generator
+    }
+
+    /// Returns true if the generator divides x<sup>n</sup> - 1, so every cyclic shift of a codeword is a codeword
+    pub fn is_cyclic(&self) -> bool {
+        let mut x_n = Polynomial::monomial(GF::ONE, self.n);
+        x_n -= Polynomial::one();
+        x_n.rem(&self.generator).is_zero()
+    }
+
+    /// Computes the n - k parity symbols of a message, highest degree first. Panics unless the message has k symbols
+    pub fn parity(&self, message: &[GF]) -> Vec<GF> {
+        if message.len() != self.k() {
+            panic!("Message length does not match k");
+        }
+
+        // The parity is -(m(x) * x^(n - k) mod g(x))
+        let mut state = PolyRemainderState::new(&self.generator);
+        state.update_slice(message);
+        state.remainder_coeffs().iter().rev().map(|&r| -r).collect()
+    }
+
+    /// Encodes a message into a codeword: the message followed by its parity. Panics unless the message has k symbols
+    pub fn encode(&self, message: &[GF]) -> Vec<GF> {
+        let mut codeword = message.to_vec();
+        codeword.extend(self.parity(message));
+        codeword
+    }
+
+    /// The syndrome r(x) mod g(x). It is zero exactly when received is a codeword. Panics unless received has n
+    /// symbols
+    pub fn syndrome(&self, received: &[GF]) -> Polynomial<GF> {
+        if received.len() != self.n {
+            panic!("Received word length does not match n");
+        }
+        let coeffs: Vec<GF> = received.iter().rev().copied().collect();
+        Polynomial::new(coeffs).rem(&self.generator)
+    }
+
+    /// Returns true if received is a codeword. Panics unless received has n symbols
+    pub fn is_codeword(&self, received: &[GF]) -> bool {
+        self.syndrome(received).is_zero()
+    }
+}
+
+impl<GF: GaloisField, D: CyclicDecoder<GF>> CyclicCode<GF, D> {
+    /// Corrects received in place with the decoder. Returns the number of corrected symbols, or None if the errors
+    /// could not be corrected, in which case received is left unchanged. Panics unless received has n symbols
+    pub fn decode(&self, received: &mut [GF]) -> Option<usize> {
+        if received.len() != self.n {
+            panic!("Received word length does not match n");
+        }
+        self.decoder.decode(self, received)
+    }
+}
+
+impl<GF: GaloisField> ReedSolomon<GF> {
+    /// The code as a cyclic code decoded by itself
+    pub fn cyclic_code(&self) -> CyclicCode<GF, Self> {
+        CyclicCode::new(self.generator_poly().clone(), self.n()).with_decoder(self.clone())
+    }
+}
+
+impl<GF: GaloisField> Bch<GF> {
+    /// The code as a cyclic code over GF(2<sup>M</sup>) with binary codewords. Decoding corrects the bits of
+    /// received words whose symbols are all 0 or 1
+    pub fn cyclic_code(&self) -> CyclicCode<GF, Self> {
+        CyclicCode::new(self.generator_poly().clone(), self.n()).with_decoder(self.clone())
+    }
+}
+
+impl<GF: GaloisField> CyclicDecoder<GF> for Bch<GF> {
+    /// Returns None if received has a symbol other than 0 or 1. Panics unless the BCH code has the same length
+    fn decode(&self, code: &CyclicCode<GF, Self>, received: &mut [GF]) -> Option<usize> {
+        if self.n() != code.n() {
+            panic!("The BCH code does not match the cyclic code");
+        }
+        if received.iter().any(|&s| s != GF::ZERO && s != GF::ONE) {
+            return None;
+        }
+        let mut bits: Vec<bool> = received.iter().map(|&s| s == GF::ONE).collect();
+        let corrected = Bch::decode(self, &mut bits)?;
+        for (s, &b) in received.iter_mut().zip(bits.iter()) {
+            *s = if b { GF::ONE } else { GF::ZERO };
+        }
+        Some(corrected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+    type Bit = gf2::GFu8<0x3>;
+
+    #[test]
+    fn matches_reed_solomon() {
+        let rs = ReedSolomon::<GF>::new(30, 20);
+        let code = rs.cyclic_code();
+        let message: Vec<GF> = (0..20).map(|_| GF::new(rand::thread_rng().gen())).collect();
+        let codeword = code.encode(&message);
+        assert_eq!(codeword, rs.encode(&message));
+        assert!(code.is_codeword(&codeword));
+
+        let mut received = codeword.clone();
+        received[3] += GF::new(9);
+        received[25] += GF::new(1);
+        assert!(!code.syndrome(&received).is_zero());
+        assert_eq!(code.decode(&mut received), Some(2));
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn bch_decoder() {
+        let bch = Bch::<gf2::GFu16<0x13>>::new(5, 3);
+        let code = bch.cyclic_code();
+        assert!(code.is_cyclic());
+        let to_symbols = |bits: &[bool]| -> Vec<gf2::GFu16<0x13>> {
+            bits.iter().map(|&b| gf2::GFu16::new(b as u16)).collect()
+        };
+        let message = [true, false, false, true, true];
+        let codeword = to_symbols(&bch.encode(&message));
+        assert_eq!(code.encode(&to_symbols(&message)), codeword);
+
+        let mut received = codeword.clone();
+        for p in [0, 6, 14] {
+            received[p] += gf2::GFu16::ONE;
+        }
+        assert_eq!(code.decode(&mut received), Some(3));
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn detect_only() {
+        // CRC-8 style: x^8 + x^2 + x + 1 over bits, shortened to 40 bits
+        let code = CyclicCode::<Bit>::new(Polynomial::from_gf2(0x107), 40);
+        assert!(!code.is_cyclic());
+        let message: Vec<Bit> = (0..32)
+            .map(|_| Bit::new(rand::thread_rng().gen_range(0..2)))
+            .collect();
+        let mut received = code.encode(&message);
+        assert_eq!(code.decode(&mut received), Some(0));
+        received[17] += Bit::ONE;
+        let before = received.clone();
+        assert_eq!(code.decode(&mut received), None);
+        assert_eq!(received, before);
+    }
+
+    #[test]
+    fn error_trapping_bursts() {
+        // The (15, 9) code generated by x^6 + x^5 + x^4 + x^3 + 1 corrects every burst of length 3 or less
+        let code = CyclicCode::<Bit>::new(Polynomial::from_gf2(0x79), 15)
+            .with_decoder(ErrorTrapping::burst(3));
+        assert!(code.is_cyclic());
+        for start in 0..15 {
+            for pattern in [0b1, 0b11, 0b101, 0b111] {
+                let message: Vec<Bit> = (0..9)
+                    .map(|_| Bit::new(rand::thread_rng().gen_range(0..2)))
+                    .collect();
+                let codeword = code.encode(&message);
+                let mut received = codeword.clone();
+                // Bursts may wrap around the end of the word
+                for i in 0..3 {
+                    if (pattern >> i) & 1 == 1 {
+                        received[(start + i) % 15] += Bit::ONE;
+                    }
+                }
+                assert_eq!(
+                    code.decode(&mut received),
+                    Some((pattern as u32).count_ones() as usize)
+                );
+                assert_eq!(received, codeword);
+            }
+        }
+    }
+
+    #[test]
+    fn error_trapping_symbols() {
+        let rs = ReedSolomon::<GF>::new(255, 251);
+        let code = CyclicCode::<GF>::new(rs.generator_poly().clone(), 255)
+            .with_decoder(ErrorTrapping::new(2));
+        let message: Vec<GF> = (0..251)
+            .map(|_| GF::new(rand::thread_rng().gen()))
+            .collect();
+        let codeword = code.encode(&message);
+        for p in [0, 100, 251, 254] {
+            let mut received = codeword.clone();
+            received[p] += GF::new(0x5a);
+            assert_eq!(code.decode(&mut received), Some(1));
+            assert_eq!(received, codeword);
+
+            // Two errors within n - k = 4 consecutive positions
+            received[(p + 3) % 255] += GF::new(rand::thread_rng().gen_range(1..=255));
+            received[p] += GF::new(0x5a);
+            assert_eq!(code.decode(&mut received), Some(2));
+            assert_eq!(received, codeword);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! A common interface for erasure codes over byte shards.
+//!
+//! An erasure code turns k data shards into n - k parity shards, all of equal length, such that lost shards can be
+//! rebuilt from the remaining ones. Storage code written against `ErasureCode` works with any of the codes:
+//! `ReedSolomon` over GF(2<sup>8</sup>), GF(2<sup>16</sup>), GF(2<sup>32</sup>) or GF(2<sup>64</sup>), the RAID-6
+//! dual parity `Raid6`, the single parity `XorParity` and the locally repairable `Lrc`, whose `Lrc::repair_plan`
+//! reads only the local group of a single lost shard.
+//!
+//! The product-matrix regenerating codes `ProductMatrixMbr` and `ProductMatrixMsr` work on field elements rather
+//! than byte shards. They rebuild a lost node from one symbol of each of d helpers, far less than the whole message
+//! a Reed-Solomon repair downloads.
+//!
+//! ```
+//! use galois_field_2pm::erasure::{ErasureCode, XorParity};
+//!
+//! fn store_and_lose<C: ErasureCode>(code: &C, data: &[&[u8]], lost: usize) -> Vec<Vec<u8>> {
+//!     let mut shards: Vec<Option<Vec<u8>>> = data.iter().map(|d| Some(d.to_vec())).collect();
+//!     shards.extend(code.encode_shards(data).into_iter().map(Some));
+//!     shards[lost] = None;
+//!     assert!(code.reconstruct(&mut shards));
+//!     shards.into_iter().map(Option::unwrap).collect()
+//! }
+//!
+//! let code = XorParity::new(3);
+//! let shards = store_and_lose(&code, &[b"abc", b"def", b"ghi"], 1);
+//! assert_eq!(shards[1], b"def");
+//! ```
+
+use crate::rs::ReedSolomon;
+use crate::GaloisField;
+
+mod lrc;
+mod raid6;
+mod regenerating;
+
+pub use lrc::{Lrc, RepairPlan};
+pub use raid6::{Raid6, Raid6Field};
+pub use regenerating::{ProductMatrixMbr, ProductMatrixMsr};
+
+/// An erasure code over byte shards
+pub trait ErasureCode {
+    /// The number of data shards k
+    fn num_data(&self) -> usize;
+
+    /// The number of parity shards n - k
+    fn num_parity(&self) -> usize;
+
+    /// The total number of shards n
+    fn num_shards(&self) -> usize {
+        self.num_data() + self.num_parity()
+    }
+
+    /// Computes the parity shards from k equally long data shards. Panics unless there are k shards of equal length
+    fn encode_shards(&self, data: &[&[u8]]) -> Vec<Vec<u8>>;
+
+    /// Rebuilds missing shards in place. shards holds the n data and parity shards in order, with None for the
+    /// missing ones. Returns false, leaving shards unchanged, if the missing shards cannot be rebuilt.
+    /// Panics unless there are n shards
+    fn reconstruct(&self, shards: &mut [Option<Vec<u8>>]) -> bool;
+
+    /// Returns true if the parity shards match the data shards. shards holds the n data and parity shards in order.
+    /// Panics unless there are n shards
+    fn verify(&self, shards: &[&[u8]]) -> bool {
+        if shards.len() != self.num_shards() {
+            panic!("Number of shards does not match n");
+        }
+        let (data, parity) = shards.split_at(self.num_data());
+        self.encode_shards(data)
+            .iter()
+            .zip(parity)
+            .all(|(expected, &actual)| expected[..] == actual[..])
+    }
+}
+
+impl<GF: GaloisField> ErasureCode for ReedSolomon<GF> {
+    fn num_data(&self) -> usize {
+        self.k()
+    }
+
+    fn num_parity(&self) -> usize {
+        self.parity_len()
+    }
+
+    fn encode_shards(&self, data: &[&[u8]]) -> Vec<Vec<u8>> {
+        ReedSolomon::encode_shards(self, data)
+    }
+
+    fn reconstruct(&self, shards: &mut [Option<Vec<u8>>]) -> bool {
+        ReedSolomon::reconstruct(self, shards)
+    }
+}
+
+/// A single parity shard holding the XOR of the k data shards, as in RAID-5. Any one lost shard can be rebuilt
+#[derive(Clone, Copy, Debug)]
+pub struct XorParity {
+    k: usize,
+}
+
+impl XorParity {
+    /// Constructs the code with k data shards. Panics if k is zero
+    pub fn new(k: usize) -> Self {
+        if k == 0 {
+            panic!("XOR parity needs at least one data shard");
+        }
+        Self { k }
+    }
+}
+
+impl ErasureCode for XorParity {
+    fn num_data(&self) -> usize {
+        self.k
+    }
+
+    fn num_parity(&self) -> usize {
+        1
+    }
+
+    fn encode_shards(&self, data: &[&[u8]]) -> Vec<Vec<u8>> {
+        if data.len() != self.k {
+            panic!("Number of data shards does not match k");
+        }
+        vec![xor_all(data.iter().copied())]
+    }
+
+    fn reconstruct(&self, shards: &mut [Option<Vec<u8>>]) -> bool {
+        if shards.len() != self.k + 1 {
+            panic!("Number of shards does not match n");
+        }
+        let missing: Vec<usize> = (0..shards.len()).filter(|&i| shards[i].is_none()).collect();
+        match missing[..] {
+            [] => true,
+            [lost] => {
+                shards[lost] = Some(xor_all(shards.iter().flatten().map(|s| &s[..])));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+// The XOR of equally long shards. Panics if they differ in length
+fn xor_all<'a>(mut shards: impl Iterator<Item = &'a [u8]>) -> Vec<u8> {
+    let mut out = shards.next().map_or(Vec::new(), |s| s.to_vec());
+    for s in shards {
+        if s.len() != out.len() {
+            panic!("Shards must have the same length");
+        }
+        out.iter_mut().zip(s).for_each(|(o, &b)| *o ^= b);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    fn random_shards(count: usize, len: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|_| (0..len).map(|_| rand::thread_rng().gen()).collect())
+            .collect()
+    }
+
+    // Encodes, loses up to n - k random shards and rebuilds them through the trait alone
+    fn round_trip<C: ErasureCode>(code: &C) {
+        let data = random_shards(code.num_data(), 24);
+        let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        let mut all = data.clone();
+        all.extend(code.encode_shards(&refs));
+        assert_eq!(all.len(), code.num_shards());
+
+        let all_refs: Vec<&[u8]> = all.iter().map(|s| s.as_slice()).collect();
+        assert!(code.verify(&all_refs));
+        let mut corrupted = all.clone();
+        corrupted[0][5] ^= 1;
+        let corrupted_refs: Vec<&[u8]> = corrupted.iter().map(|s| s.as_slice()).collect();
+        assert!(!code.verify(&corrupted_refs));
+
+        let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+        let mut indices: Vec<usize> = (0..code.num_shards()).collect();
+        indices.shuffle(&mut rand::thread_rng());
+        for &i in indices[..code.num_parity()].iter() {
+            shards[i] = None;
+        }
+        assert!(code.reconstruct(&mut shards));
+        let rebuilt: Vec<Vec<u8>> = shards.into_iter().map(Option::unwrap).collect();
+        assert_eq!(rebuilt, all);
+
+        // One shard too many lost
+        let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+        for &i in indices[..=code.num_parity()].iter() {
+            shards[i] = None;
+        }
+        assert!(!code.reconstruct(&mut shards));
+    }
+
+    #[test]
+    fn reed_solomon() {
+        round_trip(&ReedSolomon::<gf2_lut::GFu8<0x11d>>::new(9, 6));
+    }
+
+    #[test]
+    fn raid6() {
+        round_trip(&Raid6::new(4));
+    }
+
+    #[test]
+    fn xor_parity() {
+        round_trip(&XorParity::new(5));
+    }
+}
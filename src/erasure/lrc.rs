@@ -0,0 +1,443 @@
+use super::{xor_all, ErasureCode};
+use crate::linalg::Matrix;
+use crate::rs::{mul_add, ReedSolomon};
+use crate::GaloisField;
+
+/// A locally repairable code in the style of Azure storage. The k data shards are split into l local groups of
+/// consecutive shards, sizes differing by at most one, and each group gets the XOR of its data as a local parity.
+/// r global parities G<sub>j</sub> = Σ α<sub>i</sub><sup>j+1</sup> D<sub>i</sub> with α<sub>i</sub> = x<sup>i</sup>
+/// cover all the data. The shards are the k data shards, then the l local parities, then the r global parities.
+///
+/// A single lost shard of a group is rebuilt from the k / l other shards of its group instead of k shards. With
+/// r ≤ 2 any r + 1 lost shards can be rebuilt, as can many patterns of up to l + r. Shards hold symbols as in
+/// `ReedSolomon::encode_shards`, so POLY must be primitive and the field GF(2<sup>8</sup>), GF(2<sup>16</sup>),
+/// GF(2<sup>32</sup>) or GF(2<sup>64</sup>)
+#[derive(Clone, Debug)]
+pub struct Lrc<GF: GaloisField> {
+    k: usize,
+    l: usize,
+    r: usize,
+    // The α_i
+    alphas: Vec<GF>,
+}
+
+/// The shards `Lrc::reconstruct` reads for a failure pattern and how it rebuilds the lost ones
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepairPlan {
+    /// The shards read, in increasing order
+    pub reads: Vec<usize>,
+    /// The lost shards rebuilt from the rest of their local group alone
+    pub local: Vec<usize>,
+    /// The lost shards rebuilt after solving for the lost data with the global parities
+    pub global: Vec<usize>,
+}
+
+impl RepairPlan {
+    /// The number of shards read
+    pub fn cost(&self) -> usize {
+        self.reads.len()
+    }
+}
+
+// A repair plan together with the lost data shards it solves for and the parity shards whose equations it uses
+struct Solution {
+    plan: RepairPlan,
+    unknowns: Vec<usize>,
+    equations: Vec<usize>,
+}
+
+impl<GF: GaloisField> Lrc<GF> {
+    /// Constructs the code with k data shards in l local groups and r global parities.
+    /// Panics unless 0 < l ≤ k < 2<sup>M</sup>
+    pub fn new(k: usize, l: usize, r: usize) -> Self {
+        if l == 0 || l > k || k as u128 >= GF::NUM_ELEM {
+            panic!("Locally repairable codes need 0 < l <= k < 2^M");
+        }
+        let x = GF::ONE.mul_x();
+        Self {
+            k,
+            l,
+            r,
+            alphas: (0..k).map(|i| x.pow(i as u128)).collect(),
+        }
+    }
+
+    /// The number of local groups l
+    pub fn num_groups(&self) -> usize {
+        self.l
+    }
+
+    /// The number of global parities r
+    pub fn num_global(&self) -> usize {
+        self.r
+    }
+
+    /// The local group of a data or local parity shard, or None for a global parity
+    pub fn group(&self, shard: usize) -> Option<usize> {
+        if shard < self.k {
+            Some(shard * self.l / self.k)
+        } else if shard < self.k + self.l {
+            Some(shard - self.k)
+        } else {
+            None
+        }
+    }
+
+    /// The data shards of group g followed by its local parity
+    pub fn group_members(&self, g: usize) -> Vec<usize> {
+        (0..self.k)
+            .filter(|&i| self.group(i) == Some(g))
+            .chain([self.k + g])
+            .collect()
+    }
+
+    /// Plans the repair of the lost shards, preferring local repairs: a group missing one shard is rebuilt from
+    /// the rest of the group. The data of groups missing more is solved for with the local parities of those
+    /// groups and as few global parities as possible, which needs all present data. Returns None if the lost
+    /// shards cannot be rebuilt. Panics if a shard index is not less than n
+    pub fn repair_plan(&self, lost: &[usize]) -> Option<RepairPlan> {
+        self.solve(lost).map(|s| s.plan)
+    }
+
+    fn solve(&self, lost: &[usize]) -> Option<Solution> {
+        let n = self.num_shards();
+        if lost.iter().any(|&i| i >= n) {
+            panic!("Shard index out of range");
+        }
+        let is_lost = |i: usize| lost.contains(&i);
+        let mut reads = Vec::new();
+        let mut local = Vec::new();
+        let mut global = Vec::new();
+        let mut unknowns = Vec::new();
+
+        for g in 0..self.l {
+            let members = self.group_members(g);
+            let (missing, present): (Vec<usize>, Vec<usize>) =
+                members.into_iter().partition(|&i| is_lost(i));
+            if missing.len() == 1 {
+                local.push(missing[0]);
+                reads.extend(present);
+            } else {
+                unknowns.extend(missing.iter().filter(|&&i| i < self.k));
+                global.extend(missing);
+            }
+        }
+        global.extend((self.k + self.l..n).filter(|&i| is_lost(i)));
+
+        // Each local parity of a group with unknowns and each global parity gives a linear equation in the
+        // unknowns. Locals are taken first since they only need their group
+        let mut equations = Vec::new();
+        if !unknowns.is_empty() {
+            let candidates = unknowns
+                .iter()
+                .filter_map(|&i| self.group(i))
+                .map(|g| self.k + g)
+                .chain(self.k + self.l..n)
+                .filter(|&p| !is_lost(p));
+            let mut rows: Vec<Vec<GF>> = Vec::new();
+            for p in candidates {
+                if rows.len() == unknowns.len() || equations.contains(&p) {
+                    continue;
+                }
+                rows.push(unknowns.iter().map(|&i| self.coefficient(i, p)).collect());
+                if Matrix::from_rows(&rows).rank() == rows.len() {
+                    equations.push(p);
+                } else {
+                    rows.pop();
+                }
+            }
+            if rows.len() < unknowns.len() {
+                return None;
+            }
+        }
+
+        // Solving with a global parity or recomputing one needs every data shard
+        if equations.iter().any(|&p| p >= self.k + self.l)
+            || global.iter().any(|&p| p >= self.k + self.l)
+        {
+            reads.extend((0..self.k).filter(|&i| !is_lost(i)));
+        } else {
+            for &p in equations.iter() {
+                reads.extend(
+                    self.group_members(p - self.k)
+                        .into_iter()
+                        .filter(|&i| i < self.k && !is_lost(i)),
+                );
+            }
+        }
+        reads.extend(equations.iter().copied());
+        reads.sort_unstable();
+        reads.dedup();
+        local.sort_unstable();
+        global.sort_unstable();
+
+        Some(Solution {
+            plan: RepairPlan {
+                reads,
+                local,
+                global,
+            },
+            unknowns,
+            equations,
+        })
+    }
+
+    // The coefficient of data shard i in parity shard p
+    fn coefficient(&self, i: usize, p: usize) -> GF {
+        if p < self.k + self.l {
+            if self.group(i) == Some(p - self.k) {
+                GF::ONE
+            } else {
+                GF::ZERO
+            }
+        } else {
+            self.alphas[i].pow((p - self.k - self.l + 1) as u128)
+        }
+    }
+
+    // Σ coefficient(i, p) * data[i], with data holding every data shard
+    fn parity_shard(&self, p: usize, data: &[&[u8]]) -> Vec<u8> {
+        if p < self.k + self.l {
+            return xor_all(
+                (0..self.k)
+                    .filter(|&i| self.group(i) == Some(p - self.k))
+                    .map(|i| data[i]),
+            );
+        }
+        let mut out = vec![0u8; data[0].len()];
+        for (i, d) in data.iter().enumerate() {
+            mul_add(self.coefficient(i, p), d, &mut out);
+        }
+        out
+    }
+}
+
+impl<GF: GaloisField> ErasureCode for Lrc<GF> {
+    fn num_data(&self) -> usize {
+        self.k
+    }
+
+    fn num_parity(&self) -> usize {
+        self.l + self.r
+    }
+
+    fn encode_shards(&self, data: &[&[u8]]) -> Vec<Vec<u8>> {
+        if data.len() != self.k {
+            panic!("Number of data shards does not match k");
+        }
+        let len = data[0].len();
+        if data.iter().any(|d| d.len() != len)
+            || !len.is_multiple_of(ReedSolomon::<GF>::symbol_bytes())
+        {
+            panic!("Shards must have the same length, a multiple of the symbol size");
+        }
+        (self.k..self.num_shards())
+            .map(|p| self.parity_shard(p, data))
+            .collect()
+    }
+
+    fn reconstruct(&self, shards: &mut [Option<Vec<u8>>]) -> bool {
+        if shards.len() != self.num_shards() {
+            panic!("Number of shards does not match n");
+        }
+        let lost: Vec<usize> = (0..shards.len()).filter(|&i| shards[i].is_none()).collect();
+        let Some(solution) = self.solve(&lost) else {
+            return false;
+        };
+        if lost.is_empty() {
+            return true;
+        }
+        let len = shards.iter().flatten().next().unwrap().len();
+        if shards.iter().flatten().any(|s| s.len() != len) {
+            panic!("Shards must have the same length");
+        }
+
+        for &i in solution.plan.local.iter() {
+            let g = self.group(i).unwrap();
+            let rebuilt = xor_all(
+                self.group_members(g)
+                    .into_iter()
+                    .filter(|&j| j != i)
+                    .map(|j| &shards[j].as_ref().unwrap()[..]),
+            );
+            shards[i] = Some(rebuilt);
+        }
+
+        // Equation p reads Σ_unknown c_ip D_i = P_p + Σ_known c_ip D_i
+        let unknowns = &solution.unknowns;
+        if !unknowns.is_empty() {
+            let mut a = Matrix::new(unknowns.len(), unknowns.len());
+            let mut rhs = Vec::new();
+            for (row, &p) in solution.equations.iter().enumerate() {
+                for (col, &i) in unknowns.iter().enumerate() {
+                    a.set(row, col, self.coefficient(i, p));
+                }
+                let mut sum = shards[p].clone().unwrap();
+                for i in (0..self.k).filter(|i| !unknowns.contains(i)) {
+                    mul_add(
+                        self.coefficient(i, p),
+                        shards[i].as_ref().unwrap(),
+                        &mut sum,
+                    );
+                }
+                rhs.push(sum);
+            }
+            let inverse = a.inverse().expect("The equations are independent");
+            for (col, &i) in unknowns.iter().enumerate() {
+                let mut rebuilt = vec![0u8; len];
+                for (row, sum) in rhs.iter().enumerate() {
+                    mul_add(inverse.get(col, row), sum, &mut rebuilt);
+                }
+                shards[i] = Some(rebuilt);
+            }
+        }
+
+        // The data is complete, so lost parities are recomputed
+        let parities: Vec<(usize, Vec<u8>)> = {
+            let data: Vec<&[u8]> = shards[..self.k]
+                .iter()
+                .map(|d| &d.as_ref().unwrap()[..])
+                .collect();
+            solution
+                .plan
+                .global
+                .iter()
+                .filter(|&&p| p >= self.k)
+                .map(|&p| (p, self.parity_shard(p, &data)))
+                .collect()
+        };
+        for (p, shard) in parities {
+            shards[p] = Some(shard);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11D>;
+
+    fn encoded<C: ErasureCode>(code: &C, len: usize) -> Vec<Vec<u8>> {
+        let data: Vec<Vec<u8>> = (0..code.num_data())
+            .map(|_| (0..len).map(|_| rand::thread_rng().gen()).collect())
+            .collect();
+        let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        let mut all = data.clone();
+        all.extend(code.encode_shards(&refs));
+        all
+    }
+
+    // Loses the shards, checks the plan is possible exactly when reconstruct succeeds and returns whether it did
+    fn lose(code: &Lrc<GF>, all: &[Vec<u8>], lost: &[usize]) -> bool {
+        let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+        for &i in lost {
+            shards[i] = None;
+        }
+        let before = shards.clone();
+        let plan = code.repair_plan(lost);
+        if !code.reconstruct(&mut shards) {
+            assert!(plan.is_none());
+            assert_eq!(shards, before);
+            return false;
+        }
+        let plan = plan.unwrap();
+        assert!(plan.reads.iter().all(|i| !lost.contains(i)));
+        let mut rebuilt: Vec<usize> = plan
+            .local
+            .iter()
+            .chain(plan.global.iter())
+            .copied()
+            .collect();
+        rebuilt.sort_unstable();
+        let mut expected = lost.to_vec();
+        expected.sort_unstable();
+        assert_eq!(rebuilt, expected);
+        let shards: Vec<Vec<u8>> = shards.into_iter().map(Option::unwrap).collect();
+        assert_eq!(shards, all);
+        true
+    }
+
+    #[test]
+    fn parities() {
+        let code = Lrc::<GF>::new(6, 2, 2);
+        let all = encoded(&code, 20);
+        for b in 0..20 {
+            let s: Vec<u8> = all.iter().map(|shard| shard[b]).collect();
+            assert_eq!(s[6], s[0] ^ s[1] ^ s[2]);
+            assert_eq!(s[7], s[3] ^ s[4] ^ s[5]);
+            for j in 0..2 {
+                let expected = (0..6).fold(GF::ZERO, |acc, i| {
+                    acc + GF::new(2).pow((i * (j + 1)) as u128) * GF::new(s[i])
+                });
+                assert_eq!(s[8 + j], expected.value);
+            }
+        }
+        let refs: Vec<&[u8]> = all.iter().map(|s| s.as_slice()).collect();
+        assert!(code.verify(&refs));
+    }
+
+    #[test]
+    fn local_repair() {
+        // Azure's LRC(12, 2, 2)
+        let code = Lrc::<GF>::new(12, 2, 2);
+        assert_eq!(code.group_members(1), vec![6, 7, 8, 9, 10, 11, 13]);
+        let all = encoded(&code, 16);
+        for i in 0..14 {
+            let plan = code.repair_plan(&[i]).unwrap();
+            assert_eq!(plan.cost(), 6);
+            assert_eq!(plan.local, vec![i]);
+            assert!(lose(&code, &all, &[i]));
+        }
+        // A global parity needs all the data
+        assert_eq!(code.repair_plan(&[15]).unwrap().cost(), 12);
+    }
+
+    #[test]
+    fn any_r_plus_one_failures() {
+        for (k, l) in [(6, 2), (12, 2), (7, 3)] {
+            let code = Lrc::<GF>::new(k, l, 2);
+            let n = code.num_shards();
+            let all = encoded(&code, 8);
+            for a in 0..n {
+                for b in a + 1..n {
+                    for c in b + 1..n {
+                        assert!(lose(&code, &all, &[a, b, c]));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn beyond_r_plus_one() {
+        let code = Lrc::<GF>::new(6, 2, 2);
+        let all = encoded(&code, 8);
+        // Two losses in each group are solved with both locals and both globals
+        let plan = code.repair_plan(&[0, 1, 3, 4]).unwrap();
+        assert_eq!(plan.reads, vec![2, 5, 6, 7, 8, 9]);
+        assert!(lose(&code, &all, &[0, 1, 3, 4]));
+        // A whole group of data is three unknowns with two global equations
+        assert!(!lose(&code, &all, &[0, 1, 2, 6]));
+        assert!(!lose(&code, &all, &[0, 1, 2, 8]));
+    }
+
+    #[test]
+    fn wide_symbols() {
+        type Wide = gf2::GFu16<0x1_002D>;
+        let code = Lrc::<Wide>::new(8, 2, 2);
+        let all = encoded(&code, 10);
+        let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+        for i in [1, 2, 6] {
+            shards[i] = None;
+        }
+        assert!(code.reconstruct(&mut shards));
+        assert_eq!(
+            shards.into_iter().map(Option::unwrap).collect::<Vec<_>>(),
+            all
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use super::{xor_all, ErasureCode};
+use crate::{gf2_lut, GaloisField};
+
+/// GF(2<sup>8</sup>) with the polynomial x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x<sup>2</sup> + 1 of RAID-6
+pub type Raid6Field = gf2_lut::GFu8<0x11D>;
+
+/// RAID-6 dual parity as in Linux md: P = D<sub>0</sub> + D<sub>1</sub> + ... is the XOR of the data disks and
+/// Q = g<sup>0</sup>D<sub>0</sub> + g<sup>1</sup>D<sub>1</sub> + ... with g = 2. The shards are the k data disks
+/// followed by P and Q. Any two lost disks are rebuilt with closed form formulas
+#[derive(Clone, Copy, Debug)]
+pub struct Raid6 {
+    k: usize,
+}
+
+impl Raid6 {
+    /// Constructs the code with k data disks. Panics unless 1 ≤ k ≤ 255
+    pub fn new(k: usize) -> Self {
+        if k == 0 || k > 255 {
+            panic!("RAID-6 needs between 1 and 255 data disks");
+        }
+        Self { k }
+    }
+
+    /// Computes the P and Q parity of k equally long data disks. Panics unless there are k disks of equal length
+    pub fn pq(&self, data: &[&[u8]]) -> (Vec<u8>, Vec<u8>) {
+        if data.len() != self.k {
+            panic!("Number of data shards does not match k");
+        }
+        let p = xor_all(data.iter().copied());
+
+        // Horner's rule from the last disk down needs only multiplications by g = 2
+        let mut q = data[self.k - 1].to_vec();
+        for d in data[..self.k - 1].iter().rev() {
+            q.iter_mut()
+                .zip(d.iter())
+                .for_each(|(q, &b)| *q = mul2(*q) ^ b);
+        }
+        (p, q)
+    }
+
+    // Q + Σ g^i D_i over the present data disks, which is Σ g^i D_i over the missing ones
+    fn q_syndrome(&self, shards: &[Option<Vec<u8>>], q: &[u8]) -> Vec<u8> {
+        let mut out = vec![0u8; q.len()];
+        for i in (0..self.k).rev() {
+            out.iter_mut().for_each(|o| *o = mul2(*o));
+            if let Some(d) = &shards[i] {
+                out.iter_mut().zip(d.iter()).for_each(|(o, &b)| *o ^= b);
+            }
+        }
+        out.iter_mut().zip(q.iter()).for_each(|(o, &b)| *o ^= b);
+        out
+    }
+
+    // P + Σ D_i over the present data disks
+    fn p_syndrome(&self, shards: &[Option<Vec<u8>>], p: &[u8]) -> Vec<u8> {
+        xor_all(shards[..self.k].iter().flatten().map(|d| &d[..]).chain([p]))
+    }
+}
+
+impl ErasureCode for Raid6 {
+    fn num_data(&self) -> usize {
+        self.k
+    }
+
+    fn num_parity(&self) -> usize {
+        2
+    }
+
+    fn encode_shards(&self, data: &[&[u8]]) -> Vec<Vec<u8>> {
+        let (p, q) = self.pq(data);
+        vec![p, q]
+    }
+
+    fn reconstruct(&self, shards: &mut [Option<Vec<u8>>]) -> bool {
+        if shards.len() != self.k + 2 {
+            panic!("Number of shards does not match n");
+        }
+        let missing: Vec<usize> = (0..shards.len()).filter(|&i| shards[i].is_none()).collect();
+        let g = Raid6Field::new(2);
+        let (kp, kq) = (self.k, self.k + 1);
+        match missing[..] {
+            [] => return true,
+            // Only parity lost: recompute it
+            [x] | [x, _] if x >= kp => {}
+            // One data disk and possibly Q lost: D_x = P + Σ others
+            [x] | [x, _] if missing.len() == 1 || missing[1] == kq => {
+                let p = shards[kp].as_ref().unwrap();
+                shards[x] = Some(self.p_syndrome(shards, p));
+            }
+            // A data disk and P lost: g^x D_x = Q + Σ g^i D_i over the others
+            [x, y] if y == kp => {
+                let q = shards[kq].as_ref().unwrap();
+                let qx = self.q_syndrome(shards, q);
+                let c = mul_table(g.pow(x as u128).inverse());
+                shards[x] = Some(qx.iter().map(|&b| c[b as usize]).collect());
+            }
+            // Two data disks lost: D_x + D_y = P_xy and g^x D_x + g^y D_y = Q_xy give
+            // D_x = (g^(y-x) P_xy + g^(-x) Q_xy) / (g^(y-x) + 1)
+            [x, y] if y < kp => {
+                let p = shards[kp].as_ref().unwrap();
+                let q = shards[kq].as_ref().unwrap();
+                let pxy = self.p_syndrome(shards, p);
+                let qxy = self.q_syndrome(shards, q);
+                let gyx = g.pow((y - x) as u128);
+                let denominator = (gyx + Raid6Field::ONE).inverse();
+                let a = mul_table(gyx * denominator);
+                let b = mul_table(g.pow(x as u128).inverse() * denominator);
+                let dx: Vec<u8> = pxy
+                    .iter()
+                    .zip(qxy.iter())
+                    .map(|(&p, &q)| a[p as usize] ^ b[q as usize])
+                    .collect();
+                let dy: Vec<u8> = dx.iter().zip(pxy.iter()).map(|(&d, &p)| d ^ p).collect();
+                shards[x] = Some(dx);
+                shards[y] = Some(dy);
+            }
+            _ => return false,
+        }
+
+        // Recompute any lost parity from the complete data
+        let data: Vec<&[u8]> = shards[..self.k]
+            .iter()
+            .map(|d| &d.as_ref().unwrap()[..])
+            .collect();
+        let (p, q) = self.pq(&data);
+        if shards[kp].is_none() {
+            shards[kp] = Some(p);
+        }
+        if shards[kq].is_none() {
+            shards[kq] = Some(q);
+        }
+        true
+    }
+}
+
+// Multiplication by x = 2 of a byte
+fn mul2(b: u8) -> u8 {
+    (b << 1) ^ if b & 0x80 != 0 { 0x1D } else { 0 }
+}
+
+// The products of c with every byte
+fn mul_table(c: Raid6Field) -> Vec<u8> {
+    (0..=255).map(|b| (c * Raid6Field::new(b)).value).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn random_shards(count: usize, len: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|_| (0..len).map(|_| rand::thread_rng().gen()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parity() {
+        let raid = Raid6::new(5);
+        let data = random_shards(5, 30);
+        let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        let (p, q) = raid.pq(&refs);
+        let g = Raid6Field::new(2);
+        for b in 0..30 {
+            let expected = (0..5).fold(Raid6Field::ZERO, |acc, i| {
+                acc + g.pow(i as u128) * Raid6Field::new(data[i][b])
+            });
+            assert_eq!(q[b], expected.value);
+            assert_eq!(p[b], data.iter().fold(0, |acc, d| acc ^ d[b]));
+        }
+    }
+
+    #[test]
+    fn every_double_failure() {
+        let raid = Raid6::new(6);
+        let data = random_shards(6, 40);
+        let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        let mut all = data.clone();
+        all.extend(raid.encode_shards(&refs));
+
+        for x in 0..8 {
+            for y in x..8 {
+                let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+                shards[x] = None;
+                shards[y] = None;
+                assert!(raid.reconstruct(&mut shards));
+                let rebuilt: Vec<Vec<u8>> = shards.into_iter().map(Option::unwrap).collect();
+                assert_eq!(rebuilt, all);
+            }
+        }
+
+        let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+        for i in [0, 3, 7] {
+            shards[i] = None;
+        }
+        assert!(!raid.reconstruct(&mut shards));
+    }
+}
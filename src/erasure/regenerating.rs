@@ -0,0 +1,474 @@
+use crate::linalg::{dot, Matrix};
+use crate::poly::Interpolator;
+use crate::GaloisField;
+
+/// The product-matrix minimum bandwidth regenerating (MBR) code of Rashmi, Shah and Kumar. A message of
+/// B = k(k+1)/2 + k(d-k) symbols fills a symmetric d × d matrix M = [S T; T<sup>T</sup> 0] and node i stores the
+/// d symbols ψ<sub>i</sub><sup>T</sup>M, with ψ<sub>i</sub> = (1, x<sub>i</sub>, ..., x<sub>i</sub><sup>d-1</sup>).
+/// Any k nodes hold the message, and a lost node is rebuilt from one symbol of each of d other nodes, d symbols in
+/// total where a Reed-Solomon repair downloads B
+#[derive(Clone, Debug)]
+pub struct ProductMatrixMbr<GF: GaloisField> {
+    n: usize,
+    k: usize,
+    d: usize,
+    points: Vec<GF>,
+}
+
+/// The product-matrix minimum storage regenerating (MSR) code of Rashmi, Shah and Kumar with d = 2k - 2 helpers.
+/// A message of B = k(k-1) symbols fills two symmetric α × α matrices S<sub>1</sub> and S<sub>2</sub>, α = k - 1,
+/// and node i stores the α symbols φ<sub>i</sub><sup>T</sup>(S<sub>1</sub> + λ<sub>i</sub>S<sub>2</sub>) with
+/// φ<sub>i</sub> = (1, x<sub>i</sub>, ..., x<sub>i</sub><sup>α-1</sup>) and λ<sub>i</sub> =
+/// x<sub>i</sub><sup>α</sup>. Nodes store B / k symbols as with an MDS code, any k of them hold the message, and a
+/// lost node is rebuilt from one symbol of each of d other nodes, 2α symbols instead of B
+#[derive(Clone, Debug)]
+pub struct ProductMatrixMsr<GF: GaloisField> {
+    n: usize,
+    k: usize,
+    points: Vec<GF>,
+    lambdas: Vec<GF>,
+}
+
+impl<GF: GaloisField> ProductMatrixMbr<GF> {
+    /// Constructs the code with n nodes, any k of which hold the message, repaired from d helpers.
+    /// Panics unless 0 < k ≤ d < n ≤ 2<sup>M</sup>
+    pub fn new(n: usize, k: usize, d: usize) -> Self {
+        if k == 0 || k > d || d >= n || n as u128 > GF::NUM_ELEM {
+            panic!("Product-matrix MBR codes need 0 < k <= d < n <= 2^M");
+        }
+        Self {
+            n,
+            k,
+            d,
+            points: (0..n).map(|i| GF::from_u128(i as u128)).collect(),
+        }
+    }
+
+    /// The number of nodes n
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The number of nodes k that hold the message
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The number of helpers d of a repair
+    pub fn d(&self) -> usize {
+        self.d
+    }
+
+    /// The number of symbols α = d a node stores
+    pub fn node_len(&self) -> usize {
+        self.d
+    }
+
+    /// The number of message symbols B = k(k+1)/2 + k(d-k)
+    pub fn message_len(&self) -> usize {
+        self.k * (self.k + 1) / 2 + self.k * (self.d - self.k)
+    }
+
+    /// Encodes the message into the contents of the n nodes. The message fills the upper triangle of S row by row,
+    /// then T row by row. Panics if the message does not have B symbols
+    pub fn encode(&self, message: &[GF]) -> Vec<Vec<GF>> {
+        if message.len() != self.message_len() {
+            panic!("Message length does not match B");
+        }
+        let (k, d) = (self.k, self.d);
+        let mut m = Matrix::new(d, d);
+        let (s, t) = message.split_at(k * (k + 1) / 2);
+        fill_symmetric(&mut m, s, k);
+        for (idx, &v) in t.iter().enumerate() {
+            let (i, j) = (idx / (d - k), k + idx % (d - k));
+            m.set(i, j, v);
+            m.set(j, i, v);
+        }
+        let psi = Matrix::vandermonde(&self.points, d).transpose();
+        let nodes = &psi * &m;
+        (0..self.n).map(|i| nodes.row(i).to_vec()).collect()
+    }
+
+    /// The symbol a helper holding helper_data sends for the repair of node failed, ψ<sub>helper</sub><sup>T</sup>
+    /// M ψ<sub>failed</sub>. Panics if failed is not less than n or helper_data does not have α symbols
+    pub fn repair_symbol(&self, failed: usize, helper_data: &[GF]) -> GF {
+        if helper_data.len() != self.d {
+            panic!("Node length does not match alpha");
+        }
+        dot(helper_data, &powers(self.points[failed], self.d))
+    }
+
+    /// Rebuilds node failed from the `repair_symbol`s of the d helpers. Mψ<sub>failed</sub> is the polynomial whose
+    /// values at the helper points are the received symbols, and since M is symmetric it is the lost content.
+    /// Panics unless there are d distinct helpers other than failed with one symbol each
+    pub fn repair(&self, failed: usize, helpers: &[usize], symbols: &[GF]) -> Vec<GF> {
+        check_helpers(failed, helpers, symbols, self.d);
+        interpolate(&self.node_points(helpers), symbols, self.d)
+    }
+
+    /// Recovers the message from the contents of k distinct nodes. Panics unless there are k distinct nodes of α
+    /// symbols each
+    pub fn decode(&self, nodes: &[usize], data: &[&[GF]]) -> Vec<GF> {
+        let (k, d) = (self.k, self.d);
+        check_nodes(nodes, data, k, d);
+        let points = self.node_points(nodes);
+        let interpolator = Interpolator::new(&points).expect("Nodes must be distinct");
+        let column = |c: usize, rows: &[&[GF]]| -> Vec<GF> { rows.iter().map(|r| r[c]).collect() };
+
+        // The last d - k columns of the data are Φ T with Φ the k × k Vandermonde matrix of the nodes
+        let t: Vec<Vec<GF>> = (k..d)
+            .map(|c| coefficients(&interpolator, &column(c, data), k))
+            .collect();
+        // and the first k columns are Φ S + Δ T^T, where row r of Δ is x_r^k, ..., x_r^(d-1)
+        let mut message = Vec::with_capacity(self.message_len());
+        let mut s = Matrix::new(k, k);
+        for c in 0..k {
+            let rhs: Vec<GF> = points
+                .iter()
+                .zip(data)
+                .map(|(&x, row)| {
+                    let delta = x.pow(k as u128);
+                    row[c] + delta * t.iter().rev().fold(GF::ZERO, |acc, tc| acc * x + tc[c])
+                })
+                .collect();
+            for (r, v) in coefficients(&interpolator, &rhs, k).into_iter().enumerate() {
+                s.set(r, c, v);
+            }
+        }
+        for i in 0..k {
+            message.extend_from_slice(&s.row(i)[i..]);
+        }
+        for i in 0..k {
+            message.extend(t.iter().map(|tc| tc[i]));
+        }
+        message
+    }
+
+    fn node_points(&self, nodes: &[usize]) -> Vec<GF> {
+        nodes.iter().map(|&i| self.points[i]).collect()
+    }
+}
+
+impl<GF: GaloisField> ProductMatrixMsr<GF> {
+    /// Constructs the code with n nodes, any k of which hold the message, repaired from d = 2k - 2 helpers. The
+    /// points x<sub>i</sub> are chosen so that the λ<sub>i</sub> are distinct. Panics unless 2 ≤ k and 2k - 2 < n
+    /// and there are n such points in GF(2<sup>M</sup>)
+    pub fn new(n: usize, k: usize) -> Self {
+        if k < 2 || 2 * k - 2 >= n {
+            panic!("Product-matrix MSR codes need 2 <= k and 2k - 2 < n");
+        }
+        let alpha = (k - 1) as u128;
+        let mut points = Vec::with_capacity(n);
+        let mut lambdas = Vec::with_capacity(n);
+        for v in 0..GF::NUM_ELEM {
+            if points.len() == n {
+                break;
+            }
+            let x = GF::from_u128(v);
+            let lambda = x.pow(alpha);
+            if !lambdas.contains(&lambda) {
+                points.push(x);
+                lambdas.push(lambda);
+            }
+        }
+        if points.len() < n {
+            panic!("The field has too few points with distinct lambda");
+        }
+        Self {
+            n,
+            k,
+            points,
+            lambdas,
+        }
+    }
+
+    /// The number of nodes n
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The number of nodes k that hold the message
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The number of helpers d = 2k - 2 of a repair
+    pub fn d(&self) -> usize {
+        2 * self.k - 2
+    }
+
+    /// The number of symbols α = k - 1 a node stores
+    pub fn node_len(&self) -> usize {
+        self.k - 1
+    }
+
+    /// The number of message symbols B = k(k-1)
+    pub fn message_len(&self) -> usize {
+        self.k * (self.k - 1)
+    }
+
+    /// Encodes the message into the contents of the n nodes. The message fills the upper triangle of
+    /// S<sub>1</sub> row by row, then that of S<sub>2</sub>. Panics if the message does not have B symbols
+    pub fn encode(&self, message: &[GF]) -> Vec<Vec<GF>> {
+        if message.len() != self.message_len() {
+            panic!("Message length does not match B");
+        }
+        let alpha = self.node_len();
+        let mut m = Matrix::new(2 * alpha, alpha);
+        let (s1, s2) = message.split_at(message.len() / 2);
+        fill_symmetric(&mut m, s1, alpha);
+        let mut lower = Matrix::new(alpha, alpha);
+        fill_symmetric(&mut lower, s2, alpha);
+        for i in 0..alpha {
+            for j in 0..alpha {
+                m.set(alpha + i, j, lower.get(i, j));
+            }
+        }
+        // ψ_i = (φ_i, λ_i φ_i) is the Vandermonde row of x_i with 2α entries
+        let psi = Matrix::vandermonde(&self.points, 2 * alpha).transpose();
+        let nodes = &psi * &m;
+        (0..self.n).map(|i| nodes.row(i).to_vec()).collect()
+    }
+
+    /// The symbol a helper holding helper_data sends for the repair of node failed, ψ<sub>helper</sub><sup>T</sup>
+    /// M φ<sub>failed</sub>. Panics if failed is not less than n or helper_data does not have α symbols
+    pub fn repair_symbol(&self, failed: usize, helper_data: &[GF]) -> GF {
+        if helper_data.len() != self.node_len() {
+            panic!("Node length does not match alpha");
+        }
+        dot(helper_data, &powers(self.points[failed], self.node_len()))
+    }
+
+    /// Rebuilds node failed from the `repair_symbol`s of the d helpers. They are the values of the polynomial with
+    /// coefficients Mφ<sub>failed</sub> = (S<sub>1</sub>φ<sub>failed</sub>, S<sub>2</sub>φ<sub>failed</sub>) at the
+    /// helper points, and the lost content is S<sub>1</sub>φ<sub>failed</sub> + λ<sub>failed</sub>
+    /// S<sub>2</sub>φ<sub>failed</sub>. Panics unless there are d distinct helpers other than failed with one
+    /// symbol each
+    pub fn repair(&self, failed: usize, helpers: &[usize], symbols: &[GF]) -> Vec<GF> {
+        check_helpers(failed, helpers, symbols, self.d());
+        let alpha = self.node_len();
+        let w = interpolate(&self.node_points(helpers), symbols, 2 * alpha);
+        let lambda = self.lambdas[failed];
+        (0..alpha).map(|i| w[i] + lambda * w[alpha + i]).collect()
+    }
+
+    /// Recovers the message from the contents of k distinct nodes. Panics unless there are k distinct nodes of α
+    /// symbols each
+    pub fn decode(&self, nodes: &[usize], data: &[&[GF]]) -> Vec<GF> {
+        let (k, alpha) = (self.k, self.node_len());
+        check_nodes(nodes, data, k, alpha);
+        let points = self.node_points(nodes);
+        let phis: Vec<Vec<GF>> = points.iter().map(|&x| powers(x, alpha)).collect();
+
+        // Entry (i, j) of C Φ^T is P_ij + λ_i Q_ij with the symmetric P = Φ S_1 Φ^T and Q = Φ S_2 Φ^T, so the
+        // entries (i, j) and (j, i) give P_ij and Q_ij off the diagonal
+        let mut p = Matrix::new(k, k);
+        let mut q = Matrix::new(k, k);
+        for i in 0..k {
+            for j in i + 1..k {
+                let a = dot(data[i], &phis[j]);
+                let b = dot(data[j], &phis[i]);
+                let (li, lj) = (self.lambdas[nodes[i]], self.lambdas[nodes[j]]);
+                let qij = (a + b) / (li + lj);
+                let pij = a + li * qij;
+                for (r, c) in [(i, j), (j, i)] {
+                    p.set(r, c, pij);
+                    q.set(r, c, qij);
+                }
+            }
+        }
+
+        // Row i of P without its diagonal is φ_i^T S_1 evaluated at the other α points, and any α of the rows
+        // φ_i^T S_1 give S_1
+        let mut message = Vec::with_capacity(self.message_len());
+        for pq in [p, q] {
+            let rows: Vec<Vec<GF>> = (0..alpha)
+                .map(|i| {
+                    let others: Vec<usize> = (0..k).filter(|&j| j != i).collect();
+                    let xs: Vec<GF> = others.iter().map(|&j| points[j]).collect();
+                    let ys: Vec<GF> = others.iter().map(|&j| pq.get(i, j)).collect();
+                    interpolate(&xs, &ys, alpha)
+                })
+                .collect();
+            let interpolator = Interpolator::new(&points[..alpha]).expect("Nodes must be distinct");
+            for c in 0..alpha {
+                let ys: Vec<GF> = rows.iter().map(|r| r[c]).collect();
+                message.extend_from_slice(&coefficients(&interpolator, &ys, alpha)[c..]);
+            }
+        }
+        message
+    }
+
+    fn node_points(&self, nodes: &[usize]) -> Vec<GF> {
+        nodes.iter().map(|&i| self.points[i]).collect()
+    }
+}
+
+// Fills the upper left size × size block of m symmetrically from the upper triangle given row by row
+fn fill_symmetric<GF: GaloisField>(m: &mut Matrix<GF>, upper: &[GF], size: usize) {
+    let mut values = upper.iter();
+    for i in 0..size {
+        for j in i..size {
+            let v = *values.next().unwrap();
+            m.set(i, j, v);
+            m.set(j, i, v);
+        }
+    }
+}
+
+// 1, x, ..., x^(len-1)
+fn powers<GF: GaloisField>(x: GF, len: usize) -> Vec<GF> {
+    let mut power = GF::ONE;
+    (0..len)
+        .map(|_| {
+            let p = power;
+            power *= x;
+            p
+        })
+        .collect()
+}
+
+// The len coefficients of the polynomial through the points (xs[i], ys[i])
+fn interpolate<GF: GaloisField>(xs: &[GF], ys: &[GF], len: usize) -> Vec<GF> {
+    coefficients(
+        &Interpolator::new(xs).expect("Nodes must be distinct"),
+        ys,
+        len,
+    )
+}
+
+fn coefficients<GF: GaloisField>(
+    interpolator: &Interpolator<GF>,
+    ys: &[GF],
+    len: usize,
+) -> Vec<GF> {
+    let mut coeffs = interpolator.interpolate(ys).coeffs().to_vec();
+    coeffs.resize(len, GF::ZERO);
+    coeffs
+}
+
+fn check_helpers<GF: GaloisField>(failed: usize, helpers: &[usize], symbols: &[GF], d: usize) {
+    if helpers.len() != d || symbols.len() != d {
+        panic!("Repair needs one symbol from each of d helpers");
+    }
+    if helpers.contains(&failed) {
+        panic!("The failed node cannot help repair itself");
+    }
+}
+
+fn check_nodes<GF: GaloisField>(nodes: &[usize], data: &[&[GF]], k: usize, alpha: usize) {
+    if nodes.len() != k || data.len() != k {
+        panic!("Decoding needs the contents of k nodes");
+    }
+    if data.iter().any(|d| d.len() != alpha) {
+        panic!("Node length does not match alpha");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    fn random<GF: GaloisField>(len: usize) -> Vec<GF> {
+        (0..len)
+            .map(|_| GF::from_u128(rand::thread_rng().gen::<u128>() % GF::NUM_ELEM))
+            .collect()
+    }
+
+    // Every k subset of nodes decodes, and every node is repaired from random helper sets
+    fn check<GF: GaloisField>(
+        (n, k, d): (usize, usize, usize),
+        message: &[GF],
+        nodes: &[Vec<GF>],
+        decode: impl Fn(&[usize], &[&[GF]]) -> Vec<GF>,
+        repair_symbol: impl Fn(usize, &[GF]) -> GF,
+        repair: impl Fn(usize, &[usize], &[GF]) -> Vec<GF>,
+    ) {
+        for mask in 0u32..1 << n {
+            if mask.count_ones() as usize != k {
+                continue;
+            }
+            let subset: Vec<usize> = (0..n).filter(|&i| mask >> i & 1 == 1).collect();
+            let data: Vec<&[GF]> = subset.iter().map(|&i| &nodes[i][..]).collect();
+            assert_eq!(decode(&subset, &data), message);
+        }
+        for failed in 0..n {
+            for _ in 0..4 {
+                let mut helpers: Vec<usize> = (0..n).filter(|&i| i != failed).collect();
+                helpers.shuffle(&mut rand::thread_rng());
+                helpers.truncate(d);
+                let symbols: Vec<GF> = helpers
+                    .iter()
+                    .map(|&h| repair_symbol(failed, &nodes[h]))
+                    .collect();
+                assert_eq!(repair(failed, &helpers, &symbols), nodes[failed]);
+            }
+        }
+    }
+
+    fn check_mbr<GF: GaloisField>(n: usize, k: usize, d: usize) {
+        let code = ProductMatrixMbr::<GF>::new(n, k, d);
+        let message = random::<GF>(code.message_len());
+        let nodes = code.encode(&message);
+        assert!(nodes.iter().all(|node| node.len() == d));
+        check(
+            (n, k, d),
+            &message,
+            &nodes,
+            |s, data| code.decode(s, data),
+            |f, h| code.repair_symbol(f, h),
+            |f, h, s| code.repair(f, h, s),
+        );
+    }
+
+    fn check_msr<GF: GaloisField>(n: usize, k: usize) {
+        let code = ProductMatrixMsr::<GF>::new(n, k);
+        let message = random::<GF>(code.message_len());
+        let nodes = code.encode(&message);
+        assert!(nodes.iter().all(|node| node.len() == k - 1));
+        check(
+            (n, k, code.d()),
+            &message,
+            &nodes,
+            |s, data| code.decode(s, data),
+            |f, h| code.repair_symbol(f, h),
+            |f, h, s| code.repair(f, h, s),
+        );
+    }
+
+    #[test]
+    fn mbr() {
+        check_mbr::<gf2_lut::GFu8<0x11D>>(6, 3, 4);
+        check_mbr::<gf2_lut::GFu8<0x11D>>(5, 2, 4);
+        check_mbr::<gf2_lut::GFu8<0x11D>>(7, 4, 4);
+        check_mbr::<gf2::GFu16<0x1_002D>>(10, 5, 8);
+        check_mbr::<gf2::GFu8<0x13>>(16, 1, 15);
+    }
+
+    #[test]
+    fn msr() {
+        check_msr::<gf2_lut::GFu8<0x11D>>(5, 3);
+        check_msr::<gf2_lut::GFu8<0x11D>>(6, 2);
+        // α = 3 divides 255, so not every x^3 is distinct
+        check_msr::<gf2_lut::GFu8<0x11D>>(9, 4);
+        check_msr::<gf2::GFu16<0x1_002D>>(12, 6);
+    }
+
+    #[test]
+    fn msr_storage() {
+        // Like an MDS code the nodes store B / k symbols, while a repair downloads 2α symbols instead of B
+        let code = ProductMatrixMsr::<gf2_lut::GFu8<0x11D>>::new(9, 5);
+        assert_eq!(code.message_len(), code.k() * code.node_len());
+        assert_eq!(code.d(), 8);
+        assert!(code.d() < code.message_len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn msr_too_few_nodes() {
+        ProductMatrixMsr::<gf2_lut::GFu8<0x11D>>::new(4, 3);
+    }
+}
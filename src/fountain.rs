@@ -0,0 +1,327 @@
+//! LT (Luby transform) fountain codes over GF(2).
+//!
+//! The source is split into k equally long blocks. Every encoded symbol is the XOR of a random set of blocks, whose
+//! size is drawn from the robust soliton distribution. The set is derived from the symbol id alone, so an encoder can
+//! produce an unlimited stream of symbols and the decoder only needs the ids of those it receives. Decoding peels
+//! symbols that cover a single unknown block, which succeeds with high probability after slightly more than k
+//! symbols, in any order and from any subset of the stream.
+//!
+//! ```
+//! use galois_field_2pm::fountain::LtCode;
+//!
+//! let blocks: Vec<Vec<u8>> = (0..20u8).map(|i| vec![i; 8]).collect();
+//! let refs: Vec<&[u8]> = blocks.iter().map(|b| b.as_slice()).collect();
+//! let code = LtCode::new(20);
+//!
+//! // Symbols may be lost, here every third one
+//! let mut decoder = code.decoder(8);
+//! let mut id = 0;
+//! while !decoder.is_complete() {
+//!     if id % 3 != 0 {
+//!         decoder.add_symbol(id, &code.encode_symbol(&refs, id));
+//!     }
+//!     id += 1;
+//! }
+//! assert_eq!(decoder.into_blocks(), Some(blocks));
+//! ```
+
+/// An LT code over k source blocks with a robust soliton degree distribution
+#[derive(Clone, Debug)]
+pub struct LtCode {
+    k: usize,
+    // cdf[d - 1] is the probability of a degree at most d
+    cdf: Vec<f64>,
+}
+
+impl LtCode {
+    /// Constructs the code for k blocks with the robust soliton parameters c = 0.1 and δ = 0.05.
+    /// Panics if k is zero
+    pub fn new(k: usize) -> Self {
+        Self::with_params(k, 0.1, 0.05)
+    }
+
+    /// Constructs the code for k blocks with the robust soliton distribution of parameters c and δ: the ideal soliton
+    /// ρ(1) = 1/k, ρ(d) = 1/(d(d - 1)) plus a spike τ around k/R, where R = c ln(k/δ)√k, normalized.
+    /// δ bounds the probability that decoding fails after k + O(√k ln<sup>2</sup>(k/δ)) symbols.
+    /// Panics unless k > 0, c > 0 and 0 < δ < 1
+    pub fn with_params(k: usize, c: f64, delta: f64) -> Self {
+        if k == 0 || c <= 0.0 || delta <= 0.0 || delta >= 1.0 {
+            panic!("LT codes need k > 0, c > 0 and 0 < delta < 1");
+        }
+        let kf = k as f64;
+        let r = c * (kf / delta).ln() * kf.sqrt();
+        let spike = ((kf / r).floor() as usize).clamp(1, k);
+
+        let mut pmf: Vec<f64> = (1..=k)
+            .map(|d| {
+                let rho = if d == 1 {
+                    1.0 / kf
+                } else {
+                    1.0 / (d * (d - 1)) as f64
+                };
+                let tau = match d.cmp(&spike) {
+                    core::cmp::Ordering::Less => r / (d as f64 * kf),
+                    core::cmp::Ordering::Equal => r * (r / delta).ln() / kf,
+                    core::cmp::Ordering::Greater => 0.0,
+                };
+                rho + tau.max(0.0)
+            })
+            .collect();
+        let beta: f64 = pmf.iter().sum();
+        pmf.iter_mut().for_each(|p| *p /= beta);
+
+        let mut acc = 0.0;
+        let mut cdf: Vec<f64> = pmf
+            .iter()
+            .map(|p| {
+                acc += p;
+                acc
+            })
+            .collect();
+        cdf[k - 1] = 1.0;
+        Self { k, cdf }
+    }
+
+    /// The number of source blocks k
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The probability of each degree d = 1, ..., k, at index d - 1
+    pub fn degree_distribution(&self) -> Vec<f64> {
+        let mut previous = 0.0;
+        self.cdf
+            .iter()
+            .map(|&c| {
+                let p = c - previous;
+                previous = c;
+                p
+            })
+            .collect()
+    }
+
+    /// The distinct source blocks combined into the symbol with the given id
+    pub fn neighbors(&self, id: u64) -> Vec<usize> {
+        let mut rng = SplitMix64(id);
+        let u = rng.next_f64();
+        let degree = self.cdf.partition_point(|&c| c < u) + 1;
+
+        let mut neighbors = Vec::with_capacity(degree);
+        while neighbors.len() < degree.min(self.k) {
+            let b = (rng.next_u64() % self.k as u64) as usize;
+            if !neighbors.contains(&b) {
+                neighbors.push(b);
+            }
+        }
+        neighbors
+    }
+
+    /// Computes the symbol with the given id, the XOR of its neighbor blocks.
+    /// Panics unless there are k blocks of equal length
+    pub fn encode_symbol(&self, blocks: &[&[u8]], id: u64) -> Vec<u8> {
+        if blocks.len() != self.k {
+            panic!("Number of blocks does not match k");
+        }
+        let len = blocks[0].len();
+        if blocks.iter().any(|b| b.len() != len) {
+            panic!("Blocks must have the same length");
+        }
+
+        let mut symbol = vec![0u8; len];
+        for b in self.neighbors(id) {
+            xor_into(&mut symbol, blocks[b]);
+        }
+        symbol
+    }
+
+    /// Starts decoding blocks of block_len bytes
+    pub fn decoder(&self, block_len: usize) -> LtDecoder<'_> {
+        LtDecoder {
+            code: self,
+            block_len,
+            blocks: vec![None; self.k],
+            recovered: 0,
+            pending: Vec::new(),
+            waiting: vec![Vec::new(); self.k],
+        }
+    }
+}
+
+/// Peeling decoder of an LT code
+#[derive(Clone, Debug)]
+pub struct LtDecoder<'a> {
+    code: &'a LtCode,
+    block_len: usize,
+    blocks: Vec<Option<Vec<u8>>>,
+    recovered: usize,
+    // Received symbols with their unknown neighbors, reduced by the known blocks. None once used up
+    pending: Vec<Option<(Vec<usize>, Vec<u8>)>>,
+    // The pending symbols each block is a neighbor of
+    waiting: Vec<Vec<usize>>,
+}
+
+impl LtDecoder<'_> {
+    /// Adds a received symbol and peels as far as possible. Returns true once every block is recovered.
+    /// Panics unless the symbol has block_len bytes
+    pub fn add_symbol(&mut self, id: u64, symbol: &[u8]) -> bool {
+        if symbol.len() != self.block_len {
+            panic!("Symbol length does not match the block length");
+        }
+
+        let mut data = symbol.to_vec();
+        let mut unknown = Vec::new();
+        for b in self.code.neighbors(id) {
+            match &self.blocks[b] {
+                Some(block) => xor_into(&mut data, block),
+                None => unknown.push(b),
+            }
+        }
+
+        match unknown[..] {
+            [] => {}
+            [b] => self.recover(b, data),
+            _ => {
+                let index = self.pending.len();
+                for &b in unknown.iter() {
+                    self.waiting[b].push(index);
+                }
+                self.pending.push(Some((unknown, data)));
+            }
+        }
+        self.is_complete()
+    }
+
+    /// Returns true once every block is recovered
+    pub fn is_complete(&self) -> bool {
+        self.recovered == self.code.k
+    }
+
+    /// The number of recovered blocks
+    pub fn num_recovered(&self) -> usize {
+        self.recovered
+    }
+
+    /// The recovered blocks, with None for those still unknown
+    pub fn blocks(&self) -> &[Option<Vec<u8>>] {
+        &self.blocks
+    }
+
+    /// Returns the blocks if all were recovered
+    pub fn into_blocks(self) -> Option<Vec<Vec<u8>>> {
+        self.blocks.into_iter().collect()
+    }
+
+    // Records block b and peels the symbols it leaves with a single unknown neighbor
+    fn recover(&mut self, b: usize, data: Vec<u8>) {
+        let mut queue = vec![(b, data)];
+        while let Some((b, data)) = queue.pop() {
+            if self.blocks[b].is_some() {
+                continue;
+            }
+            for index in core::mem::take(&mut self.waiting[b]) {
+                let Some((unknown, symbol)) = &mut self.pending[index] else {
+                    continue;
+                };
+                xor_into(symbol, &data);
+                unknown.retain(|&u| u != b);
+                if let [last] = unknown[..] {
+                    let (_, symbol) = self.pending[index].take().unwrap();
+                    queue.push((last, symbol));
+                }
+            }
+            self.blocks[b] = Some(data);
+            self.recovered += 1;
+        }
+    }
+}
+
+fn xor_into(out: &mut [u8], input: &[u8]) {
+    out.iter_mut().zip(input).for_each(|(o, &i)| *o ^= i);
+}
+
+// The SplitMix64 generator, so encoder and decoder derive the same neighbors from a symbol id
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn random_blocks(count: usize, len: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|_| (0..len).map(|_| rand::thread_rng().gen()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn distribution() {
+        let code = LtCode::new(1000);
+        let pmf = code.degree_distribution();
+        assert_eq!(pmf.len(), 1000);
+        assert!((pmf.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(pmf.iter().all(|&p| p >= 0.0));
+        // Degree 2 is the most likely, as in the ideal soliton
+        let mode = (0..1000)
+            .max_by(|&a, &b| pmf[a].total_cmp(&pmf[b]))
+            .unwrap();
+        assert_eq!(mode + 1, 2);
+
+        for id in 0..100 {
+            let neighbors = code.neighbors(id);
+            assert!(!neighbors.is_empty());
+            assert!(neighbors.iter().all(|&b| b < 1000));
+            assert_eq!(neighbors, code.neighbors(id));
+        }
+    }
+
+    #[test]
+    fn decode() {
+        let k = 200;
+        let code = LtCode::new(k);
+        let blocks = random_blocks(k, 16);
+        let refs: Vec<&[u8]> = blocks.iter().map(|b| b.as_slice()).collect();
+
+        // The ids determine the neighbors, so the overhead of this run is fixed
+        let mut decoder = code.decoder(16);
+        let mut id = 1000;
+        while !decoder.add_symbol(id, &code.encode_symbol(&refs, id)) {
+            id += 1;
+            assert!(id < 1000 + 2 * k as u64);
+        }
+        assert_eq!(decoder.num_recovered(), k);
+        assert_eq!(decoder.into_blocks(), Some(blocks));
+    }
+
+    #[test]
+    fn incomplete() {
+        let code = LtCode::new(50);
+        let blocks = random_blocks(50, 4);
+        let refs: Vec<&[u8]> = blocks.iter().map(|b| b.as_slice()).collect();
+        let mut decoder = code.decoder(4);
+        for id in 0..20 {
+            assert!(!decoder.add_symbol(id, &code.encode_symbol(&refs, id)));
+        }
+        assert!(decoder.num_recovered() < 50);
+        for (block, expected) in decoder.blocks().iter().zip(blocks.iter()) {
+            if let Some(block) = block {
+                assert_eq!(block, expected);
+            }
+        }
+        assert_eq!(decoder.into_blocks(), None);
+    }
+}
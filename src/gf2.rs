@@ -1,9 +1,10 @@
 use core::fmt;
-use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use paste::paste;
 
-mod clmul;
+pub(crate) mod clmul;
 mod gf2_poly_div;
+pub mod inversion;
 
 use crate::GaloisField;
 use clmul::CarryLessMultiply;
@@ -13,7 +14,7 @@ macro_rules! assign_operator_impl {
     ($($type:ty: $trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
     $(
         paste! {
-            impl<const POLY: u128> $trait_name for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> $trait_name for [<GF $type>]<POLY, INV> {
                 fn $trait_fn(&mut self, other: Self) {
                     *self = *self $op other;
                 }
@@ -23,6 +24,38 @@ macro_rules! assign_operator_impl {
     }
 }
 
+macro_rules! ref_operator_impl {
+    ($($type:ty: $trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
+    $(
+        paste! {
+            impl<'a, const POLY: u128, const INV: u8> $trait_name<&'a [<GF $type>]<POLY, INV>> for [<GF $type>]<POLY, INV> {
+                type Output = Self;
+
+                fn $trait_fn(self, other: &'a Self) -> Self {
+                    self $op *other
+                }
+            }
+
+            impl<'a, const POLY: u128, const INV: u8> $trait_name<[<GF $type>]<POLY, INV>> for &'a [<GF $type>]<POLY, INV> {
+                type Output = [<GF $type>]<POLY, INV>;
+
+                fn $trait_fn(self, other: [<GF $type>]<POLY, INV>) -> [<GF $type>]<POLY, INV> {
+                    *self $op other
+                }
+            }
+
+            impl<'a, 'b, const POLY: u128, const INV: u8> $trait_name<&'b [<GF $type>]<POLY, INV>> for &'a [<GF $type>]<POLY, INV> {
+                type Output = [<GF $type>]<POLY, INV>;
+
+                fn $trait_fn(self, other: &'b [<GF $type>]<POLY, INV>) -> [<GF $type>]<POLY, INV> {
+                    *self $op *other
+                }
+            }
+        }
+    )*
+    }
+}
+
 macro_rules! setup_gf {
     ($($type:ty,)*) => {
     $(
@@ -30,16 +63,19 @@ macro_rules! setup_gf {
             // Define the struct
             #[repr(transparent)]
             #[derive(Clone, Copy, Eq, PartialEq)]
-            pub struct [<GF $type>]<const POLY: u128> {
+            pub struct [<GF $type>]<const POLY: u128, const INV: u8 = { inversion::AUTO }> {
                 pub value: $type,
             }
 
             // Implement the traits
-            impl<const POLY: u128> GaloisField for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> GaloisField for [<GF $type>]<POLY, INV> {
                 type StorageType = $type;
 
+                const POLY: u128 = POLY;
                 const M: u128 = crate::calc_degree(POLY) as u128;
                 const NUM_ELEM: u128 = 1 << Self::M;
+                const MASK: $type = ((1u128 << Self::M) - 1) as $type;
+                const STORAGE_BITS: u32 = $type::BITS;
 
                 const ZERO: Self = Self {value: 0};
                 const ONE: Self = Self {value: 1};
@@ -48,18 +84,98 @@ macro_rules! setup_gf {
                     if *self == Self::ZERO {
                         panic!("Cannot take inverse of zero");
                     }
+                    #[allow(clippy::let_unit_value)]
+                    let () = Self::VALID_INVERSION;
+                    match Self::strategy() {
+                        inversion::ITOH_TSUJII => inversion::itoh_tsujii(*self),
+                        inversion::FERMAT => self.pow(Self::NUM_ELEM - 2),
+                        inversion::LUT => self.lut_inverse(),
+                        _ => self.euclid_inverse(),
+                    }
+                }
+
+                fn new(value: $type) -> Self {
+                    Self {value}
+                }
+
+                fn validate(&self) -> bool {
+                    (self.value & !Self::MASK) == 0
+                }
+
+                fn to_u128(&self) -> u128 {
+                    self.value as u128
+                }
+
+                fn from_u128(value: u128) -> Self {
+                    Self {value: value as $type}
+                }
+
+                fn square(&self) -> Self {
+                    let hi = self.value.clsquare_high();
+                    let lo = self.value.clsquare_low();
+
+                    Self {
+                        value: Self::reduce(hi, lo),
+                    }
+                }
+            }
+
+            impl<const POLY: u128, const INV: u8> [<GF $type>]<POLY, INV> {
+                const VALID_INVERSION: () = {
+                    assert!(INV <= inversion::AUTO, "Unknown inversion strategy");
+                    assert!(INV != inversion::LUT || $type::BITS <= 16, "LUT inversion needs u8 or u16 storage");
+                };
+
+                /// The inversion strategy used by `inverse`, with `inversion::AUTO` resolved for this type and CPU
+                pub fn strategy() -> u8 {
+                    match INV {
+                        inversion::AUTO if $type::BITS == 8 => inversion::LUT,
+                        inversion::AUTO if Self::M >= 32 && clmul::hardware() => inversion::ITOH_TSUJII,
+                        inversion::AUTO => inversion::EUCLID,
+                        strategy => strategy,
+                    }
+                }
+
+                // x^(2M) / POLY without its x^M term, the Barrett constant of reduce
+                const BARRETT: $type = barrett_constant(POLY) as $type;
+
+                // hi:lo mod POLY. With a carry-less multiply instruction Barrett reduction takes two products,
+                // otherwise the bitwise long division is faster. The instruction is checked for once per call
+                fn reduce(hi: $type, lo: $type) -> $type {
+                    if !clmul::hardware() {
+                        return $type::gf2_poly_mod(hi, lo, POLY);
+                    }
+                    // SAFETY: the instruction is available
+                    Self::barrett(hi, lo, |a, b| unsafe { a.clmul_hardware(b) })
+                }
+
+                // Barrett reduction of hi:lo with mul returning the (high, low) halves of a carry-less product
+                fn barrett(hi: $type, lo: $type, mul: impl Fn($type, $type) -> ($type, $type)) -> $type {
+                    const BITS: u32 = $type::BITS;
+                    let m = Self::M as u32;
+                    let tail = (POLY ^ (1 << m)) as $type;
+
+                    // q = floor(floor(c / x^M) * (x^M + BARRETT) / x^M), exact for polynomials
+                    let c1 = lo.checked_shr(m).unwrap_or(0) | hi.checked_shl(BITS - m).unwrap_or(0);
+                    let (h, l) = mul(c1, Self::BARRETT);
+                    let q = c1 ^ l.checked_shr(m).unwrap_or(0) ^ h.checked_shl(BITS - m).unwrap_or(0);
+                    (lo ^ mul(q, tail).1) & Self::MASK
+                }
+
+                // Extended Euclidean algorithm on the storage type
+                fn euclid_inverse(&self) -> Self {
                     if *self == Self::ONE {
                         return Self::ONE;
                     }
 
-                    let mut temp_t: Self::StorageType;
-                    let mut t: Self::StorageType = 0;
-                    let mut new_t: Self::StorageType = 1;
+                    let mut temp_t: $type;
+                    let mut t: $type = 0;
+                    let mut new_t: $type = 1;
 
                     let mut quo = Self::ZERO;
                     let mut nt = Self::ZERO;
 
-                    let mut new_remainder: Self::StorageType;
+                    let mut new_remainder: $type;
                     let mut remainder = self.value;
                     (quo.value, new_remainder) = $type::gf2_poly_div_poly(POLY, self.value);
 
@@ -83,31 +199,24 @@ macro_rules! setup_gf {
                         value: t
                     }
                 }
-
-                fn new(value: $type) -> Self {
-                    Self {value: value}
-                }
-
-                fn validate(&self) -> bool {
-                    (self.value as u128) >= Self::NUM_ELEM
-                }
             }
 
-            impl<const POLY: u128> fmt::Debug for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> fmt::Debug for [<GF $type>]<POLY, INV> {
                 fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     write!(f, "GF<{:#0X}>(value: 0x{:0width$x})", POLY, self.value, width = (Self::M as usize / 4))
                 }
             }
 
-            impl<const POLY: u128> fmt::Display for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> fmt::Display for [<GF $type>]<POLY, INV> {
                 fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     write!(f, "0x{:0width$X}", self.value, width = (Self::M as usize / 4))
                 }
             }
 
-            impl<const POLY: u128> Add<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> Add<[<GF $type>]<POLY, INV>> for [<GF $type>]<POLY, INV> {
                 type Output = Self;
 
+                #[allow(clippy::suspicious_arithmetic_impl)]
                 fn add(self, other: Self) -> Self {
                     Self {
                         value: self.value ^ other.value,
@@ -115,9 +224,10 @@ macro_rules! setup_gf {
                 }
             }
 
-            impl<const POLY: u128> Sub<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> Sub<[<GF $type>]<POLY, INV>> for [<GF $type>]<POLY, INV> {
                 type Output = Self;
 
+                #[allow(clippy::suspicious_arithmetic_impl)]
                 fn sub(self, other: Self) -> Self {
                     Self {
                         value: self.value ^ other.value,
@@ -125,7 +235,15 @@ macro_rules! setup_gf {
                 }
             }
 
-            impl<const POLY: u128> Mul<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> Neg for [<GF $type>]<POLY, INV> {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    self
+                }
+            }
+
+            impl<const POLY: u128, const INV: u8> Mul<[<GF $type>]<POLY, INV>> for [<GF $type>]<POLY, INV> {
                 type Output = Self;
 
                 fn mul(self, other: Self) -> Self {
@@ -133,14 +251,15 @@ macro_rules! setup_gf {
                     let lo = self.value.clmul_low(other.value);
 
                     Self {
-                        value: $type::gf2_poly_mod(hi, lo, POLY),
+                        value: Self::reduce(hi, lo),
                     }
                 }
             }
 
-            impl<const POLY: u128> Div<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+            impl<const POLY: u128, const INV: u8> Div<[<GF $type>]<POLY, INV>> for [<GF $type>]<POLY, INV> {
                 type Output = Self;
 
+                #[allow(clippy::suspicious_arithmetic_impl)]
                 fn div(self, other: Self) -> Self {
                     self * other.inverse()
                 }
@@ -152,6 +271,153 @@ macro_rules! setup_gf {
                 $type: MulAssign: mul_assign: *,
                 $type: DivAssign: div_assign: /,
             }
+
+            ref_operator_impl! {
+                $type: Add: add: +,
+                $type: Sub: sub: -,
+                $type: Mul: mul: *,
+                $type: Div: div: /,
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128, const INV: u8> subtle::ConstantTimeEq for [<GF $type>]<POLY, INV> {
+                fn ct_eq(&self, other: &Self) -> subtle::Choice {
+                    subtle::ConstantTimeEq::ct_eq(&self.value, &other.value)
+                }
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128, const INV: u8> subtle::ConditionallySelectable for [<GF $type>]<POLY, INV> {
+                fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+                    Self {
+                        value: <$type as subtle::ConditionallySelectable>::conditional_select(&a.value, &b.value, choice),
+                    }
+                }
+            }
+
+            #[cfg(feature = "zeroize")]
+            impl<const POLY: u128, const INV: u8> zeroize::Zeroize for [<GF $type>]<POLY, INV> {
+                fn zeroize(&mut self) {
+                    zeroize::Zeroize::zeroize(&mut self.value);
+                }
+            }
+        }
+    )*
+    };
+}
+
+// floor(x^(2M) / poly) minus x^M, by long division one quotient bit at a time
+const fn barrett_constant(poly: u128) -> u128 {
+    let m = crate::calc_degree(poly) as u32;
+    let mut quotient: u128 = 0;
+    // The top M + 1 coefficients of the running remainder, starting from x^(2M)
+    let mut window: u128 = 1 << m;
+    let mut i = m;
+    loop {
+        if (window >> m) & 0x1 > 0 {
+            quotient |= 1 << i;
+            window ^= poly;
+        }
+        if i == 0 {
+            break;
+        }
+        window <<= 1;
+        i -= 1;
+    }
+    quotient ^ (1 << m)
+}
+
+// Inverse tables for the storage types small enough to hold them
+macro_rules! lut_inversion {
+    ($($type:ty,)*) => {
+    $(
+        paste! {
+            // inverses[a] = a^-1, from the powers of the first generator of the multiplicative group
+            const fn [<generate_inverses_ $type>](poly: u128) -> [$type; 1 << $type::BITS] {
+                let m = crate::calc_degree(poly) as u32;
+                let order = (1usize << m) - 1;
+                let mut inverses: [$type; 1 << $type::BITS] = [0; 1 << $type::BITS];
+                inverses[1] = 1;
+                if order == 1 {
+                    return inverses;
+                }
+
+                let mut powers: [$type; 1 << $type::BITS] = [0; 1 << $type::BITS];
+                let mut g: u32 = 2;
+                loop {
+                    if g as usize > order {
+                        panic!("Polynomial must be irreducible");
+                    }
+                    let mut value: u32 = 1;
+                    let mut i = 0;
+                    loop {
+                        if i == order {
+                            panic!("Polynomial must be irreducible");
+                        }
+                        powers[i] = value as $type;
+                        i += 1;
+                        // value *= g, shifting and adding
+                        let mut product: u32 = 0;
+                        let mut bit = 0;
+                        while (g >> bit) != 0 {
+                            if (g >> bit) & 1 == 1 {
+                                product ^= value;
+                            }
+                            value <<= 1;
+                            if (value >> m) & 1 == 1 {
+                                value ^= poly as u32;
+                            }
+                            bit += 1;
+                        }
+                        value = product;
+                        if value == 1 {
+                            break;
+                        }
+                    }
+                    if i == order {
+                        break;
+                    }
+                    g += 1;
+                }
+
+                let mut i = 1;
+                while i < order {
+                    inverses[powers[i] as usize] = powers[order - i];
+                    i += 1;
+                }
+                inverses
+            }
+
+            impl<const POLY: u128, const INV: u8> [<GF $type>]<POLY, INV> {
+                // Only built for the types that invert with it, so other strategies work with any POLY
+                const INVERSES: [$type; 1 << $type::BITS] =
+                    if INV == inversion::LUT || (INV == inversion::AUTO && $type::BITS == 8) {
+                        [<generate_inverses_ $type>](POLY)
+                    } else {
+                        [0; 1 << $type::BITS]
+                    };
+
+                fn lut_inverse(&self) -> Self {
+                    Self {
+                        value: Self::INVERSES[self.value as usize],
+                    }
+                }
+            }
+        }
+    )*
+    };
+}
+
+// VALID_INVERSION rejects LUT for these
+macro_rules! no_lut_inversion {
+    ($($type:ty,)*) => {
+    $(
+        paste! {
+            impl<const POLY: u128, const INV: u8> [<GF $type>]<POLY, INV> {
+                fn lut_inverse(&self) -> Self {
+                    unreachable!()
+                }
+            }
         }
     )*
     };
@@ -164,3 +430,65 @@ setup_gf! {
     u64,
     u128,
 }
+
+lut_inversion! {
+    u8,
+    u16,
+}
+
+no_lut_inversion! {
+    u32,
+    u64,
+    u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clmul::PortableClmul;
+    use rand::Rng;
+
+    // Barrett reduction with the portable and the hardware carry-less multiply against the long division
+    macro_rules! reduce_tests {
+        ($($type:ident: $storage:ty: $poly:expr,)*) => {
+            $({
+                type GF = $type<$poly>;
+                for _ in 0..1000 {
+                    let a = rand::thread_rng().gen::<$storage>() & GF::MASK;
+                    let b = rand::thread_rng().gen::<$storage>() & GF::MASK;
+                    let (hi, lo) = (PortableClmul::clmul_high(&a, b), PortableClmul::clmul_low(&a, b));
+                    let expected = <$storage>::gf2_poly_mod(hi, lo, $poly);
+
+                    let portable = |x: $storage, y: $storage| {
+                        (PortableClmul::clmul_high(&x, y), PortableClmul::clmul_low(&x, y))
+                    };
+                    assert_eq!(GF::barrett(hi, lo, portable), expected);
+                    if clmul::hardware() {
+                        // SAFETY: the instruction is available
+                        let hardware = |x: $storage, y: $storage| unsafe { x.clmul_hardware(y) };
+                        assert_eq!(GF::barrett(hi, lo, hardware), expected);
+                    }
+                    assert_eq!(GF::reduce(hi, lo), expected);
+                }
+            })*
+        };
+    }
+
+    #[test]
+    fn reduce() {
+        reduce_tests! {
+            GFu8: u8: 0x3,
+            GFu8: u8: 0x13,
+            GFu8: u8: 0x11B,
+            GFu16: u16: 0x211,
+            GFu16: u16: 0x1_002D,
+            GFu32: u32: 0x2_0009,
+            GFu32: u32: 0x1_0000_008D,
+            GFu64: u64: 0x20_0000_0000_0047,
+            GFu64: u64: 0x1_0000_0000_0000_001B,
+            GFu128: u128: 0x87,
+            GFu128: u128: 0x1_0000_0000_0000_0000_0000_0000_0087,
+            GFu128: u128: 0x8000_0000_0000_0000_0000_0000_0000_0003,
+        }
+    }
+}
@@ -1,12 +1,67 @@
-pub trait CarryLessMultiply {
+// Each hardware module reports with detected() whether the CPU has the carry-less multiply instruction and
+// provides clmul_u64, the 127 bit product of two u64 polynomials. The instruction is known at compile time when
+// the target enables it and is otherwise detected at runtime with the std feature
+#[cfg(all(
+    target_arch = "x86_64",
+    any(target_feature = "pclmulqdq", feature = "std")
+))]
+#[path = "clmul/x86_64.rs"]
+mod hardware;
+
+#[cfg(all(target_arch = "aarch64", any(target_feature = "aes", feature = "std")))]
+#[path = "clmul/aarch64.rs"]
+mod hardware;
+
+#[cfg(not(any(
+    all(
+        target_arch = "x86_64",
+        any(target_feature = "pclmulqdq", feature = "std")
+    ),
+    all(target_arch = "aarch64", any(target_feature = "aes", feature = "std"))
+)))]
+#[path = "clmul/none.rs"]
+mod hardware;
+
+// Whether CarryLessMultiply is backed by a hardware instruction. Code with a table based alternative,
+// such as CRC folding, only prefers carry-less multiplication when it is
+#[inline]
+pub(crate) fn hardware() -> bool {
+    hardware::detected()
+}
+
+pub trait CarryLessMultiply: Sized {
     fn clmul_low(&self, rhs: Self) -> Self;
     fn clmul_high(&self, rhs: Self) -> Self;
+    fn clsquare_low(&self) -> Self;
+    fn clsquare_high(&self) -> Self;
+
+    // The (high, low) halves of the product with the instruction. Only call when hardware() is true
+    unsafe fn clmul_hardware(&self, rhs: Self) -> (Self, Self);
 }
 
-macro_rules! clmul_impl {
+// The shift and XOR loops, used when no instruction is available and as the reference in tests
+pub(crate) trait PortableClmul {
+    fn clmul_low(&self, rhs: Self) -> Self;
+    fn clmul_high(&self, rhs: Self) -> Self;
+    fn clsquare_low(&self) -> Self;
+    fn clsquare_high(&self) -> Self;
+}
+
+// Moving the upper half of every group of 2 * shift bits up by shift, with shift halving down to 1, puts a 0 above
+// each bit in log2(BITS) steps. The masks keep the lower half of every group of 2 * shift bits
+const SPREAD_MASKS: [(u32, u128); 6] = [
+    (32, 0x0000_0000_FFFF_FFFF_0000_0000_FFFF_FFFF),
+    (16, 0x0000_FFFF_0000_FFFF_0000_FFFF_0000_FFFF),
+    (8, 0x00FF_00FF_00FF_00FF_00FF_00FF_00FF_00FF),
+    (4, 0x0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F),
+    (2, 0x3333_3333_3333_3333_3333_3333_3333_3333),
+    (1, 0x5555_5555_5555_5555_5555_5555_5555_5555),
+];
+
+macro_rules! portable_impl {
     ($($type:ty,)*) => {
     $(
-        impl CarryLessMultiply for $type {
+        impl PortableClmul for $type {
             fn clmul_low(&self, rhs: Self) -> Self {
                 const NUM_BITS: u32 = <$type>::BITS;
                 let mut output: Self = 0;
@@ -32,15 +87,197 @@ macro_rules! clmul_impl {
 
                 output
             }
+
+            // Squaring a GF(2) polynomial spreads its bits out, inserting a 0 between each bit
+            fn clsquare_low(&self) -> Self {
+                const HALF_BITS: u32 = <$type>::BITS / 2;
+                let mut output = *self & (<$type>::MAX >> HALF_BITS);
+
+                for &(shift, mask) in SPREAD_MASKS.iter().filter(|(shift, _)| *shift < HALF_BITS) {
+                    output = (output | output << shift) & mask as $type;
+                }
+
+                output
+            }
+
+            fn clsquare_high(&self) -> Self {
+                const HALF_BITS: u32 = <$type>::BITS / 2;
+                let mut output = *self >> HALF_BITS;
+
+                for &(shift, mask) in SPREAD_MASKS.iter().filter(|(shift, _)| *shift < HALF_BITS) {
+                    output = (output | output << shift) & mask as $type;
+                }
+
+                output
+            }
         }
     )*
     };
 }
 
-clmul_impl! {
+// The product of types up to 64 bits fits in one 64 bit carry-less multiply
+macro_rules! clmul_impl {
+    ($($type:ty,)*) => {
+    $(
+        impl CarryLessMultiply for $type {
+            fn clmul_low(&self, rhs: Self) -> Self {
+                if !hardware() {
+                    return PortableClmul::clmul_low(self, rhs);
+                }
+                // SAFETY: the instruction is available
+                unsafe { self.clmul_hardware(rhs).1 }
+            }
+
+            fn clmul_high(&self, rhs: Self) -> Self {
+                if !hardware() {
+                    return PortableClmul::clmul_high(self, rhs);
+                }
+                // SAFETY: the instruction is available
+                unsafe { self.clmul_hardware(rhs).0 }
+            }
+
+            fn clsquare_low(&self) -> Self {
+                if !hardware() {
+                    return PortableClmul::clsquare_low(self);
+                }
+                // SAFETY: the instruction is available
+                unsafe { self.clmul_hardware(*self).1 }
+            }
+
+            fn clsquare_high(&self) -> Self {
+                if !hardware() {
+                    return PortableClmul::clsquare_high(self);
+                }
+                // SAFETY: the instruction is available
+                unsafe { self.clmul_hardware(*self).0 }
+            }
+
+            unsafe fn clmul_hardware(&self, rhs: Self) -> (Self, Self) {
+                let product = hardware::clmul_u64(*self as u64, rhs as u64);
+                ((product >> <$type>::BITS) as $type, product as $type)
+            }
+        }
+    )*
+    };
+}
+
+portable_impl! {
     u8,
     u16,
     u32,
     u64,
     u128,
 }
+
+clmul_impl! {
+    u8,
+    u16,
+    u32,
+    u64,
+}
+
+// The 255 bit product of two u128 polynomials as (high, low), with Karatsuba's three multiplies. Only call when
+// the instruction is available
+unsafe fn clmul_u128(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = ((a >> 64) as u64, a as u64);
+    let (b1, b0) = ((b >> 64) as u64, b as u64);
+    let lo = hardware::clmul_u64(a0, b0);
+    let hi = hardware::clmul_u64(a1, b1);
+    let mid = hardware::clmul_u64(a0 ^ a1, b0 ^ b1) ^ lo ^ hi;
+    (hi ^ (mid >> 64), lo ^ (mid << 64))
+}
+
+impl CarryLessMultiply for u128 {
+    fn clmul_low(&self, rhs: Self) -> Self {
+        if !hardware() {
+            return PortableClmul::clmul_low(self, rhs);
+        }
+        // SAFETY: the instruction is available
+        unsafe { clmul_u128(*self, rhs).1 }
+    }
+
+    fn clmul_high(&self, rhs: Self) -> Self {
+        if !hardware() {
+            return PortableClmul::clmul_high(self, rhs);
+        }
+        // SAFETY: the instruction is available
+        unsafe { clmul_u128(*self, rhs).0 }
+    }
+
+    fn clsquare_low(&self) -> Self {
+        if !hardware() {
+            return PortableClmul::clsquare_low(self);
+        }
+        // SAFETY: the instruction is available
+        unsafe { clmul_u128(*self, *self).1 }
+    }
+
+    fn clsquare_high(&self) -> Self {
+        if !hardware() {
+            return PortableClmul::clsquare_high(self);
+        }
+        // SAFETY: the instruction is available
+        unsafe { clmul_u128(*self, *self).0 }
+    }
+
+    unsafe fn clmul_hardware(&self, rhs: Self) -> (Self, Self) {
+        clmul_u128(*self, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    macro_rules! matches_portable_tests {
+        ($($type:ty,)*) => {
+        $(
+            for _ in 0..1000 {
+                let a: $type = rand::thread_rng().gen();
+                let b: $type = rand::thread_rng().gen();
+                assert_eq!(CarryLessMultiply::clmul_low(&a, b), PortableClmul::clmul_low(&a, b));
+                assert_eq!(CarryLessMultiply::clmul_high(&a, b), PortableClmul::clmul_high(&a, b));
+                assert_eq!(CarryLessMultiply::clsquare_low(&a), PortableClmul::clsquare_low(&a));
+                assert_eq!(CarryLessMultiply::clsquare_high(&a), PortableClmul::clsquare_high(&a));
+            }
+        )*
+        }
+    }
+
+    macro_rules! portable_square_tests {
+        ($($type:ty,)*) => {
+        $(
+            for _ in 0..1000 {
+                let a: $type = rand::thread_rng().gen();
+                assert_eq!(PortableClmul::clsquare_low(&a), PortableClmul::clmul_low(&a, a));
+                assert_eq!(PortableClmul::clsquare_high(&a), PortableClmul::clmul_high(&a, a));
+            }
+            assert_eq!(PortableClmul::clsquare_low(&<$type>::MAX), <$type>::MAX / 3);
+            assert_eq!(PortableClmul::clsquare_high(&<$type>::MAX), <$type>::MAX / 3);
+        )*
+        }
+    }
+
+    #[test]
+    fn portable_square() {
+        portable_square_tests! {
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+        }
+    }
+
+    #[test]
+    fn matches_portable() {
+        matches_portable_tests! {
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+// PMULL products, part of the cryptographic extension that the aes target feature enables
+use core::arch::aarch64::vmull_p64;
+
+#[cfg(target_feature = "aes")]
+#[inline]
+pub(super) fn detected() -> bool {
+    true
+}
+
+#[cfg(not(target_feature = "aes"))]
+#[inline]
+pub(super) fn detected() -> bool {
+    static DETECTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *DETECTED.get_or_init(|| std::arch::is_aarch64_feature_detected!("aes"))
+}
+
+// Only call when detected() is true
+#[target_feature(enable = "aes")]
+pub(super) unsafe fn clmul_u64(a: u64, b: u64) -> u128 {
+    vmull_p64(a, b)
+}
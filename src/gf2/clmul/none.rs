@@ -0,0 +1,9 @@
+// Targets without a carry-less multiply instruction, or without std to detect it
+#[inline]
+pub(super) fn detected() -> bool {
+    false
+}
+
+pub(super) unsafe fn clmul_u64(_: u64, _: u64) -> u128 {
+    unreachable!()
+}
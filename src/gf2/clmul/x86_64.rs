@@ -0,0 +1,28 @@
+// PCLMULQDQ products
+use core::arch::x86_64::{__m128i, _mm_clmulepi64_si128, _mm_cvtsi64_si128, _mm_storeu_si128};
+
+#[cfg(target_feature = "pclmulqdq")]
+#[inline]
+pub(super) fn detected() -> bool {
+    true
+}
+
+#[cfg(not(target_feature = "pclmulqdq"))]
+#[inline]
+pub(super) fn detected() -> bool {
+    static DETECTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *DETECTED.get_or_init(|| std::arch::is_x86_feature_detected!("pclmulqdq"))
+}
+
+// Only call when detected() is true
+#[target_feature(enable = "pclmulqdq")]
+pub(super) unsafe fn clmul_u64(a: u64, b: u64) -> u128 {
+    let product = _mm_clmulepi64_si128(
+        _mm_cvtsi64_si128(a as i64),
+        _mm_cvtsi64_si128(b as i64),
+        0x00,
+    );
+    let mut out = 0u128;
+    _mm_storeu_si128(&mut out as *mut u128 as *mut __m128i, product);
+    out
+}
@@ -0,0 +1,190 @@
+//! Inversion algorithms of the `gf2` types.
+//!
+//! The second const parameter of a `gf2` type selects how `inverse` works, for example
+//! `gf2::GFu64<POLY, { inversion::ITOH_TSUJII }>`. The default `AUTO` picks per type and CPU from the
+//! `benches/inversion.rs` timings of a release build on an x86_64 CPU, per inversion, with the PCLMULQDQ
+//! carry-less multiply and with the software one:
+//!
+//! | M   | Euclid          | Itoh–Tsujii     | Fermat          | LUT    |
+//! |-----|-----------------|-----------------|-----------------|--------|
+//! | 8   | 134 / 177 ns    | 221 / 426 ns    | 269 / 526 ns    | 1.0 ns |
+//! | 16  | 240 / 362 ns    | 456 / 1950 ns   | 550 / 3510 ns   | 1.5 ns |
+//! | 32  | 1.06 / 1.86 µs  | 0.94 / 9.8 µs   | 1.30 / 18 µs    |        |
+//! | 64  | 3.3 / 11 µs     | 1.7 / 43 µs     | 2.7 / 84 µs     |        |
+//! | 127 | 19 / 130 µs     | 6.8 / 162 µs    | 13 / 409 µs     |        |
+//!
+//! So `AUTO` uses `LUT` for `GFu8` and `EUCLID` up to M = 16, leaving the 128 KiB tables of `GFu16` to an explicit
+//! choice. From M = 32 it uses `ITOH_TSUJII` when the carry-less multiply is done in hardware and `EUCLID`
+//! otherwise. `FERMAT` remains for its fixed sequence of operations.
+//!
+//! The inverse table of `LUT` is built at compile time and the build fails if POLY is reducible. Since `AUTO`
+//! inverts `GFu8` with it, a `GFu8` over a reducible polynomial needs an explicit strategy to call `inverse`:
+//!
+//! ```compile_fail
+//! use galois_field_2pm::{GaloisField, gf2};
+//!
+//! // x^8 + x^2 + 1 = (x^4 + x + 1)^2
+//! let _ = gf2::GFu8::<0x105>::new(3).inverse();
+//! ```
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::gf2::inversion;
+//!
+//! let _ = gf2::GFu8::<0x105, { inversion::EUCLID }>::new(3).inverse();
+//! ```
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::gf2::inversion;
+//!
+//! type Euclid = gf2::GFu128<0x8000_0000_0000_0000_0000_0000_0000_0003>;
+//! type ItohTsujii = gf2::GFu128<0x8000_0000_0000_0000_0000_0000_0000_0003, { inversion::ITOH_TSUJII }>;
+//!
+//! let a = 0x1234_5678_9ABC_DEF0_0FED_CBA9_8765_4321;
+//! assert_eq!(Euclid::new(a).inverse().value, ItohTsujii::new(a).inverse().value);
+//! ```
+
+use crate::GaloisField;
+
+/// The extended Euclidean algorithm on the storage type. Fast for small M, with data dependent loop counts
+pub const EUCLID: u8 = 0;
+
+/// Itoh–Tsujii inversion: a<sup>-1</sup> = (a<sup>2<sup>M-1</sup>-1</sup>)<sup>2</sup> with an addition chain of
+/// M - 1 squarings and about log<sub>2</sub> M multiplications. The sequence of operations only depends on M
+pub const ITOH_TSUJII: u8 = 1;
+
+/// Fermat inversion: a<sup>-1</sup> = a<sup>2<sup>M</sup>-2</sup> by square and multiply
+pub const FERMAT: u8 = 2;
+
+/// A table of all inverses computed at compile time. Only for `GFu8` and `GFu16`, other types fail to compile
+pub const LUT: u8 = 3;
+
+/// `LUT` for `GFu8`, `EUCLID` up to M = 16 and from M = 32 `ITOH_TSUJII` with a hardware carry-less multiply,
+/// `EUCLID` without
+pub const AUTO: u8 = 4;
+
+/// Computes a<sup>-1</sup> with the Itoh–Tsujii addition chain. Returns 0 for 0
+pub fn itoh_tsujii<GF: GaloisField>(a: GF) -> GF {
+    // beta = a^(2^k - 1), built along the bits of M - 1 with
+    // a^(2^(2k) - 1) = (a^(2^k - 1))^(2^k) * a^(2^k - 1) and a^(2^(k+1) - 1) = (a^(2^k - 1))^2 * a
+    let target = GF::M - 1;
+    if target == 0 {
+        // GF(2), where every nonzero element is its own inverse
+        return a;
+    }
+    let mut beta = a;
+    let mut k = 1;
+    for i in (0..(127 - target.leading_zeros())).rev() {
+        let mut frobenius = beta;
+        for _ in 0..k {
+            frobenius = frobenius.square();
+        }
+        beta *= frobenius;
+        k *= 2;
+        if (target >> i) & 1 == 1 {
+            beta = beta.square() * a;
+            k += 1;
+        }
+    }
+    beta.square()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2;
+    use rand::Rng;
+
+    macro_rules! itoh_tsujii_tests {
+        ($($type:ident: $storage:ty: $poly:expr,)*) => {
+            $({
+                type Euclid = gf2::$type<$poly>;
+                type ItohTsujii = gf2::$type<$poly, ITOH_TSUJII>;
+                for _ in 0..100 {
+                    let value = rand::thread_rng().gen_range(1..Euclid::NUM_ELEM) as $storage;
+                    let a = Euclid::new(value);
+                    assert_eq!(itoh_tsujii(a), a.inverse());
+                    assert_eq!(ItohTsujii::new(value).inverse().value, a.inverse().value);
+                    assert_eq!(ItohTsujii::new(value) * ItohTsujii::new(value).inverse(), ItohTsujii::ONE);
+                }
+            })*
+        }
+    }
+
+    #[test]
+    fn matches_euclid() {
+        itoh_tsujii_tests! {
+            GFu8: u8: 0x3,
+            GFu8: u8: 0x7,
+            GFu8: u8: 0x11B,
+            GFu16: u16: 0x1_002D,
+            GFu32: u32: 0x2_0009,
+            GFu64: u64: 0x1_0000_0000_0000_001B,
+            GFu64: u64: 0x20_0000_0000_0047,
+            GFu128: u128: 0x8000_0000_0000_0000_0000_0000_0000_0003,
+        }
+    }
+
+    #[test]
+    fn strategies() {
+        type Auto8 = gf2::GFu8<0x11B>;
+        type Fermat8 = gf2::GFu8<0x11B, FERMAT>;
+        type Lut16 = gf2::GFu16<0x1_002D, LUT>;
+        type Euclid16 = gf2::GFu16<0x1_002D, EUCLID>;
+        type Fermat64 = gf2::GFu64<0x1_0000_0000_0000_001B, FERMAT>;
+        type Euclid64 = gf2::GFu64<0x1_0000_0000_0000_001B, EUCLID>;
+        assert_eq!(Auto8::strategy(), LUT);
+        assert_eq!(gf2::GFu16::<0x1_002D>::strategy(), EUCLID);
+        assert_eq!(gf2::GFu32::<0x2_0009>::strategy(), EUCLID);
+        let wide = if crate::gf2::clmul::hardware() {
+            ITOH_TSUJII
+        } else {
+            EUCLID
+        };
+        assert_eq!(gf2::GFu32::<0x1_0000_008D>::strategy(), wide);
+        assert_eq!(gf2::GFu64::<0x1_0000_0000_0000_001B>::strategy(), wide);
+        assert_eq!(Euclid64::strategy(), EUCLID);
+        assert_eq!(Fermat64::strategy(), FERMAT);
+
+        // Every inverse in the tables. x is not a generator modulo the AES polynomial, x + 1 is
+        for value in 1..=255 {
+            let a = gf2::GFu8::<0x11B, EUCLID>::new(value);
+            assert_eq!(Auto8::new(value).inverse().value, a.inverse().value);
+            assert_eq!(Fermat8::new(value).inverse().value, a.inverse().value);
+        }
+        for value in 1..=u16::MAX {
+            assert_eq!(
+                Lut16::new(value).inverse().value,
+                Euclid16::new(value).inverse().value
+            );
+        }
+        for _ in 0..100 {
+            let value = rand::thread_rng().gen_range(1..=u64::MAX);
+            assert_eq!(
+                Fermat64::new(value).inverse().value,
+                Euclid64::new(value).inverse().value
+            );
+        }
+
+        assert_eq!(gf2::GFu8::<0x3>::ONE.inverse(), gf2::GFu8::<0x3>::ONE);
+
+        // Only the table needs POLY irreducible. x + 1 is a unit modulo (x^4 + x + 1)^2
+        type Reducible = gf2::GFu8<0x105, EUCLID>;
+        assert_eq!(
+            Reducible::new(3) * Reducible::new(3).inverse(),
+            Reducible::ONE
+        );
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!(itoh_tsujii(gf2::GFu16::<0x1_002D>::ZERO).value, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inverse_of_zero() {
+        let _ = gf2::GFu32::<0x2_0009, ITOH_TSUJII>::ZERO.inverse();
+    }
+}
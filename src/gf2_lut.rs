@@ -1,5 +1,5 @@
 use core::fmt;
-use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use paste::paste;
 
 use crate::GaloisField;
@@ -30,6 +30,38 @@ macro_rules! assign_operator_impl {
     }
 }
 
+macro_rules! ref_operator_impl {
+    ($($type:ty: $trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
+    $(
+        paste! {
+            impl<'a, const POLY: u128> $trait_name<&'a [<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn $trait_fn(self, other: &'a Self) -> Self {
+                    self $op *other
+                }
+            }
+
+            impl<'a, const POLY: u128> $trait_name<[<GF $type>]<POLY>> for &'a [<GF $type>]<POLY> {
+                type Output = [<GF $type>]<POLY>;
+
+                fn $trait_fn(self, other: [<GF $type>]<POLY>) -> [<GF $type>]<POLY> {
+                    *self $op other
+                }
+            }
+
+            impl<'a, 'b, const POLY: u128> $trait_name<&'b [<GF $type>]<POLY>> for &'a [<GF $type>]<POLY> {
+                type Output = [<GF $type>]<POLY>;
+
+                fn $trait_fn(self, other: &'b [<GF $type>]<POLY>) -> [<GF $type>]<POLY> {
+                    *self $op *other
+                }
+            }
+        }
+    )*
+    }
+}
+
 macro_rules! setup_gf {
     ($($type:ty,)*) => {
     $(
@@ -45,8 +77,11 @@ macro_rules! setup_gf {
             impl<const POLY: u128> GaloisField for [<GF $type>]<POLY> {
                 type StorageType = $type;
 
+                const POLY: u128 = POLY;
                 const M: u128 = crate::calc_degree(POLY) as u128;
                 const NUM_ELEM: u128 = 1 << Self::M;
+                const MASK: $type = ((1u128 << Self::M) - 1) as $type;
+                const STORAGE_BITS: u32 = $type::BITS;
 
                 const ZERO: Self = Self {value: 0};
                 const ONE: Self = Self {value: 1};
@@ -60,11 +95,27 @@ macro_rules! setup_gf {
                 }
 
                 fn new(value: $type) -> Self {
-                    Self {value: value}
+                    Self {value}
                 }
 
                 fn validate(&self) -> bool {
-                    (self.value as u128) >= Self::NUM_ELEM
+                    (self.value & !Self::MASK) == 0
+                }
+
+                fn to_u128(&self) -> u128 {
+                    self.value as u128
+                }
+
+                fn from_u128(value: u128) -> Self {
+                    Self {value: value as $type}
+                }
+
+                fn square(&self) -> Self {
+                    if *self == Self::ZERO {
+                        Self::ZERO
+                    } else {
+                        Self::alpha_pow(2 * self.log_alpha())
+                    }
                 }
             }
 
@@ -113,8 +164,8 @@ macro_rules! setup_gf {
                 }
 
                 [<Tables $type:upper>] {
-                    exp_tbl: exp_tbl,
-                    log_tbl: log_tbl,
+                    exp_tbl,
+                    log_tbl,
                 }
             }
 
@@ -138,6 +189,7 @@ macro_rules! setup_gf {
             impl<const POLY: u128> Add<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
                 type Output = Self;
 
+                #[allow(clippy::suspicious_arithmetic_impl)]
                 fn add(self, other: Self) -> Self {
                     Self {
                         value: self.value ^ other.value,
@@ -148,6 +200,7 @@ macro_rules! setup_gf {
             impl<const POLY: u128> Sub<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
                 type Output = Self;
 
+                #[allow(clippy::suspicious_arithmetic_impl)]
                 fn sub(self, other: Self) -> Self {
                     Self {
                         value: self.value ^ other.value,
@@ -155,6 +208,14 @@ macro_rules! setup_gf {
                 }
             }
 
+            impl<const POLY: u128> Neg for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    self
+                }
+            }
+
             impl<const POLY: u128> Mul<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
                 type Output = Self;
 
@@ -187,6 +248,36 @@ macro_rules! setup_gf {
                 $type: MulAssign: mul_assign: *,
                 $type: DivAssign: div_assign: /,
             }
+
+            ref_operator_impl! {
+                $type: Add: add: +,
+                $type: Sub: sub: -,
+                $type: Mul: mul: *,
+                $type: Div: div: /,
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128> subtle::ConstantTimeEq for [<GF $type>]<POLY> {
+                fn ct_eq(&self, other: &Self) -> subtle::Choice {
+                    subtle::ConstantTimeEq::ct_eq(&self.value, &other.value)
+                }
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128> subtle::ConditionallySelectable for [<GF $type>]<POLY> {
+                fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+                    Self {
+                        value: <$type as subtle::ConditionallySelectable>::conditional_select(&a.value, &b.value, choice),
+                    }
+                }
+            }
+
+            #[cfg(feature = "zeroize")]
+            impl<const POLY: u128> zeroize::Zeroize for [<GF $type>]<POLY> {
+                fn zeroize(&mut self) {
+                    zeroize::Zeroize::zeroize(&mut self.value);
+                }
+            }
         }
     )*
     }
@@ -197,7 +288,9 @@ setup_gf! {
     u16,
 }
 
+// The alpha_pow test of GF(2), which has no powers of alpha besides 1, loops over an empty range
 #[cfg(test)]
+#[allow(clippy::reversed_empty_ranges)]
 mod tests {
     use super::*;
 
@@ -0,0 +1,363 @@
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use paste::paste;
+
+use crate::GaloisField;
+
+// Carry-less product of two nibbles. Index is (a << 4) | b. Shared by every field
+const NIBBLE_CLMUL: [u8; 256] = generate_nibble_clmul();
+
+const fn generate_nibble_clmul() -> [u8; 256] {
+    let mut tbl: [u8; 256] = [0; 256];
+    let mut idx: usize = 0;
+    while idx < 256 {
+        let a = (idx >> 4) as u8;
+        let b = (idx & 0xF) as u8;
+        let mut product: u8 = 0;
+        let mut i = 0;
+        while i < 4 {
+            if ((b >> i) & 0x1) > 0 {
+                product ^= a << i;
+            }
+            i += 1;
+        }
+        tbl[idx] = product;
+        idx += 1;
+    }
+    tbl
+}
+
+// Returns (byte * x^shift) mod poly for every byte
+const fn generate_reduction_tbl(poly: u128, shift: u32) -> [u128; 256] {
+    let m = crate::calc_degree(poly) as u32;
+    let mut tbl: [u128; 256] = [0; 256];
+    let mut byte: usize = 0;
+    while byte < 256 {
+        let mut value: u128 = 0;
+        let mut i: i32 = 7;
+        while i >= 0 {
+            value = mulx_mod(value, poly, m) ^ (((byte >> i) & 0x1) as u128);
+            i -= 1;
+        }
+        let mut j = 0;
+        while j < shift {
+            value = mulx_mod(value, poly, m);
+            j += 1;
+        }
+        tbl[byte] = value;
+        byte += 1;
+    }
+    tbl
+}
+
+const fn mulx_mod(value: u128, poly: u128, m: u32) -> u128 {
+    let shifted = value << 1;
+    if ((shifted >> m) & 0x1) > 0 {
+        shifted ^ poly
+    } else {
+        shifted
+    }
+}
+
+macro_rules! assign_operator_impl {
+    ($($type:ty: $trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
+    $(
+        paste! {
+            impl<const POLY: u128> $trait_name for [<GF $type>]<POLY> {
+                fn $trait_fn(&mut self, other: Self) {
+                    *self = *self $op other;
+                }
+            }
+        }
+    )*
+    }
+}
+
+macro_rules! ref_operator_impl {
+    ($($type:ty: $trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
+    $(
+        paste! {
+            impl<'a, const POLY: u128> $trait_name<&'a [<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn $trait_fn(self, other: &'a Self) -> Self {
+                    self $op *other
+                }
+            }
+
+            impl<'a, const POLY: u128> $trait_name<[<GF $type>]<POLY>> for &'a [<GF $type>]<POLY> {
+                type Output = [<GF $type>]<POLY>;
+
+                fn $trait_fn(self, other: [<GF $type>]<POLY>) -> [<GF $type>]<POLY> {
+                    *self $op other
+                }
+            }
+
+            impl<'a, 'b, const POLY: u128> $trait_name<&'b [<GF $type>]<POLY>> for &'a [<GF $type>]<POLY> {
+                type Output = [<GF $type>]<POLY>;
+
+                fn $trait_fn(self, other: &'b [<GF $type>]<POLY>) -> [<GF $type>]<POLY> {
+                    *self $op *other
+                }
+            }
+        }
+    )*
+    }
+}
+
+macro_rules! setup_gf {
+    ($($type:ty: $wide:ty,)*) => {
+    $(
+        paste! {
+            // Define the struct
+            #[repr(transparent)]
+            #[derive(Clone, Copy, Eq, PartialEq)]
+            pub struct [<GF $type>]<const POLY: u128> {
+                pub value: $type,
+            }
+
+            // Implement the traits
+            impl<const POLY: u128> GaloisField for [<GF $type>]<POLY> {
+                type StorageType = $type;
+
+                const POLY: u128 = POLY;
+                const M: u128 = crate::calc_degree(POLY) as u128;
+                const NUM_ELEM: u128 = 1 << Self::M;
+                const MASK: $type = ((1u128 << Self::M) - 1) as $type;
+                const STORAGE_BITS: u32 = $type::BITS;
+
+                const ZERO: Self = Self {value: 0};
+                const ONE: Self = Self {value: 1};
+
+                fn inverse(&self) -> Self {
+                    if *self == Self::ZERO {
+                        panic!("Cannot take inverse of zero");
+                    }
+
+                    // a^(2^M - 2) = a^-1
+                    self.pow(Self::NUM_ELEM - 2)
+                }
+
+                fn new(value: $type) -> Self {
+                    Self {value}
+                }
+
+                fn validate(&self) -> bool {
+                    (self.value & !Self::MASK) == 0
+                }
+
+                fn to_u128(&self) -> u128 {
+                    self.value as u128
+                }
+
+                fn from_u128(value: u128) -> Self {
+                    Self {value: value as $type}
+                }
+
+                fn square(&self) -> Self {
+                    *self * *self
+                }
+            }
+
+            // Implement all the behind the scenes detail
+            struct [<Tables $type:upper>] {
+                reduce_lo: [$type; 256],
+                reduce_hi: [$type; 256],
+            }
+
+            const fn [<generate_tables_ $type:lower>](poly: u128) -> [<Tables $type:upper>] {
+                let m = crate::calc_degree(poly) as u32;
+                let lo = generate_reduction_tbl(poly, m);
+                let hi = generate_reduction_tbl(poly, m + 8);
+
+                let mut reduce_lo: [$type; 256] = [0; 256];
+                let mut reduce_hi: [$type; 256] = [0; 256];
+                let mut i: usize = 0;
+                while i < 256 {
+                    reduce_lo[i] = lo[i] as $type;
+                    reduce_hi[i] = hi[i] as $type;
+                    i += 1;
+                }
+
+                [<Tables $type:upper>] {
+                    reduce_lo,
+                    reduce_hi,
+                }
+            }
+
+            impl<const POLY: u128> [<GF $type>]<POLY> {
+                const TABLES: [<Tables $type:upper>] = [<generate_tables_ $type:lower>](POLY);
+
+                // Carry-less product of the two values, one nibble pair at a time
+                fn clmul(a: $type, b: $type) -> $wide {
+                    const NIBBLES: u32 = $type::BITS / 4;
+                    let mut product: $wide = 0;
+
+                    for i in 0..NIBBLES {
+                        let a_nib = ((a >> (4 * i)) & 0xF) as usize;
+                        for j in 0..NIBBLES {
+                            let b_nib = ((b >> (4 * j)) & 0xF) as usize;
+                            product ^= (NIBBLE_CLMUL[(a_nib << 4) | b_nib] as $wide) << (4 * (i + j));
+                        }
+                    }
+
+                    product
+                }
+
+                // The product has degree at most 2M - 2 so the bits above M fit in two bytes
+                fn reduce(product: $wide) -> $type {
+                    let lo = (product as $type) & Self::MASK;
+                    let hi = (product >> Self::M) as usize;
+
+                    lo ^ Self::TABLES.reduce_lo[hi & 0xFF] ^ Self::TABLES.reduce_hi[(hi >> 8) & 0xFF]
+                }
+            }
+
+            impl<const POLY: u128> fmt::Debug for [<GF $type>]<POLY> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "GF<{:#0X}>(value: 0x{:0width$x})", POLY, self.value, width = (Self::M as usize / 4))
+                }
+            }
+
+            impl<const POLY: u128> fmt::Display for [<GF $type>]<POLY> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "0x{:0width$X}", self.value, width = (Self::M as usize / 4))
+                }
+            }
+
+            impl<const POLY: u128> Add<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn add(self, other: Self) -> Self {
+                    Self {
+                        value: self.value ^ other.value,
+                    }
+                }
+            }
+
+            impl<const POLY: u128> Sub<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn sub(self, other: Self) -> Self {
+                    Self {
+                        value: self.value ^ other.value,
+                    }
+                }
+            }
+
+            impl<const POLY: u128> Neg for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    self
+                }
+            }
+
+            impl<const POLY: u128> Mul<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn mul(self, other: Self) -> Self {
+                    Self {
+                        value: Self::reduce(Self::clmul(self.value, other.value)),
+                    }
+                }
+            }
+
+            impl<const POLY: u128> Div<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn div(self, other: Self) -> Self {
+                    self * other.inverse()
+                }
+            }
+
+            assign_operator_impl! {
+                $type: AddAssign: add_assign: +,
+                $type: SubAssign: sub_assign: -,
+                $type: MulAssign: mul_assign: *,
+                $type: DivAssign: div_assign: /,
+            }
+
+            ref_operator_impl! {
+                $type: Add: add: +,
+                $type: Sub: sub: -,
+                $type: Mul: mul: *,
+                $type: Div: div: /,
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128> subtle::ConstantTimeEq for [<GF $type>]<POLY> {
+                fn ct_eq(&self, other: &Self) -> subtle::Choice {
+                    subtle::ConstantTimeEq::ct_eq(&self.value, &other.value)
+                }
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128> subtle::ConditionallySelectable for [<GF $type>]<POLY> {
+                fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+                    Self {
+                        value: <$type as subtle::ConditionallySelectable>::conditional_select(&a.value, &b.value, choice),
+                    }
+                }
+            }
+
+            #[cfg(feature = "zeroize")]
+            impl<const POLY: u128> zeroize::Zeroize for [<GF $type>]<POLY> {
+                fn zeroize(&mut self) {
+                    zeroize::Zeroize::zeroize(&mut self.value);
+                }
+            }
+        }
+    )*
+    };
+}
+
+setup_gf! {
+    u8: u16,
+    u16: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2;
+    use rand::Rng;
+
+    #[test]
+    fn nibble_clmul() {
+        assert_eq!(NIBBLE_CLMUL[0x00], 0);
+        assert_eq!(NIBBLE_CLMUL[0x11], 1);
+        assert_eq!(NIBBLE_CLMUL[0x33], 0b101);
+        assert_eq!(NIBBLE_CLMUL[0xFF], 0b1010101);
+    }
+
+    macro_rules! matches_gf2_tests {
+        ($($type:ty: $poly:expr,)*) => {
+        $(
+            paste! {
+                #[test]
+                fn [<matches_gf2_ $poly>]() {
+                    type GF = [<GF $type>]<$poly>;
+                    type Ref = gf2::[<GF $type>]<$poly>;
+                    for _ in 0..10000 {
+                        let a = rand::thread_rng().gen_range(0..GF::NUM_ELEM) as $type;
+                        let b = rand::thread_rng().gen_range(1..GF::NUM_ELEM) as $type;
+                        assert_eq!((GF::new(a) * GF::new(b)).value, (Ref::new(a) * Ref::new(b)).value);
+                        assert_eq!((GF::new(a) / GF::new(b)).value, (Ref::new(a) / Ref::new(b)).value);
+                    }
+                }
+            }
+        )*
+        }
+    }
+
+    matches_gf2_tests! {
+        u8: 0x11b,
+        u16: 0x1053,
+        u16: 0x1002d,
+        u16: 0x1100b,
+    }
+}
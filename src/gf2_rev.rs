@@ -0,0 +1,426 @@
+//! GF(2<sup>M</sup>) with the coefficients in reflected (LSB first) order.
+//!
+//! The M bits of an element hold the coefficient of x<sup>0</sup> in bit M - 1 and the coefficient of
+//! x<sup>M-1</sup> in bit 0, the order of GCM, reflected CRCs and several FEC standards. The types take the usual
+//! POLY and compute in the reflected order directly: reflecting turns a product into a Montgomery product with
+//! R = x<sup>M-1</sup> modulo the reciprocal polynomial, so a multiplication is one carry-less product and a
+//! Montgomery reduction. Inversion goes by way of the natural order.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2, gf2_rev};
+//!
+//! // x^3 + x + 1 with x stored as 0b010 and x^2 + 1 as 0b101 either way round
+//! type Rev = gf2_rev::GFu8<0xB>;
+//! type Natural = gf2::GFu8<0xB>;
+//!
+//! let a = Rev::from_natural(0b011);
+//! assert_eq!(a.value, 0b110);
+//! assert_eq!((a * a).to_natural(), (Natural::new(0b011) * Natural::new(0b011)).value);
+//! assert_eq!(Rev::ONE.value, 0b100);
+//! ```
+
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use paste::paste;
+
+use crate::gf2;
+use crate::gf2::clmul::CarryLessMultiply;
+use crate::GaloisField;
+
+// The inverse of a polynomial with constant term 1 modulo x^k, one coefficient at a time
+const fn inverse_mod_xk(a: u128, k: u32) -> u128 {
+    let mut inv: u128 = 1;
+    let mut i = 1;
+    while i < k {
+        let mut coeff = 0;
+        let mut j = 0;
+        while j <= i {
+            coeff ^= (a >> j) & (inv >> (i - j)) & 0x1;
+            j += 1;
+        }
+        inv |= coeff << i;
+        i += 1;
+    }
+    inv
+}
+
+// The M low coefficients of the reciprocal x^M p(1/x), which has constant term 1
+const fn reciprocal_tail(poly: u128) -> u128 {
+    let m = crate::calc_degree(poly) as u32;
+    (poly.reverse_bits() >> (127 - m)) & ((1u128 << m) - 1)
+}
+
+macro_rules! assign_operator_impl {
+    ($($type:ty: $trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
+    $(
+        paste! {
+            impl<const POLY: u128> $trait_name for [<GF $type>]<POLY> {
+                fn $trait_fn(&mut self, other: Self) {
+                    *self = *self $op other;
+                }
+            }
+        }
+    )*
+    }
+}
+
+macro_rules! ref_operator_impl {
+    ($($type:ty: $trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
+    $(
+        paste! {
+            impl<'a, const POLY: u128> $trait_name<&'a [<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn $trait_fn(self, other: &'a Self) -> Self {
+                    self $op *other
+                }
+            }
+
+            impl<'a, const POLY: u128> $trait_name<[<GF $type>]<POLY>> for &'a [<GF $type>]<POLY> {
+                type Output = [<GF $type>]<POLY>;
+
+                fn $trait_fn(self, other: [<GF $type>]<POLY>) -> [<GF $type>]<POLY> {
+                    *self $op other
+                }
+            }
+
+            impl<'a, 'b, const POLY: u128> $trait_name<&'b [<GF $type>]<POLY>> for &'a [<GF $type>]<POLY> {
+                type Output = [<GF $type>]<POLY>;
+
+                fn $trait_fn(self, other: &'b [<GF $type>]<POLY>) -> [<GF $type>]<POLY> {
+                    *self $op *other
+                }
+            }
+        }
+    )*
+    }
+}
+
+macro_rules! setup_gf {
+    ($($type:ty,)*) => {
+    $(
+        paste! {
+            // Define the struct
+            #[repr(transparent)]
+            #[derive(Clone, Copy, Eq, PartialEq)]
+            pub struct [<GF $type>]<const POLY: u128> {
+                pub value: $type,
+            }
+
+            // Implement the traits
+            impl<const POLY: u128> GaloisField for [<GF $type>]<POLY> {
+                type StorageType = $type;
+
+                const POLY: u128 = POLY;
+                const M: u128 = crate::calc_degree(POLY) as u128;
+                const NUM_ELEM: u128 = 1 << Self::M;
+                const MASK: $type = ((1u128 << Self::M) - 1) as $type;
+                const STORAGE_BITS: u32 = $type::BITS;
+                const NATURAL_REPR: bool = false;
+
+                const ZERO: Self = Self {value: 0};
+                const ONE: Self = Self {value: 1 << (Self::M - 1)};
+
+                fn inverse(&self) -> Self {
+                    if *self == Self::ZERO {
+                        panic!("Cannot take inverse of zero");
+                    }
+                    Self::from_natural(gf2::[<GF $type>]::<POLY>::new(self.to_natural()).inverse().value)
+                }
+
+                fn new(value: $type) -> Self {
+                    Self {value}
+                }
+
+                fn validate(&self) -> bool {
+                    (self.value & !Self::MASK) == 0
+                }
+
+                fn to_u128(&self) -> u128 {
+                    self.value as u128
+                }
+
+                fn from_u128(value: u128) -> Self {
+                    Self {value: value as $type}
+                }
+
+                fn to_coefficients(&self) -> Vec<bool> {
+                    let value = self.to_natural() as u128;
+                    (0..Self::M).map(|i| ((value >> i) & 0x1) > 0).collect()
+                }
+
+                fn from_coefficients(coeffs: &[bool]) -> Self {
+                    if coeffs.len() as u128 > Self::M {
+                        panic!("Too many coefficients for the field");
+                    }
+                    let value = coeffs
+                        .iter()
+                        .enumerate()
+                        .fold(0u128, |acc, (i, &c)| acc | ((c as u128) << i));
+                    Self::from_natural(value as $type)
+                }
+
+                fn get_coeff(&self, i: usize) -> bool {
+                    if i as u128 >= Self::M {
+                        panic!("Coefficient index out of range");
+                    }
+                    ((self.value >> (Self::M as usize - 1 - i)) & 0x1) > 0
+                }
+
+                fn set_coeff(&mut self, i: usize, coeff: bool) {
+                    if i as u128 >= Self::M {
+                        panic!("Coefficient index out of range");
+                    }
+                    let bit = Self::M as usize - 1 - i;
+                    self.value = (self.value & !(1 << bit)) | ((coeff as $type) << bit);
+                }
+
+                // The coefficient of x^(M-1) leaves at bit 0
+                fn mul_x(&self) -> Self {
+                    let mut value = self.value >> 1;
+                    if (self.value & 0x1) > 0 {
+                        value ^= Self::REFLECTED_TAIL;
+                    }
+                    Self {value}
+                }
+
+                // The constant coefficient sits at bit M - 1. Dividing POLY by x leaves its x^M term at bit 0
+                fn div_x(&self) -> Self {
+                    let value = if ((self.value >> (Self::M - 1)) & 0x1) > 0 {
+                        ((self.value ^ Self::REFLECTED_TAIL) << 1) | 1
+                    } else {
+                        self.value << 1
+                    };
+                    Self {value: value & Self::MASK}
+                }
+
+                fn square(&self) -> Self {
+                    Self {
+                        value: Self::reduce(self.value.clsquare_high(), self.value.clsquare_low()),
+                    }
+                }
+            }
+
+            impl<const POLY: u128> [<GF $type>]<POLY> {
+                // POLY without x^M in the reflected order, bit M - 1 - i for x^i
+                const REFLECTED_TAIL: $type = ((reciprocal_tail(POLY) >> 1) | (1 << (Self::M - 1))) as $type;
+
+                // R = x^(M-1) and -1 / p*(x) mod R for the reciprocal p*(x) = x^M + RECIPROCAL_TAIL
+                const RECIPROCAL_TAIL: $type = reciprocal_tail(POLY) as $type;
+                const R_BITS: u32 = Self::M as u32 - 1;
+                const R_MASK: $type = ((1u128 << Self::R_BITS) - 1) as $type;
+                const NEG_INV: $type = inverse_mod_xk(reciprocal_tail(POLY), Self::R_BITS) as $type;
+
+                /// Constructs an element from its value in the natural order, bit i the coefficient of x<sup>i</sup>
+                pub fn from_natural(value: $type) -> Self {
+                    Self {
+                        value: Self::reflect(value),
+                    }
+                }
+
+                /// The value of the element in the natural order, bit i the coefficient of x<sup>i</sup>
+                pub fn to_natural(&self) -> $type {
+                    Self::reflect(self.value)
+                }
+
+                // Reverses the M low bits
+                fn reflect(value: $type) -> $type {
+                    value.reverse_bits() >> ($type::BITS - Self::M as u32)
+                }
+
+                // Montgomery reduction hi:lo / x^(M-1) mod p*, which is fully reduced as hi:lo has degree at most
+                // 2M - 2
+                fn reduce(hi: $type, lo: $type) -> $type {
+                    const BITS: u32 = $type::BITS;
+                    let m = Self::M as u32;
+
+                    // Adding q * p* = q * x^M + q * tail clears the R_BITS low bits
+                    let q = lo.clmul_low(Self::NEG_INV) & Self::R_MASK;
+                    let lo = lo ^ q.clmul_low(Self::RECIPROCAL_TAIL) ^ q.checked_shl(m).unwrap_or(0);
+                    let hi = hi ^ q.clmul_high(Self::RECIPROCAL_TAIL) ^ (q >> (BITS - m));
+
+                    (lo >> Self::R_BITS) | hi.checked_shl(BITS - Self::R_BITS).unwrap_or(0)
+                }
+            }
+
+            impl<const POLY: u128, const INV: u8> From<gf2::[<GF $type>]<POLY, INV>> for [<GF $type>]<POLY> {
+                fn from(a: gf2::[<GF $type>]<POLY, INV>) -> Self {
+                    Self::from_natural(a.value)
+                }
+            }
+
+            impl<const POLY: u128, const INV: u8> From<[<GF $type>]<POLY>> for gf2::[<GF $type>]<POLY, INV> {
+                fn from(a: [<GF $type>]<POLY>) -> Self {
+                    Self::new(a.to_natural())
+                }
+            }
+
+            impl<const POLY: u128> fmt::Debug for [<GF $type>]<POLY> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "GFRev<{:#0X}>(value: 0x{:0width$x})", POLY, self.value, width = (Self::M as usize / 4))
+                }
+            }
+
+            impl<const POLY: u128> fmt::Display for [<GF $type>]<POLY> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "0x{:0width$X}", self.value, width = (Self::M as usize / 4))
+                }
+            }
+
+            impl<const POLY: u128> Add<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn add(self, other: Self) -> Self {
+                    Self {
+                        value: self.value ^ other.value,
+                    }
+                }
+            }
+
+            impl<const POLY: u128> Sub<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn sub(self, other: Self) -> Self {
+                    Self {
+                        value: self.value ^ other.value,
+                    }
+                }
+            }
+
+            impl<const POLY: u128> Neg for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    self
+                }
+            }
+
+            impl<const POLY: u128> Mul<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                fn mul(self, other: Self) -> Self {
+                    let hi = self.value.clmul_high(other.value);
+                    let lo = self.value.clmul_low(other.value);
+
+                    Self {
+                        value: Self::reduce(hi, lo),
+                    }
+                }
+            }
+
+            impl<const POLY: u128> Div<[<GF $type>]<POLY>> for [<GF $type>]<POLY> {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn div(self, other: Self) -> Self {
+                    self * other.inverse()
+                }
+            }
+
+            assign_operator_impl! {
+                $type: AddAssign: add_assign: +,
+                $type: SubAssign: sub_assign: -,
+                $type: MulAssign: mul_assign: *,
+                $type: DivAssign: div_assign: /,
+            }
+
+            ref_operator_impl! {
+                $type: Add: add: +,
+                $type: Sub: sub: -,
+                $type: Mul: mul: *,
+                $type: Div: div: /,
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128> subtle::ConstantTimeEq for [<GF $type>]<POLY> {
+                fn ct_eq(&self, other: &Self) -> subtle::Choice {
+                    subtle::ConstantTimeEq::ct_eq(&self.value, &other.value)
+                }
+            }
+
+            #[cfg(feature = "subtle")]
+            impl<const POLY: u128> subtle::ConditionallySelectable for [<GF $type>]<POLY> {
+                fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+                    Self {
+                        value: <$type as subtle::ConditionallySelectable>::conditional_select(&a.value, &b.value, choice),
+                    }
+                }
+            }
+
+            #[cfg(feature = "zeroize")]
+            impl<const POLY: u128> zeroize::Zeroize for [<GF $type>]<POLY> {
+                fn zeroize(&mut self) {
+                    zeroize::Zeroize::zeroize(&mut self.value);
+                }
+            }
+        }
+    )*
+    };
+}
+
+setup_gf! {
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    macro_rules! matches_gf2_tests {
+        ($($type:ty: $poly:expr,)*) => {
+        $(
+            paste! {
+                #[test]
+                fn [<matches_gf2_ $poly>]() {
+                    type GF = [<GF $type>]<$poly>;
+                    type Ref = gf2::[<GF $type>]<$poly>;
+                    assert_eq!(GF::ONE.to_natural(), 1);
+                    for _ in 0..1000 {
+                        let a = (rand::thread_rng().gen::<u128>() & (GF::NUM_ELEM - 1)) as $type;
+                        let b = ((rand::thread_rng().gen::<u128>() & (GF::NUM_ELEM - 1)) as $type).max(1);
+                        let (ra, rb) = (GF::from_natural(a), GF::from_natural(b));
+                        assert_eq!((ra * rb).to_natural(), (Ref::new(a) * Ref::new(b)).value);
+                        assert_eq!((ra / rb).to_natural(), (Ref::new(a) / Ref::new(b)).value);
+                        assert_eq!(rb * rb.inverse(), GF::ONE);
+                        assert_eq!(ra.square().to_natural(), Ref::new(a).square().value);
+                        assert_eq!(ra.mul_x().to_natural(), Ref::new(a).mul_x().value);
+                        assert_eq!(ra.div_x().to_natural(), Ref::new(a).div_x().value);
+                        assert_eq!(ra.to_coefficients(), Ref::new(a).to_coefficients());
+                        assert_eq!(Ref::from(ra), Ref::new(a));
+                        assert_eq!(GF::from(Ref::new(a)), ra);
+                    }
+                }
+            }
+        )*
+        }
+    }
+
+    matches_gf2_tests! {
+        u8: 0x3,
+        u8: 0xb,
+        u8: 0x11b,
+        u16: 0x1002d,
+        u32: 0x2_0009,
+        u64: 0x1_0000_0000_0000_001b,
+        u128: 0x8000_0000_0000_0000_0000_0000_0000_0003,
+    }
+
+    #[test]
+    fn coefficients() {
+        type GF = GFu16<0x1002d>;
+        let mut a = GF::ZERO;
+        a.set_coeff(0, true);
+        assert_eq!(a, GF::ONE);
+        a.set_coeff(15, true);
+        assert_eq!(a.value, 0x8001);
+        assert!(a.get_coeff(15));
+        assert_eq!(GF::from_coefficients(&[false, true]).value, 0x4000);
+    }
+}
@@ -0,0 +1,345 @@
+//! Binary Goppa codes over GF(2<sup>M</sup>), the codes behind the McEliece cryptosystem.
+//!
+//! A Goppa polynomial g(x) of degree t over GF(2<sup>M</sup>) and a support L = (α<sub>0</sub>, ..., α<sub>n-1</sub>)
+//! of distinct field elements that are not roots of g define the binary code of the words c with
+//! Σ c<sub>i</sub> / (x - α<sub>i</sub>) ≡ 0 mod g(x). For an irreducible g it has k ≥ n - M * t and corrects up to
+//! t bit errors with Patterson's algorithm.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2_lut};
+//! use galois_field_2pm::goppa::Goppa;
+//! use galois_field_2pm::poly::Polynomial;
+//!
+//! type GF = gf2_lut::GFu8<0x43>;
+//! // g(x) = x^2 + x + α^5 is irreducible, so the code corrects 2 errors
+//! let g = Polynomial::new(vec![GF::new(0x20), GF::ONE, GF::ONE]);
+//! let support: Vec<GF> = (0..64).map(GF::new).collect();
+//! let code = Goppa::new(g, support);
+//! assert_eq!((code.n(), code.k()), (64, 52));
+//!
+//! let message: Vec<bool> = (0..52).map(|i| i % 3 == 0).collect();
+//! let mut received = code.encode(&message);
+//! received[5] ^= true;
+//! received[40] ^= true;
+//! assert_eq!(code.decode(&mut received), Some(2));
+//! assert_eq!(code.message(&received), message);
+//! ```
+
+use crate::bitmatrix::BitMatrix;
+use crate::poly::Polynomial;
+use crate::GaloisField;
+
+/// A binary Goppa code with an irreducible Goppa polynomial. Codewords are systematic on an information set:
+/// the message bits sit at the positions given by `information_set`, the rest are parity
+#[derive(Clone, Debug)]
+pub struct Goppa<GF: GaloisField> {
+    goppa_poly: Polynomial<GF>,
+    support: Vec<GF>,
+    parity_check: BitMatrix,
+    // The parity check matrix in reduced row echelon form, its pivot columns and the remaining columns
+    reduced: BitMatrix,
+    pivots: Vec<usize>,
+    information_set: Vec<usize>,
+    // The square root of x modulo g(x)
+    sqrt_x: Polynomial<GF>,
+}
+
+impl<GF: GaloisField> Goppa<GF> {
+    /// Constructs the code from its Goppa polynomial and support. Panics unless the polynomial is irreducible of
+    /// degree t ≥ 1, and the support elements are distinct, not roots of it and more than M * t of them
+    pub fn new(goppa_poly: Polynomial<GF>, support: Vec<GF>) -> Self {
+        let t = goppa_poly.degree();
+        if t < 1 {
+            panic!("The Goppa polynomial must have degree at least 1");
+        }
+        let t = t as usize;
+        let goppa_poly = goppa_poly.monic();
+        let factors = goppa_poly.factor();
+        if factors.len() != 1 || factors[0].1 != 1 {
+            panic!("The Goppa polynomial must be irreducible");
+        }
+        let m = GF::M as usize;
+        if support.len() <= m * t {
+            panic!("The support must have more than M * t elements");
+        }
+        if (1..support.len()).any(|i| support[..i].contains(&support[i])) {
+            panic!("The support elements must be distinct");
+        }
+        if support.iter().any(|&a| goppa_poly.eval(a) == GF::ZERO) {
+            panic!("The support must not contain roots of the Goppa polynomial");
+        }
+
+        // Row i of the field parity check matrix holds α_j^i / g(α_j). Each entry expands into M binary rows
+        let n = support.len();
+        let mut parity_check = BitMatrix::new(m * t, n);
+        for (j, &a) in support.iter().enumerate() {
+            let mut entry = goppa_poly.eval(a).inverse();
+            for i in 0..t {
+                for (b, bit) in entry.to_coefficients().into_iter().enumerate() {
+                    parity_check.set(i * m + b, j, bit);
+                }
+                entry *= a;
+            }
+        }
+
+        let mut reduced = parity_check.clone();
+        let rank = reduced.row_reduce();
+        let pivots: Vec<usize> = (0..rank)
+            .map(|r| (0..n).find(|&c| reduced.get(r, c)).unwrap())
+            .collect();
+        let information_set = (0..n).filter(|c| !pivots.contains(c)).collect();
+
+        // Squaring is linear, so sqrt(x) = x^(2^(M t - 1)) mod g(x)
+        let mut sqrt_x = Polynomial::monomial(GF::ONE, 1);
+        for _ in 1..m * t {
+            sqrt_x = (&sqrt_x * &sqrt_x).rem(&goppa_poly);
+        }
+
+        Self {
+            goppa_poly,
+            support,
+            parity_check,
+            reduced,
+            pivots,
+            information_set,
+            sqrt_x,
+        }
+    }
+
+    /// The codeword length n in bits, the size of the support
+    pub fn n(&self) -> usize {
+        self.support.len()
+    }
+
+    /// The message length k in bits
+    pub fn k(&self) -> usize {
+        self.information_set.len()
+    }
+
+    /// The number of bit errors that can be corrected, the degree of the Goppa polynomial
+    pub fn t(&self) -> usize {
+        self.goppa_poly.degree() as usize
+    }
+
+    /// The monic Goppa polynomial g(x)
+    pub fn goppa_poly(&self) -> &Polynomial<GF> {
+        &self.goppa_poly
+    }
+
+    /// The support α<sub>0</sub>, ..., α<sub>n-1</sub>
+    pub fn support(&self) -> &[GF] {
+        &self.support
+    }
+
+    /// The binary M * t × n parity check matrix. Its rows may be dependent
+    pub fn parity_check(&self) -> &BitMatrix {
+        &self.parity_check
+    }
+
+    /// The k codeword positions holding the message bits, in increasing order
+    pub fn information_set(&self) -> &[usize] {
+        &self.information_set
+    }
+
+    /// Encodes a message into a codeword. Panics unless the message has k bits
+    pub fn encode(&self, message: &[bool]) -> Vec<bool> {
+        if message.len() != self.k() {
+            panic!("Message length does not match k");
+        }
+
+        // Row r of the reduced matrix says the pivot bit is the sum of the information bits it covers
+        let mut codeword = vec![false; self.n()];
+        for (&c, &bit) in self.information_set.iter().zip(message) {
+            codeword[c] = bit;
+        }
+        for (r, &p) in self.pivots.iter().enumerate() {
+            codeword[p] = self
+                .information_set
+                .iter()
+                .zip(message)
+                .fold(false, |acc, (&c, &bit)| {
+                    acc ^ (bit & self.reduced.get(r, c))
+                });
+        }
+        codeword
+    }
+
+    /// Extracts the message bits of a codeword. Panics unless the codeword has n bits
+    pub fn message(&self, codeword: &[bool]) -> Vec<bool> {
+        if codeword.len() != self.n() {
+            panic!("Codeword length does not match n");
+        }
+        self.information_set.iter().map(|&c| codeword[c]).collect()
+    }
+
+    /// Computes the syndrome S(x) = Σ r<sub>i</sub> / (x - α<sub>i</sub>) mod g(x). It is zero exactly when
+    /// received is a codeword. Panics unless received has n bits
+    pub fn syndrome(&self, received: &[bool]) -> Polynomial<GF> {
+        if received.len() != self.n() {
+            panic!("Received word length does not match n");
+        }
+
+        // 1 / (x - a) = (g(a) - g(x)) / ((x - a) g(a)) mod g(x), where the quotient is g(x) / (x - a) without its
+        // remainder
+        received
+            .iter()
+            .zip(self.support.iter())
+            .filter(|(&bit, _)| bit)
+            .fold(Polynomial::zero(), |acc, (_, &a)| {
+                let linear = Polynomial::new(vec![-a, GF::ONE]);
+                let quotient = self.goppa_poly.div_rem(&linear).0;
+                acc - quotient * self.goppa_poly.eval(a).inverse()
+            })
+    }
+
+    /// Returns true if received is a codeword. Panics unless received has n bits
+    pub fn is_codeword(&self, received: &[bool]) -> bool {
+        self.syndrome(received).is_zero()
+    }
+
+    /// Corrects up to t bit errors in place with Patterson's algorithm. Returns the number of corrected bits, or
+    /// None if the errors could not be corrected, in which case received is left unchanged. Panics unless received
+    /// has n bits
+    pub fn decode(&self, received: &mut [bool]) -> Option<usize> {
+        let syndrome = self.syndrome(received);
+        if syndrome.is_zero() {
+            return Some(0);
+        }
+
+        // The error locator σ(x) = Π (x - α_i) over the error positions satisfies σ'(x) ≡ S(x) σ(x) mod g(x).
+        // Writing σ = a^2 + x b^2 gives b^2 (x + 1 / S) ≡ a^2, so b sqrt(x + 1 / S) ≡ a
+        let x = Polynomial::monomial(GF::ONE, 1);
+        let inverse = syndrome.inverse_mod(&self.goppa_poly)?;
+        let locator = if inverse == x {
+            x
+        } else {
+            let root = self.sqrt_mod(&(&inverse + &x));
+            let t = self.t() as isize;
+            let (a, _, b) = Polynomial::partial_extended_gcd(&self.goppa_poly, &root, t / 2 + 1);
+            &(&a * &a) + &(&x * &(&b * &b))
+        };
+        let num_errors = locator.degree() as usize;
+        if num_errors > self.t() {
+            return None;
+        }
+
+        let positions: Vec<usize> = (0..self.n())
+            .filter(|&i| locator.eval(self.support[i]) == GF::ZERO)
+            .collect();
+        if positions.len() != num_errors {
+            return None;
+        }
+
+        let mut corrected = received.to_vec();
+        for &p in positions.iter() {
+            corrected[p] ^= true;
+        }
+        if !self.is_codeword(&corrected) {
+            return None;
+        }
+        received.copy_from_slice(&corrected);
+        Some(num_errors)
+    }
+
+    // The square root modulo g(x). With p(x) = e(x)^2 + x o(x)^2 it is e(x) + sqrt(x) o(x)
+    fn sqrt_mod(&self, p: &Polynomial<GF>) -> Polynomial<GF> {
+        let half = |start: usize| {
+            let coeffs = p
+                .coeffs()
+                .iter()
+                .skip(start)
+                .step_by(2)
+                .map(|&c| c.pow(GF::NUM_ELEM / 2))
+                .collect();
+            Polynomial::new(coeffs)
+        };
+        (&half(0) + &(&self.sqrt_x * &half(1))).rem(&self.goppa_poly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x43>;
+
+    fn random_irreducible<F: GaloisField>(degree: usize) -> Polynomial<F> {
+        loop {
+            let mut coeffs: Vec<F> = (0..degree)
+                .map(|_| F::from_u128(rand::thread_rng().gen_range(0..F::NUM_ELEM)))
+                .collect();
+            coeffs.push(F::ONE);
+            let g = Polynomial::new(coeffs);
+            let factors = g.factor();
+            if factors.len() == 1 && factors[0].1 == 1 {
+                return g;
+            }
+        }
+    }
+
+    fn random_bits(len: usize) -> Vec<bool> {
+        (0..len).map(|_| rand::thread_rng().gen()).collect()
+    }
+
+    #[test]
+    fn parity_check() {
+        let code = Goppa::new(random_irreducible::<GF>(4), (0..64).map(GF::new).collect());
+        assert_eq!(code.t(), 4);
+        assert!(code.k() >= 64 - 6 * 4);
+        for _ in 0..10 {
+            let codeword = code.encode(&random_bits(code.k()));
+            assert!(code.parity_check().mul_vec(&codeword).iter().all(|&b| !b));
+            assert!(code.is_codeword(&codeword));
+        }
+    }
+
+    #[test]
+    fn decode() {
+        for t in [2, 3, 5, 6] {
+            let code = Goppa::new(random_irreducible::<GF>(t), (0..64).map(GF::new).collect());
+            for errors in 0..=t {
+                let message = random_bits(code.k());
+                let codeword = code.encode(&message);
+                let mut received = codeword.clone();
+                let mut positions: Vec<usize> = (0..code.n()).collect();
+                positions.shuffle(&mut rand::thread_rng());
+                for &p in positions[..errors].iter() {
+                    received[p] ^= true;
+                }
+
+                assert_eq!(code.decode(&mut received), Some(errors));
+                assert_eq!(received, codeword);
+                assert_eq!(code.message(&received), message);
+            }
+        }
+    }
+
+    #[test]
+    fn shuffled_support() {
+        // A support that is a proper subset of the field, in random order
+        type Big = gf2::GFu16<0x409>;
+        let mut support: Vec<Big> = (0..1024).map(Big::new).collect();
+        support.shuffle(&mut rand::thread_rng());
+        support.truncate(300);
+        let code = Goppa::new(random_irreducible::<Big>(12), support);
+        assert_eq!(code.n(), 300);
+
+        let codeword = code.encode(&random_bits(code.k()));
+        let mut received = codeword.clone();
+        for p in [0, 17, 99, 100, 101, 150, 201, 222, 250, 277, 298, 299] {
+            received[p] ^= true;
+        }
+        assert_eq!(code.decode(&mut received), Some(12));
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reducible() {
+        let g = Polynomial::new(vec![GF::ZERO, GF::ONE, GF::ONE]);
+        let _ = Goppa::new(g, (0..64).map(GF::new).collect());
+    }
+}
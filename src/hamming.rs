@@ -0,0 +1,259 @@
+//! Binary Hamming codes and their extended SECDED variants.
+//!
+//! The Hamming code with r parity bits has length n = 2<sup>r</sup> - 1 and k = n - r message bits. The columns
+//! of its parity check matrix are the n distinct nonzero vectors of r bits, so the syndrome of a single bit error is
+//! the column of the flipped bit and corrects it. The extended code appends an overall parity bit, which also detects
+//! every double error (single error correction, double error detection).
+//!
+//! Codewords are systematic: the k message bits followed by the parity bits, with the overall parity bit last.
+//!
+//! ```
+//! use galois_field_2pm::hamming::Hamming;
+//!
+//! let code = Hamming::extended(3);
+//! assert_eq!((code.n(), code.k()), (8, 4));
+//!
+//! let message = [true, false, true, true];
+//! let mut received = code.encode(&message);
+//! received[1] ^= true;
+//! assert_eq!(code.decode(&mut received), Some(1));
+//! assert_eq!(received[..4], message[..]);
+//!
+//! // Two errors are detected but not corrected
+//! received[0] ^= true;
+//! received[6] ^= true;
+//! assert_eq!(code.decode(&mut received), None);
+//! ```
+
+use crate::bitmatrix::BitMatrix;
+
+/// A binary Hamming code with r parity bits, optionally extended by an overall parity bit
+#[derive(Clone, Debug)]
+pub struct Hamming {
+    r: usize,
+    extended: bool,
+    // The r × n parity check matrix of the unextended code, [A | I]
+    parity_check: BitMatrix,
+    // The position of the bit whose column has the value of the index, or n for the zero syndrome
+    positions: Vec<usize>,
+}
+
+impl Hamming {
+    /// Constructs the (2<sup>r</sup> - 1, 2<sup>r</sup> - 1 - r) Hamming code. Panics unless 2 ≤ r ≤ 16
+    pub fn new(r: usize) -> Self {
+        if !(2..=16).contains(&r) {
+            panic!("Hamming codes need 2 <= r <= 16");
+        }
+        let n = (1 << r) - 1;
+
+        // The message bits take the columns that are not powers of two in increasing order, the parity bits the
+        // unit columns
+        let columns: Vec<usize> = (1..=n)
+            .filter(|c: &usize| !c.is_power_of_two())
+            .chain((0..r).map(|i| 1 << i))
+            .collect();
+        let mut parity_check = BitMatrix::new(r, n);
+        let mut positions = vec![n; n + 1];
+        for (j, &c) in columns.iter().enumerate() {
+            for i in 0..r {
+                parity_check.set(i, j, (c >> i) & 1 == 1);
+            }
+            positions[c] = j;
+        }
+
+        Self {
+            r,
+            extended: false,
+            parity_check,
+            positions,
+        }
+    }
+
+    /// Constructs the (2<sup>r</sup>, 2<sup>r</sup> - 1 - r) extended Hamming code, which corrects single errors
+    /// and detects double errors. Panics unless 2 ≤ r ≤ 16
+    pub fn extended(r: usize) -> Self {
+        Self {
+            extended: true,
+            ..Self::new(r)
+        }
+    }
+
+    /// The codeword length n in bits
+    pub fn n(&self) -> usize {
+        (1 << self.r) - 1 + self.extended as usize
+    }
+
+    /// The message length k in bits
+    pub fn k(&self) -> usize {
+        (1 << self.r) - 1 - self.r
+    }
+
+    /// The number of parity bits of the unextended code
+    pub fn r(&self) -> usize {
+        self.r
+    }
+
+    /// Returns true for the extended code with the overall parity bit
+    pub fn is_extended(&self) -> bool {
+        self.extended
+    }
+
+    /// The parity check matrix. For the extended code it has an extra all ones row covering every bit
+    pub fn parity_check(&self) -> BitMatrix {
+        if !self.extended {
+            return self.parity_check.clone();
+        }
+        let n = self.n();
+        let mut h = BitMatrix::new(self.r + 1, n);
+        for i in 0..self.r {
+            for j in 0..n - 1 {
+                h.set(i, j, self.parity_check.get(i, j));
+            }
+        }
+        for j in 0..n {
+            h.set(self.r, j, true);
+        }
+        h
+    }
+
+    /// The k × n generator matrix [I | P]
+    pub fn generator(&self) -> BitMatrix {
+        let (n, k) = (self.n(), self.k());
+        let mut g = BitMatrix::new(k, n);
+        let mut unit = vec![false; k];
+        for row in 0..k {
+            unit[row] = true;
+            for (c, bit) in self.encode(&unit).into_iter().enumerate() {
+                g.set(row, c, bit);
+            }
+            unit[row] = false;
+        }
+        g
+    }
+
+    /// Encodes a message into a codeword. Panics unless the message has k bits
+    pub fn encode(&self, message: &[bool]) -> Vec<bool> {
+        let k = self.k();
+        if message.len() != k {
+            panic!("Message length does not match k");
+        }
+
+        // Parity bit i is the sum of the message bits whose columns have bit i set
+        let mut codeword = message.to_vec();
+        for i in 0..self.r {
+            let parity = (0..k).fold(false, |acc, j| {
+                acc ^ (message[j] & self.parity_check.get(i, j))
+            });
+            codeword.push(parity);
+        }
+        if self.extended {
+            let overall = codeword.iter().fold(false, |acc, &b| acc ^ b);
+            codeword.push(overall);
+        }
+        codeword
+    }
+
+    /// Computes the syndrome, the parity check matrix times received. All bits are zero exactly when received is a
+    /// codeword. Panics unless received has n bits
+    pub fn syndrome(&self, received: &[bool]) -> Vec<bool> {
+        if received.len() != self.n() {
+            panic!("Received word length does not match n");
+        }
+        self.parity_check().mul_vec(received)
+    }
+
+    /// Corrects a single bit error in place. Returns the number of corrected bits. The extended code returns None
+    /// for a detected double error and leaves received unchanged. Errors of higher weight are miscorrected or, for
+    /// the extended code, may be detected. Panics unless received has n bits
+    pub fn decode(&self, received: &mut [bool]) -> Option<usize> {
+        let syndrome = self.syndrome(received);
+        let value = (0..self.r).fold(0, |acc, i| acc | ((syndrome[i] as usize) << i));
+        if self.extended {
+            // An odd number of errors flips the overall parity
+            let odd = syndrome[self.r];
+            match (value, odd) {
+                (0, false) => Some(0),
+                (0, true) => {
+                    received[self.n() - 1] ^= true;
+                    Some(1)
+                }
+                (_, true) => {
+                    received[self.positions[value]] ^= true;
+                    Some(1)
+                }
+                (_, false) => None,
+            }
+        } else if value == 0 {
+            Some(0)
+        } else {
+            received[self.positions[value]] ^= true;
+            Some(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn random_bits(len: usize) -> Vec<bool> {
+        (0..len).map(|_| rand::thread_rng().gen()).collect()
+    }
+
+    #[test]
+    fn parameters() {
+        for r in 2..=8 {
+            let code = Hamming::new(r);
+            assert_eq!(code.n(), (1 << r) - 1);
+            assert_eq!(code.k(), (1 << r) - 1 - r);
+            let g = code.generator();
+            let h = code.parity_check();
+            assert_eq!(h.rank(), r);
+            assert!((&g * &h.transpose()).row_masks().iter().all(|&m| m == 0));
+
+            let extended = Hamming::extended(r);
+            assert_eq!(extended.n(), 1 << r);
+            let g = extended.generator();
+            let h = extended.parity_check();
+            assert_eq!(h.rank(), r + 1);
+            assert!((&g * &h.transpose()).row_masks().iter().all(|&m| m == 0));
+        }
+    }
+
+    #[test]
+    fn single_errors() {
+        for code in [Hamming::new(4), Hamming::extended(4), Hamming::new(7)] {
+            let codeword = code.encode(&random_bits(code.k()));
+            assert!(code.syndrome(&codeword).iter().all(|&b| !b));
+            for p in 0..code.n() {
+                let mut received = codeword.clone();
+                received[p] ^= true;
+                assert_eq!(code.decode(&mut received), Some(1));
+                assert_eq!(received, codeword);
+            }
+        }
+    }
+
+    #[test]
+    fn double_errors_detected() {
+        let code = Hamming::extended(4);
+        let codeword = code.encode(&random_bits(code.k()));
+        for p in 0..16 {
+            for q in p + 1..16 {
+                let mut received = codeword.clone();
+                received[p] ^= true;
+                received[q] ^= true;
+                let before = received.clone();
+                assert_eq!(code.decode(&mut received), None);
+                assert_eq!(received, before);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_few_parity_bits() {
+        let _ = Hamming::new(1);
+    }
+}
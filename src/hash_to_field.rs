@@ -0,0 +1,145 @@
+//! Deterministic derivation of field elements from byte strings.
+//!
+//! `expand_message_xmd` is the expander of RFC 9380 over any `digest` hash. `hash_to_field` expands a message
+//! with a domain separation tag and cuts the output into ⌈M/8⌉ byte big endian chunks masked to M bits. Reducing a
+//! uniform polynomial modulo the field polynomial, or masking it, hits every element equally often, so unlike
+//! prime fields GF(2<sup>M</sup>) needs no extra bytes to hide a bias.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::hash_to_field::hash_to_field;
+//! use sha2::Sha256;
+//!
+//! type GF = gf2::GFu16<0x1002D>;
+//! let elements: Vec<GF> = hash_to_field::<GF, Sha256>(b"abc", b"galois_field_2pm-test", 4);
+//! assert_eq!(elements[0], GF::new(0x73EF));
+//! ```
+
+use digest::core_api::BlockSizeUser;
+use digest::Digest;
+
+use crate::GaloisField;
+
+/// The longest domain separation tag used as is. Longer tags are hashed first
+pub const MAX_DST_LEN: usize = 255;
+
+/// expand_message_xmd of RFC 9380: len_in_bytes uniform bytes from msg and the domain separation tag dst. Panics
+/// if len_in_bytes exceeds 65535 or 255 hash outputs
+pub fn expand_message_xmd<D: Digest + BlockSizeUser>(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Vec<u8> {
+    let b_in_bytes = <D as Digest>::output_size();
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    if ell > 255 || len_in_bytes > 65535 {
+        panic!("Requested too many bytes");
+    }
+
+    let oversize;
+    let dst = if dst.len() > MAX_DST_LEN {
+        oversize = D::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize();
+        &oversize[..]
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let b_0 = D::new()
+        .chain_update(vec![0u8; D::block_size()])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    let mut uniform = Vec::with_capacity(ell * b_in_bytes);
+    let mut b_i = D::new()
+        .chain_update(&b_0)
+        .chain_update([1])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    uniform.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mixed: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        b_i = D::new()
+            .chain_update(mixed)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        uniform.extend_from_slice(&b_i);
+    }
+    uniform.truncate(len_in_bytes);
+    uniform
+}
+
+/// Derives count field elements from msg under the domain separation tag dst. Each element is
+/// `GaloisField::from_random_bytes` of the next ⌈M/8⌉ bytes of `expand_message_xmd`
+pub fn hash_to_field<GF: GaloisField, D: Digest + BlockSizeUser>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Vec<GF> {
+    let chunk = GF::M.div_ceil(8) as usize;
+    expand_message_xmd::<D>(msg, dst, count * chunk)
+        .chunks(chunk)
+        .map(|bytes| GF::from_random_bytes(bytes).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2;
+    use sha2::Sha256;
+
+    const QUUX: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn rfc_9380_vectors() {
+        assert_eq!(
+            hex(&expand_message_xmd::<Sha256>(b"", QUUX, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex(&expand_message_xmd::<Sha256>(b"abc", QUUX, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        let long = expand_message_xmd::<Sha256>(b"abc", QUUX, 0x80);
+        assert_eq!(&hex(&long)[..32], "abba86a6129e366fc877aab32fc4ffc7");
+        assert_eq!(
+            &hex(&long)[192..],
+            "058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"
+        );
+    }
+
+    #[test]
+    fn elements() {
+        let dst = b"galois_field_2pm-test";
+        type GF16 = gf2::GFu16<0x1002D>;
+        let expected = [0x73EF, 0xB5E0, 0xEAF2, 0xFFFA].map(GF16::new);
+        assert_eq!(hash_to_field::<GF16, Sha256>(b"abc", dst, 4), expected);
+
+        // Six bit elements take one byte each
+        type GF6 = gf2::GFu8<0x43>;
+        let expected = [0x22, 0x3D, 0x16].map(GF6::new);
+        assert_eq!(hash_to_field::<GF6, Sha256>(b"abc", dst, 3), expected);
+
+        // Oversized tags are hashed, so they still separate domains
+        let long_dst = [0x55u8; 300];
+        assert_ne!(
+            hash_to_field::<GF16, Sha256>(b"abc", &long_dst, 4),
+            hash_to_field::<GF16, Sha256>(b"abc", &long_dst[..299], 4)
+        );
+    }
+}
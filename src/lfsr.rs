@@ -0,0 +1,349 @@
+//! Linear feedback shift registers over GF(2).
+//!
+//! An LFSR with the characteristic polynomial p(x) = x<sup>L</sup> + c<sub>L-1</sub>x<sup>L-1</sup> + ... +
+//! c<sub>0</sub> outputs a sequence with s<sub>n+L</sub> = c<sub>L-1</sub>s<sub>n+L-1</sub> + ... +
+//! c<sub>0</sub>s<sub>n</sub>. The taps are the lower coefficients c<sub>L-1</sub>, ..., c<sub>0</sub> as the bits
+//! of a u128, so p(x) may have degree up to 128. When p(x) is primitive and the state is nonzero the output is an
+//! m-sequence of period 2<sup>L</sup> - 1.
+//!
+//! `FibonacciLfsr` holds the next L outputs and feeds back their parity. `GaloisLfsr` holds a remainder modulo
+//! p(x), multiplies it by x each step and outputs the coefficient shifted out. Both also step a byte at a time,
+//! packing the first output bit into the least significant bit.
+//!
+//! `GoldCode` and `KasamiCode` combine m-sequences into the families of spreading codes with low cross-correlation
+//! used by GPS and CDMA.
+//!
+//! ```
+//! use galois_field_2pm::lfsr::{FibonacciLfsr, GaloisLfsr, Lfsr};
+//!
+//! // x^4 + x + 1 is primitive, so both registers repeat after 15 steps
+//! let mut fib = FibonacciLfsr::new(0b10011, 0b0001);
+//! let bits: Vec<bool> = (0..30).map(|_| fib.step()).collect();
+//! assert_eq!(bits[..15], bits[15..]);
+//!
+//! let mut gal = GaloisLfsr::new(0b10011, 0b0001);
+//! let mut bytes = [0u8; 4];
+//! gal.fill_bytes(&mut bytes);
+//! assert_ne!(bytes, [0; 4]);
+//! ```
+
+mod gold;
+
+pub use gold::{decimate, preferred_pair, GoldCode, KasamiCode};
+
+/// The stepping interface shared by the register configurations
+pub trait Lfsr {
+    /// The degree L of the characteristic polynomial, the number of state bits
+    fn degree(&self) -> u32;
+
+    /// The register contents
+    fn state(&self) -> u128;
+
+    /// Advances one step and returns the output bit
+    fn step(&mut self) -> bool;
+
+    /// Advances eight steps and returns the outputs, the first in the least significant bit
+    fn next_byte(&mut self) -> u8 {
+        step_byte(self)
+    }
+
+    /// Fills out with output bytes
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        for b in out.iter_mut() {
+            *b = self.next_byte();
+        }
+    }
+}
+
+// Eight single steps
+fn step_byte<L: Lfsr + ?Sized>(lfsr: &mut L) -> u8 {
+    (0..8).fold(0, |acc, i| acc | ((lfsr.step() as u8) << i))
+}
+
+// The L low bits
+fn state_mask(degree: u32) -> u128 {
+    u128::MAX >> (128 - degree)
+}
+
+// Splits a polynomial of degree 1 to 127 into its degree and taps
+fn split_poly(poly: u128) -> (u32, u128) {
+    let degree = crate::calc_degree(poly);
+    if degree < 1 {
+        panic!("Polynomial must have degree at least 1");
+    }
+    (degree as u32, poly ^ (1 << degree))
+}
+
+fn check_taps(degree: u32, taps: u128, state: u128) {
+    if degree == 0 || degree > 128 {
+        panic!("Degree must be between 1 and 128");
+    }
+    let mask = state_mask(degree);
+    if taps & !mask != 0 || state & !mask != 0 {
+        panic!("Taps and state must fit in degree bits");
+    }
+}
+
+/// A Fibonacci (external XOR) LFSR. Bit i of the state is the output i steps ahead
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FibonacciLfsr {
+    degree: u32,
+    taps: u128,
+    state: u128,
+    // Bit j of a byte step's new bits, s_(n+L+j), is the parity of the state masked with byte_masks[j]
+    byte_masks: [u128; 8],
+}
+
+impl FibonacciLfsr {
+    /// Constructs the register for a polynomial of degree 1 to 127 with the given state. Panics if the state does
+    /// not fit in the degree
+    pub fn new(poly: u128, state: u128) -> Self {
+        let (degree, taps) = split_poly(poly);
+        Self::from_taps(degree, taps, state)
+    }
+
+    /// Constructs the register for x<sup>degree</sup> + taps, which allows degree 128. Panics unless
+    /// 1 ≤ degree ≤ 128 and the taps and state fit in degree bits
+    pub fn from_taps(degree: u32, taps: u128, state: u128) -> Self {
+        check_taps(degree, taps, state);
+
+        // Track each sequence bit as a combination of the current state bits
+        let l = degree as usize;
+        let mut seq: Vec<u128> = (0..l).map(|i| 1 << i).collect();
+        for j in 0..8 {
+            let bit = (0..l)
+                .filter(|&i| (taps >> i) & 1 == 1)
+                .fold(0, |acc, i| acc ^ seq[j + i]);
+            seq.push(bit);
+        }
+        let mut byte_masks = [0; 8];
+        byte_masks.copy_from_slice(&seq[l..]);
+
+        Self {
+            degree,
+            taps,
+            state,
+            byte_masks,
+        }
+    }
+
+    /// The taps c<sub>L-1</sub>, ..., c<sub>0</sub>
+    pub fn taps(&self) -> u128 {
+        self.taps
+    }
+
+    /// Replaces the state. Panics if it does not fit in the degree
+    pub fn set_state(&mut self, state: u128) {
+        check_taps(self.degree, self.taps, state);
+        self.state = state;
+    }
+}
+
+impl Lfsr for FibonacciLfsr {
+    fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    fn state(&self) -> u128 {
+        self.state
+    }
+
+    fn step(&mut self) -> bool {
+        let out = self.state & 1 == 1;
+        let feedback = (self.state & self.taps).count_ones() as u128 & 1;
+        self.state = (self.state >> 1) | (feedback << (self.degree - 1));
+        out
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        if self.degree < 8 {
+            return step_byte(self);
+        }
+        let out = self.state as u8;
+        let new = self
+            .byte_masks
+            .iter()
+            .enumerate()
+            .fold(0u128, |acc, (j, m)| {
+                acc | (((self.state & m).count_ones() as u128 & 1) << j)
+            });
+        self.state = (self.state >> 8) | (new << (self.degree - 8));
+        out
+    }
+}
+
+/// A Galois (internal XOR) LFSR. The state is a polynomial of degree less than L, bit i the coefficient of
+/// x<sup>i</sup>, and each step multiplies it by x modulo p(x)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GaloisLfsr {
+    degree: u32,
+    taps: u128,
+    state: u128,
+    // For the top byte b of the state, the output byte and the state after eight steps from b alone
+    byte_table: Vec<(u8, u128)>,
+}
+
+impl GaloisLfsr {
+    /// Constructs the register for a polynomial of degree 1 to 127 with the given state. Panics if the state does
+    /// not fit in the degree
+    pub fn new(poly: u128, state: u128) -> Self {
+        let (degree, taps) = split_poly(poly);
+        Self::from_taps(degree, taps, state)
+    }
+
+    /// Constructs the register for x<sup>degree</sup> + taps, which allows degree 128. Panics unless
+    /// 1 ≤ degree ≤ 128 and the taps and state fit in degree bits
+    pub fn from_taps(degree: u32, taps: u128, state: u128) -> Self {
+        check_taps(degree, taps, state);
+        let mut lfsr = Self {
+            degree,
+            taps,
+            state,
+            byte_table: Vec::new(),
+        };
+        if degree >= 8 {
+            lfsr.byte_table = (0..=255u8)
+                .map(|b| {
+                    let mut top = Self {
+                        state: (b as u128) << (degree - 8),
+                        ..lfsr.clone()
+                    };
+                    let out = step_byte(&mut top);
+                    (out, top.state)
+                })
+                .collect();
+        }
+        lfsr
+    }
+
+    /// The taps c<sub>L-1</sub>, ..., c<sub>0</sub>
+    pub fn taps(&self) -> u128 {
+        self.taps
+    }
+
+    /// Replaces the state. Panics if it does not fit in the degree
+    pub fn set_state(&mut self, state: u128) {
+        check_taps(self.degree, self.taps, state);
+        self.state = state;
+    }
+}
+
+impl Lfsr for GaloisLfsr {
+    fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    fn state(&self) -> u128 {
+        self.state
+    }
+
+    fn step(&mut self) -> bool {
+        let out = (self.state >> (self.degree - 1)) & 1 == 1;
+        self.state = (self.state << 1) & state_mask(self.degree);
+        if out {
+            self.state ^= self.taps;
+        }
+        out
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        if self.degree < 8 {
+            return step_byte(self);
+        }
+        // The low bits only shift during eight steps, the top byte contributes through the table
+        let (out, top) = self.byte_table[(self.state >> (self.degree - 8)) as usize];
+        let low = match self.degree {
+            8 => 0,
+            l => self.state & state_mask(l - 8),
+        };
+        self.state = (low << 8) ^ top;
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polys::{self, mul_mod_gf2, pow_x_mod};
+    use rand::Rng;
+
+    fn bits(lfsr: &mut impl Lfsr, n: usize) -> Vec<bool> {
+        (0..n).map(|_| lfsr.step()).collect()
+    }
+
+    fn satisfies_recurrence(taps: u128, degree: usize, s: &[bool]) -> bool {
+        (0..s.len() - degree).all(|n| {
+            let sum = (0..degree)
+                .filter(|&i| (taps >> i) & 1 == 1)
+                .fold(false, |acc, i| acc ^ s[n + i]);
+            sum == s[n + degree]
+        })
+    }
+
+    #[test]
+    fn m_sequences() {
+        for poly in [polys::GF2_3, polys::GF2_5, polys::GF2_8, polys::GF2_10] {
+            let (degree, taps) = split_poly(poly);
+            let period = (1 << degree) - 1;
+            let mut fib = FibonacciLfsr::new(poly, 1);
+            let mut gal = GaloisLfsr::new(poly, 1);
+            for lfsr in [&mut fib as &mut dyn Lfsr, &mut gal] {
+                let s: Vec<bool> = (0..2 * period).map(|_| lfsr.step()).collect();
+                assert!(satisfies_recurrence(taps, degree as usize, &s));
+                assert_eq!(s[..period], s[period..]);
+                // An m-sequence has 2^(L-1) ones per period and no shorter period
+                assert_eq!(
+                    s[..period].iter().filter(|&&b| b).count(),
+                    1 << (degree - 1)
+                );
+                assert!((1..period).all(|p| period % p != 0 || s[..period - p] != s[p..period]));
+            }
+        }
+    }
+
+    #[test]
+    fn galois_multiplies_by_x() {
+        let poly = polys::GF2_31;
+        let seed = rand::thread_rng().gen_range(1..1 << 31);
+        let mut lfsr = GaloisLfsr::new(poly, seed);
+        for n in 1..100 {
+            lfsr.step();
+            assert_eq!(lfsr.state(), mul_mod_gf2(seed, pow_x_mod(n, poly), poly));
+        }
+    }
+
+    #[test]
+    fn byte_steps() {
+        let (degree, taps) = split_poly(polys::GF2_64);
+        for (degree, taps) in [(4, 0b0011), (8, 0x1D), (degree, taps), (128, 0x87)] {
+            let seed = rand::thread_rng().gen::<u128>() & state_mask(degree);
+            let mut fib = FibonacciLfsr::from_taps(degree, taps, seed);
+            let mut fib_bits = fib.clone();
+            let mut gal = GaloisLfsr::from_taps(degree, taps, seed);
+            let mut gal_bits = gal.clone();
+            for _ in 0..50 {
+                let expected = bits(&mut fib_bits, 8);
+                let b = fib.next_byte();
+                assert_eq!(
+                    (0..8).map(|i| (b >> i) & 1 == 1).collect::<Vec<_>>(),
+                    expected
+                );
+                assert_eq!(fib.state(), fib_bits.state());
+
+                let expected = bits(&mut gal_bits, 8);
+                let b = gal.next_byte();
+                assert_eq!(
+                    (0..8).map(|i| (b >> i) & 1 == 1).collect::<Vec<_>>(),
+                    expected
+                );
+                assert_eq!(gal.state(), gal_bits.state());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn state_too_wide() {
+        let _ = FibonacciLfsr::new(polys::GF2_4, 0x10);
+    }
+}
@@ -0,0 +1,222 @@
+use super::{FibonacciLfsr, Lfsr};
+use crate::gf2;
+use crate::polys::{self, pow_x_mod};
+use crate::rs::berlekamp_massey;
+use crate::GaloisField;
+
+type GF2 = gf2::GFu8<0x3>;
+
+/// The characteristic polynomial of an m-sequence of the primitive polynomial poly decimated by q, the sequence
+/// s<sub>0</sub>, s<sub>q</sub>, s<sub>2q</sub>, .... It is the minimal polynomial of α<sup>q</sup>. Panics unless
+/// poly is primitive with degree at most 127
+pub fn decimate(poly: u128, q: u128) -> u128 {
+    if !polys::is_primitive_gf2(poly) {
+        panic!("Polynomial must be primitive");
+    }
+    let n = crate::calc_degree(poly) as u128;
+
+    // s_t is the top coefficient of x^t mod poly, so the decimated sequence needs no stepping. When α^q lies in a
+    // subfield some phases decimate to the zero sequence, so the start is moved until it does not
+    let period = (1 << n) - 1;
+    let step = q % period;
+    (0..n)
+        .map(|start| {
+            let sequence: Vec<GF2> = (0..2 * n)
+                .scan(start, |exp, _| {
+                    let s = GF2::new((pow_x_mod(*exp, poly) >> (n - 1)) as u8);
+                    *exp = (*exp + step) % period;
+                    Some(s)
+                })
+                .collect();
+            berlekamp_massey(&sequence)
+        })
+        .find(|connection| connection.degree() > 0)
+        .unwrap()
+        .coeffs()
+        .iter()
+        .rev()
+        .enumerate()
+        .fold(0, |acc, (i, c)| acc | ((c.value as u128) << i))
+}
+
+/// The polynomial forming a preferred pair with the primitive polynomial poly of degree n, the decimation by
+/// 2<sup>k</sup> + 1 with k = 1 for odd n and k = 2 for n ≡ 2 mod 4. None when n is a multiple of 4, which has no
+/// preferred pairs. Panics unless poly is primitive
+pub fn preferred_pair(poly: u128) -> Option<u128> {
+    match crate::calc_degree(poly) % 4 {
+        0 => None,
+        2 => Some(decimate(poly, 5)),
+        _ => Some(decimate(poly, 3)),
+    }
+}
+
+/// A Gold code, the sum of two m-sequences of the same degree n. For a preferred pair the codes of the different
+/// phases have three valued cross-correlation. Both registers start with the all ones state as in GPS
+#[derive(Clone, Debug)]
+pub struct GoldCode {
+    first: FibonacciLfsr,
+    second: FibonacciLfsr,
+}
+
+impl GoldCode {
+    /// Constructs the code of poly_a and poly_b with the second sequence advanced by phase chips. Panics unless
+    /// the polynomials have the same degree
+    pub fn new(poly_a: u128, poly_b: u128, phase: usize) -> Self {
+        let degree = crate::calc_degree(poly_a);
+        if degree < 1 || degree != crate::calc_degree(poly_b) {
+            panic!("Polynomials must have the same degree");
+        }
+        let ones = u128::MAX >> (128 - degree);
+        let mut second = FibonacciLfsr::new(poly_b, ones);
+        for _ in 0..phase {
+            second.step();
+        }
+        Self {
+            first: FibonacciLfsr::new(poly_a, ones),
+            second,
+        }
+    }
+
+    /// The period 2<sup>n</sup> - 1 of the code for primitive polynomials
+    pub fn period(&self) -> u128 {
+        (1 << self.first.degree()) - 1
+    }
+
+    /// The next chip
+    pub fn step(&mut self) -> bool {
+        self.first.step() ^ self.second.step()
+    }
+
+    /// The next len chips
+    pub fn chips(&mut self, len: usize) -> Vec<bool> {
+        (0..len).map(|_| self.step()).collect()
+    }
+}
+
+/// A sequence of the small Kasami set of a primitive polynomial of even degree n. The set has 2<sup>n/2</sup>
+/// sequences: the m-sequence u itself and u plus each phase of u decimated by 2<sup>n/2</sup> + 1
+#[derive(Clone, Debug)]
+pub struct KasamiCode {
+    long: FibonacciLfsr,
+    short: Option<FibonacciLfsr>,
+}
+
+impl KasamiCode {
+    /// Constructs sequence index of the set, 0 for u alone. Panics unless poly is primitive of even degree and
+    /// index is less than the set size
+    pub fn new(poly: u128, index: usize) -> Self {
+        let n = crate::calc_degree(poly) as u32;
+        if !n.is_multiple_of(2) {
+            panic!("Kasami sequences need an even degree");
+        }
+        if index >= Self::set_size(n) {
+            panic!("Index must be less than the set size");
+        }
+        let long = FibonacciLfsr::new(poly, u128::MAX >> (128 - n));
+        let short = (index > 0).then(|| {
+            let mut short = FibonacciLfsr::new(decimate(poly, (1 << (n / 2)) + 1), 1);
+            for _ in 1..index {
+                short.step();
+            }
+            short
+        });
+        Self { long, short }
+    }
+
+    /// The number of sequences 2<sup>n/2</sup> in the set of degree n
+    pub fn set_size(n: u32) -> usize {
+        1 << (n / 2)
+    }
+
+    /// The period 2<sup>n</sup> - 1
+    pub fn period(&self) -> u128 {
+        (1 << self.long.degree()) - 1
+    }
+
+    /// The next chip
+    pub fn step(&mut self) -> bool {
+        let short = self.short.as_mut().is_some_and(|s| s.step());
+        self.long.step() ^ short
+    }
+
+    /// The next len chips
+    pub fn chips(&mut self, len: usize) -> Vec<bool> {
+        (0..len).map(|_| self.step()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Periodic cross-correlation at every shift, with chips mapped to ±1
+    fn cross_correlation(a: &[bool], b: &[bool]) -> Vec<i64> {
+        let n = a.len();
+        (0..n)
+            .map(|shift| {
+                (0..n)
+                    .map(|t| if a[t] ^ b[(t + shift) % n] { -1 } else { 1 })
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gps_ca_code() {
+        // G1 = 1 + x^3 + x^10 and G2 = 1 + x^2 + x^3 + x^6 + x^8 + x^9 + x^10 in the register notation of
+        // IS-GPS-200 are the reciprocals of these characteristic polynomials. PRN 1 delays G2 by 5 chips
+        let mut prn1 = GoldCode::new(0x481, 0x597, 1023 - 5);
+        let first: Vec<bool> = prn1.chips(10);
+        // The first ten chips are 1440 in octal
+        let expected: Vec<bool> = (0..10).rev().map(|i| (0o1440 >> i) & 1 == 1).collect();
+        assert_eq!(first, expected);
+    }
+
+    #[test]
+    fn gold_correlation() {
+        for poly in [polys::GF2_5, polys::GF2_6, polys::GF2_7] {
+            let n = crate::calc_degree(poly) as u32;
+            let other = preferred_pair(poly).unwrap();
+            assert!(polys::is_primitive_gf2(other));
+            assert_eq!(crate::calc_degree(other), n as i16);
+
+            let t = 1 + (1 << ((n + 2) / 2));
+            let period = (1 << n) - 1;
+            let codes: Vec<Vec<bool>> = [0, 1, 7]
+                .iter()
+                .map(|&phase| GoldCode::new(poly, other, phase).chips(period))
+                .collect();
+            for a in codes.iter() {
+                for b in codes.iter().filter(|&b| b != a) {
+                    for c in cross_correlation(a, b) {
+                        assert!([-1, -t, t - 2].contains(&c), "{c}");
+                    }
+                }
+            }
+        }
+        assert_eq!(preferred_pair(polys::GF2_8), None);
+    }
+
+    #[test]
+    fn kasami_correlation() {
+        for poly in [polys::GF2_4, polys::GF2_6, polys::GF2_8] {
+            let n = crate::calc_degree(poly) as u32;
+            let period = (1 << n) - 1;
+            let s = 1 << (n / 2);
+            let codes: Vec<Vec<bool>> = (0..KasamiCode::set_size(n))
+                .map(|index| KasamiCode::new(poly, index).chips(period))
+                .collect();
+            for (i, a) in codes.iter().enumerate() {
+                for (j, b) in codes.iter().enumerate() {
+                    for (shift, c) in cross_correlation(a, b).into_iter().enumerate() {
+                        if i == j && shift == 0 {
+                            assert_eq!(c, period as i64);
+                        } else {
+                            assert!([-1, -1 - s, s - 1].contains(&c), "{c}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
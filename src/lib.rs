@@ -14,8 +14,12 @@
 //!
 //!   - If p(x) is primitive and M ≤ 16 then the look up table implementation can be used (module gf2_lut)
 //!
+//!   - If M ≤ 16 and the look up tables must stay small (e.g. cache constrained targets) then the nibble table implementation can be used (module gf2_nibble)
+//!
 //!   - Else use the computation based implementation (module gf2)
 //!
+//!   - If the elements arrive with the coefficient of x<sup>0</sup> in the most significant bit, as in GCM or reflected CRCs, the reflected implementation computes in that order directly (module gf2_rev)
+//!
 //! Lastly we must use one of the structs to represent the elements in the field. The struct GFuX can be used for M ≤ X.
 //!
 //! At this point we can define the GF type and use it.
@@ -44,10 +48,38 @@ use paste::paste;
 use rand::Rng;
 
 use core::fmt::{Debug, Display};
-use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
-
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+pub mod aes;
+pub mod basis;
+pub mod bch;
+pub mod bitmatrix;
+pub mod bulk;
+pub mod crc;
+pub mod cyclic;
+pub mod erasure;
+pub mod fountain;
 pub mod gf2;
 pub mod gf2_lut;
+pub mod gf2_nibble;
+pub mod gf2_rev;
+pub mod ghash;
+pub mod goppa;
+pub mod hamming;
+#[cfg(feature = "digest")]
+pub mod hash_to_field;
+pub mod lfsr;
+pub mod linalg;
+pub mod mapping;
+pub mod poly;
+pub mod polymac;
+pub mod polys;
+pub mod rabin;
+pub mod reed_muller;
+pub mod rs;
+pub mod scrambler;
+pub mod simd;
+pub mod wire;
 
 /// A trait used to indicate that a type can be used to represent the elements of a Galois Field.
 pub trait GaloisField:
@@ -57,24 +89,38 @@ pub trait GaloisField:
     + PartialEq
     + Debug
     + Display
-    + Add
+    + Add<Output = Self>
     + AddAssign
-    + Sub
+    + Sub<Output = Self>
     + SubAssign
-    + Mul
+    + Neg<Output = Self>
+    + Mul<Output = Self>
     + MulAssign
-    + Div
+    + Div<Output = Self>
     + DivAssign
 {
     /// The underlying type used to store the representation of an element in the field
     type StorageType;
 
+    /// The polynomial used to define the field, represented as a u128
+    const POLY: u128;
+
     /// The degree of the polynomial used to define the field. Specifies GF(2<sup>M</sup>)
     const M: u128;
 
     /// The number of elements in the field. This value is 2<sup>M</sup>
     const NUM_ELEM: u128;
 
+    /// A bitmask of the M bits that a valid element may have set
+    const MASK: Self::StorageType;
+
+    /// The number of bits in the underlying storage type
+    const STORAGE_BITS: u32;
+
+    /// Whether the stored value is the element in the polynomial basis, bit i the coefficient of x<sup>i</sup>.
+    /// Kernels working on raw storage values, such as the GFNI products of `simd::mul_slices`, need it
+    const NATURAL_REPR: bool = true;
+
     /// The additive identity of the field
     const ZERO: Self;
 
@@ -89,6 +135,180 @@ pub trait GaloisField:
 
     /// Used to check if the value stored is a valid element in the current field
     fn validate(&self) -> bool;
+
+    /// Returns the stored value widened to a u128
+    fn to_u128(&self) -> u128;
+
+    /// Constructs a GF element from a u128. The value is truncated to the storage type
+    fn from_u128(value: u128) -> Self;
+
+    /// Returns the M GF(2) coefficients of the element. Index i holds the coefficient of x<sup>i</sup>
+    fn to_coefficients(&self) -> Vec<bool> {
+        let value = self.to_u128();
+        (0..Self::M).map(|i| ((value >> i) & 0x1) > 0).collect()
+    }
+
+    /// Constructs an element from its GF(2) coefficients. Index i holds the coefficient of x<sup>i</sup>.
+    /// Panics if more than M coefficients are given
+    fn from_coefficients(coeffs: &[bool]) -> Self {
+        if coeffs.len() as u128 > Self::M {
+            panic!("Too many coefficients for the field");
+        }
+        let value = coeffs
+            .iter()
+            .enumerate()
+            .fold(0u128, |acc, (i, &c)| acc | ((c as u128) << i));
+        Self::from_u128(value)
+    }
+
+    /// Constructs an element from the first ⌈M/8⌉ bytes, read big endian and masked to M bits, so uniformly random
+    /// bytes give a uniformly random element. Returns None if fewer bytes are given
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        let len = Self::M.div_ceil(8) as usize;
+        let value = bytes
+            .get(..len)?
+            .iter()
+            .fold(0u128, |acc, &b| (acc << 8) | b as u128);
+        let element = Self::from_u128(value & (Self::NUM_ELEM - 1));
+        element.validate().then_some(element)
+    }
+
+    /// Returns the coordinates of self with respect to basis, so self = Σ coords\[i\] * basis\[i\].
+    /// Panics unless basis has M linearly independent elements. Use `basis::Basis` to convert many elements
+    fn coordinates(&self, basis: &[Self]) -> Vec<bool> {
+        basis::Basis::new(basis)
+            .expect("Elements do not form a basis")
+            .coordinates(*self)
+    }
+
+    /// Reconstructs Σ coords\[i\] * basis\[i\]. Panics if the lengths differ
+    fn from_coordinates(coords: &[bool], basis: &[Self]) -> Self {
+        if coords.len() != basis.len() {
+            panic!("Number of coordinates does not match the basis");
+        }
+        basis
+            .iter()
+            .zip(coords.iter())
+            .filter(|(_, &c)| c)
+            .fold(Self::ZERO, |acc, (&b, _)| acc + b)
+    }
+
+    /// Returns the coefficient of x<sup>i</sup>. Panics if i ≥ M
+    fn get_coeff(&self, i: usize) -> bool {
+        if i as u128 >= Self::M {
+            panic!("Coefficient index out of range");
+        }
+        ((self.to_u128() >> i) & 0x1) > 0
+    }
+
+    /// Sets the coefficient of x<sup>i</sup>. Panics if i ≥ M
+    fn set_coeff(&mut self, i: usize, coeff: bool) {
+        if i as u128 >= Self::M {
+            panic!("Coefficient index out of range");
+        }
+        let value = (self.to_u128() & !(1u128 << i)) | ((coeff as u128) << i);
+        *self = Self::from_u128(value);
+    }
+
+    /// Returns the M × M matrix over GF(2) that maps the coefficients of x to the coefficients of self * x
+    fn mul_matrix(&self) -> bitmatrix::BitMatrix {
+        let m = Self::M as usize;
+        let mut matrix = bitmatrix::BitMatrix::new(m, m);
+        let mut column = *self;
+        for c in 0..m {
+            for (r, &bit) in column.to_coefficients().iter().enumerate() {
+                matrix.set(r, c, bit);
+            }
+            column = column.mul_x();
+        }
+        matrix
+    }
+
+    /// Returns the characteristic polynomial over GF(2) of the multiplication by self map.
+    /// This is the product of (x - self<sup>2<sup>i</sup></sup>) for 0 ≤ i < M
+    fn char_poly(&self) -> u128 {
+        let mut conjugate = *self;
+        let mut poly = poly::Polynomial::one();
+        for _ in 0..Self::M {
+            poly *= poly::Polynomial::new(vec![-conjugate, Self::ONE]);
+            conjugate = conjugate.square();
+        }
+        poly.coeffs()
+            .iter()
+            .enumerate()
+            .fold(0u128, |acc, (i, &c)| {
+                if c == Self::ONE {
+                    acc | (1u128 << i)
+                } else {
+                    acc
+                }
+            })
+    }
+
+    /// Returns the minimal polynomial over GF(2) of self.
+    /// This is the product of (x - c) over the distinct conjugates c = self<sup>2<sup>i</sup></sup>
+    fn minimal_poly(&self) -> u128 {
+        let mut poly = poly::Polynomial::new(vec![-*self, Self::ONE]);
+        let mut conjugate = self.square();
+        while conjugate != *self {
+            poly *= poly::Polynomial::new(vec![-conjugate, Self::ONE]);
+            conjugate = conjugate.square();
+        }
+        poly.coeffs()
+            .iter()
+            .enumerate()
+            .fold(0u128, |acc, (i, &c)| {
+                if c == Self::ONE {
+                    acc | (1u128 << i)
+                } else {
+                    acc
+                }
+            })
+    }
+
+    /// Returns the absolute trace self + self<sup>2</sup> + ... + self<sup>2<sup>M-1</sup></sup>, which is always 0 or 1
+    fn trace(&self) -> bool {
+        let mut sum = *self;
+        let mut conjugate = *self;
+        for _ in 1..Self::M {
+            conjugate = conjugate.square();
+            sum += conjugate;
+        }
+        sum == Self::ONE
+    }
+
+    /// Computes self * x with a single shift and conditional reduction
+    fn mul_x(&self) -> Self {
+        let mut value = self.to_u128() << 1;
+        if ((value >> Self::M) & 0x1) > 0 {
+            value ^= Self::POLY;
+        }
+        Self::from_u128(value)
+    }
+
+    /// Computes self / x with a single conditional reduction and shift
+    fn div_x(&self) -> Self {
+        let mut value = self.to_u128();
+        if (value & 0x1) > 0 {
+            value ^= Self::POLY;
+        }
+        Self::from_u128(value >> 1)
+    }
+
+    /// Computes self * self. Cheaper than a general multiplication
+    fn square(&self) -> Self;
+
+    /// Computes self<sup>exp</sup> using square and multiply. 0<sup>0</sup> is defined as ONE
+    fn pow(&self, exp: u128) -> Self {
+        let mut result = Self::ONE;
+        for i in (0..(128 - exp.leading_zeros())).rev() {
+            result = result.square();
+            if ((exp >> i) & 0x1) > 0 {
+                result *= *self;
+            }
+        }
+        result
+    }
 }
 
 #[allow(dead_code)]
@@ -108,6 +328,58 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn constant_time() {
+        use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+        let (a, b) = (
+            gf2::GFu32::<0x2_0009>::new(5),
+            gf2::GFu32::<0x2_0009>::new(7),
+        );
+        assert!(bool::from(a.ct_eq(&a)));
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_eq!(
+            ConditionallySelectable::conditional_select(&a, &b, Choice::from(0)),
+            a
+        );
+        assert_eq!(
+            ConditionallySelectable::conditional_select(&a, &b, Choice::from(1)),
+            b
+        );
+
+        let (c, d) = (
+            gf2_lut::GFu8::<0x11d>::new(3),
+            gf2_nibble::GFu16::<0x1053>::new(9),
+        );
+        assert!(bool::from(c.ct_eq(&gf2_lut::GFu8::new(3))));
+        let mut e = d;
+        e.conditional_assign(&gf2_nibble::GFu16::ZERO, Choice::from(1));
+        assert!(bool::from(e.ct_eq(&gf2_nibble::GFu16::ZERO)));
+        assert!(!bool::from(d.ct_eq(&e)));
+    }
+
+    #[test]
+    fn from_random_bytes() {
+        type GF6 = gf2::GFu8<0x43>;
+        assert_eq!(GF6::from_random_bytes(&[0xFF]), Some(GF6::new(0x3F)));
+        assert_eq!(GF6::from_random_bytes(&[]), None);
+
+        type GF17 = gf2::GFu32<0x2_0009>;
+        assert_eq!(GF17::from_random_bytes(&[0x12, 0x34]), None);
+        assert_eq!(
+            GF17::from_random_bytes(&[0xFF, 0x12, 0x34, 0x56]),
+            Some(GF17::new(0x1_1234))
+        );
+
+        let bytes: Vec<u8> = (0..16).map(|_| rand::thread_rng().gen()).collect();
+        let a = gf2::GFu128::<0x8000_0000_0000_0000_0000_0000_0000_0003>::from_random_bytes(&bytes);
+        assert_eq!(
+            a.unwrap().value,
+            u128::from_be_bytes(bytes.try_into().unwrap()) & (u128::MAX >> 1)
+        );
+    }
+
     macro_rules! associative_test {
         ($type:ty, $op:tt) => {
             for i in 0..GF::NUM_ELEM {
@@ -153,6 +425,53 @@ mod tests {
         };
     }
 
+    macro_rules! mul_x_div_x_test {
+        ($type:ty) => {
+            let x = GF::ONE.mul_x();
+            if GF::M > 1 {
+                assert_eq!(x, GF::new(2));
+            }
+            for i in 0..GF::NUM_ELEM {
+                let a = GF::new(i as $type);
+                assert_eq!(a.mul_x(), a * x);
+                assert_eq!(a.div_x(), a / x);
+                assert_eq!(a.mul_x().div_x(), a);
+            }
+        };
+    }
+
+    macro_rules! trace_test {
+        ($type:ty) => {
+            let mut ones = 0;
+            for i in 0..GF::NUM_ELEM {
+                let a = GF::new(i as $type);
+                assert_eq!(a.square().trace(), a.trace());
+                assert_eq!(
+                    (a + GF::ONE.mul_x()).trace(),
+                    a.trace() ^ GF::ONE.mul_x().trace()
+                );
+                ones += a.trace() as u128;
+            }
+            // The trace is a non zero linear map to GF(2)
+            assert_eq!(ones, GF::NUM_ELEM / 2);
+        };
+    }
+
+    macro_rules! square_pow_test {
+        ($type:ty) => {
+            for i in 0..GF::NUM_ELEM {
+                let a = GF::new(i as $type);
+                assert_eq!(a.square(), a * a);
+
+                let mut expected = GF::ONE;
+                for exp in 0..10 {
+                    assert_eq!(a.pow(exp), expected);
+                    expected *= a;
+                }
+            }
+        };
+    }
+
     macro_rules! inverse_multiplication_test {
         ($type:ty) => {
             for i in 1..GF::NUM_ELEM {
@@ -178,6 +497,9 @@ mod tests {
             commutative_test!($type, *);
             identity_test!($type, *, ONE);
             inverse_multiplication_test!($type);
+            square_pow_test!($type);
+            mul_x_div_x_test!($type);
+            trace_test!($type);
         }
     }
 
@@ -208,6 +530,106 @@ mod tests {
                     distributive_test!($type);
                 }
 
+                #[test]
+                fn [<$mod _neg_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    for i in 0..GF::NUM_ELEM {
+                        let a = GF::new(i as $type);
+                        assert_eq!(-a, a);
+                        assert_eq!(a + (-a), GF::ZERO);
+                    }
+                }
+
+                #[test]
+                fn [<$mod _u128_conversion_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    for i in 0..GF::NUM_ELEM {
+                        assert_eq!(GF::from_u128(i), GF::new(i as $type));
+                        assert_eq!(GF::from_u128(i).to_u128(), i);
+                    }
+                }
+
+                #[test]
+                fn [<$mod _ref_ops_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    for i in 0..GF::NUM_ELEM {
+                        for j in 1..GF::NUM_ELEM {
+                            let a = GF::new(i as $type);
+                            let b = GF::new(j as $type);
+                            assert_eq!(&a + &b, a + b);
+                            assert_eq!(&a - b, a - b);
+                            assert_eq!(a * &b, a * b);
+                            assert_eq!(&a / &b, a / b);
+                        }
+                    }
+                    let elems: Vec<GF> = (0..GF::NUM_ELEM).map(|i| GF::new(i as $type)).collect();
+                    let sum = elems.iter().fold(GF::ZERO, |acc, x| acc + x);
+                    assert_eq!(sum, if GF::M == 1 { GF::ONE } else { GF::ZERO });
+                }
+
+                #[test]
+                fn [<$mod _coefficients_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    for i in 0..GF::NUM_ELEM {
+                        let a = GF::new(i as $type);
+                        let coeffs = a.to_coefficients();
+                        assert_eq!(coeffs.len() as u128, GF::M);
+                        for (j, &c) in coeffs.iter().enumerate() {
+                            assert_eq!(c, ((i >> j) & 0x1) > 0);
+                        }
+                        assert_eq!(GF::from_coefficients(&coeffs), a);
+                    }
+                    assert_eq!(GF::from_coefficients(&[true]), GF::ONE);
+                    assert_eq!(GF::from_coefficients(&[]), GF::ZERO);
+                }
+
+                #[test]
+                fn [<$mod _get_set_coeff_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    for i in 0..GF::NUM_ELEM {
+                        let a = GF::new(i as $type);
+                        let mut b = GF::ZERO;
+                        for j in 0..(GF::M as usize) {
+                            assert_eq!(a.get_coeff(j), ((i >> j) & 0x1) > 0);
+                            b.set_coeff(j, a.get_coeff(j));
+                        }
+                        assert_eq!(a, b);
+                        for j in 0..(GF::M as usize) {
+                            b.set_coeff(j, false);
+                        }
+                        assert_eq!(b, GF::ZERO);
+                    }
+                }
+
+                #[test]
+                #[should_panic]
+                fn [<$mod _get_coeff_out_of_range_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    let _ = GF::ONE.get_coeff(GF::M as usize);
+                }
+
+                #[test]
+                #[should_panic]
+                fn [<$mod _too_many_coefficients_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    let _ = GF::from_coefficients(&vec![false; GF::M as usize + 1]);
+                }
+
+                #[test]
+                fn [<$mod _constants_ $poly>]() {
+                    type GF = $mod::[<GF $type>]<$poly>;
+                    assert_eq!(GF::POLY, $poly);
+                    assert_eq!(GF::MASK as u128, GF::NUM_ELEM - 1);
+                    assert_eq!(GF::STORAGE_BITS, $type::BITS);
+
+                    for i in 0..GF::NUM_ELEM {
+                        assert!(GF::new(i as $type).validate());
+                    }
+                    if GF::M < GF::STORAGE_BITS as u128 {
+                        assert!(!GF::new(GF::NUM_ELEM as $type).validate());
+                    }
+                }
+
                 #[test]
                 #[should_panic]
                 fn [<$mod _div_by_0_ $poly>]() {
@@ -244,6 +666,15 @@ mod tests {
         gf2: u8: 0x43,
         gf2: u8: 0x83,
         gf2: u8: 0x11d,
+
+        gf2_nibble: u16: 0x3,
+        gf2_nibble: u16: 0x7,
+        gf2_nibble: u16: 0xb,
+        gf2_nibble: u16: 0x13,
+        gf2_nibble: u8: 0x25,
+        gf2_nibble: u8: 0x43,
+        gf2_nibble: u8: 0x83,
+        gf2_nibble: u8: 0x11d,
     }
 
     macro_rules! associative_spot_test {
@@ -293,6 +724,18 @@ mod tests {
         };
     }
 
+    macro_rules! square_pow_spot_test {
+        ($type:ty, $a:tt) => {
+            for i in 0..NUM_VALS {
+                assert_eq!($a[i].mul_x(), $a[i] * GF::new(2));
+                assert_eq!($a[i].div_x(), $a[i] / GF::new(2));
+                assert_eq!($a[i].square(), $a[i] * $a[i]);
+                assert_eq!($a[i].pow(5), $a[i] * $a[i] * $a[i] * $a[i] * $a[i]);
+                assert_eq!($a[i].pow((GF::NUM_ELEM - 1) as u128), GF::ONE);
+            }
+        };
+    }
+
     macro_rules! addition_spot_test {
         ($type:ty, $a:tt, $b:tt, $c:tt) => {
             associative_spot_test!($type, $a, $b, $c, +);
@@ -308,6 +751,7 @@ mod tests {
             commutative_spot_test!($type, $a, $b, *);
             identity_spot_test!($type, $a, *, ONE);
             inverse_multiplication_spot_test!($type, $a);
+            square_pow_spot_test!($type, $a);
         }
     }
 
@@ -356,6 +800,11 @@ mod tests {
         gf2_lut: u16: 0x805,
         gf2_lut: u16: 0x1053,
 
+        gf2_nibble: u8: 0x11b,
+        gf2_nibble: u16: 0x211,
+        gf2_nibble: u16: 0x1053,
+        gf2_nibble: u16: 0x1002d,
+
         gf2: u8: 0x11b,
         gf2: u8: 0x1dd,
         gf2: u16: 0x211,
@@ -0,0 +1,381 @@
+//! Dense linear algebra over a Galois Field.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2_lut};
+//! use galois_field_2pm::linalg::Matrix;
+//!
+//! type GF = gf2_lut::GFu8<0x11d>;
+//! let a = Matrix::from_rows(&[vec![GF::new(1), GF::new(2)], vec![GF::new(3), GF::new(4)]]);
+//! let i = Matrix::identity(2);
+//!
+//! assert_eq!(&a * &i, a);
+//! assert_eq!(&a + &a, Matrix::new(2, 2));
+//! ```
+
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+
+use crate::GaloisField;
+
+mod code;
+mod elimination;
+mod moore;
+mod plu;
+mod solve;
+mod vandermonde;
+mod vector;
+
+pub use moore::{gf2_rank, rank_distance};
+pub use plu::Plu;
+pub use vector::{axpy, dot, hamming_distance, hamming_weight, scale, support};
+
+/// A dense matrix with entries in GF, stored row major
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Matrix<GF: GaloisField> {
+    rows: usize,
+    cols: usize,
+    data: Vec<GF>,
+}
+
+impl<GF: GaloisField> Matrix<GF> {
+    /// Constructs an all zero matrix
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![GF::ZERO; rows * cols],
+        }
+    }
+
+    /// Constructs the n × n identity matrix
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::new(n, n);
+        for i in 0..n {
+            m.set(i, i, GF::ONE);
+        }
+        m
+    }
+
+    /// Constructs a matrix from its rows. Panics if the rows differ in length
+    pub fn from_rows(rows: &[Vec<GF>]) -> Self {
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            panic!("Rows must have the same length");
+        }
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.concat(),
+        }
+    }
+
+    /// The number of rows
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the entry at (row, col). Panics if out of range
+    pub fn get(&self, row: usize, col: usize) -> GF {
+        self.data[self.index(row, col)]
+    }
+
+    /// Sets the entry at (row, col). Panics if out of range
+    pub fn set(&mut self, row: usize, col: usize, value: GF) {
+        let idx = self.index(row, col);
+        self.data[idx] = value;
+    }
+
+    /// Returns a row
+    pub fn row(&self, row: usize) -> &[GF] {
+        if row >= self.rows {
+            panic!("Matrix index out of range");
+        }
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Returns a column
+    pub fn col(&self, col: usize) -> Vec<GF> {
+        (0..self.rows).map(|r| self.get(r, col)).collect()
+    }
+
+    /// Returns the transpose
+    pub fn transpose(&self) -> Self {
+        let mut t = Self::new(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                t.set(c, r, self.get(r, c));
+            }
+        }
+        t
+    }
+
+    /// Computes self * v. Panics if v does not have num_cols entries
+    pub fn mul_vec(&self, v: &[GF]) -> Vec<GF> {
+        if v.len() != self.cols {
+            panic!("Vector length does not match the number of columns");
+        }
+        (0..self.rows)
+            .map(|r| {
+                self.row(r)
+                    .iter()
+                    .zip(v.iter())
+                    .fold(GF::ZERO, |acc, (&a, &b)| acc + a * b)
+            })
+            .collect()
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        if row >= self.rows || col >= self.cols {
+            panic!("Matrix index out of range");
+        }
+        row * self.cols + col
+    }
+
+    // Entrywise combination of two matrices of the same shape
+    fn zip_with(&self, other: &Self, f: impl Fn(GF, GF) -> GF) -> Self {
+        if self.rows != other.rows || self.cols != other.cols {
+            panic!("Matrix dimensions do not match");
+        }
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        }
+    }
+}
+
+impl<GF: GaloisField> Add<&Matrix<GF>> for &Matrix<GF> {
+    type Output = Matrix<GF>;
+
+    fn add(self, other: &Matrix<GF>) -> Matrix<GF> {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<GF: GaloisField> Sub<&Matrix<GF>> for &Matrix<GF> {
+    type Output = Matrix<GF>;
+
+    fn sub(self, other: &Matrix<GF>) -> Matrix<GF> {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<GF: GaloisField> Mul<&Matrix<GF>> for &Matrix<GF> {
+    type Output = Matrix<GF>;
+
+    fn mul(self, other: &Matrix<GF>) -> Matrix<GF> {
+        if self.cols != other.rows {
+            panic!("Matrix dimensions do not match");
+        }
+        let mut output = Matrix::new(self.rows, other.cols);
+        for r in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self.get(r, k);
+                if a == GF::ZERO {
+                    continue;
+                }
+                for c in 0..other.cols {
+                    let idx = output.index(r, c);
+                    output.data[idx] += a * other.get(k, c);
+                }
+            }
+        }
+        output
+    }
+}
+
+impl<GF: GaloisField> Mul<GF> for &Matrix<GF> {
+    type Output = Matrix<GF>;
+
+    fn mul(self, scalar: GF) -> Matrix<GF> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|&a| a * scalar).collect(),
+        }
+    }
+}
+
+macro_rules! owned_operator_impl {
+    ($($trait_name:ident: $trait_fn:ident: $op:tt,)*) => {
+    $(
+        impl<GF: GaloisField> $trait_name<Matrix<GF>> for Matrix<GF> {
+            type Output = Matrix<GF>;
+
+            fn $trait_fn(self, other: Matrix<GF>) -> Matrix<GF> {
+                &self $op &other
+            }
+        }
+    )*
+    }
+}
+
+owned_operator_impl! {
+    Add: add: +,
+    Sub: sub: -,
+    Mul: mul: *,
+}
+
+impl<GF: GaloisField> fmt::Display for Matrix<GF> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in 0..self.rows {
+            let line: Vec<String> = self.row(r).iter().map(|a| format!("{}", a)).collect();
+            writeln!(f, "{}", line.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+// Serialized as nested arrays of rows, together with POLY which is checked on deserialize
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MatrixRepr {
+    poly: u128,
+    rows: Vec<Vec<u128>>,
+}
+
+#[cfg(feature = "serde")]
+impl<GF: GaloisField> serde::Serialize for Matrix<GF> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MatrixRepr {
+            poly: GF::POLY,
+            rows: (0..self.rows)
+                .map(|r| self.row(r).iter().map(|a| a.to_u128()).collect())
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, GF: GaloisField> serde::Deserialize<'de> for Matrix<GF> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let repr = MatrixRepr::deserialize(deserializer)?;
+        if repr.poly != GF::POLY {
+            return Err(D::Error::custom(format!(
+                "matrix is over POLY {:#X}, expected {:#X}",
+                repr.poly,
+                GF::POLY
+            )));
+        }
+
+        let cols = repr.rows.first().map_or(0, |r| r.len());
+        if repr.rows.iter().any(|r| r.len() != cols) {
+            return Err(D::Error::custom("rows must have the same length"));
+        }
+
+        let mut data = Vec::with_capacity(repr.rows.len() * cols);
+        for &value in repr.rows.iter().flatten() {
+            let a = GF::from_u128(value);
+            if a.to_u128() != value || !a.validate() {
+                return Err(D::Error::custom(format!(
+                    "entry {:#X} is not a field element",
+                    value
+                )));
+            }
+            data.push(a);
+        }
+        Ok(Self {
+            rows: repr.rows.len(),
+            cols,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_matrix(rows: usize, cols: usize) -> Matrix<GF> {
+        let rows: Vec<Vec<GF>> = (0..rows)
+            .map(|_| {
+                (0..cols)
+                    .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+                    .collect()
+            })
+            .collect();
+        Matrix::from_rows(&rows)
+    }
+
+    #[test]
+    fn basics() {
+        let a = Matrix::from_rows(&[
+            vec![GF::new(1), GF::new(2), GF::new(3)],
+            vec![GF::new(4), GF::new(5), GF::new(6)],
+        ]);
+        assert_eq!(a.num_rows(), 2);
+        assert_eq!(a.num_cols(), 3);
+        assert_eq!(a.get(1, 2), GF::new(6));
+        assert_eq!(a.row(1), &[GF::new(4), GF::new(5), GF::new(6)]);
+        assert_eq!(a.col(1), vec![GF::new(2), GF::new(5)]);
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(a.transpose().get(2, 1), GF::new(6));
+        assert_eq!(format!("{}", a), "0x01 0x02 0x03\n0x04 0x05 0x06\n");
+    }
+
+    #[test]
+    fn ring_operations() {
+        let a = random_matrix(3, 4);
+        let b = random_matrix(4, 5);
+        let c = random_matrix(5, 2);
+
+        assert_eq!(&a * &Matrix::identity(4), a);
+        assert_eq!(&Matrix::identity(3) * &a, a);
+        assert_eq!(&(&a * &b) * &c, &a * &(&b * &c));
+        assert_eq!((&a * &b).transpose(), &b.transpose() * &a.transpose());
+        assert_eq!(&a + &a, Matrix::new(3, 4));
+        assert_eq!(&(&a + &a) - &a, a);
+
+        let d = random_matrix(4, 5);
+        assert_eq!(&a * &(&b + &d), &(&a * &b) + &(&a * &d));
+        assert_eq!(a.clone() * b.clone(), &a * &b);
+
+        let s = GF::new(0x35);
+        assert_eq!(&(&a * s) * &b, &(&a * &b) * s);
+
+        let v: Vec<GF> = (0..4).map(|i| GF::new(i + 7)).collect();
+        let column = Matrix::from_rows(&v.iter().map(|&x| vec![x]).collect::<Vec<_>>());
+        assert_eq!(a.mul_vec(&v), (&a * &column).col(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_mul() {
+        let _ = &random_matrix(2, 3) * &random_matrix(2, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged_rows() {
+        let _ = Matrix::from_rows(&[vec![GF::ONE], vec![GF::ONE, GF::ONE]]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = Matrix::from_rows(&[vec![GF::new(1), GF::new(2)], vec![GF::new(3), GF::new(255)]]);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, r#"{"poly":285,"rows":[[1,2],[3,255]]}"#);
+        assert_eq!(serde_json::from_str::<Matrix<GF>>(&json).unwrap(), a);
+        assert!(serde_json::from_str::<Matrix<GF>>(r#"{"poly":283,"rows":[[1]]}"#).is_err());
+        assert!(serde_json::from_str::<Matrix<GF>>(r#"{"poly":285,"rows":[[1],[2,3]]}"#).is_err());
+        assert!(serde_json::from_str::<Matrix<GF>>(r#"{"poly":285,"rows":[[256]]}"#).is_err());
+    }
+}
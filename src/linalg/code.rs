@@ -0,0 +1,142 @@
+use super::Matrix;
+use crate::GaloisField;
+
+impl<GF: GaloisField> Matrix<GF> {
+    /// Brings a generator matrix into standard form [I<sub>k</sub> | P], where k is its rank.
+    /// Returns the standard form and the column permutation used: column j of the standard form comes from
+    /// column perm\[j\] of the row reduced matrix. The permutation is the identity when the pivots come first.
+    /// Zero rows of the reduced matrix are dropped
+    pub fn standard_form(&self) -> (Self, Vec<usize>) {
+        let mut reduced = self.clone();
+        let pivots = reduced.row_reduce();
+
+        // Pivot columns first, then the rest in order
+        let mut perm = pivots.clone();
+        perm.extend((0..self.cols).filter(|c| !pivots.contains(c)));
+
+        let mut standard = Self::new(pivots.len(), self.cols);
+        for r in 0..pivots.len() {
+            for (c, &p) in perm.iter().enumerate() {
+                standard.set(r, c, reduced.get(r, p));
+            }
+        }
+        (standard, perm)
+    }
+
+    /// Derives a parity-check matrix H from a generator matrix G, so G * H<sup>T</sup> = 0 and H has
+    /// n - rank(G) independent rows. For G = [I | P] this is H = [-P<sup>T</sup> | I]
+    pub fn parity_check_from_generator(&self) -> Self {
+        self.kernel_matrix()
+    }
+
+    /// Derives a generator matrix G from a parity-check matrix H, so G * H<sup>T</sup> = 0 and G has
+    /// n - rank(H) independent rows
+    pub fn generator_from_parity_check(&self) -> Self {
+        self.kernel_matrix()
+    }
+
+    // The null space basis as the rows of a matrix with the same number of columns
+    fn kernel_matrix(&self) -> Self {
+        let kernel = self.null_space();
+        let mut m = Self::new(kernel.len(), self.cols);
+        for (r, v) in kernel.iter().enumerate() {
+            for (c, &a) in v.iter().enumerate() {
+                m.set(r, c, a);
+            }
+        }
+        m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_matrix(rows: usize, cols: usize) -> Matrix<GF> {
+        let mut m = Matrix::new(rows, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                m.set(r, c, GF::new(rand::thread_rng().gen_range(0..=255)));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn systematic_code() {
+        // G = [I | P] for a (7, 3) code
+        let p = random_matrix(3, 4);
+        let mut g = Matrix::new(3, 7);
+        for r in 0..3 {
+            g.set(r, r, GF::ONE);
+            for c in 0..4 {
+                g.set(r, 3 + c, p.get(r, c));
+            }
+        }
+
+        let (standard, perm) = g.standard_form();
+        assert_eq!(standard, g);
+        assert_eq!(perm, (0..7).collect::<Vec<_>>());
+
+        // H = [-P^T | I]
+        let h = g.parity_check_from_generator();
+        let mut expected = Matrix::new(4, 7);
+        for r in 0..4 {
+            for c in 0..3 {
+                expected.set(r, c, -p.get(c, r));
+            }
+            expected.set(r, 3 + r, GF::ONE);
+        }
+        assert_eq!(h, expected);
+        assert_eq!(&g * &h.transpose(), Matrix::new(3, 4));
+    }
+
+    #[test]
+    fn round_trip() {
+        for (k, n) in [(1, 5), (4, 8), (7, 10), (5, 5)] {
+            let g = random_matrix(k, n);
+            let h = g.parity_check_from_generator();
+            assert_eq!(h.num_rows(), n - g.rank());
+            assert_eq!(h.num_cols(), n);
+            assert_eq!(&g * &h.transpose(), Matrix::new(k, h.num_rows()));
+
+            // Going back gives a generator of the same code, so the row reduced forms agree
+            let g2 = h.generator_from_parity_check();
+            assert_eq!(g2.rank(), g.rank());
+            let rank = g.rank();
+            let reduced = g.rref();
+            let g2_reduced = g2.rref();
+            for r in 0..rank {
+                assert_eq!(g2_reduced.row(r), reduced.row(r));
+            }
+        }
+    }
+
+    #[test]
+    fn non_systematic_pivots() {
+        // Column 0 is zero so the pivots are not the first columns
+        let g = Matrix::from_rows(&[
+            vec![GF::ZERO, GF::new(1), GF::new(2), GF::new(3)],
+            vec![GF::ZERO, GF::new(4), GF::new(5), GF::new(6)],
+        ]);
+        let (standard, perm) = g.standard_form();
+        assert_eq!(perm[..2], [1, 2]);
+        assert_eq!(perm[2..], [0, 3]);
+        for r in 0..2 {
+            for c in 0..2 {
+                assert_eq!(standard.get(r, c), if r == c { GF::ONE } else { GF::ZERO });
+            }
+        }
+
+        // Rank deficient generators lose rows
+        let g = Matrix::from_rows(&[
+            vec![GF::new(1), GF::new(2), GF::new(3)],
+            vec![GF::new(2), GF::new(4), GF::new(6)],
+        ]);
+        assert_eq!(g.standard_form().0.num_rows(), 1);
+    }
+}
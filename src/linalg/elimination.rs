@@ -0,0 +1,247 @@
+use super::Matrix;
+use crate::GaloisField;
+
+impl<GF: GaloisField> Matrix<GF> {
+    /// Returns the reduced row echelon form
+    pub fn rref(&self) -> Self {
+        let mut m = self.clone();
+        m.row_reduce();
+        m
+    }
+
+    /// Reduces the matrix to reduced row echelon form in place using Gauss-Jordan elimination.
+    /// Returns the pivot columns in increasing order. Pivot i is in row i, so the rank is the number of pivots
+    pub fn row_reduce(&mut self) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        for col in 0..self.cols {
+            let row = pivots.len();
+            if row == self.rows {
+                break;
+            }
+
+            // Every non zero entry is invertible so any one makes a pivot
+            let Some(pivot_row) = (row..self.rows).find(|&r| self.get(r, col) != GF::ZERO) else {
+                continue;
+            };
+            self.swap_rows(row, pivot_row);
+            self.scale_row(row, self.get(row, col).inverse());
+            for r in 0..self.rows {
+                let factor = self.get(r, col);
+                if r != row && factor != GF::ZERO {
+                    self.add_scaled_row(r, row, factor);
+                }
+            }
+            pivots.push(col);
+        }
+        pivots
+    }
+
+    /// The dimension of the row (and column) space
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().len()
+    }
+
+    /// Returns a basis of the null space {v : self * v = 0}. Has num_cols - rank vectors, each of length num_cols.
+    /// Basis vector i has a one in the i<sup>th</sup> non pivot column and zeros in the other non pivot columns
+    pub fn null_space(&self) -> Vec<Vec<GF>> {
+        let mut reduced = self.clone();
+        let pivots = reduced.row_reduce();
+
+        let free = (0..self.cols).filter(|c| !pivots.contains(c));
+        free.map(|f| {
+            let mut v = vec![GF::ZERO; self.cols];
+            v[f] = GF::ONE;
+            for (row, &p) in pivots.iter().enumerate() {
+                v[p] = -reduced.get(row, f);
+            }
+            v
+        })
+        .collect()
+    }
+
+    // The elementary row operations
+    pub(crate) fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for c in 0..self.cols {
+                self.data.swap(a * self.cols + c, b * self.cols + c);
+            }
+        }
+    }
+
+    pub(crate) fn scale_row(&mut self, row: usize, scalar: GF) {
+        for c in 0..self.cols {
+            self.data[row * self.cols + c] *= scalar;
+        }
+    }
+
+    // Row dst -= factor * row src
+    pub(crate) fn add_scaled_row(&mut self, dst: usize, src: usize, factor: GF) {
+        for c in 0..self.cols {
+            let value = self.data[src * self.cols + c];
+            self.data[dst * self.cols + c] -= factor * value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_matrix(rows: usize, cols: usize) -> Matrix<GF> {
+        let mut m = Matrix::new(rows, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                m.set(r, c, GF::new(rand::thread_rng().gen_range(0..=255)));
+            }
+        }
+        m
+    }
+
+    // [A | I]
+    fn augment_identity<T: GaloisField>(a: &Matrix<T>) -> Matrix<T> {
+        let (rows, cols) = (a.num_rows(), a.num_cols());
+        let mut augmented = Matrix::new(rows, cols + rows);
+        for r in 0..rows {
+            for c in 0..cols {
+                augmented.set(r, c, a.get(r, c));
+            }
+            augmented.set(r, cols + r, T::ONE);
+        }
+        augmented
+    }
+
+    // Checks the shape of a reduced row echelon form
+    fn assert_rref(m: &Matrix<GF>, pivots: &[usize]) {
+        for (row, &col) in pivots.iter().enumerate() {
+            assert!(m.row(row)[..col].iter().all(|&a| a == GF::ZERO));
+            for r in 0..m.num_rows() {
+                let expected = if r == row { GF::ONE } else { GF::ZERO };
+                assert_eq!(m.get(r, col), expected);
+            }
+        }
+        for r in pivots.len()..m.num_rows() {
+            assert!(m.row(r).iter().all(|&a| a == GF::ZERO));
+        }
+        assert!(pivots.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn known_matrix() {
+        let a = Matrix::from_rows(&[
+            vec![GF::new(2), GF::new(4), GF::new(1)],
+            vec![GF::new(1), GF::new(2), GF::new(1)],
+        ]);
+        let mut r = a.clone();
+        assert_eq!(r.row_reduce(), vec![0, 2]);
+        // Column 1 is twice column 0
+        let expected = Matrix::from_rows(&[
+            vec![GF::ONE, GF::new(2), GF::ZERO],
+            vec![GF::ZERO, GF::ZERO, GF::ONE],
+        ]);
+        assert_eq!(r, expected);
+        assert_eq!(a.rref(), expected);
+    }
+
+    #[test]
+    fn random_matrices() {
+        for (rows, cols) in [(1, 1), (3, 3), (4, 7), (7, 4), (10, 10), (0, 3), (3, 0)] {
+            let a = random_matrix(rows, cols);
+
+            // Reducing [A | I] gives [R | E] where E is invertible and E * A = R
+            let mut augmented = augment_identity(&a);
+            augmented.row_reduce();
+            let mut r_part = Matrix::new(rows, cols);
+            let mut e_part = Matrix::new(rows, rows);
+            for r in 0..rows {
+                for c in 0..cols {
+                    r_part.set(r, c, augmented.get(r, c));
+                }
+                for c in 0..rows {
+                    e_part.set(r, c, augmented.get(r, cols + c));
+                }
+            }
+
+            let mut reduced = a.clone();
+            let pivots = reduced.row_reduce();
+            assert_eq!(reduced, r_part);
+            assert_eq!(&e_part * &a, reduced);
+            assert_rref(&reduced, &pivots);
+            assert_eq!(reduced.rref(), reduced);
+        }
+    }
+
+    #[test]
+    fn rank_deficient() {
+        // A 4 × 6 matrix of rank at most 3
+        let mut m = &random_matrix(4, 3) * &random_matrix(3, 6);
+        let pivots = m.row_reduce();
+        assert!(pivots.len() <= 3);
+        assert_rref(&m, &pivots);
+
+        assert!(Matrix::<GF>::new(3, 3).row_reduce().is_empty());
+        assert_eq!(Matrix::<GF>::identity(4).rref(), Matrix::identity(4));
+    }
+
+    #[test]
+    fn rank_and_null_space() {
+        for (rows, cols, inner) in [(4, 6, 3), (5, 5, 5), (6, 4, 2), (3, 3, 1), (2, 5, 5)] {
+            let a = &random_matrix(rows, inner) * &random_matrix(inner, cols);
+            let rank = a.rank();
+            assert!(rank <= inner.min(rows).min(cols));
+            assert_eq!(rank, a.transpose().rank());
+
+            let kernel = a.null_space();
+            assert_eq!(kernel.len(), cols - rank);
+            for v in kernel.iter() {
+                assert!(a.mul_vec(v).iter().all(|&x| x == GF::ZERO));
+            }
+            if !kernel.is_empty() {
+                assert_eq!(Matrix::from_rows(&kernel).rank(), kernel.len());
+            }
+        }
+
+        let a = Matrix::from_rows(&[
+            vec![GF::new(1), GF::new(2), GF::new(3)],
+            vec![GF::new(2), GF::new(4), GF::new(6)],
+        ]);
+        assert_eq!(a.rank(), 1);
+        assert_eq!(
+            a.null_space(),
+            vec![
+                vec![GF::new(2), GF::ONE, GF::ZERO],
+                vec![GF::new(3), GF::ZERO, GF::ONE]
+            ]
+        );
+
+        assert_eq!(Matrix::<GF>::new(2, 3).rank(), 0);
+        assert_eq!(Matrix::<GF>::new(2, 3).null_space().len(), 3);
+        assert!(Matrix::<GF>::identity(4).null_space().is_empty());
+    }
+
+    #[test]
+    fn large_field() {
+        type GF2 = gf2::GFu64<0x2_0000_2001>;
+        let rows: Vec<Vec<GF2>> = (0..5)
+            .map(|_| {
+                (0..5)
+                    .map(|_| GF2::new(rand::thread_rng().gen_range(0..(1u64 << 33))))
+                    .collect()
+            })
+            .collect();
+        let a = Matrix::from_rows(&rows);
+
+        // Random matrices over a large field are almost surely invertible
+        let mut augmented = augment_identity(&a);
+        assert_eq!(augmented.row_reduce(), vec![0, 1, 2, 3, 4]);
+        let inverse = Matrix::from_rows(
+            &(0..5)
+                .map(|r| augmented.row(r)[5..].to_vec())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(&inverse * &a, Matrix::identity(5));
+    }
+}
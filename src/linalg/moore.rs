@@ -0,0 +1,88 @@
+use super::Matrix;
+use crate::bitmatrix::BitMatrix;
+use crate::GaloisField;
+
+impl<GF: GaloisField> Matrix<GF> {
+    /// Constructs the rows × elements.len() Moore matrix whose entry (i, j) is elements[j]<sup>2<sup>i</sup></sup>.
+    /// A square Moore matrix is invertible exactly when the elements are linearly independent over GF(2)
+    pub fn moore(elements: &[GF], rows: usize) -> Self {
+        let mut m = Self::new(rows, elements.len());
+        for (j, &e) in elements.iter().enumerate() {
+            let mut conjugate = e;
+            for i in 0..rows {
+                m.set(i, j, conjugate);
+                conjugate = conjugate.square();
+            }
+        }
+        m
+    }
+}
+
+/// The rank over GF(2) of the elements, i.e. the dimension of the GF(2) space they span.
+/// For a vector over GF(2<sup>M</sup>) this is its rank weight
+pub fn gf2_rank<GF: GaloisField>(elements: &[GF]) -> usize {
+    let m = GF::M as usize;
+    let mut matrix = BitMatrix::new(elements.len(), m);
+    for (r, e) in elements.iter().enumerate() {
+        for (c, bit) in e.to_coefficients().into_iter().enumerate() {
+            matrix.set(r, c, bit);
+        }
+    }
+    matrix.rank()
+}
+
+/// The rank distance between two vectors, the GF(2) rank of their difference. Panics if the lengths differ
+pub fn rank_distance<GF: GaloisField>(a: &[GF], b: &[GF]) -> usize {
+    if a.len() != b.len() {
+        panic!("Vector lengths do not match");
+    }
+    let diff: Vec<GF> = a.iter().zip(b.iter()).map(|(&x, &y)| x - y).collect();
+    gf2_rank(&diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    #[test]
+    fn moore() {
+        let elements = [GF::new(3), GF::new(5), GF::new(0x80)];
+        let m = Matrix::moore(&elements, 4);
+        assert_eq!(m.num_rows(), 4);
+        assert_eq!(m.row(0), &elements);
+        assert_eq!(m.row(1), &elements.map(|e| e.square()));
+        assert_eq!(m.get(3, 1), GF::new(5).pow(8));
+    }
+
+    #[test]
+    fn invertible_iff_independent() {
+        for _ in 0..100 {
+            let n = rand::thread_rng().gen_range(1..=5);
+            let elements: Vec<GF> = (0..n)
+                .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+                .collect();
+            let independent = gf2_rank(&elements) == n;
+            assert_eq!(Matrix::moore(&elements, n).inverse().is_some(), independent);
+        }
+    }
+
+    #[test]
+    fn rank_weight() {
+        assert_eq!(gf2_rank::<GF>(&[]), 0);
+        assert_eq!(gf2_rank(&[GF::ZERO, GF::ZERO]), 0);
+        // 3 = 1 + 2
+        assert_eq!(gf2_rank(&[GF::new(1), GF::new(2), GF::new(3)]), 2);
+        assert_eq!(gf2_rank(&[GF::new(1), GF::new(2), GF::new(4)]), 3);
+        let all: Vec<GF> = (0..=255).map(GF::new).collect();
+        assert_eq!(gf2_rank(&all), 8);
+
+        let a = [GF::new(7), GF::new(9), GF::new(1)];
+        let b = [GF::new(7), GF::new(8), GF::new(0)];
+        assert_eq!(rank_distance(&a, &b), 1);
+        assert_eq!(rank_distance(&a, &a), 0);
+    }
+}
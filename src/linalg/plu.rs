@@ -0,0 +1,272 @@
+use super::Matrix;
+use crate::GaloisField;
+
+/// PLU decomposition P * A = L * U of a square matrix, with L unit lower triangular and U upper triangular.
+/// Factor once with `Matrix::plu` and then solve for as many right hand sides as needed in O(n<sup>2</sup>) each
+#[derive(Clone, Debug)]
+pub struct Plu<GF: GaloisField> {
+    // Row i of P * A is row perm[i] of A
+    perm: Vec<usize>,
+    // L below the diagonal (its unit diagonal is implied) and U on and above it
+    lu: Matrix<GF>,
+}
+
+impl<GF: GaloisField> Matrix<GF> {
+    /// Computes the PLU decomposition. Singular matrices are factored too, giving a U with a zero on the diagonal.
+    /// Panics if the matrix is not square
+    pub fn plu(&self) -> Plu<GF> {
+        if self.rows != self.cols {
+            panic!("PLU decomposition needs a square matrix");
+        }
+
+        let n = self.rows;
+        let mut lu = self.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        for k in 0..n {
+            // Any non zero entry works as a pivot since there is no rounding
+            let Some(p) = (k..n).find(|&r| lu.get(r, k) != GF::ZERO) else {
+                continue;
+            };
+            lu.swap_rows(k, p);
+            perm.swap(k, p);
+
+            let pivot_inv = lu.get(k, k).inverse();
+            for i in (k + 1)..n {
+                let factor = lu.get(i, k) * pivot_inv;
+                if factor == GF::ZERO {
+                    continue;
+                }
+                for j in (k + 1)..n {
+                    let value = lu.get(i, j) - factor * lu.get(k, j);
+                    lu.set(i, j, value);
+                }
+                lu.set(i, k, factor);
+            }
+        }
+        Plu { perm, lu }
+    }
+
+    /// Solves self * x = b for a lower triangular matrix by forward substitution. Entries above the diagonal are
+    /// ignored. Returns None if the diagonal has a zero. Panics if the matrix is not square or b has the wrong length
+    pub fn forward_substitute(&self, b: &[GF]) -> Option<Vec<GF>> {
+        self.check_triangular_system(b);
+        let mut x: Vec<GF> = Vec::with_capacity(b.len());
+        for (i, &bi) in b.iter().enumerate() {
+            let sum = (0..i).fold(bi, |acc, j| acc - self.get(i, j) * x[j]);
+            x.push(divide(sum, self.get(i, i))?);
+        }
+        Some(x)
+    }
+
+    /// Solves self * x = b for an upper triangular matrix by back substitution. Entries below the diagonal are
+    /// ignored. Returns None if the diagonal has a zero. Panics if the matrix is not square or b has the wrong length
+    pub fn back_substitute(&self, b: &[GF]) -> Option<Vec<GF>> {
+        self.check_triangular_system(b);
+        let n = self.rows;
+        let mut x = vec![GF::ZERO; n];
+        for i in (0..n).rev() {
+            let sum = ((i + 1)..n).fold(b[i], |acc, j| acc - self.get(i, j) * x[j]);
+            x[i] = divide(sum, self.get(i, i))?;
+        }
+        Some(x)
+    }
+
+    fn check_triangular_system(&self, b: &[GF]) {
+        if self.rows != self.cols {
+            panic!("Triangular solves need a square matrix");
+        }
+        if b.len() != self.rows {
+            panic!("Right hand side length does not match the number of rows");
+        }
+    }
+}
+
+fn divide<GF: GaloisField>(a: GF, b: GF) -> Option<GF> {
+    if b == GF::ZERO {
+        None
+    } else {
+        Some(a / b)
+    }
+}
+
+impl<GF: GaloisField> Plu<GF> {
+    /// The row permutation: row i of P * A is row permutation()\[i\] of A
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// The permutation matrix P
+    pub fn p(&self) -> Matrix<GF> {
+        let n = self.perm.len();
+        let mut p = Matrix::new(n, n);
+        for (i, &j) in self.perm.iter().enumerate() {
+            p.set(i, j, GF::ONE);
+        }
+        p
+    }
+
+    /// The unit lower triangular factor L
+    pub fn l(&self) -> Matrix<GF> {
+        let n = self.perm.len();
+        let mut l = Matrix::identity(n);
+        for i in 0..n {
+            for j in 0..i {
+                l.set(i, j, self.lu.get(i, j));
+            }
+        }
+        l
+    }
+
+    /// The upper triangular factor U
+    pub fn u(&self) -> Matrix<GF> {
+        let n = self.perm.len();
+        let mut u = Matrix::new(n, n);
+        for i in 0..n {
+            for j in i..n {
+                u.set(i, j, self.lu.get(i, j));
+            }
+        }
+        u
+    }
+
+    /// Whether the factored matrix is singular
+    pub fn is_singular(&self) -> bool {
+        (0..self.perm.len()).any(|i| self.lu.get(i, i) == GF::ZERO)
+    }
+
+    /// Solves A * x = b using the factors. Returns None if A is singular.
+    /// Panics if b does not have one entry per row
+    pub fn solve(&self, b: &[GF]) -> Option<Vec<GF>> {
+        if b.len() != self.perm.len() {
+            panic!("Right hand side length does not match the number of rows");
+        }
+
+        // L * y = P * b then U * x = y. The lu matrix serves as both factors since each solve
+        // only reads its own triangle, with the unit diagonal of L handled here
+        let n = self.perm.len();
+        let mut y: Vec<GF> = Vec::with_capacity(n);
+        for i in 0..n {
+            let sum = (0..i).fold(b[self.perm[i]], |acc, j| acc - self.lu.get(i, j) * y[j]);
+            y.push(sum);
+        }
+        self.lu.back_substitute(&y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_matrix(rows: usize, cols: usize) -> Matrix<GF> {
+        let mut m = Matrix::new(rows, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                m.set(r, c, GF::new(rand::thread_rng().gen_range(0..=255)));
+            }
+        }
+        m
+    }
+
+    fn random_vec(n: usize) -> Vec<GF> {
+        (0..n)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+            .collect()
+    }
+
+    #[test]
+    fn factors() {
+        for n in [0, 1, 2, 5, 16] {
+            let a = random_matrix(n, n);
+            let plu = a.plu();
+            assert_eq!(&plu.p() * &a, &plu.l() * &plu.u());
+            for i in 0..n {
+                assert_eq!(plu.l().get(i, i), GF::ONE);
+                for j in (i + 1)..n {
+                    assert_eq!(plu.l().get(i, j), GF::ZERO);
+                    assert_eq!(plu.u().get(j, i), GF::ZERO);
+                }
+            }
+            assert_eq!(plu.is_singular(), a.rank() < n);
+        }
+    }
+
+    #[test]
+    fn needs_pivoting() {
+        // The top left entry is zero
+        let a = Matrix::from_rows(&[
+            vec![GF::ZERO, GF::new(1), GF::new(2)],
+            vec![GF::new(3), GF::new(4), GF::new(5)],
+            vec![GF::new(6), GF::new(7), GF::new(9)],
+        ]);
+        let plu = a.plu();
+        assert_eq!(plu.permutation()[0], 1);
+        assert_eq!(&plu.p() * &a, &plu.l() * &plu.u());
+
+        let x = random_vec(3);
+        assert_eq!(plu.solve(&a.mul_vec(&x)), a.solve(&a.mul_vec(&x)));
+    }
+
+    #[test]
+    fn solve_many() {
+        let a = random_matrix(12, 12);
+        let plu = a.plu();
+        if plu.is_singular() {
+            assert_eq!(plu.solve(&random_vec(12)), None);
+            return;
+        }
+        for _ in 0..10 {
+            let x = random_vec(12);
+            assert_eq!(plu.solve(&a.mul_vec(&x)), Some(x));
+        }
+    }
+
+    #[test]
+    fn singular() {
+        // Rank 2
+        let a = &random_matrix(4, 2) * &random_matrix(2, 4);
+        let plu = a.plu();
+        assert!(plu.is_singular());
+        assert_eq!(&plu.p() * &a, &plu.l() * &plu.u());
+        assert_eq!(plu.solve(&random_vec(4)), None);
+    }
+
+    #[test]
+    fn triangular_solves() {
+        let full = random_matrix(6, 6);
+        let mut lower = full.clone();
+        let mut upper = full.clone();
+        for i in 0..6 {
+            let d = GF::new(rand::thread_rng().gen_range(1..=255));
+            for m in [&mut lower, &mut upper] {
+                m.set(i, i, d);
+            }
+            for j in (i + 1)..6 {
+                lower.set(i, j, GF::ZERO);
+                upper.set(j, i, GF::ZERO);
+            }
+        }
+
+        // Entries on the other side of the diagonal are ignored
+        let x = random_vec(6);
+        let mut full_lower = full.clone();
+        let mut full_upper = full;
+        for i in 0..6 {
+            full_lower.set(i, i, lower.get(i, i));
+            full_upper.set(i, i, upper.get(i, i));
+        }
+        assert_eq!(
+            full_lower.forward_substitute(&lower.mul_vec(&x)),
+            Some(x.clone())
+        );
+        assert_eq!(full_upper.back_substitute(&upper.mul_vec(&x)), Some(x));
+
+        let mut zero_diag = Matrix::identity(3);
+        zero_diag.set(1, 1, GF::ZERO);
+        assert_eq!(zero_diag.forward_substitute(&[GF::ONE; 3]), None);
+        assert_eq!(zero_diag.back_substitute(&[GF::ONE; 3]), None);
+    }
+}
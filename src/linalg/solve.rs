@@ -0,0 +1,181 @@
+use super::Matrix;
+use crate::GaloisField;
+
+impl<GF: GaloisField> Matrix<GF> {
+    /// Solves self * x = b. Returns one solution, with every free variable set to zero, or None if there is none.
+    /// Panics if b does not have num_rows entries
+    pub fn solve(&self, b: &[GF]) -> Option<Vec<GF>> {
+        self.solution_set(b).map(|(x, _)| x)
+    }
+
+    /// Returns every solution of self * x = b as a particular solution plus a basis of the null space,
+    /// so the solutions are x + Σ c<sub>i</sub> k<sub>i</sub>. None if the system is inconsistent.
+    /// Panics if b does not have num_rows entries
+    pub fn solution_set(&self, b: &[GF]) -> Option<(Vec<GF>, Vec<Vec<GF>>)> {
+        if b.len() != self.rows {
+            panic!("Right hand side length does not match the number of rows");
+        }
+
+        let mut augmented = self.hconcat(&Matrix::from_col(b));
+        let pivots = augmented.row_reduce();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+
+        let mut x = vec![GF::ZERO; self.cols];
+        for (row, &p) in pivots.iter().enumerate() {
+            x[p] = augmented.get(row, self.cols);
+        }
+        Some((x, self.null_space()))
+    }
+
+    /// Returns the inverse of a square matrix, or None if it is singular. Panics if the matrix is not square
+    pub fn inverse(&self) -> Option<Self> {
+        if self.rows != self.cols {
+            panic!("Only square matrices have an inverse");
+        }
+
+        let n = self.rows;
+        let mut augmented = self.hconcat(&Self::identity(n));
+        if augmented
+            .row_reduce()
+            .iter()
+            .take_while(|&&p| p < n)
+            .count()
+            < n
+        {
+            return None;
+        }
+
+        let mut inverse = Self::new(n, n);
+        for r in 0..n {
+            for c in 0..n {
+                inverse.set(r, c, augmented.get(r, n + c));
+            }
+        }
+        Some(inverse)
+    }
+
+    // Single column matrix
+    fn from_col(v: &[GF]) -> Self {
+        Self {
+            rows: v.len(),
+            cols: 1,
+            data: v.to_vec(),
+        }
+    }
+
+    // [self | other]
+    fn hconcat(&self, other: &Self) -> Self {
+        if self.rows != other.rows {
+            panic!("Matrix dimensions do not match");
+        }
+        let mut data = Vec::with_capacity(self.data.len() + other.data.len());
+        for r in 0..self.rows {
+            data.extend_from_slice(self.row(r));
+            data.extend_from_slice(other.row(r));
+        }
+        Self {
+            rows: self.rows,
+            cols: self.cols + other.cols,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_matrix(rows: usize, cols: usize) -> Matrix<GF> {
+        let mut m = Matrix::new(rows, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                m.set(r, c, GF::new(rand::thread_rng().gen_range(0..=255)));
+            }
+        }
+        m
+    }
+
+    fn random_vec(n: usize) -> Vec<GF> {
+        (0..n)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+            .collect()
+    }
+
+    #[test]
+    fn square_systems() {
+        for n in [1, 2, 5, 10] {
+            let a = random_matrix(n, n);
+            let x = random_vec(n);
+            let b = a.mul_vec(&x);
+
+            match a.inverse() {
+                Some(inverse) => {
+                    assert_eq!(&inverse * &a, Matrix::identity(n));
+                    assert_eq!(&a * &inverse, Matrix::identity(n));
+                    assert_eq!(a.solve(&b), Some(x));
+                }
+                None => assert!(a.rank() < n),
+            }
+        }
+    }
+
+    #[test]
+    fn underdetermined() {
+        let a = random_matrix(3, 6);
+        let x = random_vec(6);
+        let b = a.mul_vec(&x);
+
+        let (particular, kernel) = a.solution_set(&b).unwrap();
+        assert_eq!(a.mul_vec(&particular), b);
+        assert_eq!(kernel.len(), 6 - a.rank());
+
+        // Any combination of the kernel added to the particular solution is a solution
+        let mut y = particular.clone();
+        for k in kernel.iter() {
+            let c = GF::new(rand::thread_rng().gen_range(0..=255));
+            for (yi, &ki) in y.iter_mut().zip(k.iter()) {
+                *yi += c * ki;
+            }
+        }
+        assert_eq!(a.mul_vec(&y), b);
+
+        // The original x differs from the particular solution by a kernel vector
+        let diff: Vec<GF> = x
+            .iter()
+            .zip(particular.iter())
+            .map(|(&p, &q)| p - q)
+            .collect();
+        let mut rows = kernel.clone();
+        rows.push(diff);
+        assert_eq!(Matrix::from_rows(&rows).rank(), kernel.len());
+    }
+
+    #[test]
+    fn inconsistent() {
+        // Two equal rows with different right hand sides
+        let a = Matrix::from_rows(&[vec![GF::new(1), GF::new(2)], vec![GF::new(1), GF::new(2)]]);
+        assert_eq!(a.solve(&[GF::new(3), GF::new(4)]), None);
+        assert_eq!(
+            a.solve(&[GF::new(3), GF::new(3)]),
+            Some(vec![GF::new(3), GF::ZERO])
+        );
+        assert_eq!(a.inverse(), None);
+
+        // Overdetermined but consistent
+        let a = random_matrix(8, 3);
+        let x = random_vec(3);
+        assert_eq!(a.solve(&a.mul_vec(&x)), Some(x));
+    }
+
+    #[test]
+    #[should_panic]
+    fn inverse_not_square() {
+        let _ = random_matrix(2, 3).inverse();
+    }
+}
@@ -0,0 +1,141 @@
+use super::Matrix;
+use crate::GaloisField;
+
+impl<GF: GaloisField> Matrix<GF> {
+    /// Constructs the rows × points.len() Vandermonde matrix whose entry (i, j) is points[j]<sup>i</sup>.
+    /// Multiplying a row vector of polynomial coefficients by it evaluates the polynomial at every point
+    pub fn vandermonde(points: &[GF], rows: usize) -> Self {
+        let mut m = Self::new(rows, points.len());
+        for (j, &x) in points.iter().enumerate() {
+            let mut power = GF::ONE;
+            for i in 0..rows {
+                m.set(i, j, power);
+                power *= x;
+            }
+        }
+        m
+    }
+
+    /// Inverts the square Vandermonde matrix of the points in O(n<sup>2</sup>) instead of O(n<sup>3</sup>).
+    /// Row j of the inverse holds the coefficients of the j<sup>th</sup> Lagrange basis polynomial.
+    /// Returns None if two points are equal
+    pub fn vandermonde_inverse(points: &[GF]) -> Option<Self> {
+        let n = points.len();
+        let mut inverse = Self::new(n, n);
+        for (j, basis) in lagrange_basis(points)?.enumerate() {
+            for (i, &c) in basis.iter().enumerate() {
+                inverse.set(j, i, c);
+            }
+        }
+        Some(inverse)
+    }
+
+    /// Solves V * x = b for the square Vandermonde matrix V of the points in O(n<sup>2</sup>) without forming
+    /// the inverse. Returns None if two points are equal. Panics if b does not have one entry per point
+    pub fn vandermonde_solve(points: &[GF], b: &[GF]) -> Option<Vec<GF>> {
+        if b.len() != points.len() {
+            panic!("Right hand side length does not match the number of points");
+        }
+        let solution = lagrange_basis(points)?
+            .map(|basis| {
+                basis
+                    .iter()
+                    .zip(b.iter())
+                    .fold(GF::ZERO, |acc, (&l, &bi)| acc + l * bi)
+            })
+            .collect();
+        Some(solution)
+    }
+}
+
+// Coefficients of the Lagrange basis polynomials of the points, lowest degree first, O(n) each after an
+// O(n^2) setup. None if two points are equal
+fn lagrange_basis<GF: GaloisField>(points: &[GF]) -> Option<impl Iterator<Item = Vec<GF>> + '_> {
+    // master(t) = ∏ (t - x_k)
+    let n = points.len();
+    let mut master = vec![GF::ZERO; n + 1];
+    master[0] = GF::ONE;
+    for (k, &x) in points.iter().enumerate() {
+        for i in (1..=k + 1).rev() {
+            master[i] = master[i - 1] - x * master[i];
+        }
+        master[0] = -(x * master[0]);
+    }
+
+    // The denominators ∏ (x_j - x_k) for k ≠ j
+    let mut denominators = Vec::with_capacity(n);
+    for (j, &xj) in points.iter().enumerate() {
+        let d = points
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| k != j)
+            .fold(GF::ONE, |acc, (_, &xk)| acc * (xj - xk));
+        if d == GF::ZERO {
+            return None;
+        }
+        denominators.push(d.inverse());
+    }
+
+    Some(points.iter().zip(denominators).map(move |(&xj, d_inv)| {
+        // master(t) / (t - x_j) by synthetic division
+        let mut basis = vec![GF::ZERO; n];
+        let mut carry = GF::ZERO;
+        for i in (0..n).rev() {
+            carry = master[i + 1] + xj * carry;
+            basis[i] = carry * d_inv;
+        }
+        basis
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use crate::poly::Polynomial;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    #[test]
+    fn vandermonde() {
+        let points = [GF::new(2), GF::new(3), GF::new(7)];
+        let v = Matrix::vandermonde(&points, 4);
+        assert_eq!(v.num_rows(), 4);
+        assert_eq!(v.num_cols(), 3);
+        assert_eq!(v.row(0), &[GF::ONE; 3]);
+        assert_eq!(v.get(3, 1), GF::new(3).pow(3));
+
+        // Row vector of coefficients times V evaluates the polynomial
+        let coeffs = [GF::new(5), GF::new(9), GF::new(1), GF::new(4)];
+        let p = Polynomial::new(coeffs.to_vec());
+        assert_eq!(v.transpose().mul_vec(&coeffs), p.eval_many(&points));
+    }
+
+    #[test]
+    fn structured_inverse() {
+        for n in [1, 2, 5, 20, 64] {
+            let points: Vec<GF> = (0..n).map(|i| GF::new(2).pow(i as u128)).collect();
+            let v = Matrix::vandermonde(&points, n);
+
+            let inverse = Matrix::vandermonde_inverse(&points).unwrap();
+            assert_eq!(&inverse * &v, Matrix::identity(n));
+            assert_eq!(Some(inverse), v.inverse());
+
+            let b: Vec<GF> = (0..n).map(|i| GF::new(3 * i as u8 + 2)).collect();
+            let x = Matrix::vandermonde_solve(&points, &b).unwrap();
+            assert_eq!(v.mul_vec(&x), b);
+        }
+        assert_eq!(
+            Matrix::<GF>::vandermonde_inverse(&[]),
+            Some(Matrix::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn repeated_points() {
+        let points = [GF::new(2), GF::new(3), GF::new(2)];
+        assert_eq!(Matrix::vandermonde_inverse(&points), None);
+        assert_eq!(Matrix::vandermonde_solve(&points, &[GF::ONE; 3]), None);
+        assert_eq!(Matrix::vandermonde(&points, 3).inverse(), None);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::GaloisField;
+
+/// Returns Σ a<sub>i</sub> b<sub>i</sub>. Panics if the lengths differ
+pub fn dot<GF: GaloisField>(a: &[GF], b: &[GF]) -> GF {
+    check_lengths(a, b);
+    a.iter()
+        .zip(b.iter())
+        .fold(GF::ZERO, |acc, (&x, &y)| acc + x * y)
+}
+
+/// Multiplies every entry of x by a in place
+pub fn scale<GF: GaloisField>(a: GF, x: &mut [GF]) {
+    x.iter_mut().for_each(|xi| *xi *= a);
+}
+
+/// y += a * x. Panics if the lengths differ
+pub fn axpy<GF: GaloisField>(a: GF, x: &[GF], y: &mut [GF]) {
+    check_lengths(x, y);
+    if a == GF::ZERO {
+        return;
+    }
+    for (yi, &xi) in y.iter_mut().zip(x.iter()) {
+        *yi += a * xi;
+    }
+}
+
+/// The number of non zero entries
+pub fn hamming_weight<GF: GaloisField>(x: &[GF]) -> usize {
+    x.iter().filter(|&&xi| xi != GF::ZERO).count()
+}
+
+/// The number of positions where a and b differ. Panics if the lengths differ
+pub fn hamming_distance<GF: GaloisField>(a: &[GF], b: &[GF]) -> usize {
+    check_lengths(a, b);
+    a.iter().zip(b.iter()).filter(|(x, y)| x != y).count()
+}
+
+/// The positions of the non zero entries
+pub fn support<GF: GaloisField>(x: &[GF]) -> Vec<usize> {
+    x.iter()
+        .enumerate()
+        .filter(|&(_, &xi)| xi != GF::ZERO)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn check_lengths<GF: GaloisField>(a: &[GF], b: &[GF]) {
+    if a.len() != b.len() {
+        panic!("Vector lengths do not match");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use crate::linalg::Matrix;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn vec_of(values: &[u8]) -> Vec<GF> {
+        values.iter().map(|&v| GF::new(v)).collect()
+    }
+
+    #[test]
+    fn dot_scale_axpy() {
+        let a = vec_of(&[1, 2, 3, 0]);
+        let b = vec_of(&[4, 5, 6, 7]);
+        assert_eq!(
+            dot(&a, &b),
+            GF::new(4) + GF::new(2) * GF::new(5) + GF::new(3) * GF::new(6)
+        );
+        assert_eq!(
+            dot(&a, &b),
+            Matrix::from_rows(core::slice::from_ref(&a)).mul_vec(&b)[0]
+        );
+        assert_eq!(dot::<GF>(&[], &[]), GF::ZERO);
+
+        let mut x = a.clone();
+        scale(GF::new(9), &mut x);
+        assert_eq!(x, a.iter().map(|&v| v * GF::new(9)).collect::<Vec<_>>());
+
+        let mut y = b.clone();
+        axpy(GF::new(9), &a, &mut y);
+        let expected: Vec<GF> = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&p, &q)| GF::new(9) * p + q)
+            .collect();
+        assert_eq!(y, expected);
+
+        // Adding x twice cancels in characteristic 2
+        axpy(GF::ONE, &x, &mut y);
+        axpy(GF::ONE, &x, &mut y);
+        assert_eq!(y, expected);
+    }
+
+    #[test]
+    fn weights() {
+        let a = vec_of(&[0, 3, 0, 9, 1]);
+        let b = vec_of(&[0, 3, 1, 8, 1]);
+        assert_eq!(hamming_weight(&a), 3);
+        assert_eq!(hamming_distance(&a, &b), 2);
+        assert_eq!(hamming_distance(&a, &a), 0);
+        assert_eq!(support(&a), vec![1, 3, 4]);
+        assert!(support::<GF>(&vec_of(&[0, 0])).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_lengths() {
+        let _ = dot(&vec_of(&[1, 2]), &vec_of(&[1]));
+    }
+}
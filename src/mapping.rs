@@ -0,0 +1,168 @@
+//! Mapping between GF(2<sup>M</sup>) symbols and 2<sup>M</sup>-ary constellation indices.
+//!
+//! A constellation index k selects the k-th point of a modulation constellation (e.g. the k-th amplitude level
+//! of a PAM or the k-th phase of a PSK). The bits of the symbol are the label of that point.
+//! With the Gray mapping the labels of adjacent constellation points differ in exactly one bit.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::mapping::{self, BitOrder, SymbolMapping};
+//!
+//! type GF = gf2::GFu8<0x13>;
+//! let index = mapping::symbol_to_index(GF::new(0b0011), SymbolMapping::Gray, BitOrder::MsbFirst);
+//! assert_eq!(index, 2);
+//! assert_eq!(mapping::index_to_symbol::<GF>(index, SymbolMapping::Gray, BitOrder::MsbFirst), GF::new(0b0011));
+//! ```
+
+use crate::GaloisField;
+
+/// How constellation indices are labelled with symbol bits
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SymbolMapping {
+    /// The label of index k is k itself
+    Natural,
+    /// The label of index k is the binary reflected Gray code of k
+    Gray,
+}
+
+/// Which coefficient of the symbol is treated as the most significant bit of the label
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BitOrder {
+    /// The coefficient of x<sup>M-1</sup> is the most significant bit of the label
+    MsbFirst,
+    /// The coefficient of x<sup>0</sup> is the most significant bit of the label
+    LsbFirst,
+}
+
+/// Returns the binary reflected Gray code of value
+pub const fn gray_encode(value: u128) -> u128 {
+    value ^ (value >> 1)
+}
+
+/// Inverse of gray_encode
+pub const fn gray_decode(value: u128) -> u128 {
+    let mut output = value;
+    let mut shift = 1;
+    while shift < 128 {
+        output ^= output >> shift;
+        shift <<= 1;
+    }
+    output
+}
+
+/// Reverses the order of the lowest num_bits bits of value. Bits above num_bits must be zero
+pub const fn reverse_bits(value: u128, num_bits: u32) -> u128 {
+    if num_bits == 0 {
+        0
+    } else {
+        value.reverse_bits() >> (128 - num_bits)
+    }
+}
+
+/// Returns the constellation index labelled by symbol
+pub fn symbol_to_index<GF: GaloisField>(
+    symbol: GF,
+    mapping: SymbolMapping,
+    order: BitOrder,
+) -> u128 {
+    let label = match order {
+        BitOrder::MsbFirst => symbol.to_u128(),
+        BitOrder::LsbFirst => reverse_bits(symbol.to_u128(), GF::M as u32),
+    };
+
+    match mapping {
+        SymbolMapping::Natural => label,
+        SymbolMapping::Gray => gray_decode(label),
+    }
+}
+
+/// Returns the symbol labelling constellation index. Panics if index ≥ 2<sup>M</sup>
+pub fn index_to_symbol<GF: GaloisField>(
+    index: u128,
+    mapping: SymbolMapping,
+    order: BitOrder,
+) -> GF {
+    if index >= GF::NUM_ELEM {
+        panic!("Constellation index out of range");
+    }
+
+    let label = match mapping {
+        SymbolMapping::Natural => index,
+        SymbolMapping::Gray => gray_encode(index),
+    };
+
+    match order {
+        BitOrder::MsbFirst => GF::from_u128(label),
+        BitOrder::LsbFirst => GF::from_u128(reverse_bits(label, GF::M as u32)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+
+    #[test]
+    fn gray_code() {
+        let expected: [u128; 8] = [0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100];
+        for (i, &g) in expected.iter().enumerate() {
+            assert_eq!(gray_encode(i as u128), g);
+            assert_eq!(gray_decode(g), i as u128);
+        }
+        for i in 0..1000u128 {
+            assert_eq!(gray_decode(gray_encode(i << 100)), i << 100);
+        }
+    }
+
+    #[test]
+    fn reverse() {
+        assert_eq!(reverse_bits(0b0011, 4), 0b1100);
+        assert_eq!(reverse_bits(0b1, 1), 0b1);
+        assert_eq!(reverse_bits(1, 128), 1 << 127);
+    }
+
+    #[test]
+    fn round_trip() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        for mapping in [SymbolMapping::Natural, SymbolMapping::Gray] {
+            for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+                for i in 0..GF::NUM_ELEM {
+                    let symbol: GF = index_to_symbol(i, mapping, order);
+                    assert_eq!(symbol_to_index(symbol, mapping, order), i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gray_neighbours_differ_in_one_bit() {
+        type GF = gf2::GFu8<0x25>;
+        for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            for i in 1..GF::NUM_ELEM {
+                let a: GF = index_to_symbol(i - 1, SymbolMapping::Gray, order);
+                let b: GF = index_to_symbol(i, SymbolMapping::Gray, order);
+                assert_eq!((a + b).to_u128().count_ones(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn lsb_first() {
+        type GF = gf2::GFu8<0x13>;
+        assert_eq!(
+            symbol_to_index(GF::new(0b0001), SymbolMapping::Natural, BitOrder::LsbFirst),
+            0b1000
+        );
+        assert_eq!(
+            index_to_symbol::<GF>(0b0010, SymbolMapping::Natural, BitOrder::LsbFirst),
+            GF::new(0b0100)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_range() {
+        type GF = gf2::GFu8<0x13>;
+        let _: GF = index_to_symbol(16, SymbolMapping::Natural, BitOrder::MsbFirst);
+    }
+}
@@ -0,0 +1,994 @@
+//! Dense polynomials with coefficients in a Galois Field.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2_lut};
+//! use galois_field_2pm::poly::Polynomial;
+//!
+//! type GF = gf2_lut::GFu8<0x11d>;
+//! // p(x) = x + 2
+//! let p = Polynomial::new(vec![GF::new(2), GF::ONE]);
+//! // q(x) = x + 3
+//! let q = Polynomial::new(vec![GF::new(3), GF::ONE]);
+//!
+//! let r = &p * &q;
+//! assert_eq!(r.degree(), 2);
+//! assert_eq!(r.eval(GF::new(2)), GF::ZERO);
+//! ```
+
+use core::ops::{
+    Add, AddAssign, Mul, MulAssign, Neg, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+use crate::GaloisField;
+
+mod display;
+mod factor;
+mod karatsuba;
+mod remainder;
+#[cfg(feature = "serde")]
+mod serialize;
+mod tree;
+
+pub use display::{CoeffStyle, ParsePolynomialError, PolyDisplay};
+pub use remainder::PolyRemainderState;
+
+// The number of points eval_many runs Horner's rule for at once
+const EVAL_BLOCK: usize = 8;
+
+/// A polynomial over GF. Index i of the coefficients holds the coefficient of x<sup>i</sup>.
+/// The leading coefficient is never zero, so the zero polynomial has no coefficients.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Polynomial<GF: GaloisField> {
+    coeffs: Vec<GF>,
+}
+
+impl<GF: GaloisField> Polynomial<GF> {
+    /// Constructs a polynomial from its coefficients, lowest degree first
+    pub fn new(coeffs: Vec<GF>) -> Self {
+        let mut p = Self { coeffs };
+        p.normalize();
+        p
+    }
+
+    /// The zero polynomial
+    pub fn zero() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+
+    /// The constant polynomial 1
+    pub fn one() -> Self {
+        Self {
+            coeffs: vec![GF::ONE],
+        }
+    }
+
+    /// Returns coeff * x<sup>degree</sup>
+    pub fn monomial(coeff: GF, degree: usize) -> Self {
+        let mut coeffs = vec![GF::ZERO; degree + 1];
+        coeffs[degree] = coeff;
+        Self::new(coeffs)
+    }
+
+    /// Constructs a polynomial with coefficients 0 and 1 from a polynomial over GF(2) represented as a u128
+    pub fn from_gf2(poly: u128) -> Self {
+        let coeffs = (0..(128 - poly.leading_zeros()))
+            .map(|i| {
+                if ((poly >> i) & 0x1) > 0 {
+                    GF::ONE
+                } else {
+                    GF::ZERO
+                }
+            })
+            .collect();
+        Self::new(coeffs)
+    }
+
+    /// The coefficients, lowest degree first. Empty for the zero polynomial
+    pub fn coeffs(&self) -> &[GF] {
+        &self.coeffs
+    }
+
+    /// Consumes the polynomial and returns its coefficients, lowest degree first
+    pub fn into_coeffs(self) -> Vec<GF> {
+        self.coeffs
+    }
+
+    /// Returns the coefficient of x<sup>i</sup>. Zero past the degree
+    pub fn coeff(&self, i: usize) -> GF {
+        self.coeffs.get(i).copied().unwrap_or(GF::ZERO)
+    }
+
+    /// The degree of the polynomial. For the zero polynomial returns -1
+    pub fn degree(&self) -> isize {
+        self.coeffs.len() as isize - 1
+    }
+
+    /// Checks if this is the zero polynomial
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The coefficient of the highest power of x. For the zero polynomial returns zero
+    pub fn leading_coeff(&self) -> GF {
+        self.coeffs.last().copied().unwrap_or(GF::ZERO)
+    }
+
+    /// Evaluates the polynomial at x using Horner's rule
+    pub fn eval(&self, x: GF) -> GF {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(GF::ZERO, |acc, &c| acc * x + c)
+    }
+
+    /// Evaluates the polynomial at every point. Horner's rule runs for eight points at once, so each pass over the
+    /// coefficients serves eight independent chains of multiplications, which is several times faster than separate
+    /// `eval` calls with table based fields. The subproduct tree of `eval_at_points` only catches up at thousands of
+    /// points and coefficients, and points in geometric progression are cheaper with `eval_geometric`
+    pub fn eval_many(&self, points: &[GF]) -> Vec<GF> {
+        let mut output = Vec::with_capacity(points.len());
+        for block in points.chunks(EVAL_BLOCK) {
+            let mut acc = [GF::ZERO; EVAL_BLOCK];
+            for &c in self.coeffs.iter().rev() {
+                for (a, &x) in acc.iter_mut().zip(block) {
+                    *a = *a * x + c;
+                }
+            }
+            output.extend_from_slice(&acc[..block.len()]);
+        }
+        output
+    }
+
+    /// Evaluates the polynomial at first, first * step, first * step<sup>2</sup>, ... (count points).
+    /// Each term is updated with one multiplication per point, which suits syndrome and Chien search style loops
+    pub fn eval_geometric(&self, first: GF, step: GF, count: usize) -> Vec<GF> {
+        // terms[i] = c_i * x^i for the current point x
+        let mut terms: Vec<GF> = Vec::with_capacity(self.coeffs.len());
+        let mut first_pow = GF::ONE;
+        let mut step_pows: Vec<GF> = Vec::with_capacity(self.coeffs.len());
+        let mut step_pow = GF::ONE;
+        for &c in self.coeffs.iter() {
+            terms.push(c * first_pow);
+            step_pows.push(step_pow);
+            first_pow *= first;
+            step_pow *= step;
+        }
+
+        let mut output = Vec::with_capacity(count);
+        for _ in 0..count {
+            output.push(terms.iter().fold(GF::ZERO, |acc, &t| acc + t));
+            for (t, &p) in terms.iter_mut().zip(step_pows.iter()) {
+                *t *= p;
+            }
+        }
+        output
+    }
+
+    /// Divides self by divisor and returns (quotient, remainder). Panics if divisor is zero
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        if divisor.is_zero() {
+            panic!("Divide by zero polynomial");
+        }
+        if self.degree() < divisor.degree() {
+            return (Self::zero(), self.clone());
+        }
+
+        let d = divisor.coeffs.len() - 1;
+        let lead_inv = divisor.leading_coeff().inverse();
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![GF::ZERO; remainder.len() - d];
+
+        for i in (0..quotient.len()).rev() {
+            let q = remainder[i + d] * lead_inv;
+            quotient[i] = q;
+            if q == GF::ZERO {
+                continue;
+            }
+            for (j, &c) in divisor.coeffs.iter().enumerate() {
+                remainder[i + j] -= q * c;
+            }
+        }
+
+        remainder.truncate(d);
+        (Self::new(quotient), Self::new(remainder))
+    }
+
+    /// Returns self mod divisor. Panics if divisor is zero
+    pub fn rem(&self, divisor: &Self) -> Self {
+        self.div_rem(divisor).1
+    }
+
+    /// The monic greatest common divisor of self and other. The gcd of two zero polynomials is zero
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            a = b;
+            b = r;
+        }
+        a.make_monic();
+        a
+    }
+
+    /// Extended Euclidean algorithm. Returns (g, s, t) where g is the monic gcd of a and b and s * a + t * b = g
+    pub fn extended_gcd(a: &Self, b: &Self) -> (Self, Self, Self) {
+        let ((mut g, mut s, mut t), _) = Self::euclid(a, b, 0);
+        if let Some(&lead) = g.coeffs.last() {
+            let lead_inv = lead.inverse();
+            g = &g * lead_inv;
+            s = &s * lead_inv;
+            t = &t * lead_inv;
+        }
+        (g, s, t)
+    }
+
+    /// Extended Euclidean algorithm that stops at the first remainder r with degree less than stop_degree.
+    /// Returns (r, s, t) where s * a + t * b = r. The remainder is not normalized
+    pub fn partial_extended_gcd(a: &Self, b: &Self, stop_degree: isize) -> (Self, Self, Self) {
+        if a.degree() < stop_degree {
+            return (a.clone(), Self::one(), Self::zero());
+        }
+        Self::euclid(a, b, stop_degree).1
+    }
+
+    // Runs the extended Euclidean algorithm until the newest remainder has degree less than stop_degree.
+    // Returns the last two (remainder, s, t) triples, oldest first
+    #[allow(clippy::type_complexity)]
+    fn euclid(a: &Self, b: &Self, stop_degree: isize) -> ((Self, Self, Self), (Self, Self, Self)) {
+        let (mut r0, mut r1) = (a.clone(), b.clone());
+        let (mut s0, mut s1) = (Self::one(), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::one());
+
+        while r1.degree() >= stop_degree && !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            let s = &s0 - &(&q * &s1);
+            let t = &t0 - &(&q * &t1);
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1, s);
+            (t0, t1) = (t1, t);
+        }
+
+        ((r0, s0, t0), (r1, s1, t1))
+    }
+
+    /// Returns the inverse of self modulo modulus, if it exists
+    pub fn inverse_mod(&self, modulus: &Self) -> Option<Self> {
+        let (g, s, _) = Self::extended_gcd(&self.rem(modulus), modulus);
+        if g == Self::one() {
+            Some(s.rem(modulus))
+        } else {
+            None
+        }
+    }
+
+    /// Scales the polynomial so its leading coefficient is one. The zero polynomial is returned unchanged
+    pub fn monic(mut self) -> Self {
+        self.make_monic();
+        self
+    }
+
+    /// Multiplies every coefficient by scalar, reusing the coefficient storage
+    pub fn mul_scalar(mut self, scalar: GF) -> Self {
+        if scalar == GF::ZERO {
+            self.coeffs.clear();
+        } else {
+            self.coeffs.iter_mut().for_each(|c| *c *= scalar);
+        }
+        self
+    }
+
+    /// Drops every term of degree above degree, i.e. reduces modulo x<sup>degree + 1</sup>
+    pub fn truncate(&mut self, degree: usize) {
+        self.coeffs.truncate(degree + 1);
+        self.normalize();
+    }
+
+    // Scale so the leading coefficient is one. The zero polynomial is left unchanged
+    fn make_monic(&mut self) {
+        if let Some(&lead) = self.coeffs.last() {
+            let lead_inv = lead.inverse();
+            for c in self.coeffs.iter_mut() {
+                *c *= lead_inv;
+            }
+        }
+    }
+
+    // Drop leading zero coefficients
+    fn normalize(&mut self) {
+        while self.coeffs.last() == Some(&GF::ZERO) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+impl<GF: GaloisField> Polynomial<GF> {
+    /// Returns the polynomial of lowest degree passing through every (x, y) point.
+    /// Returns None if two points share an x coordinate
+    pub fn interpolate(points: &[(GF, GF)]) -> Option<Self> {
+        let xs: Vec<GF> = points.iter().map(|&(x, _)| x).collect();
+        let ys: Vec<GF> = points.iter().map(|&(_, y)| y).collect();
+        Some(Interpolator::new(&xs)?.interpolate(&ys))
+    }
+
+    // Divides self by (x - root), discarding the remainder
+    fn div_linear(&self, root: GF) -> Self {
+        if self.coeffs.len() < 2 {
+            return Self::zero();
+        }
+        let mut quotient = vec![GF::ZERO; self.coeffs.len() - 1];
+        let mut carry = GF::ZERO;
+        for i in (0..quotient.len()).rev() {
+            carry = self.coeffs[i + 1] + carry * root;
+            quotient[i] = carry;
+        }
+        Self::new(quotient)
+    }
+}
+
+/// Lagrange interpolation at a fixed set of x coordinates.
+/// The barycentric weights are computed once so interpolating many sets of y values only costs O(n<sup>2</sup>) each
+#[derive(Clone, Debug)]
+pub struct Interpolator<GF: GaloisField> {
+    xs: Vec<GF>,
+    weights: Vec<GF>,
+    // The product of (x - x_j) over all x coordinates
+    master: Polynomial<GF>,
+}
+
+impl<GF: GaloisField> Interpolator<GF> {
+    /// Precomputes the barycentric weights. Returns None if the x coordinates are not distinct
+    pub fn new(xs: &[GF]) -> Option<Self> {
+        let mut weights = Vec::with_capacity(xs.len());
+        for (j, &xj) in xs.iter().enumerate() {
+            let mut denom = GF::ONE;
+            for (k, &xk) in xs.iter().enumerate() {
+                if k != j {
+                    denom *= xj - xk;
+                }
+            }
+            if denom == GF::ZERO {
+                return None;
+            }
+            weights.push(denom.inverse());
+        }
+
+        let master = xs.iter().fold(Polynomial::one(), |acc, &x| {
+            &acc * &Polynomial::new(vec![-x, GF::ONE])
+        });
+
+        Some(Self {
+            xs: xs.to_vec(),
+            weights,
+            master,
+        })
+    }
+
+    /// The x coordinates
+    pub fn xs(&self) -> &[GF] {
+        &self.xs
+    }
+
+    /// Returns the polynomial of lowest degree taking value ys\[j\] at xs\[j\]. Panics if the lengths differ
+    pub fn interpolate(&self, ys: &[GF]) -> Polynomial<GF> {
+        if ys.len() != self.xs.len() {
+            panic!("Number of y values does not match the number of x coordinates");
+        }
+
+        let mut coeffs = vec![GF::ZERO; self.xs.len()];
+        for ((&xj, &wj), &yj) in self.xs.iter().zip(self.weights.iter()).zip(ys.iter()) {
+            let scale = wj * yj;
+            if scale == GF::ZERO {
+                continue;
+            }
+            let basis = self.master.div_linear(xj);
+            for (c, &b) in coeffs.iter_mut().zip(basis.coeffs.iter()) {
+                *c += scale * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+
+    /// Evaluates the interpolating polynomial of ys at x without constructing it. Panics if the lengths differ
+    pub fn eval(&self, ys: &[GF], x: GF) -> GF {
+        if ys.len() != self.xs.len() {
+            panic!("Number of y values does not match the number of x coordinates");
+        }
+
+        if let Some(j) = self.xs.iter().position(|&xj| xj == x) {
+            return ys[j];
+        }
+
+        let sum = self
+            .xs
+            .iter()
+            .zip(self.weights.iter())
+            .zip(ys.iter())
+            .fold(GF::ZERO, |acc, ((&xj, &wj), &yj)| acc + wj * yj / (x - xj));
+        self.master.eval(x) * sum
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<GF: GaloisField> Polynomial<GF> {
+    /// Generates a uniformly random polynomial of degree at most degree, i.e. every coefficient up to x<sup>degree</sup>
+    /// is uniform over the field. The result has lower degree when the top coefficients happen to be zero
+    pub fn random<R: rand::Rng + ?Sized>(degree: usize, rng: &mut R) -> Self {
+        Self::new((0..=degree).map(|_| Self::random_coeff(rng)).collect())
+    }
+
+    /// Generates a uniformly random monic polynomial of exactly the given degree
+    pub fn random_monic<R: rand::Rng + ?Sized>(degree: usize, rng: &mut R) -> Self {
+        let mut coeffs: Vec<GF> = (0..degree).map(|_| Self::random_coeff(rng)).collect();
+        coeffs.push(GF::ONE);
+        Self::new(coeffs)
+    }
+
+    fn random_coeff<R: rand::Rng + ?Sized>(rng: &mut R) -> GF {
+        GF::from_u128(rng.gen::<u128>() & (GF::NUM_ELEM - 1))
+    }
+}
+
+impl<GF: GaloisField> From<Vec<GF>> for Polynomial<GF> {
+    fn from(coeffs: Vec<GF>) -> Self {
+        Self::new(coeffs)
+    }
+}
+
+// Clearing the coefficients leaves the zero polynomial, so the trimmed form is kept
+#[cfg(feature = "zeroize")]
+impl<GF: GaloisField + zeroize::Zeroize> zeroize::Zeroize for Polynomial<GF> {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.coeffs);
+    }
+}
+
+impl<GF: GaloisField> Add<&Polynomial<GF>> for &Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn add(self, other: &Polynomial<GF>) -> Polynomial<GF> {
+        let (long, short) = if self.coeffs.len() >= other.coeffs.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut coeffs = long.coeffs.clone();
+        for (c, &s) in coeffs.iter_mut().zip(short.coeffs.iter()) {
+            *c += s;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<GF: GaloisField> Sub<&Polynomial<GF>> for &Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn sub(self, other: &Polynomial<GF>) -> Polynomial<GF> {
+        let mut coeffs = self.coeffs.clone();
+        if coeffs.len() < other.coeffs.len() {
+            coeffs.resize(other.coeffs.len(), GF::ZERO);
+        }
+        for (c, &o) in coeffs.iter_mut().zip(other.coeffs.iter()) {
+            *c -= o;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<GF: GaloisField> Mul<&Polynomial<GF>> for &Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn mul(self, other: &Polynomial<GF>) -> Polynomial<GF> {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero();
+        }
+
+        // Karatsuba above a cutoff, schoolbook below it
+        let mut coeffs = vec![GF::ZERO; self.coeffs.len() + other.coeffs.len() - 1];
+        karatsuba::mul_acc(&self.coeffs, &other.coeffs, &mut coeffs);
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<GF: GaloisField> Mul<GF> for &Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn mul(self, scalar: GF) -> Polynomial<GF> {
+        Polynomial::new(self.coeffs.iter().map(|&c| c * scalar).collect())
+    }
+}
+
+impl<GF: GaloisField> Mul<GF> for Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn mul(self, scalar: GF) -> Polynomial<GF> {
+        self.mul_scalar(scalar)
+    }
+}
+
+/// Multiplication by x<sup>k</sup>
+impl<GF: GaloisField> Shl<usize> for Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn shl(mut self, k: usize) -> Polynomial<GF> {
+        self <<= k;
+        self
+    }
+}
+
+impl<GF: GaloisField> Shl<usize> for &Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn shl(self, k: usize) -> Polynomial<GF> {
+        if self.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut coeffs = Vec::with_capacity(self.coeffs.len() + k);
+        coeffs.resize(k, GF::ZERO);
+        coeffs.extend_from_slice(&self.coeffs);
+        Polynomial { coeffs }
+    }
+}
+
+impl<GF: GaloisField> ShlAssign<usize> for Polynomial<GF> {
+    fn shl_assign(&mut self, k: usize) {
+        if !self.is_zero() {
+            self.coeffs.splice(0..0, core::iter::repeat_n(GF::ZERO, k));
+        }
+    }
+}
+
+/// Division by x<sup>k</sup>, discarding the terms of degree below k
+impl<GF: GaloisField> Shr<usize> for Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn shr(mut self, k: usize) -> Polynomial<GF> {
+        self >>= k;
+        self
+    }
+}
+
+impl<GF: GaloisField> Shr<usize> for &Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn shr(self, k: usize) -> Polynomial<GF> {
+        Polynomial {
+            coeffs: self.coeffs.iter().skip(k).copied().collect(),
+        }
+    }
+}
+
+impl<GF: GaloisField> ShrAssign<usize> for Polynomial<GF> {
+    fn shr_assign(&mut self, k: usize) {
+        self.coeffs.drain(..k.min(self.coeffs.len()));
+    }
+}
+
+impl<GF: GaloisField> Neg for Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn neg(self) -> Polynomial<GF> {
+        self
+    }
+}
+
+impl<GF: GaloisField> Neg for &Polynomial<GF> {
+    type Output = Polynomial<GF>;
+
+    fn neg(self) -> Polynomial<GF> {
+        self.clone()
+    }
+}
+
+macro_rules! owned_operator_impl {
+    ($($trait_name:ident: $trait_fn:ident: $assign_name:ident: $assign_fn:ident: $op:tt,)*) => {
+    $(
+        impl<GF: GaloisField> $trait_name<Polynomial<GF>> for Polynomial<GF> {
+            type Output = Polynomial<GF>;
+
+            fn $trait_fn(self, other: Polynomial<GF>) -> Polynomial<GF> {
+                &self $op &other
+            }
+        }
+
+        impl<'a, GF: GaloisField> $trait_name<&'a Polynomial<GF>> for Polynomial<GF> {
+            type Output = Polynomial<GF>;
+
+            fn $trait_fn(self, other: &'a Polynomial<GF>) -> Polynomial<GF> {
+                &self $op other
+            }
+        }
+
+        impl<'a, GF: GaloisField> $trait_name<Polynomial<GF>> for &'a Polynomial<GF> {
+            type Output = Polynomial<GF>;
+
+            fn $trait_fn(self, other: Polynomial<GF>) -> Polynomial<GF> {
+                self $op &other
+            }
+        }
+
+        impl<GF: GaloisField> $assign_name<Polynomial<GF>> for Polynomial<GF> {
+            fn $assign_fn(&mut self, other: Polynomial<GF>) {
+                *self = &*self $op &other;
+            }
+        }
+
+        impl<'a, GF: GaloisField> $assign_name<&'a Polynomial<GF>> for Polynomial<GF> {
+            fn $assign_fn(&mut self, other: &'a Polynomial<GF>) {
+                *self = &*self $op other;
+            }
+        }
+    )*
+    }
+}
+
+owned_operator_impl! {
+    Add: add: AddAssign: add_assign: +,
+    Sub: sub: SubAssign: sub_assign: -,
+    Mul: mul: MulAssign: mul_assign: *,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_poly(degree: usize) -> Polynomial<GF> {
+        let mut coeffs: Vec<GF> = (0..degree)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+            .collect();
+        coeffs.push(GF::new(rand::thread_rng().gen_range(1..=255)));
+        Polynomial::new(coeffs)
+    }
+
+    #[test]
+    fn normalization() {
+        let p = Polynomial::new(vec![GF::ONE, GF::new(5), GF::ZERO, GF::ZERO]);
+        assert_eq!(p.degree(), 1);
+        assert_eq!(p.coeffs(), &[GF::ONE, GF::new(5)]);
+        assert_eq!(p.leading_coeff(), GF::new(5));
+        assert_eq!(p.coeff(7), GF::ZERO);
+
+        let z = Polynomial::new(vec![GF::ZERO; 3]);
+        assert!(z.is_zero());
+        assert_eq!(z, Polynomial::zero());
+        assert_eq!(z.degree(), -1);
+        assert_eq!(z.leading_coeff(), GF::ZERO);
+
+        assert_eq!(Polynomial::monomial(GF::new(3), 4).degree(), 4);
+        assert!(Polynomial::monomial(GF::ZERO, 4).is_zero());
+    }
+
+    #[test]
+    fn from_gf2() {
+        let p = Polynomial::<GF>::from_gf2(0b1011);
+        assert_eq!(p.coeffs(), &[GF::ONE, GF::ONE, GF::ZERO, GF::ONE]);
+        assert!(Polynomial::<GF>::from_gf2(0).is_zero());
+        assert_eq!(Polynomial::<GF>::from_gf2(1 << 127).degree(), 127);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize() {
+        use zeroize::Zeroize;
+
+        let mut p = Polynomial::new(vec![GF::new(3), GF::ONE, GF::new(7)]);
+        p.zeroize();
+        assert!(p.is_zero());
+        let mut a = GF::new(0x53);
+        a.zeroize();
+        assert_eq!(a, GF::ZERO);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random() {
+        let mut rng = rand::thread_rng();
+        for degree in 0..20 {
+            let p = Polynomial::<GF>::random(degree, &mut rng);
+            assert!(p.degree() <= degree as isize);
+            assert!(p.coeffs().iter().all(|c| c.validate()));
+
+            let m = Polynomial::<GF>::random_monic(degree, &mut rng);
+            assert_eq!(m.degree(), degree as isize);
+            assert_eq!(m.leading_coeff(), GF::ONE);
+        }
+
+        // Every value shows up as a coefficient
+        let p = Polynomial::<GF>::random(10000, &mut rng);
+        let mut seen = [false; 256];
+        for c in p.coeffs() {
+            seen[c.value as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        type GF2 = gf2::GFu64<0x2_0000_2001>;
+        let p = Polynomial::<GF2>::random(100, &mut rng);
+        assert!(p.coeffs().iter().all(|c| c.validate()));
+    }
+
+    #[test]
+    fn shifts() {
+        let p = random_poly(10);
+        let x3 = Polynomial::monomial(GF::ONE, 3);
+        assert_eq!(&p << 3, &p * &x3);
+        assert_eq!(p.clone() << 3, &p * &x3);
+        assert_eq!(&p << 0, p);
+        assert_eq!((&p << 3) >> 3, p);
+        assert_eq!(&p >> 3, p.div_rem(&x3).0);
+        assert_eq!(p.clone() >> 3, p.div_rem(&x3).0);
+        assert!((&p >> 11).is_zero());
+        assert!((Polynomial::<GF>::zero() << 4).is_zero());
+
+        let mut q = p.clone();
+        q <<= 5;
+        q >>= 2;
+        assert_eq!(q, &p << 3);
+    }
+
+    #[test]
+    fn scalar_monic_truncate() {
+        let p = random_poly(10);
+        let c = GF::new(0x53);
+        assert_eq!(
+            p.clone().mul_scalar(c),
+            Polynomial::new(p.coeffs().iter().map(|&a| a * c).collect())
+        );
+        assert!(p.clone().mul_scalar(GF::ZERO).is_zero());
+
+        let m = p.clone().monic();
+        assert_eq!(m.leading_coeff(), GF::ONE);
+        assert_eq!(m.clone().mul_scalar(p.leading_coeff()), p);
+        assert!(Polynomial::<GF>::zero().monic().is_zero());
+
+        let mut t = p.clone();
+        t.truncate(4);
+        assert_eq!(t, p.rem(&Polynomial::monomial(GF::ONE, 5)));
+        t.truncate(20);
+        assert_eq!(t, p.rem(&Polynomial::monomial(GF::ONE, 5)));
+        t.truncate(0);
+        assert_eq!(t, Polynomial::new(vec![p.coeff(0)]));
+    }
+
+    #[test]
+    fn eval() {
+        // x^2 + 3x + 2 = (x + 1)(x + 2)
+        let p = Polynomial::new(vec![GF::new(2), GF::new(3), GF::ONE]);
+        assert_eq!(p.eval(GF::ONE), GF::ZERO);
+        assert_eq!(p.eval(GF::new(2)), GF::ZERO);
+        assert_eq!(p.eval(GF::ZERO), GF::new(2));
+        assert_eq!(Polynomial::<GF>::zero().eval(GF::new(9)), GF::ZERO);
+    }
+
+    #[test]
+    fn eval_many() {
+        let p = random_poly(10);
+        let points: Vec<GF> = (0..=255).map(GF::new).collect();
+        let values = p.eval_many(&points);
+        for (&x, &y) in points.iter().zip(values.iter()) {
+            assert_eq!(p.eval(x), y);
+        }
+        assert!(p.eval_many(&[]).is_empty());
+        // Partial blocks of points
+        for len in 1..20 {
+            assert_eq!(p.eval_many(&points[..len]), values[..len]);
+        }
+        assert_eq!(
+            Polynomial::zero().eval_many(&points[..3]),
+            vec![GF::ZERO; 3]
+        );
+
+        let first = GF::new(7);
+        let step = GF::new(2);
+        let values = p.eval_geometric(first, step, 300);
+        let mut x = first;
+        for &y in values.iter() {
+            assert_eq!(p.eval(x), y);
+            x *= step;
+        }
+        assert_eq!(
+            Polynomial::<GF>::zero().eval_geometric(first, step, 3),
+            vec![GF::ZERO; 3]
+        );
+    }
+
+    #[test]
+    fn interpolate() {
+        for n in 0..20 {
+            let p = if n == 0 {
+                Polynomial::zero()
+            } else {
+                random_poly(n - 1)
+            };
+            let xs: Vec<GF> = (0..n).map(|i| GF::new(3 * i as u8 + 1)).collect();
+            let points: Vec<(GF, GF)> = xs.iter().map(|&x| (x, p.eval(x))).collect();
+            assert_eq!(Polynomial::interpolate(&points).unwrap(), p);
+
+            let interp = Interpolator::new(&xs).unwrap();
+            let ys: Vec<GF> = points.iter().map(|&(_, y)| y).collect();
+            assert_eq!(interp.interpolate(&ys), p);
+            for i in 0..=255 {
+                assert_eq!(interp.eval(&ys, GF::new(i)), p.eval(GF::new(i)));
+            }
+        }
+    }
+
+    #[test]
+    fn interpolate_repeated_x() {
+        let points = [
+            (GF::new(1), GF::new(2)),
+            (GF::new(5), GF::new(3)),
+            (GF::new(1), GF::new(4)),
+        ];
+        assert_eq!(Polynomial::interpolate(&points), None);
+    }
+
+    #[test]
+    fn add_sub() {
+        for _ in 0..100 {
+            let a = random_poly(rand::thread_rng().gen_range(0..10));
+            let b = random_poly(rand::thread_rng().gen_range(0..10));
+            let x = GF::new(rand::thread_rng().gen_range(0..=255));
+
+            let sum = &a + &b;
+            assert_eq!(sum.eval(x), a.eval(x) + b.eval(x));
+            assert_eq!(&sum - &b, a);
+            assert_eq!(&a - &a, Polynomial::zero());
+            assert_eq!(-a.clone(), a);
+
+            let mut c = a.clone();
+            c += &b;
+            c -= b;
+            assert_eq!(c, a);
+        }
+    }
+
+    #[test]
+    fn mul() {
+        for _ in 0..100 {
+            let a = random_poly(rand::thread_rng().gen_range(0..10));
+            let b = random_poly(rand::thread_rng().gen_range(0..10));
+            let x = GF::new(rand::thread_rng().gen_range(0..=255));
+
+            let prod = &a * &b;
+            assert_eq!(prod.degree(), a.degree() + b.degree());
+            assert_eq!(prod.eval(x), a.eval(x) * b.eval(x));
+            assert_eq!(&a * &Polynomial::one(), a);
+            assert!((&a * &Polynomial::zero()).is_zero());
+
+            let s = GF::new(rand::thread_rng().gen_range(1..=255));
+            assert_eq!(&a * s, &a * &Polynomial::new(vec![s]));
+            assert!((a.clone() * GF::ZERO).is_zero());
+
+            let mut c = a.clone();
+            c *= b.clone();
+            assert_eq!(c, prod);
+        }
+    }
+
+    #[test]
+    fn div_rem() {
+        for _ in 0..100 {
+            let a = random_poly(rand::thread_rng().gen_range(0..15));
+            let b = random_poly(rand::thread_rng().gen_range(0..8));
+
+            let (q, r) = a.div_rem(&b);
+            assert!(r.degree() < b.degree());
+            assert_eq!(&(&q * &b) + &r, a);
+            assert_eq!(a.rem(&b), r);
+
+            let (q, r) = (&a * &b).div_rem(&b);
+            assert_eq!(q, a);
+            assert!(r.is_zero());
+        }
+
+        let (q, r) = Polynomial::<GF>::zero().div_rem(&Polynomial::one());
+        assert!(q.is_zero() && r.is_zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero() {
+        let _ = Polynomial::<GF>::one().div_rem(&Polynomial::zero());
+    }
+
+    #[test]
+    fn gcd() {
+        for _ in 0..100 {
+            let a = random_poly(rand::thread_rng().gen_range(0..6));
+            let b = random_poly(rand::thread_rng().gen_range(0..6));
+            let c = random_poly(rand::thread_rng().gen_range(1..6));
+
+            let g = (&a * &c).gcd(&(&b * &c));
+            assert_eq!(g.leading_coeff(), GF::ONE);
+            assert!((&a * &c).rem(&g).is_zero());
+            assert!((&b * &c).rem(&g).is_zero());
+            assert!(g.rem(&c).is_zero());
+        }
+
+        // (x + 1)(x + 2) and (x + 1)(x + 3) have gcd x + 1
+        let x1 = Polynomial::new(vec![GF::ONE, GF::ONE]);
+        let x2 = Polynomial::new(vec![GF::new(2), GF::ONE]);
+        let x3 = Polynomial::new(vec![GF::new(3), GF::ONE]);
+        assert_eq!((&x1 * &x2).gcd(&(&x1 * &x3)), x1);
+        assert_eq!(x2.gcd(&x3), Polynomial::one());
+        assert_eq!((&x2 * GF::new(7)).gcd(&Polynomial::zero()), x2);
+        assert!(Polynomial::<GF>::zero().gcd(&Polynomial::zero()).is_zero());
+    }
+
+    #[test]
+    fn extended_gcd() {
+        for _ in 0..100 {
+            let a = random_poly(rand::thread_rng().gen_range(0..10));
+            let b = random_poly(rand::thread_rng().gen_range(0..10));
+            let c = random_poly(rand::thread_rng().gen_range(0..4));
+            let a = &a * &c;
+            let b = &b * &c;
+
+            let (g, s, t) = Polynomial::extended_gcd(&a, &b);
+            assert_eq!(g, a.gcd(&b));
+            assert_eq!(&(&s * &a) + &(&t * &b), g);
+        }
+
+        let (g, s, t) = Polynomial::<GF>::extended_gcd(&Polynomial::zero(), &Polynomial::zero());
+        assert!(g.is_zero());
+        assert_eq!(s, Polynomial::one());
+        assert!(t.is_zero());
+    }
+
+    #[test]
+    fn partial_extended_gcd() {
+        for _ in 0..100 {
+            let a = random_poly(12);
+            let b = random_poly(rand::thread_rng().gen_range(0..12));
+            let stop = rand::thread_rng().gen_range(0..12);
+
+            let (r, s, t) = Polynomial::partial_extended_gcd(&a, &b, stop);
+            assert_eq!(&(&s * &a) + &(&t * &b), r);
+            assert!(r.degree() < stop);
+        }
+    }
+
+    #[test]
+    fn inverse_mod() {
+        // A quadratic with no roots is irreducible
+        let f = (1..=255)
+            .map(|c| Polynomial::new(vec![GF::new(c), GF::ONE, GF::ONE]))
+            .find(|f| (0..=255).all(|i| f.eval(GF::new(i)) != GF::ZERO))
+            .unwrap();
+        for _ in 0..100 {
+            let a = random_poly(rand::thread_rng().gen_range(0..2));
+            let inv = a.inverse_mod(&f).unwrap();
+            assert_eq!((&a * &inv).rem(&f), Polynomial::one());
+        }
+
+        let x1 = Polynomial::new(vec![GF::ONE, GF::ONE]);
+        assert_eq!(x1.inverse_mod(&(&x1 * &x1)), None);
+    }
+
+    #[test]
+    fn mul_large() {
+        let a = random_poly(300);
+        let b = random_poly(200);
+        let prod = &a * &b;
+        assert_eq!(prod.degree(), 500);
+        for _ in 0..20 {
+            let x = GF::new(rand::thread_rng().gen_range(0..=255));
+            assert_eq!(prod.eval(x), a.eval(x) * b.eval(x));
+        }
+    }
+
+    #[test]
+    fn computational_field() {
+        type GF2 = gf2::GFu64<0x2_0000_2001>;
+        let a = Polynomial::new(vec![GF2::new(0x1_2345_6789), GF2::ONE]);
+        let b = Polynomial::new(vec![GF2::new(0x1_2345_6789), GF2::ONE]);
+        let prod = &a * &b;
+        assert_eq!(prod.eval(GF2::new(0x1_2345_6789)), GF2::ZERO);
+        assert_eq!(prod.coeff(1), GF2::ZERO);
+    }
+}
@@ -0,0 +1,255 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::Polynomial;
+use crate::GaloisField;
+
+/// How coefficients are written when formatting a polynomial
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CoeffStyle {
+    /// Powers of the field generator a = x mod POLY, e.g. `a^5`. Coefficients that are not a power of a,
+    /// and every coefficient of fields with M > 16, fall back to `Value`
+    Power,
+    /// The element's value in hex, e.g. `0x20`
+    Value,
+}
+
+/// Formatter returned by [`Polynomial::display`]
+pub struct PolyDisplay<'a, GF: GaloisField> {
+    poly: &'a Polynomial<GF>,
+    var: &'a str,
+    style: CoeffStyle,
+}
+
+impl<'a, GF: GaloisField> PolyDisplay<'a, GF> {
+    /// Sets the name of the variable. Defaults to `x`
+    pub fn var(mut self, var: &'a str) -> Self {
+        self.var = var;
+        self
+    }
+
+    /// Sets how coefficients are written. Defaults to `CoeffStyle::Power`
+    pub fn coeff_style(mut self, style: CoeffStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn fmt_coeff(&self, c: GF) -> String {
+        if c == GF::ONE {
+            return String::from("1");
+        }
+        match (self.style, log_a(c)) {
+            (CoeffStyle::Power, Some(1)) => String::from("a"),
+            (CoeffStyle::Power, Some(k)) => format!("a^{}", k),
+            _ => format!("{}", c),
+        }
+    }
+}
+
+impl<GF: GaloisField> fmt::Display for PolyDisplay<'_, GF> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.poly.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut first = true;
+        for (i, &c) in self.poly.coeffs.iter().enumerate().rev() {
+            if c == GF::ZERO {
+                continue;
+            }
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+
+            let monomial = match i {
+                0 => String::new(),
+                1 => String::from(self.var),
+                _ => format!("{}^{}", self.var, i),
+            };
+            match (i, c == GF::ONE) {
+                (0, _) => write!(f, "{}", self.fmt_coeff(c))?,
+                (_, true) => write!(f, "{}", monomial)?,
+                (_, false) => write!(f, "{}*{}", self.fmt_coeff(c), monomial)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Discrete log base a by exhaustive search, only attempted for small fields
+fn log_a<GF: GaloisField>(c: GF) -> Option<u128> {
+    if GF::M > 16 {
+        return None;
+    }
+    let mut power = GF::ONE;
+    for k in 0..GF::NUM_ELEM {
+        if power == c {
+            return Some(k);
+        }
+        power = power.mul_x();
+        if power == GF::ONE {
+            break;
+        }
+    }
+    None
+}
+
+/// The error returned when parsing a polynomial fails
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParsePolynomialError {
+    term: String,
+}
+
+impl fmt::Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid polynomial term '{}'", self.term)
+    }
+}
+
+impl std::error::Error for ParsePolynomialError {}
+
+impl<GF: GaloisField> Polynomial<GF> {
+    /// Returns a formatter with a configurable variable name and coefficient style.
+    /// The `Display` implementation is `self.display()`, which writes e.g. `x^3 + a^5*x + 1`
+    pub fn display(&self) -> PolyDisplay<'_, GF> {
+        PolyDisplay {
+            poly: self,
+            var: "x",
+            style: CoeffStyle::Power,
+        }
+    }
+
+    /// Parses a sum of terms in the variable var, the inverse of `display`. Terms are separated by `+`
+    /// (or `-`, which is the same in characteristic 2) and are products of factors separated by `*`.
+    /// A factor is the variable, the field generator `a`, either raised to an optional `^n`, or a value
+    /// in decimal or `0x` hex. Repeated powers are added together and whitespace is ignored
+    pub fn parse(s: &str, var: &str) -> Result<Self, ParsePolynomialError> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut coeffs: Vec<GF> = Vec::new();
+
+        for term in s.split(['+', '-']) {
+            let err = || ParsePolynomialError {
+                term: String::from(term),
+            };
+            if term.is_empty() {
+                return Err(err());
+            }
+
+            let mut coeff = GF::ONE;
+            let mut degree = 0usize;
+            for factor in term.split('*') {
+                let (base, exp) = match factor.split_once('^') {
+                    Some((base, exp)) => (base, exp.parse::<usize>().map_err(|_| err())?),
+                    None => (factor, 1),
+                };
+                if base == var {
+                    degree += exp;
+                } else if base == "a" {
+                    coeff *= GF::ONE.mul_x().pow(exp as u128);
+                } else {
+                    let value = match base.strip_prefix("0x").or_else(|| base.strip_prefix("0X")) {
+                        Some(hex) => u128::from_str_radix(hex, 16),
+                        None => base.parse::<u128>(),
+                    }
+                    .map_err(|_| err())?;
+                    let element = GF::from_u128(value);
+                    if element.to_u128() != value || !element.validate() {
+                        return Err(err());
+                    }
+                    coeff *= element.pow(exp as u128);
+                }
+            }
+
+            if coeffs.len() <= degree {
+                coeffs.resize(degree + 1, GF::ZERO);
+            }
+            coeffs[degree] += coeff;
+        }
+        Ok(Self::new(coeffs))
+    }
+}
+
+impl<GF: GaloisField> fmt::Display for Polynomial<GF> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display())
+    }
+}
+
+impl<GF: GaloisField> FromStr for Polynomial<GF> {
+    type Err = ParsePolynomialError;
+
+    /// Parses a polynomial in the variable `x`, see `Polynomial::parse`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, "x")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    #[test]
+    fn display() {
+        // a^5 = 0x20, a^8 = 0x1d
+        let p = Polynomial::new(vec![GF::ONE, GF::new(0x20), GF::ZERO, GF::ONE]);
+        assert_eq!(p.to_string(), "x^3 + a^5*x + 1");
+        assert_eq!(p.display().var("z").to_string(), "z^3 + a^5*z + 1");
+        assert_eq!(
+            p.display().coeff_style(CoeffStyle::Value).to_string(),
+            "x^3 + 0x20*x + 1"
+        );
+
+        let p = Polynomial::new(vec![GF::new(0x1d), GF::new(2), GF::new(3)]);
+        assert_eq!(p.to_string(), "a^25*x^2 + a*x + a^8");
+        assert_eq!(Polynomial::<GF>::zero().to_string(), "0");
+
+        // Large fields write values
+        type GF2 = gf2::GFu32<0x1_0000_008D>;
+        let p = Polynomial::new(vec![GF2::new(0x20), GF2::ONE]);
+        assert_eq!(p.to_string(), "x + 0x00000020");
+    }
+
+    #[test]
+    fn parse() {
+        let p = Polynomial::new(vec![GF::ONE, GF::new(0x20), GF::ZERO, GF::ONE]);
+        assert_eq!("x^3 + a^5*x + 1".parse(), Ok(p.clone()));
+        assert_eq!("x^3+0x20*x+1".parse(), Ok(p.clone()));
+        assert_eq!(" 1 - 32 * x + x ^ 3 ".parse(), Ok(p.clone()));
+        assert_eq!(Polynomial::parse("z^3 + a^4*a*z + 1", "z"), Ok(p));
+        assert_eq!("x + x".parse(), Ok(Polynomial::<GF>::zero()));
+        assert_eq!("0".parse(), Ok(Polynomial::<GF>::zero()));
+
+        assert!("x^3 +".parse::<Polynomial<GF>>().is_err());
+        assert!("0x100*x".parse::<Polynomial<GF>>().is_err());
+        assert!("y^2".parse::<Polynomial<GF>>().is_err());
+        assert!("x^-1".parse::<Polynomial<GF>>().is_err());
+        assert_eq!(
+            "x^3 + b".parse::<Polynomial<GF>>().unwrap_err().to_string(),
+            "invalid polynomial term 'b'"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for _ in 0..100 {
+            let p = Polynomial::new(
+                (0..20)
+                    .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+                    .collect(),
+            );
+            assert_eq!(p.to_string().parse(), Ok(p.clone()));
+            assert_eq!(
+                p.display()
+                    .coeff_style(CoeffStyle::Value)
+                    .to_string()
+                    .parse(),
+                Ok(p.clone())
+            );
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use super::Polynomial;
+use crate::GaloisField;
+
+impl<GF: GaloisField> Polynomial<GF> {
+    /// Returns the formal derivative
+    pub fn derivative(&self) -> Self {
+        // In characteristic 2 the even powers vanish
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| if (i & 0x1) > 0 { c } else { GF::ZERO })
+            .collect();
+        Self::new(coeffs)
+    }
+
+    /// Factors the polynomial into monic irreducible factors, returned with their multiplicities and sorted by degree.
+    /// The leading coefficient is not included. Panics for the zero polynomial
+    pub fn factor(&self) -> Vec<(Self, usize)> {
+        if self.is_zero() {
+            panic!("Cannot factor the zero polynomial");
+        }
+
+        let mut factors = Vec::new();
+        for (sqf, multiplicity) in self.squarefree_decomposition() {
+            for (ddf, degree) in sqf.distinct_degree_factors() {
+                for irreducible in ddf.equal_degree_factors(degree) {
+                    factors.push((irreducible, multiplicity));
+                }
+            }
+        }
+        factors.sort_by_key(|(f, _)| f.degree());
+        factors
+    }
+
+    /// Square-free decomposition. Returns (a<sub>i</sub>, i) sorted by i, where self = lc * ∏ a<sub>i</sub><sup>i</sup>
+    /// and the a<sub>i</sub> are monic, square free and pairwise coprime. Constants give an empty decomposition.
+    ///
+    /// In characteristic 2 the derivative of a square vanishes, so those parts are found by taking square roots
+    /// of the coefficients (a<sup>2<sup>M-1</sup></sup>) and recursing. Panics for the zero polynomial
+    pub fn squarefree_decomposition(&self) -> Vec<(Self, usize)> {
+        if self.is_zero() {
+            panic!("Cannot decompose the zero polynomial");
+        }
+
+        let mut f = self.clone();
+        f.make_monic();
+
+        let mut parts: Vec<(Self, usize)> = Vec::new();
+        if f.degree() < 1 {
+            return parts;
+        }
+
+        let derivative = f.derivative();
+        if derivative.is_zero() {
+            // f is a perfect square
+            for (part, i) in f.sqrt_coeffs().squarefree_decomposition() {
+                parts.push((part, 2 * i));
+            }
+            return parts;
+        }
+
+        let mut c = f.gcd(&derivative);
+        let mut w = f.div_rem(&c).0;
+        let mut i = 1;
+        while w.degree() > 0 {
+            let y = w.gcd(&c);
+            let fac = w.div_rem(&y).0;
+            if fac.degree() > 0 {
+                parts.push((fac, i));
+            }
+            i += 1;
+            w = y;
+            c = c.div_rem(&w).0;
+        }
+
+        // What remains has every multiplicity divisible by 2
+        if c.degree() > 0 {
+            for (part, i) in c.sqrt_coeffs().squarefree_decomposition() {
+                match parts.iter_mut().find(|(_, j)| *j == 2 * i) {
+                    Some(existing) => existing.0 = &existing.0 * &part,
+                    None => parts.push((part, 2 * i)),
+                }
+            }
+        }
+
+        parts.sort_by_key(|&(_, i)| i);
+        parts
+    }
+
+    // For a polynomial in x^2 returns its square root
+    fn sqrt_coeffs(&self) -> Self {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .step_by(2)
+            .map(|&c| c.pow(GF::NUM_ELEM / 2))
+            .collect();
+        Self::new(coeffs)
+    }
+
+    // For a monic square free polynomial returns (g_d, d) where g_d is the product of all its irreducible factors of degree d
+    fn distinct_degree_factors(&self) -> Vec<(Self, usize)> {
+        let mut factors = Vec::new();
+        let mut f = self.clone();
+        let x = Self::monomial(GF::ONE, 1);
+        let mut h = x.rem(&f);
+        let mut d = 1;
+
+        while f.degree() >= 2 * d as isize {
+            h = h.frobenius_mod(&f);
+            let g = f.gcd(&(&h - &x));
+            if g.degree() > 0 {
+                f = f.div_rem(&g).0;
+                h = h.rem(&f);
+                factors.push((g, d));
+            }
+            d += 1;
+        }
+        if f.degree() > 0 {
+            let degree = f.degree() as usize;
+            factors.push((f, degree));
+        }
+        factors
+    }
+
+    // Splits a monic square free polynomial whose irreducible factors all have the given degree (Cantor-Zassenhaus)
+    fn equal_degree_factors(&self, degree: usize) -> Vec<Self> {
+        if self.degree() as usize == degree {
+            return vec![self.clone()];
+        }
+
+        // Candidate splitting polynomials come from a fixed xorshift sequence so factoring is deterministic
+        let mut state: u128 = 0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C834;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            GF::from_u128(state & (GF::NUM_ELEM - 1))
+        };
+
+        loop {
+            let a = Self::new((0..self.degree()).map(|_| next()).collect());
+            if a.degree() < 1 {
+                continue;
+            }
+
+            // The trace a + a^2 + ... + a^(2^(M*degree - 1)) mod self is 0 or 1 modulo each factor
+            let mut trace = a.clone();
+            let mut term = a;
+            for _ in 1..(GF::M as usize * degree) {
+                term = (&term * &term).rem(self);
+                trace += &term;
+            }
+
+            let g = self.gcd(&trace);
+            if g.degree() > 0 && g.degree() < self.degree() {
+                let h = self.div_rem(&g).0;
+                let mut factors = g.equal_degree_factors(degree);
+                factors.extend(h.equal_degree_factors(degree));
+                return factors;
+            }
+        }
+    }
+
+    // Returns self^(2^M) mod modulus
+    fn frobenius_mod(&self, modulus: &Self) -> Self {
+        let mut h = self.clone();
+        for _ in 0..GF::M {
+            h = (&h * &h).rem(modulus);
+        }
+        h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2_lut, gf2_nibble};
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x13>;
+
+    fn random_monic(degree: usize) -> Polynomial<GF> {
+        let mut coeffs: Vec<GF> = (0..degree)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..16)))
+            .collect();
+        coeffs.push(GF::ONE);
+        Polynomial::new(coeffs)
+    }
+
+    fn product(factors: &[(Polynomial<GF>, usize)]) -> Polynomial<GF> {
+        let mut p = Polynomial::one();
+        for (f, e) in factors.iter() {
+            for _ in 0..*e {
+                p *= f;
+            }
+        }
+        p
+    }
+
+    fn is_irreducible(f: &Polynomial<GF>) -> bool {
+        // Brute force: no monic factor of degree 1..=deg/2
+        let d = f.degree() as usize;
+        for deg in 1..=(d / 2) {
+            let count = 16usize.pow(deg as u32);
+            for idx in 0..count {
+                let mut coeffs: Vec<GF> = (0..deg)
+                    .map(|k| GF::new(((idx >> (4 * k)) & 0xF) as u8))
+                    .collect();
+                coeffs.push(GF::ONE);
+                if f.rem(&Polynomial::new(coeffs)).is_zero() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn derivative() {
+        // (x^3 + 3x^2 + 5x + 7)' = x^2 + 5 in characteristic 2
+        let p = Polynomial::new(vec![GF::new(7), GF::new(5), GF::new(3), GF::ONE]);
+        assert_eq!(
+            p.derivative(),
+            Polynomial::new(vec![GF::new(5), GF::ZERO, GF::ONE])
+        );
+        assert!(Polynomial::<GF>::one().derivative().is_zero());
+    }
+
+    #[test]
+    fn factor_random() {
+        for _ in 0..50 {
+            let p = random_monic(rand::thread_rng().gen_range(1..9));
+            let factors = p.factor();
+            assert_eq!(product(&factors), p);
+            for (f, _) in factors.iter() {
+                assert_eq!(f.leading_coeff(), GF::ONE);
+                assert!(is_irreducible(f));
+            }
+        }
+    }
+
+    #[test]
+    fn factor_with_multiplicities() {
+        let a = Polynomial::new(vec![GF::new(3), GF::ONE]);
+        // x^2 + x + c has no roots in GF(16) for some c, making it irreducible
+        let b = (1..16)
+            .map(|c| Polynomial::new(vec![GF::new(c), GF::ONE, GF::ONE]))
+            .find(|b| (0..16).all(|x| b.eval(GF::new(x)) != GF::ZERO))
+            .unwrap();
+        let p = &product(&[(a.clone(), 3), (b.clone(), 4)]) * GF::new(9);
+
+        let factors = p.factor();
+        assert_eq!(factors, vec![(a, 3), (b, 4)]);
+    }
+
+    #[test]
+    fn squarefree_decomposition() {
+        for _ in 0..50 {
+            let parts: Vec<(Polynomial<GF>, usize)> = (1..=5)
+                .map(|i| (random_monic(rand::thread_rng().gen_range(0..3)), i))
+                .collect();
+            let p = product(&parts);
+
+            let decomposition = p.squarefree_decomposition();
+            assert_eq!(product(&decomposition), p);
+            for (k, (a, i)) in decomposition.iter().enumerate() {
+                assert!(a.degree() > 0);
+                assert_eq!(a.leading_coeff(), GF::ONE);
+                assert_eq!(a.gcd(&a.derivative()), Polynomial::one());
+                for (b, j) in decomposition.iter().skip(k + 1) {
+                    assert!(i < j);
+                    assert_eq!(a.gcd(b), Polynomial::one());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn squarefree_characteristic_2() {
+        // (x + 3)^2 * (x + 5)^4 * (x + 7)^6 only has even exponents so its derivative is zero
+        let a = Polynomial::new(vec![GF::new(3), GF::ONE]);
+        let b = Polynomial::new(vec![GF::new(5), GF::ONE]);
+        let c = Polynomial::new(vec![GF::new(7), GF::ONE]);
+        let p = product(&[(a.clone(), 2), (b.clone(), 4), (c.clone(), 6)]);
+        assert!(p.derivative().is_zero());
+        assert_eq!(
+            p.squarefree_decomposition(),
+            vec![(a.clone(), 2), (b.clone(), 4), (c.clone(), 6)]
+        );
+
+        // Mixed odd and even exponents
+        let p = product(&[(a.clone(), 1), (b.clone(), 2), (c.clone(), 3), (&a * &c, 0)]);
+        assert_eq!(p.squarefree_decomposition(), vec![(a, 1), (b, 2), (c, 3)]);
+
+        assert!(Polynomial::new(vec![GF::new(4)])
+            .squarefree_decomposition()
+            .is_empty());
+    }
+
+    #[test]
+    fn factor_larger_field() {
+        type GF16 = gf2_nibble::GFu16<0x1002d>;
+        // Square of the product of (x - r) for a few roots
+        let roots = [GF16::new(0x1234), GF16::new(0x0042), GF16::new(0xbeef)];
+        let mut p = Polynomial::one();
+        for &r in roots.iter() {
+            p *= Polynomial::new(vec![r, GF16::ONE]);
+        }
+        p *= &p.clone();
+        let factors = p.factor();
+        assert_eq!(factors.len(), 3);
+        for (f, e) in factors.iter() {
+            assert_eq!(f.degree(), 1);
+            assert_eq!(*e, 2);
+            assert!(roots.contains(&f.coeff(0)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn factor_zero() {
+        let _ = Polynomial::<GF>::zero().factor();
+    }
+}
@@ -0,0 +1,113 @@
+use crate::GaloisField;
+
+// Below this many coefficients in the shorter operand schoolbook multiplication is faster
+pub(crate) const KARATSUBA_CUTOFF: usize = 32;
+
+// Adds a * b into output. output must have at least a.len() + b.len() - 1 entries
+pub(crate) fn mul_acc<GF: GaloisField>(a: &[GF], b: &[GF], output: &mut [GF]) {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+    if a.len().min(b.len()) < KARATSUBA_CUTOFF {
+        schoolbook_acc(a, b, output);
+        return;
+    }
+
+    let half = a.len().max(b.len()) / 2;
+    if b.len() <= half {
+        // Unbalanced operands: split only the longer one
+        mul_acc(&a[..half], b, output);
+        mul_acc(&a[half..], b, &mut output[half..]);
+        return;
+    }
+    if a.len() <= half {
+        mul_acc(a, &b[..half], output);
+        mul_acc(a, &b[half..], &mut output[half..]);
+        return;
+    }
+
+    let (a0, a1) = a.split_at(half);
+    let (b0, b1) = b.split_at(half);
+
+    // z0 = a0 * b0, z2 = a1 * b1, z1 = (a0 + a1) * (b0 + b1) - z0 - z2
+    let mut z0 = vec![GF::ZERO; 2 * half - 1];
+    let mut z2 = vec![GF::ZERO; a1.len() + b1.len() - 1];
+    mul_acc(a0, b0, &mut z0);
+    mul_acc(a1, b1, &mut z2);
+
+    let a_sum = sum(a0, a1);
+    let b_sum = sum(b0, b1);
+    let mut z1 = vec![GF::ZERO; a_sum.len() + b_sum.len() - 1];
+    mul_acc(&a_sum, &b_sum, &mut z1);
+    for (i, &c) in z0.iter().enumerate() {
+        z1[i] -= c;
+    }
+    for (i, &c) in z2.iter().enumerate() {
+        z1[i] -= c;
+    }
+
+    for (i, &c) in z0.iter().enumerate() {
+        output[i] += c;
+    }
+    for (i, &c) in z1.iter().enumerate() {
+        output[i + half] += c;
+    }
+    for (i, &c) in z2.iter().enumerate() {
+        output[i + 2 * half] += c;
+    }
+}
+
+pub(crate) fn schoolbook_acc<GF: GaloisField>(a: &[GF], b: &[GF], output: &mut [GF]) {
+    for (i, &x) in a.iter().enumerate() {
+        if x == GF::ZERO {
+            continue;
+        }
+        for (j, &y) in b.iter().enumerate() {
+            output[i + j] += x * y;
+        }
+    }
+}
+
+fn sum<GF: GaloisField>(a: &[GF], b: &[GF]) -> Vec<GF> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut output = long.to_vec();
+    for (o, &s) in output.iter_mut().zip(short.iter()) {
+        *o += s;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu16<0x1100b>;
+
+    fn random_coeffs(len: usize) -> Vec<GF> {
+        (0..len)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..=0xFFFF)))
+            .collect()
+    }
+
+    #[test]
+    fn matches_schoolbook() {
+        for (la, lb) in [
+            (32, 32),
+            (33, 100),
+            (500, 500),
+            (1000, 31),
+            (257, 1024),
+            (64, 65),
+        ] {
+            let a = random_coeffs(la);
+            let b = random_coeffs(lb);
+            let mut expected = vec![GF::ZERO; la + lb - 1];
+            let mut output = vec![GF::ZERO; la + lb - 1];
+            schoolbook_acc(&a, &b, &mut expected);
+            mul_acc(&a, &b, &mut output);
+            assert_eq!(output, expected);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use super::Polynomial;
+use crate::GaloisField;
+
+/// Running remainder of a message modulo a fixed generator, computed the way an LFSR divider does.
+///
+/// Symbols are fed highest degree first. After feeding m<sub>k-1</sub>, ..., m<sub>0</sub> the state holds
+/// m(x) * x<sup>r</sup> mod g(x) where r is the degree of g. This is the parity of a systematic cyclic encoder
+/// and the remainder of a CRC, so whole messages never need to be buffered.
+///
+/// ```
+/// use galois_field_2pm::{GaloisField, gf2_lut};
+/// use galois_field_2pm::poly::{Polynomial, PolyRemainderState};
+///
+/// type GF = gf2_lut::GFu8<0x11d>;
+/// let g = Polynomial::new(vec![GF::new(8), GF::new(6), GF::ONE]);
+/// let mut state = PolyRemainderState::new(&g);
+/// state.update_slice(&[GF::new(1), GF::new(2)]);
+/// state.update(GF::new(3));
+///
+/// let m = Polynomial::new(vec![GF::new(3), GF::new(2), GF::new(1)]);
+/// assert_eq!(state.remainder(), (&m * &Polynomial::monomial(GF::ONE, 2)).rem(&g));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PolyRemainderState<GF: GaloisField> {
+    // Monic generator, lowest degree first
+    generator: Vec<GF>,
+    // Remainder coefficients, lowest degree first, always r long
+    state: Vec<GF>,
+}
+
+impl<GF: GaloisField> PolyRemainderState<GF> {
+    /// Starts a division by generator. Panics if the generator has degree less than 1
+    pub fn new(generator: &Polynomial<GF>) -> Self {
+        if generator.degree() < 1 {
+            panic!("Generator must have degree at least 1");
+        }
+
+        // Scaling the generator does not change remainders, and a monic one saves a division per symbol
+        let mut monic = generator.clone();
+        monic.make_monic();
+        let r = monic.degree() as usize;
+        Self {
+            generator: monic.into_coeffs(),
+            state: vec![GF::ZERO; r],
+        }
+    }
+
+    /// Feeds the next (lower degree) message symbol
+    pub fn update(&mut self, symbol: GF) {
+        let r = self.state.len();
+        let feedback = symbol + self.state[r - 1];
+        for i in (1..r).rev() {
+            self.state[i] = self.state[i - 1] - feedback * self.generator[i];
+        }
+        self.state[0] = -(feedback * self.generator[0]);
+    }
+
+    /// Feeds several message symbols, highest degree first
+    pub fn update_slice(&mut self, symbols: &[GF]) {
+        for &s in symbols.iter() {
+            self.update(s);
+        }
+    }
+
+    /// The remainder coefficients, lowest degree first. Always has r entries, including high zero coefficients
+    pub fn remainder_coeffs(&self) -> &[GF] {
+        &self.state
+    }
+
+    /// The remainder as a polynomial
+    pub fn remainder(&self) -> Polynomial<GF> {
+        Polynomial::new(self.state.clone())
+    }
+
+    /// The monic generator being divided by
+    pub fn generator(&self) -> Polynomial<GF> {
+        Polynomial::new(self.generator.clone())
+    }
+
+    /// Clears the remainder to start a new message
+    pub fn reset(&mut self) {
+        self.state.iter_mut().for_each(|s| *s = GF::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_symbols(n: usize) -> Vec<GF> {
+        (0..n)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+            .collect()
+    }
+
+    #[test]
+    fn matches_rem() {
+        for r in [1, 2, 5, 16, 32] {
+            let mut g_coeffs = random_symbols(r);
+            g_coeffs.push(GF::new(rand::thread_rng().gen_range(1..=255)));
+            let g = Polynomial::new(g_coeffs);
+
+            for n in [0, 1, 10, 100] {
+                let message = random_symbols(n);
+                let mut state = PolyRemainderState::new(&g);
+                state.update_slice(&message);
+
+                // The message is fed highest degree first
+                let m = Polynomial::new(message.iter().rev().copied().collect());
+                let expected = (&m * &Polynomial::monomial(GF::ONE, r)).rem(&g);
+                assert_eq!(state.remainder(), expected);
+                assert_eq!(state.remainder_coeffs().len(), r);
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_and_reset() {
+        let g = Polynomial::new(random_symbols(9)) + Polynomial::monomial(GF::ONE, 9);
+        let message = random_symbols(64);
+
+        let mut whole = PolyRemainderState::new(&g);
+        whole.update_slice(&message);
+
+        let mut chunked = PolyRemainderState::new(&g);
+        for chunk in message.chunks(7) {
+            chunked.update_slice(chunk);
+        }
+        assert_eq!(whole.remainder_coeffs(), chunked.remainder_coeffs());
+
+        chunked.reset();
+        assert!(chunked.remainder().is_zero());
+        chunked.update_slice(&message);
+        assert_eq!(whole.remainder_coeffs(), chunked.remainder_coeffs());
+    }
+
+    #[test]
+    fn codeword_is_multiple() {
+        // Appending the remainder gives a multiple of the generator
+        let g = Polynomial::new(vec![GF::new(8), GF::new(6), GF::new(7), GF::ONE]);
+        let message = random_symbols(20);
+        let mut state = PolyRemainderState::new(&g);
+        state.update_slice(&message);
+
+        let mut codeword: Vec<GF> = state.remainder_coeffs().to_vec();
+        codeword.extend(message.iter().rev());
+        assert!(Polynomial::new(codeword).rem(&g).is_zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn constant_generator() {
+        let _ = PolyRemainderState::new(&Polynomial::new(vec![GF::new(3)]));
+    }
+}
@@ -0,0 +1,76 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Polynomial;
+use crate::GaloisField;
+
+// POLY is stored so a polynomial cannot be read back into a different field
+#[derive(Serialize, Deserialize)]
+struct PolynomialRepr {
+    poly: u128,
+    coeffs: Vec<u128>,
+}
+
+impl<GF: GaloisField> Serialize for Polynomial<GF> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PolynomialRepr {
+            poly: GF::POLY,
+            coeffs: self.coeffs.iter().map(|c| c.to_u128()).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, GF: GaloisField> Deserialize<'de> for Polynomial<GF> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = PolynomialRepr::deserialize(deserializer)?;
+        if repr.poly != GF::POLY {
+            return Err(D::Error::custom(format!(
+                "polynomial is over POLY {:#X}, expected {:#X}",
+                repr.poly,
+                GF::POLY
+            )));
+        }
+
+        let mut coeffs = Vec::with_capacity(repr.coeffs.len());
+        for value in repr.coeffs {
+            let c = GF::from_u128(value);
+            if c.to_u128() != value || !c.validate() {
+                return Err(D::Error::custom(format!(
+                    "coefficient {:#X} is not a field element",
+                    value
+                )));
+            }
+            coeffs.push(c);
+        }
+        Ok(Self::new(coeffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    #[test]
+    fn round_trip() {
+        let p = Polynomial::new(vec![GF::new(3), GF::ZERO, GF::new(0xff)]);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, r#"{"poly":285,"coeffs":[3,0,255]}"#);
+        assert_eq!(serde_json::from_str::<Polynomial<GF>>(&json).unwrap(), p);
+        assert_eq!(
+            serde_json::from_str::<Polynomial<GF>>(r#"{"poly":285,"coeffs":[]}"#).unwrap(),
+            Polynomial::zero()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        // Other field
+        assert!(serde_json::from_str::<Polynomial<GF>>(r#"{"poly":283,"coeffs":[1]}"#).is_err());
+        // Coefficient out of range
+        assert!(serde_json::from_str::<Polynomial<GF>>(r#"{"poly":285,"coeffs":[256]}"#).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use super::Polynomial;
+use crate::GaloisField;
+
+// Quotients with fewer coefficients than this use long division instead of Newton iteration
+const NEWTON_CUTOFF: usize = 32;
+
+// levels[0] holds (x - x_i) for every point, each level above holds the products of adjacent pairs of the level below
+struct SubproductTree<GF: GaloisField> {
+    levels: Vec<Vec<Polynomial<GF>>>,
+}
+
+impl<GF: GaloisField> SubproductTree<GF> {
+    fn new(points: &[GF]) -> Self {
+        let mut levels = vec![points
+            .iter()
+            .map(|&x| Polynomial::new(vec![-x, GF::ONE]))
+            .collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels.last().unwrap().chunks(2).map(|pair| match pair {
+                [a, b] => a * b,
+                [a] => a.clone(),
+                _ => unreachable!(),
+            });
+            levels.push(next.collect());
+        }
+        Self { levels }
+    }
+
+    fn root(&self) -> &Polynomial<GF> {
+        &self.levels.last().unwrap()[0]
+    }
+
+    // Remainder tree: reduce f down the tree so each leaf holds f(x_i)
+    fn eval(&self, f: &Polynomial<GF>) -> Vec<GF> {
+        let mut remainders = vec![f.fast_rem(self.root())];
+        for level in self.levels.iter().rev().skip(1) {
+            remainders = level
+                .iter()
+                .enumerate()
+                .map(|(i, m)| remainders[i / 2].fast_rem(m))
+                .collect();
+        }
+        remainders.iter().map(|r| r.coeff(0)).collect()
+    }
+
+    // Builds sum of c_i * prod_{j != i} (x - x_j) bottom up
+    fn combine(&self, leaf_values: &[GF]) -> Polynomial<GF> {
+        let mut values: Vec<Polynomial<GF>> = leaf_values
+            .iter()
+            .map(|&c| Polynomial::new(vec![c]))
+            .collect();
+        for level in self.levels.iter().take(self.levels.len() - 1) {
+            values = values
+                .chunks(2)
+                .zip(level.chunks(2))
+                .map(|(v, m)| match (v, m) {
+                    ([v0, v1], [m0, m1]) => &(v0 * m1) + &(v1 * m0),
+                    ([v0], _) => v0.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        values.pop().unwrap_or_else(Polynomial::zero)
+    }
+}
+
+impl<GF: GaloisField> Polynomial<GF> {
+    /// Evaluates the polynomial at every point using a subproduct tree.
+    /// Costs O(M(n) log n) where M(n) is the cost of multiplying degree n polynomials, instead of O(n<sup>2</sup>)
+    pub fn eval_at_points(&self, points: &[GF]) -> Vec<GF> {
+        if points.is_empty() {
+            return Vec::new();
+        }
+        SubproductTree::new(points).eval(self)
+    }
+
+    /// Lagrange interpolation using a subproduct tree. Gives the same result as interpolate in O(M(n) log n).
+    /// Returns None if two points share an x coordinate
+    pub fn interpolate_fast(points: &[(GF, GF)]) -> Option<Self> {
+        if points.is_empty() {
+            return Some(Self::zero());
+        }
+        let xs: Vec<GF> = points.iter().map(|&(x, _)| x).collect();
+        let tree = SubproductTree::new(&xs);
+
+        // The barycentric weights are 1 / m'(x_i) where m is the product of all (x - x_i)
+        let derivs = tree.eval(&tree.root().derivative());
+        let mut leaf_values = Vec::with_capacity(points.len());
+        for (&(_, y), &d) in points.iter().zip(derivs.iter()) {
+            if d == GF::ZERO {
+                return None;
+            }
+            leaf_values.push(y / d);
+        }
+        Some(tree.combine(&leaf_values))
+    }
+
+    // Remainder using Newton iteration for the quotient when it is large
+    fn fast_rem(&self, divisor: &Self) -> Self {
+        if self.degree() < divisor.degree() {
+            return self.clone();
+        }
+        let quotient_len = (self.degree() - divisor.degree() + 1) as usize;
+        if quotient_len < NEWTON_CUTOFF {
+            return self.rem(divisor);
+        }
+
+        // rev(q) = rev(self) / rev(divisor) mod x^quotient_len
+        let rev_divisor = divisor.reversed(divisor.coeffs.len());
+        let inv = rev_divisor.inverse_series(quotient_len);
+        let rev_q = (&self.reversed(self.coeffs.len()) * &inv).truncated(quotient_len);
+        let q = rev_q.reversed(quotient_len);
+        (self - &(&q * divisor)).truncated(divisor.coeffs.len() - 1)
+    }
+
+    // Reverses the first len coefficients: x^(len - 1) * self(1 / x)
+    fn reversed(&self, len: usize) -> Self {
+        Self::new((0..len).rev().map(|i| self.coeff(i)).collect())
+    }
+
+    // Drops the coefficients of x^len and above
+    fn truncated(&self, len: usize) -> Self {
+        Self::new(self.coeffs.iter().take(len).copied().collect())
+    }
+
+    // Returns g with self * g = 1 mod x^len. The constant term must be non zero
+    fn inverse_series(&self, len: usize) -> Self {
+        let mut g = Self::new(vec![self.coeff(0).inverse()]);
+        let mut precision = 1;
+        while precision < len {
+            precision = (2 * precision).min(len);
+            // g = g + g * (1 - self * g)
+            let err = &Self::one() - &(self.truncated(precision) * &g).truncated(precision);
+            g = (&g + &(&g * &err)).truncated(precision);
+        }
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu16<0x1002d>;
+
+    fn random_poly(len: usize) -> Polynomial<GF> {
+        Polynomial::new(
+            (0..len)
+                .map(|_| GF::new(rand::thread_rng().gen_range(0..=0xFFFF)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn inverse_series() {
+        let mut coeffs = random_poly(50).into_coeffs();
+        coeffs[0] = GF::new(0x1234);
+        let f = Polynomial::new(coeffs);
+        let g = f.inverse_series(100);
+        assert_eq!((&f * &g).truncated(100), Polynomial::one());
+    }
+
+    #[test]
+    fn fast_rem() {
+        for (la, lb) in [(200, 10), (500, 100), (100, 100), (50, 80), (1000, 3)] {
+            let a = random_poly(la);
+            let b = random_poly(lb);
+            assert_eq!(a.fast_rem(&b), a.rem(&b));
+        }
+    }
+
+    #[test]
+    fn eval_at_points() {
+        let f = random_poly(300);
+        let points: Vec<GF> = (0..257)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..=0xFFFF)))
+            .collect();
+        assert_eq!(f.eval_at_points(&points), f.eval_many(&points));
+        assert!(f.eval_at_points(&[]).is_empty());
+    }
+
+    #[test]
+    fn interpolate_fast() {
+        for n in [0, 1, 2, 3, 100, 129] {
+            let f = random_poly(n);
+            let points: Vec<(GF, GF)> = (0..n)
+                .map(|i| GF::new(7 * i as u16 + 3))
+                .map(|x| (x, f.eval(x)))
+                .collect();
+            assert_eq!(Polynomial::interpolate_fast(&points), Some(f.clone()));
+            assert_eq!(
+                Polynomial::interpolate_fast(&points),
+                Polynomial::interpolate(&points)
+            );
+        }
+
+        let points = [
+            (GF::new(1), GF::new(2)),
+            (GF::new(5), GF::new(3)),
+            (GF::new(1), GF::new(4)),
+        ];
+        assert_eq!(Polynomial::interpolate_fast(&points), None);
+    }
+}
@@ -0,0 +1,136 @@
+//! Keyed polynomial evaluation MACs over any field.
+//!
+//! `PolyMac` accumulates message elements m<sub>1</sub>, ..., m<sub>n</sub> as acc = (acc + m<sub>i</sub>) * key,
+//! ending with m<sub>1</sub>key<sup>n</sup> + ... + m<sub>n</sub>key, the construction behind GHASH and Poly1305.
+//! Two different messages of the same length collide for at most n of the keys, so a random key forges with
+//! probability n / 2<sup>M</sup>. Messages of different lengths should end with an element holding the length, as
+//! GCM does, and a tag that is sent in the clear should be masked with a fresh one time value.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::polymac::PolyMac;
+//!
+//! type GF = gf2::GFu32<0x1_0000_008D>;
+//! let key = GF::new(0x1234_5678);
+//!
+//! let mut mac = PolyMac::new(key);
+//! mac.update(GF::new(7));
+//! mac.update(GF::new(9));
+//! assert_eq!(mac.finalize(), GF::new(7) * key * key + GF::new(9) * key);
+//! ```
+
+use crate::GaloisField;
+
+/// The incremental accumulator acc = (acc + m<sub>i</sub>) * key
+#[derive(Clone, Debug)]
+pub struct PolyMac<GF: GaloisField> {
+    key: GF,
+    acc: GF,
+}
+
+impl<GF: GaloisField> PolyMac<GF> {
+    /// Starts an empty message under key
+    pub fn new(key: GF) -> Self {
+        Self { key, acc: GF::ZERO }
+    }
+
+    /// Absorbs one message element
+    pub fn update(&mut self, m: GF) {
+        self.acc = (self.acc + m) * self.key;
+    }
+
+    /// Absorbs message elements in order
+    pub fn update_slice(&mut self, ms: &[GF]) {
+        for &m in ms.iter() {
+            self.update(m);
+        }
+    }
+
+    /// Absorbs bytes as elements of ⌊M/8⌋ big endian bytes each, padding the last one with zero bytes. Panics if
+    /// M < 8
+    pub fn update_bytes(&mut self, data: &[u8]) {
+        let chunk = (GF::M / 8) as usize;
+        if chunk == 0 {
+            panic!("Field must have at least 8 bits");
+        }
+        for bytes in data.chunks(chunk) {
+            let value = (0..chunk).fold(0u128, |acc, i| {
+                (acc << 8) | *bytes.get(i).unwrap_or(&0) as u128
+            });
+            self.update(GF::from_u128(value));
+        }
+    }
+
+    /// The tag of the elements so far
+    pub fn finalize(&self) -> GF {
+        self.acc
+    }
+
+    /// Clears the state to authenticate a new message with the same key
+    pub fn reset(&mut self) {
+        self.acc = GF::ZERO;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<GF: GaloisField + zeroize::Zeroize> zeroize::Zeroize for PolyMac<GF> {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.acc.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2;
+    use crate::poly::Polynomial;
+    use rand::Rng;
+
+    type GF = gf2::GFu64<0x1_0000_0000_0000_001B>;
+
+    #[test]
+    fn horner_evaluation() {
+        let key = GF::new(rand::thread_rng().gen());
+        let ms: Vec<GF> = (0..20).map(|_| GF::new(rand::thread_rng().gen())).collect();
+
+        let mut mac = PolyMac::new(key);
+        mac.update_slice(&ms[..7]);
+        for &m in ms[7..].iter() {
+            mac.update(m);
+        }
+        // m_1 key^n + ... + m_n key
+        let poly = Polynomial::new(ms.iter().rev().copied().collect());
+        assert_eq!(mac.finalize(), poly.eval(key) * key);
+
+        mac.reset();
+        assert_eq!(mac.finalize(), GF::ZERO);
+    }
+
+    #[test]
+    fn bytes() {
+        type GF16 = gf2::GFu16<0x1_002D>;
+        let key = GF16::new(0xBEEF);
+        let mut mac = PolyMac::new(key);
+        mac.update_bytes(&[0x12, 0x34, 0x56]);
+
+        let mut expected = PolyMac::new(key);
+        expected.update_slice(&[GF16::new(0x1234), GF16::new(0x5600)]);
+        assert_eq!(mac.finalize(), expected.finalize());
+
+        // Nine bit elements take one byte each
+        let key = gf2::GFu16::<0x211>::new(0x1A5);
+        let mut mac = PolyMac::new(key);
+        mac.update_bytes(&[0xFF, 0x01]);
+        assert_eq!(
+            mac.finalize(),
+            (gf2::GFu16::new(0xFF) * key + gf2::GFu16::new(0x01)) * key
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_field_bytes() {
+        PolyMac::new(gf2::GFu8::<0x13>::ONE).update_bytes(&[1]);
+    }
+}
@@ -0,0 +1,672 @@
+//! Polynomials over GF(2) represented as u128, as used for the POLY parameter of the GF types.
+//!
+//! Bit i holds the coefficient of x<sup>i</sup>, so x<sup>3</sup> + x + 1 is 0xB.
+//!
+//! ```
+//! use galois_field_2pm::polys;
+//!
+//! assert!(polys::is_irreducible_gf2(0x11B));
+//! assert!(!polys::is_primitive_gf2(0x11B));
+//! assert!(polys::is_primitive_gf2(0x11D));
+//! assert_eq!(polys::GF2_8, 0x11D);
+//! ```
+
+// The GF2_M constants are primitive polynomials of minimum weight: the trinomial x^M + x^k + 1 with the smallest k
+// when one exists, otherwise the pentanomial x^M + x^a + x^b + x^c + 1 with the smallest (a, b, c)
+
+/// Primitive polynomial for GF(2<sup>1</sup>): x + 1
+pub const GF2_1: u128 = 0x3;
+
+/// Primitive polynomial for GF(2<sup>2</sup>): x<sup>2</sup> + x + 1
+pub const GF2_2: u128 = 0x7;
+
+/// Primitive polynomial for GF(2<sup>3</sup>): x<sup>3</sup> + x + 1
+pub const GF2_3: u128 = 0xB;
+
+/// Primitive polynomial for GF(2<sup>4</sup>): x<sup>4</sup> + x + 1
+pub const GF2_4: u128 = 0x13;
+
+/// Primitive polynomial for GF(2<sup>5</sup>): x<sup>5</sup> + x<sup>2</sup> + 1
+pub const GF2_5: u128 = 0x25;
+
+/// Primitive polynomial for GF(2<sup>6</sup>): x<sup>6</sup> + x + 1
+pub const GF2_6: u128 = 0x43;
+
+/// Primitive polynomial for GF(2<sup>7</sup>): x<sup>7</sup> + x + 1
+pub const GF2_7: u128 = 0x83;
+
+/// Primitive polynomial for GF(2<sup>8</sup>): x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x<sup>2</sup> + 1
+pub const GF2_8: u128 = 0x11D;
+
+/// Primitive polynomial for GF(2<sup>9</sup>): x<sup>9</sup> + x<sup>4</sup> + 1
+pub const GF2_9: u128 = 0x211;
+
+/// Primitive polynomial for GF(2<sup>10</sup>): x<sup>10</sup> + x<sup>3</sup> + 1
+pub const GF2_10: u128 = 0x409;
+
+/// Primitive polynomial for GF(2<sup>11</sup>): x<sup>11</sup> + x<sup>2</sup> + 1
+pub const GF2_11: u128 = 0x805;
+
+/// Primitive polynomial for GF(2<sup>12</sup>): x<sup>12</sup> + x<sup>6</sup> + x<sup>4</sup> + x + 1
+pub const GF2_12: u128 = 0x1053;
+
+/// Primitive polynomial for GF(2<sup>13</sup>): x<sup>13</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1
+pub const GF2_13: u128 = 0x201B;
+
+/// Primitive polynomial for GF(2<sup>14</sup>): x<sup>14</sup> + x<sup>5</sup> + x<sup>3</sup> + x + 1
+pub const GF2_14: u128 = 0x402B;
+
+/// Primitive polynomial for GF(2<sup>15</sup>): x<sup>15</sup> + x + 1
+pub const GF2_15: u128 = 0x8003;
+
+/// Primitive polynomial for GF(2<sup>16</sup>): x<sup>16</sup> + x<sup>5</sup> + x<sup>3</sup> + x<sup>2</sup> + 1
+pub const GF2_16: u128 = 0x1_002D;
+
+/// Primitive polynomial for GF(2<sup>17</sup>): x<sup>17</sup> + x<sup>3</sup> + 1
+pub const GF2_17: u128 = 0x2_0009;
+
+/// Primitive polynomial for GF(2<sup>18</sup>): x<sup>18</sup> + x<sup>7</sup> + 1
+pub const GF2_18: u128 = 0x4_0081;
+
+/// Primitive polynomial for GF(2<sup>19</sup>): x<sup>19</sup> + x<sup>5</sup> + x<sup>2</sup> + x + 1
+pub const GF2_19: u128 = 0x8_0027;
+
+/// Primitive polynomial for GF(2<sup>20</sup>): x<sup>20</sup> + x<sup>3</sup> + 1
+pub const GF2_20: u128 = 0x10_0009;
+
+/// Primitive polynomial for GF(2<sup>21</sup>): x<sup>21</sup> + x<sup>2</sup> + 1
+pub const GF2_21: u128 = 0x20_0005;
+
+/// Primitive polynomial for GF(2<sup>22</sup>): x<sup>22</sup> + x + 1
+pub const GF2_22: u128 = 0x40_0003;
+
+/// Primitive polynomial for GF(2<sup>23</sup>): x<sup>23</sup> + x<sup>5</sup> + 1
+pub const GF2_23: u128 = 0x80_0021;
+
+/// Primitive polynomial for GF(2<sup>24</sup>): x<sup>24</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1
+pub const GF2_24: u128 = 0x100_001B;
+
+/// Primitive polynomial for GF(2<sup>25</sup>): x<sup>25</sup> + x<sup>3</sup> + 1
+pub const GF2_25: u128 = 0x200_0009;
+
+/// Primitive polynomial for GF(2<sup>26</sup>): x<sup>26</sup> + x<sup>6</sup> + x<sup>2</sup> + x + 1
+pub const GF2_26: u128 = 0x400_0047;
+
+/// Primitive polynomial for GF(2<sup>27</sup>): x<sup>27</sup> + x<sup>5</sup> + x<sup>2</sup> + x + 1
+pub const GF2_27: u128 = 0x800_0027;
+
+/// Primitive polynomial for GF(2<sup>28</sup>): x<sup>28</sup> + x<sup>3</sup> + 1
+pub const GF2_28: u128 = 0x1000_0009;
+
+/// Primitive polynomial for GF(2<sup>29</sup>): x<sup>29</sup> + x<sup>2</sup> + 1
+pub const GF2_29: u128 = 0x2000_0005;
+
+/// Primitive polynomial for GF(2<sup>30</sup>): x<sup>30</sup> + x<sup>6</sup> + x<sup>4</sup> + x + 1
+pub const GF2_30: u128 = 0x4000_0053;
+
+/// Primitive polynomial for GF(2<sup>31</sup>): x<sup>31</sup> + x<sup>3</sup> + 1
+pub const GF2_31: u128 = 0x8000_0009;
+
+/// Primitive polynomial for GF(2<sup>32</sup>): x<sup>32</sup> + x<sup>7</sup> + x<sup>6</sup> + x<sup>2</sup> + 1
+pub const GF2_32: u128 = 0x1_0000_00C5;
+
+/// Primitive polynomial for GF(2<sup>33</sup>): x<sup>33</sup> + x<sup>13</sup> + 1
+pub const GF2_33: u128 = 0x2_0000_2001;
+
+/// Primitive polynomial for GF(2<sup>34</sup>): x<sup>34</sup> + x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + 1
+pub const GF2_34: u128 = 0x4_0000_0119;
+
+/// Primitive polynomial for GF(2<sup>35</sup>): x<sup>35</sup> + x<sup>2</sup> + 1
+pub const GF2_35: u128 = 0x8_0000_0005;
+
+/// Primitive polynomial for GF(2<sup>36</sup>): x<sup>36</sup> + x<sup>11</sup> + 1
+pub const GF2_36: u128 = 0x10_0000_0801;
+
+/// Primitive polynomial for GF(2<sup>37</sup>): x<sup>37</sup> + x<sup>6</sup> + x<sup>4</sup> + x + 1
+pub const GF2_37: u128 = 0x20_0000_0053;
+
+/// Primitive polynomial for GF(2<sup>38</sup>): x<sup>38</sup> + x<sup>6</sup> + x<sup>5</sup> + x + 1
+pub const GF2_38: u128 = 0x40_0000_0063;
+
+/// Primitive polynomial for GF(2<sup>39</sup>): x<sup>39</sup> + x<sup>4</sup> + 1
+pub const GF2_39: u128 = 0x80_0000_0011;
+
+/// Primitive polynomial for GF(2<sup>40</sup>): x<sup>40</sup> + x<sup>5</sup> + x<sup>4</sup> + x<sup>3</sup> + 1
+pub const GF2_40: u128 = 0x100_0000_0039;
+
+/// Primitive polynomial for GF(2<sup>41</sup>): x<sup>41</sup> + x<sup>3</sup> + 1
+pub const GF2_41: u128 = 0x200_0000_0009;
+
+/// Primitive polynomial for GF(2<sup>42</sup>): x<sup>42</sup> + x<sup>7</sup> + x<sup>4</sup> + x<sup>3</sup> + 1
+pub const GF2_42: u128 = 0x400_0000_0099;
+
+/// Primitive polynomial for GF(2<sup>43</sup>): x<sup>43</sup> + x<sup>6</sup> + x<sup>4</sup> + x<sup>3</sup> + 1
+pub const GF2_43: u128 = 0x800_0000_0059;
+
+/// Primitive polynomial for GF(2<sup>44</sup>): x<sup>44</sup> + x<sup>6</sup> + x<sup>5</sup> + x<sup>2</sup> + 1
+pub const GF2_44: u128 = 0x1000_0000_0065;
+
+/// Primitive polynomial for GF(2<sup>45</sup>): x<sup>45</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1
+pub const GF2_45: u128 = 0x2000_0000_001B;
+
+/// Primitive polynomial for GF(2<sup>46</sup>): x<sup>46</sup> + x<sup>8</sup> + x<sup>7</sup> + x<sup>6</sup> + 1
+pub const GF2_46: u128 = 0x4000_0000_01C1;
+
+/// Primitive polynomial for GF(2<sup>47</sup>): x<sup>47</sup> + x<sup>5</sup> + 1
+pub const GF2_47: u128 = 0x8000_0000_0021;
+
+/// Primitive polynomial for GF(2<sup>48</sup>): x<sup>48</sup> + x<sup>9</sup> + x<sup>7</sup> + x<sup>4</sup> + 1
+pub const GF2_48: u128 = 0x1_0000_0000_0291;
+
+/// Primitive polynomial for GF(2<sup>49</sup>): x<sup>49</sup> + x<sup>9</sup> + 1
+pub const GF2_49: u128 = 0x2_0000_0000_0201;
+
+/// Primitive polynomial for GF(2<sup>50</sup>): x<sup>50</sup> + x<sup>4</sup> + x<sup>3</sup> + x<sup>2</sup> + 1
+pub const GF2_50: u128 = 0x4_0000_0000_001D;
+
+/// Primitive polynomial for GF(2<sup>51</sup>): x<sup>51</sup> + x<sup>6</sup> + x<sup>3</sup> + x + 1
+pub const GF2_51: u128 = 0x8_0000_0000_004B;
+
+/// Primitive polynomial for GF(2<sup>52</sup>): x<sup>52</sup> + x<sup>3</sup> + 1
+pub const GF2_52: u128 = 0x10_0000_0000_0009;
+
+/// Primitive polynomial for GF(2<sup>53</sup>): x<sup>53</sup> + x<sup>6</sup> + x<sup>2</sup> + x + 1
+pub const GF2_53: u128 = 0x20_0000_0000_0047;
+
+/// Primitive polynomial for GF(2<sup>54</sup>): x<sup>54</sup> + x<sup>8</sup> + x<sup>6</sup> + x<sup>3</sup> + 1
+pub const GF2_54: u128 = 0x40_0000_0000_0149;
+
+/// Primitive polynomial for GF(2<sup>55</sup>): x<sup>55</sup> + x<sup>24</sup> + 1
+pub const GF2_55: u128 = 0x80_0000_0100_0001;
+
+/// Primitive polynomial for GF(2<sup>56</sup>): x<sup>56</sup> + x<sup>7</sup> + x<sup>4</sup> + x<sup>2</sup> + 1
+pub const GF2_56: u128 = 0x100_0000_0000_0095;
+
+/// Primitive polynomial for GF(2<sup>57</sup>): x<sup>57</sup> + x<sup>7</sup> + 1
+pub const GF2_57: u128 = 0x200_0000_0000_0081;
+
+/// Primitive polynomial for GF(2<sup>58</sup>): x<sup>58</sup> + x<sup>19</sup> + 1
+pub const GF2_58: u128 = 0x400_0000_0008_0001;
+
+/// Primitive polynomial for GF(2<sup>59</sup>): x<sup>59</sup> + x<sup>7</sup> + x<sup>4</sup> + x<sup>2</sup> + 1
+pub const GF2_59: u128 = 0x800_0000_0000_0095;
+
+/// Primitive polynomial for GF(2<sup>60</sup>): x<sup>60</sup> + x + 1
+pub const GF2_60: u128 = 0x1000_0000_0000_0003;
+
+/// Primitive polynomial for GF(2<sup>61</sup>): x<sup>61</sup> + x<sup>5</sup> + x<sup>2</sup> + x + 1
+pub const GF2_61: u128 = 0x2000_0000_0000_0027;
+
+/// Primitive polynomial for GF(2<sup>62</sup>): x<sup>62</sup> + x<sup>6</sup> + x<sup>5</sup> + x<sup>3</sup> + 1
+pub const GF2_62: u128 = 0x4000_0000_0000_0069;
+
+/// Primitive polynomial for GF(2<sup>63</sup>): x<sup>63</sup> + x + 1
+pub const GF2_63: u128 = 0x8000_0000_0000_0003;
+
+/// Primitive polynomial for GF(2<sup>64</sup>): x<sup>64</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1
+pub const GF2_64: u128 = 0x1_0000_0000_0000_001B;
+
+/// Irreducible (not primitive) polynomial used by AES and Rijndael: x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x + 1
+pub const AES: u128 = 0x11B;
+
+/// Primitive polynomial used by QR code Reed-Solomon: x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x<sup>2</sup> + 1
+pub const QR_CODE: u128 = 0x11D;
+
+/// Primitive polynomial used by Data Matrix and 8 bit Aztec Reed-Solomon: x<sup>8</sup> + x<sup>5</sup> + x<sup>3</sup> + x<sup>2</sup> + 1
+pub const DATA_MATRIX: u128 = 0x12D;
+
+/// Primitive polynomial used by CCSDS Reed-Solomon: x<sup>8</sup> + x<sup>7</sup> + x<sup>2</sup> + x + 1
+pub const CCSDS: u128 = 0x187;
+
+/// Primitive polynomial used by the Aztec mode message: x<sup>4</sup> + x + 1
+pub const AZTEC_PARAM: u128 = 0x13;
+
+/// Primitive polynomial used by 6 bit Aztec Reed-Solomon: x<sup>6</sup> + x + 1
+pub const AZTEC_DATA_6: u128 = 0x43;
+
+/// Primitive polynomial used by 10 bit Aztec Reed-Solomon: x<sup>10</sup> + x<sup>3</sup> + 1
+pub const AZTEC_DATA_10: u128 = 0x409;
+
+/// Primitive polynomial used by 12 bit Aztec Reed-Solomon: x<sup>12</sup> + x<sup>6</sup> + x<sup>5</sup> + x<sup>3</sup> + 1
+pub const AZTEC_DATA_12: u128 = 0x1069;
+
+/// Primitive polynomial used by PAR2 recovery files: x<sup>16</sup> + x<sup>12</sup> + x<sup>3</sup> + x + 1
+pub const PAR2: u128 = 0x1_100B;
+
+/// Checks if poly is irreducible over GF(2)
+pub fn is_irreducible_gf2(poly: u128) -> bool {
+    let n = crate::calc_degree(poly);
+    if n < 1 {
+        return false;
+    }
+    if n == 1 {
+        return true;
+    }
+    if (poly & 0x1) == 0 {
+        return false;
+    }
+
+    // Rabin's test: x^(2^n) = x mod poly and gcd(x^(2^(n/q)) - x, poly) = 1 for every prime q dividing n
+    let n = n as u128;
+    let x_pows = frobenius_powers(poly, n);
+    if x_pows[n as usize] != 0x2 {
+        return false;
+    }
+
+    prime_factors(n)
+        .iter()
+        .all(|&q| gcd_gf2(x_pows[(n / q) as usize] ^ 0x2, poly) == 1)
+}
+
+/// Checks if poly is primitive over GF(2), i.e. irreducible with x generating the multiplicative group of the field
+pub fn is_primitive_gf2(poly: u128) -> bool {
+    if !is_irreducible_gf2(poly) {
+        return false;
+    }
+    let n = crate::calc_degree(poly) as u32;
+    if n == 1 {
+        // x + 1 gives x = 1 which generates GF(2)*, while x gives x = 0
+        return poly == 0x3;
+    }
+
+    let order: u128 = (1u128 << n) - 1;
+    prime_factors(order)
+        .iter()
+        .all(|&p| pow_x_mod(order / p, poly) != 1)
+}
+
+/// Generates a random irreducible polynomial of the given degree. Panics unless 1 ≤ degree ≤ 127
+#[cfg(feature = "rand")]
+pub fn random_irreducible_gf2<R: rand::Rng + ?Sized>(degree: u32, rng: &mut R) -> u128 {
+    random_gf2(degree, rng, is_irreducible_gf2)
+}
+
+/// Generates a random primitive polynomial of the given degree. Panics unless 1 ≤ degree ≤ 127
+#[cfg(feature = "rand")]
+pub fn random_primitive_gf2<R: rand::Rng + ?Sized>(degree: u32, rng: &mut R) -> u128 {
+    random_gf2(degree, rng, is_primitive_gf2)
+}
+
+// Draws random polynomials of the given degree until one passes the check
+#[cfg(feature = "rand")]
+fn random_gf2<R: rand::Rng + ?Sized>(degree: u32, rng: &mut R, check: fn(u128) -> bool) -> u128 {
+    if !(1..=127).contains(&degree) {
+        panic!("Degree must be between 1 and 127");
+    }
+
+    // Above degree 1 a polynomial without a constant term is divisible by x
+    let free_mask: u128 = if degree == 1 {
+        0x1
+    } else {
+        ((1u128 << degree) - 1) & !0x1
+    };
+    let fixed: u128 = if degree == 1 {
+        0x2
+    } else {
+        (1u128 << degree) | 0x1
+    };
+    loop {
+        let poly = fixed | (rng.gen::<u128>() & free_mask);
+        if check(poly) {
+            return poly;
+        }
+    }
+}
+
+// Returns x^(2^k) mod poly for k = 0..=n
+fn frobenius_powers(poly: u128, n: u128) -> Vec<u128> {
+    let mut pows = Vec::with_capacity(n as usize + 1);
+    let mut cur: u128 = 0x2;
+    for _ in 0..=n {
+        pows.push(cur);
+        cur = mul_mod_gf2(cur, cur, poly);
+    }
+    pows
+}
+
+// Returns x^exp mod poly
+pub(crate) fn pow_x_mod(exp: u128, poly: u128) -> u128 {
+    let mut result: u128 = 1;
+    for i in (0..(128 - exp.leading_zeros())).rev() {
+        result = mul_mod_gf2(result, result, poly);
+        if ((exp >> i) & 0x1) > 0 {
+            result = mul_x_mod_gf2(result, poly);
+        }
+    }
+    result
+}
+
+// Returns a * x mod poly. a must already be reduced
+fn mul_x_mod_gf2(a: u128, poly: u128) -> u128 {
+    let deg = crate::calc_degree(poly) as u32;
+    let top = (a >> (deg - 1)) & 0x1;
+    let shifted = (a << 1) & ((1u128 << deg) - 1);
+    if top > 0 {
+        shifted ^ (poly & !(1u128 << deg))
+    } else {
+        shifted
+    }
+}
+
+// Returns a * b mod poly. a and b must already be reduced
+pub(crate) fn mul_mod_gf2(a: u128, b: u128, poly: u128) -> u128 {
+    let mut result: u128 = 0;
+    let mut a = a;
+    let mut b = b;
+    while b != 0 {
+        if (b & 0x1) > 0 {
+            result ^= a;
+        }
+        a = mul_x_mod_gf2(a, poly);
+        b >>= 1;
+    }
+    result
+}
+
+// Returns a mod b over GF(2). b must be non zero
+pub(crate) fn rem_gf2(a: u128, b: u128) -> u128 {
+    let deg_b = crate::calc_degree(b);
+    let mut a = a;
+    let mut deg_a = crate::calc_degree(a);
+    while deg_a >= deg_b {
+        a ^= b << (deg_a - deg_b);
+        deg_a = crate::calc_degree(a);
+    }
+    a
+}
+
+// Returns gcd(a, b) over GF(2)
+pub(crate) fn gcd_gf2(a: u128, b: u128) -> u128 {
+    let mut a = a;
+    let mut b = b;
+    while b != 0 {
+        let r = rem_gf2(a, b);
+        a = b;
+        b = r;
+    }
+    a
+}
+
+// Returns the distinct prime factors of n in increasing order
+fn prime_factors(n: u128) -> Vec<u128> {
+    let mut factors = Vec::new();
+    let mut n = n;
+
+    let mut p: u128 = 2;
+    while p < (1 << 12) && p * p <= n {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            factors.push(m);
+            continue;
+        }
+        let d = pollard_brent(m);
+        stack.push(d);
+        stack.push(m / d);
+    }
+
+    factors.sort_unstable();
+    factors.dedup();
+    factors
+}
+
+// Returns (a + b) mod m for a, b < m
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= m {
+        sum.wrapping_sub(m)
+    } else {
+        sum
+    }
+}
+
+// Returns (a * b) mod m for a, b < m
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(prod) = a.checked_mul(b) {
+        return prod % m;
+    }
+    let mut result: u128 = 0;
+    let mut a = a;
+    let mut b = b;
+    while b != 0 {
+        if (b & 0x1) > 0 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+fn pow_mod(base: u128, exp: u128, m: u128) -> u128 {
+    let mut result: u128 = 1;
+    let mut base = base % m;
+    let mut exp = exp;
+    while exp != 0 {
+        if (exp & 0x1) > 0 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+// Miller-Rabin with the first 20 primes as bases. Deterministic below 3.3 * 10^24
+fn is_prime(n: u128) -> bool {
+    const BASES: [u128; 20] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+    ];
+    if n < 2 {
+        return false;
+    }
+    for &p in BASES.iter() {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for &a in BASES.iter() {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    let mut a = a;
+    let mut b = b;
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Returns a non trivial factor of the odd composite n
+fn pollard_brent(n: u128) -> u128 {
+    const BATCH: u32 = 128;
+    let mut c: u128 = 1;
+    loop {
+        let f = |x: u128| add_mod(mul_mod(x, x, n), c, n);
+        let mut y: u128 = 2;
+        let mut x = y;
+        let mut ys = y;
+        let mut q: u128 = 1;
+        let mut g: u128 = 1;
+        let mut r: u32 = 1;
+
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = gcd(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+
+        if g == n {
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+        c += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_irreducibles() {
+        // Number of irreducible polynomials over GF(2) of degree 1 to 10
+        let expected: [usize; 10] = [2, 1, 2, 3, 6, 9, 18, 30, 56, 99];
+        for (i, &count) in expected.iter().enumerate() {
+            let deg = i + 1;
+            let found = ((1u128 << deg)..(1u128 << (deg + 1)))
+                .filter(|&p| is_irreducible_gf2(p))
+                .count();
+            assert_eq!(found, count);
+        }
+    }
+
+    #[test]
+    fn small_primitives() {
+        // Number of primitive polynomials over GF(2) of degree 1 to 10 (φ(2^n - 1) / n)
+        let expected: [usize; 10] = [1, 1, 2, 2, 6, 6, 18, 16, 48, 60];
+        for (i, &count) in expected.iter().enumerate() {
+            let deg = i + 1;
+            let found = ((1u128 << deg)..(1u128 << (deg + 1)))
+                .filter(|&p| is_primitive_gf2(p))
+                .count();
+            assert_eq!(found, count);
+        }
+    }
+
+    #[test]
+    fn known_polynomials() {
+        assert!(is_primitive_gf2(0x11D));
+        assert!(is_irreducible_gf2(0x11B));
+        assert!(!is_primitive_gf2(0x11B));
+        assert!(is_primitive_gf2(0x1100B));
+        assert!(is_irreducible_gf2(0x2_0000_2001));
+        assert!(is_irreducible_gf2(0x2_0000_0000_0004_0001));
+        assert!(is_irreducible_gf2(
+            0x8000_0000_0000_0000_0000_0000_0000_0003
+        ));
+        assert!(is_primitive_gf2(0x8000_0000_0000_0000_0000_0000_0000_0003));
+        // x^64 + x^4 + x^3 + x + 1
+        assert!(is_primitive_gf2((1u128 << 64) | 0x1B));
+        // x^128 does not fit so the largest degree is 127, x^127 + 1 is divisible by x + 1
+        assert!(!is_irreducible_gf2((1u128 << 127) | 1));
+        assert!(!is_irreducible_gf2(0));
+        assert!(!is_irreducible_gf2(1));
+        assert!(is_irreducible_gf2(0x2));
+        assert!(!is_primitive_gf2(0x2));
+        assert!(is_primitive_gf2(0x3));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_generation() {
+        let mut rng = rand::thread_rng();
+        for degree in [1, 2, 3, 8, 16, 33, 64, 100, 127] {
+            let poly = random_irreducible_gf2(degree, &mut rng);
+            assert_eq!(crate::calc_degree(poly), degree as i16);
+            assert!(is_irreducible_gf2(poly));
+        }
+        for degree in [1, 2, 3, 8, 16, 31, 64, 127] {
+            let poly = random_primitive_gf2(degree, &mut rng);
+            assert_eq!(crate::calc_degree(poly), degree as i16);
+            assert!(is_primitive_gf2(poly));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn random_degree_too_large() {
+        let _ = random_irreducible_gf2(128, &mut rand::thread_rng());
+    }
+
+    #[test]
+    fn named_constants() {
+        let gf2_m: [u128; 64] = [
+            GF2_1, GF2_2, GF2_3, GF2_4, GF2_5, GF2_6, GF2_7, GF2_8, GF2_9, GF2_10, GF2_11, GF2_12,
+            GF2_13, GF2_14, GF2_15, GF2_16, GF2_17, GF2_18, GF2_19, GF2_20, GF2_21, GF2_22, GF2_23,
+            GF2_24, GF2_25, GF2_26, GF2_27, GF2_28, GF2_29, GF2_30, GF2_31, GF2_32, GF2_33, GF2_34,
+            GF2_35, GF2_36, GF2_37, GF2_38, GF2_39, GF2_40, GF2_41, GF2_42, GF2_43, GF2_44, GF2_45,
+            GF2_46, GF2_47, GF2_48, GF2_49, GF2_50, GF2_51, GF2_52, GF2_53, GF2_54, GF2_55, GF2_56,
+            GF2_57, GF2_58, GF2_59, GF2_60, GF2_61, GF2_62, GF2_63, GF2_64,
+        ];
+        for (i, &poly) in gf2_m.iter().enumerate() {
+            assert_eq!(crate::calc_degree(poly), i as i16 + 1);
+            assert!(is_primitive_gf2(poly));
+        }
+
+        assert!(is_irreducible_gf2(AES));
+        assert!(!is_primitive_gf2(AES));
+        for poly in [
+            QR_CODE,
+            DATA_MATRIX,
+            CCSDS,
+            AZTEC_PARAM,
+            AZTEC_DATA_6,
+            AZTEC_DATA_10,
+            AZTEC_DATA_12,
+            PAR2,
+        ] {
+            assert!(is_primitive_gf2(poly));
+        }
+    }
+
+    #[test]
+    fn factoring() {
+        assert_eq!(
+            prime_factors((1 << 64) - 1),
+            vec![3, 5, 17, 257, 641, 65537, 6700417]
+        );
+        assert_eq!(prime_factors((1 << 67) - 1), vec![193707721, 761838257287]);
+        assert_eq!(prime_factors((1 << 127) - 1), vec![(1 << 127) - 1]);
+        assert_eq!(prime_factors(1), Vec::<u128>::new());
+    }
+}
@@ -0,0 +1,191 @@
+//! Rabin fingerprints and rolling hashes.
+//!
+//! The Rabin fingerprint of a byte string is the string read as a polynomial over GF(2), first byte highest degree
+//! and most significant bit first, modulo a fixed irreducible polynomial P. Appending a byte shifts the
+//! fingerprint left by 8 bits and folds the overflowing byte back with a table of its remainders. A `RollingHash`
+//! keeps the fingerprint of the last w bytes, removing the outgoing byte with a second table, which is what
+//! content defined chunking needs.
+//!
+//! ```
+//! use galois_field_2pm::rabin::Rabin;
+//!
+//! let rabin = Rabin::new(Rabin::DEFAULT_POLY);
+//! let data = b"the quick brown fox jumps over the lazy dog";
+//!
+//! let mut rolling = rabin.rolling(16);
+//! for &b in data.iter() {
+//!     rolling.roll(b);
+//! }
+//! assert_eq!(rolling.fingerprint(), rabin.fingerprint(&data[data.len() - 16..]));
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::polys::{self, mul_mod_gf2, pow_x_mod};
+
+/// Rabin fingerprints modulo an irreducible polynomial P of degree between 9 and 64
+#[derive(Clone, Debug)]
+pub struct Rabin {
+    poly: u128,
+    degree: u32,
+    // push[b] = b(x) * x^degree mod P, the reduction of a byte shifted out of the top
+    push: Vec<u64>,
+}
+
+impl Rabin {
+    /// The degree 53 polynomial `polys::GF2_53`
+    pub const DEFAULT_POLY: u128 = polys::GF2_53;
+
+    /// Constructs the fingerprint for poly. Panics unless poly is irreducible with degree between 9 and 64
+    pub fn new(poly: u128) -> Self {
+        let degree = crate::calc_degree(poly);
+        if !(9..=64).contains(&degree) {
+            panic!("Polynomial degree must be between 9 and 64");
+        }
+        let degree = degree as u32;
+        if !polys::is_irreducible_gf2(poly) {
+            panic!("Polynomial must be irreducible");
+        }
+        let top = pow_x_mod(degree as u128, poly);
+        let push = (0..256).map(|b| mul_mod_gf2(b, top, poly) as u64).collect();
+        Self { poly, degree, push }
+    }
+
+    /// The polynomial P
+    pub fn poly(&self) -> u128 {
+        self.poly
+    }
+
+    /// The degree of P, the number of bits in a fingerprint
+    pub fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    /// Appends a byte to the string with fingerprint fp and returns the new fingerprint
+    pub fn update(&self, fp: u64, byte: u8) -> u64 {
+        let mask = u64::MAX >> (64 - self.degree);
+        let top = fp >> (self.degree - 8);
+        (((fp << 8) | byte as u64) & mask) ^ self.push[top as usize]
+    }
+
+    /// Appends bytes to the string with fingerprint fp and returns the new fingerprint
+    pub fn update_slice(&self, fp: u64, bytes: &[u8]) -> u64 {
+        bytes.iter().fold(fp, |fp, &b| self.update(fp, b))
+    }
+
+    /// The fingerprint of data
+    pub fn fingerprint(&self, data: &[u8]) -> u64 {
+        self.update_slice(0, data)
+    }
+
+    /// A rolling hash over windows of window bytes. Panics if window is 0
+    pub fn rolling(&self, window: usize) -> RollingHash {
+        RollingHash::new(self.clone(), window)
+    }
+}
+
+/// The Rabin fingerprint of the last window bytes of a stream
+#[derive(Clone, Debug)]
+pub struct RollingHash {
+    rabin: Rabin,
+    window: usize,
+    // pop[b] = b(x) * x^(8 (window - 1)) mod P, the contribution of the oldest byte
+    pop: Vec<u64>,
+    bytes: VecDeque<u8>,
+    fp: u64,
+}
+
+impl RollingHash {
+    /// Starts an empty window of window bytes. Panics if window is 0
+    pub fn new(rabin: Rabin, window: usize) -> Self {
+        if window == 0 {
+            panic!("Window must hold at least one byte");
+        }
+        let shift = pow_x_mod(8 * (window as u128 - 1), rabin.poly);
+        let pop = (0..256)
+            .map(|b| mul_mod_gf2(b, shift, rabin.poly) as u64)
+            .collect();
+        Self {
+            rabin,
+            window,
+            pop,
+            bytes: VecDeque::with_capacity(window),
+            fp: 0,
+        }
+    }
+
+    /// The window length
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Whether window bytes have been seen
+    pub fn is_full(&self) -> bool {
+        self.bytes.len() == self.window
+    }
+
+    /// Slides the window over the next byte, dropping the oldest one once the window is full. Returns the new
+    /// fingerprint
+    pub fn roll(&mut self, byte: u8) -> u64 {
+        if self.is_full() {
+            let old = self.bytes.pop_front().unwrap();
+            self.fp ^= self.pop[old as usize];
+        }
+        self.bytes.push_back(byte);
+        self.fp = self.rabin.update(self.fp, byte);
+        self.fp
+    }
+
+    /// The fingerprint of the bytes in the window
+    pub fn fingerprint(&self) -> u64 {
+        self.fp
+    }
+
+    /// Empties the window
+    pub fn reset(&mut self) {
+        self.bytes.clear();
+        self.fp = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polys::rem_gf2;
+    use rand::Rng;
+
+    #[test]
+    fn matches_remainder() {
+        for poly in [polys::GF2_16, polys::GF2_53, polys::GF2_64] {
+            let rabin = Rabin::new(poly);
+            for len in [0, 1, 7, 15] {
+                let data: Vec<u8> = (0..len).map(|_| rand::thread_rng().gen()).collect();
+                let value = data.iter().fold(0u128, |acc, &b| (acc << 8) | b as u128);
+                assert_eq!(rabin.fingerprint(&data) as u128, rem_gf2(value, poly));
+            }
+        }
+    }
+
+    #[test]
+    fn rolling() {
+        let rabin = Rabin::new(Rabin::DEFAULT_POLY);
+        let data: Vec<u8> = (0..500).map(|_| rand::thread_rng().gen()).collect();
+        for window in [1, 2, 16, 48] {
+            let mut rolling = rabin.rolling(window);
+            for (i, &b) in data.iter().enumerate() {
+                let start = (i + 1).saturating_sub(window);
+                assert_eq!(rolling.roll(b), rabin.fingerprint(&data[start..=i]));
+                assert_eq!(rolling.is_full(), i + 1 >= window);
+            }
+            rolling.reset();
+            assert_eq!(rolling.fingerprint(), 0);
+            assert!(!rolling.is_full());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn reducible() {
+        let _ = Rabin::new(0x1_0000_0000_0000_0001);
+    }
+}
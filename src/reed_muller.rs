@@ -0,0 +1,265 @@
+//! Binary Reed-Muller codes.
+//!
+//! RM(r, m) has length n = 2<sup>m</sup>. Its codewords are the value tables of the polynomials of degree at most r
+//! in m binary variables: bit x of a codeword is the value at the point whose variable x<sub>i</sub> is bit i of x.
+//! The message holds the coefficients of the monomials, constant first, then by increasing degree and within a
+//! degree by the variables they contain, read as a binary number. The minimum distance is 2<sup>m-r</sup>.
+//!
+//! `decode` is Reed's majority logic decoder, correcting up to 2<sup>m-r-1</sup> - 1 errors. First order codes can
+//! also be decoded with the fast Hadamard transform, which is maximum likelihood.
+//!
+//! ```
+//! use galois_field_2pm::reed_muller::ReedMuller;
+//!
+//! // The (32, 6) code of the Mariner 9 probe corrects 7 errors
+//! let code = ReedMuller::new(1, 5);
+//! let message = [true, false, true, true, false, true];
+//! let mut received = code.encode(&message);
+//! for p in [0, 3, 8, 13, 21, 26, 31] {
+//!     received[p] ^= true;
+//! }
+//! assert_eq!(code.decode_hadamard(&received), Some(message.to_vec()));
+//! assert_eq!(code.decode(&mut received), Some(7));
+//! assert_eq!(received, code.encode(&message));
+//! ```
+
+use crate::bitmatrix::BitMatrix;
+
+/// The binary Reed-Muller code RM(r, m)
+#[derive(Clone, Debug)]
+pub struct ReedMuller {
+    r: usize,
+    m: usize,
+    // The monomials in message order, each a mask of its variables
+    monomials: Vec<usize>,
+}
+
+impl ReedMuller {
+    /// Constructs RM(r, m). Panics unless r ≤ m ≤ 20
+    pub fn new(r: usize, m: usize) -> Self {
+        if r > m || m > 20 {
+            panic!("Reed-Muller codes need r <= m <= 20");
+        }
+        let mut monomials: Vec<usize> = (0..1 << m)
+            .filter(|s: &usize| s.count_ones() as usize <= r)
+            .collect();
+        monomials.sort_by_key(|s| s.count_ones());
+        Self { r, m, monomials }
+    }
+
+    /// The order r
+    pub fn r(&self) -> usize {
+        self.r
+    }
+
+    /// The number of variables m
+    pub fn m(&self) -> usize {
+        self.m
+    }
+
+    /// The codeword length n = 2<sup>m</sup>
+    pub fn n(&self) -> usize {
+        1 << self.m
+    }
+
+    /// The message length k, the number of monomials of degree at most r
+    pub fn k(&self) -> usize {
+        self.monomials.len()
+    }
+
+    /// The minimum distance 2<sup>m-r</sup>
+    pub fn min_distance(&self) -> usize {
+        1 << (self.m - self.r)
+    }
+
+    /// The k × n generator matrix. Row i is the value table of monomial i
+    pub fn generator(&self) -> BitMatrix {
+        let mut g = BitMatrix::new(self.k(), self.n());
+        for (i, &s) in self.monomials.iter().enumerate() {
+            for x in 0..self.n() {
+                g.set(i, x, x & s == s);
+            }
+        }
+        g
+    }
+
+    /// Encodes a message into a codeword. Panics unless the message has k bits
+    pub fn encode(&self, message: &[bool]) -> Vec<bool> {
+        if message.len() != self.k() {
+            panic!("Message length does not match k");
+        }
+
+        // A monomial is one exactly at the points containing all of its variables
+        let mut codeword = vec![false; self.n()];
+        for (&s, _) in self.monomials.iter().zip(message).filter(|(_, &b)| b) {
+            for (x, c) in codeword.iter_mut().enumerate() {
+                *c ^= x & s == s;
+            }
+        }
+        codeword
+    }
+
+    /// Decodes a received word to its message with Reed's majority logic. Returns None if a vote is tied. Succeeds
+    /// with up to 2<sup>m-r-1</sup> - 1 errors. Panics unless received has n bits
+    pub fn decode_message(&self, received: &[bool]) -> Option<Vec<bool>> {
+        if received.len() != self.n() {
+            panic!("Received word length does not match n");
+        }
+
+        // The coefficient of a monomial s of the top degree d is the sum of the word over every coset of the
+        // subspace spanned by s, so each of the 2^(m-d) cosets casts a vote. Subtracting the decided terms reduces
+        // the degree by one
+        let mut word = received.to_vec();
+        let mut message = vec![false; self.k()];
+        let mut end = self.k();
+        for d in (0..=self.r).rev() {
+            let start = self
+                .monomials
+                .partition_point(|s| (s.count_ones() as usize) < d);
+            for (bit, &s) in message[start..end]
+                .iter_mut()
+                .zip(self.monomials[start..end].iter())
+            {
+                let mut ones = 0;
+                for base in (0..self.n()).filter(|&x| x & s == 0) {
+                    // Sum over the points base + y for the y inside s
+                    let mut y = s;
+                    let mut sum = word[base];
+                    while y != 0 {
+                        sum ^= word[base | y];
+                        y = (y - 1) & s;
+                    }
+                    ones += sum as usize;
+                }
+                let votes = 1 << (self.m - d);
+                if 2 * ones == votes {
+                    return None;
+                }
+                *bit = 2 * ones > votes;
+            }
+            for (_, &s) in message[start..end]
+                .iter()
+                .zip(self.monomials[start..end].iter())
+                .filter(|(&bit, _)| bit)
+            {
+                for (x, w) in word.iter_mut().enumerate() {
+                    *w ^= x & s == s;
+                }
+            }
+            end = start;
+        }
+        Some(message)
+    }
+
+    /// Corrects received in place with majority logic decoding. Returns the number of corrected bits, or None if a
+    /// vote was tied, in which case received is left unchanged. Panics unless received has n bits
+    pub fn decode(&self, received: &mut [bool]) -> Option<usize> {
+        let codeword = self.encode(&self.decode_message(received)?);
+        let corrected = received
+            .iter()
+            .zip(codeword.iter())
+            .filter(|(a, b)| a != b)
+            .count();
+        received.copy_from_slice(&codeword);
+        Some(corrected)
+    }
+
+    /// Maximum likelihood decoding of a first order code with the fast Hadamard transform. Returns the message of
+    /// the closest codeword, or None if several are closest. Panics unless r = 1 and received has n bits
+    pub fn decode_hadamard(&self, received: &[bool]) -> Option<Vec<bool>> {
+        if self.r != 1 {
+            panic!("Hadamard decoding needs a first order code");
+        }
+        if received.len() != self.n() {
+            panic!("Received word length does not match n");
+        }
+
+        // F(u) = Σ (-1)^(y(x) + u·x) is n minus twice the distance to the affine function u·x
+        let mut f: Vec<i32> = received.iter().map(|&b| if b { -1 } else { 1 }).collect();
+        let mut h = 1;
+        while h < f.len() {
+            for block in f.chunks_exact_mut(2 * h) {
+                let (lo, hi) = block.split_at_mut(h);
+                for (a, b) in lo.iter_mut().zip(hi.iter_mut()) {
+                    (*a, *b) = (*a + *b, *a - *b);
+                }
+            }
+            h *= 2;
+        }
+
+        let best = f.iter().map(|v| v.abs()).max().unwrap();
+        let mut winners = (0..f.len()).filter(|&u| f[u].abs() == best);
+        let u = winners.next().unwrap();
+        if winners.next().is_some() {
+            return None;
+        }
+        let mut message = vec![f[u] < 0];
+        message.extend((0..self.m).map(|i| (u >> i) & 1 == 1));
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    fn random_bits(len: usize) -> Vec<bool> {
+        (0..len).map(|_| rand::thread_rng().gen()).collect()
+    }
+
+    fn flip(word: &mut [bool], count: usize) {
+        let mut positions: Vec<usize> = (0..word.len()).collect();
+        positions.shuffle(&mut rand::thread_rng());
+        for &p in positions[..count].iter() {
+            word[p] ^= true;
+        }
+    }
+
+    #[test]
+    fn parameters() {
+        // k = Σ_(i ≤ r) C(m, i)
+        let code = ReedMuller::new(2, 5);
+        assert_eq!((code.n(), code.k(), code.min_distance()), (32, 16, 8));
+        assert_eq!(code.generator().rank(), 16);
+        assert_eq!(ReedMuller::new(0, 4).k(), 1);
+        assert_eq!(ReedMuller::new(4, 4).k(), 16);
+
+        // RM(m - r - 1, m) is the dual of RM(r, m)
+        let dual = ReedMuller::new(3, 5);
+        let g = ReedMuller::new(1, 5).generator();
+        assert!((&g * &dual.generator().transpose())
+            .row_masks()
+            .iter()
+            .all(|&m| m == 0));
+    }
+
+    #[test]
+    fn majority_logic() {
+        for (r, m) in [(1, 5), (2, 5), (2, 6), (3, 7)] {
+            let code = ReedMuller::new(r, m);
+            let t = (1 << (m - r - 1)) - 1;
+            for errors in 0..=t {
+                let message = random_bits(code.k());
+                let codeword = code.encode(&message);
+                let mut received = codeword.clone();
+                flip(&mut received, errors);
+                assert_eq!(code.decode_message(&received), Some(message));
+                assert_eq!(code.decode(&mut received), Some(errors));
+                assert_eq!(received, codeword);
+            }
+        }
+    }
+
+    #[test]
+    fn hadamard() {
+        let code = ReedMuller::new(1, 7);
+        for errors in [0, 10, 31] {
+            let message = random_bits(8);
+            let mut received = code.encode(&message);
+            flip(&mut received, errors);
+            assert_eq!(code.decode_hadamard(&received), Some(message));
+        }
+    }
+}
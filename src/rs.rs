@@ -0,0 +1,1309 @@
+//! Reed-Solomon codes over GF(2<sup>M</sup>).
+//!
+//! The primitive element α is taken to be x, so POLY must be a primitive polynomial. A code with n - k parity
+//! symbols has the generator polynomial g(x) = (x - α<sup>0</sup>)(x - α<sup>1</sup>)...(x - α<sup>n-k-1</sup>)
+//! and corrects up to t = (n - k) / 2 symbol errors. `ReedSolomonBuilder` replaces α by any generator element β
+//! and starts the roots at β<sup>fcr</sup> for a first consecutive root fcr, as published codes do: Data Matrix
+//! uses fcr = 1 and CCSDS uses β = α<sup>11</sup> with fcr = 112. The field polynomial is the POLY of the type.
+//!
+//! Codewords are stored highest degree first: index 0 holds the coefficient of x<sup>n-1</sup>. Encoding is
+//! systematic, so the first k symbols of a codeword are the message and the last n - k are the parity.
+//!
+//! Any n < 2<sup>M</sup> - 1 gives a shortened code: the (n, k) codewords are those of the full length
+//! (2<sup>M</sup> - 1, 2<sup>M</sup> - 1 - n + k) code whose leading symbols are zero, without storing the zeros.
+//! Parity symbols can also be punctured, that is not transmitted, and are then decoded as erasures.
+//!
+//! The codec works over any field type, including the computational `gf2::GFu32` and `gf2::GFu64` whose codewords
+//! can be millions of symbols long. Encoding takes O(k(n - k)) and decoding O(n(n - k)) multiplications, without
+//! FFTs, so long codes should keep n - k small.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2_lut};
+//! use galois_field_2pm::rs::ReedSolomon;
+//!
+//! type GF = gf2_lut::GFu8<0x11d>;
+//! let rs = ReedSolomon::<GF>::new(15, 11);
+//! let message: Vec<GF> = (1..=11).map(GF::new).collect();
+//!
+//! let mut codeword = rs.encode(&message);
+//! codeword[3] += GF::new(0x42);
+//! codeword[9] += GF::new(0x07);
+//!
+//! assert_eq!(rs.decode(&mut codeword), Some(2));
+//! assert_eq!(codeword[..11], message[..]);
+//! ```
+
+use crate::linalg::Matrix;
+use crate::poly::{PolyRemainderState, Polynomial};
+use crate::GaloisField;
+
+mod barcode;
+mod dvb;
+mod ethernet;
+mod interleaved;
+mod par2;
+mod qr;
+mod report;
+mod scratch;
+mod shards;
+mod stream;
+mod syndromes;
+
+pub use barcode::{
+    aztec, aztec_mode_message, data_matrix, AztecField10, AztecField12, AztecField6, AztecField8,
+    AztecModeField, DataMatrixField,
+};
+pub use dvb::{DvbField, DvbRs, DVB_PACKET_LEN, TS_PACKET_LEN};
+pub use ethernet::{pack_10bit, rs_fec_kp4, rs_fec_kr4, unpack_10bit, EthernetFecField};
+pub use interleaved::InterleavedRs;
+pub use par2::{Par2, Par2Field, PAR2_MAX_INPUTS};
+pub use qr::{QrBlocks, QrEcLevel, QrField};
+pub use report::{DecodeReport, DecodeStatus};
+pub use scratch::DecodeScratch;
+pub(crate) use shards::mul_add;
+pub use stream::StreamEncoder;
+pub use syndromes::{syndromes, syndromes_lut};
+
+/// The algorithm `ReedSolomon::decode` uses to solve the key equation Λ(x)S(x) = Ω(x) mod x<sup>n-k</sup>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyEquationSolver {
+    /// Berlekamp-Massey followed by a multiplication for the evaluator
+    #[default]
+    BerlekampMassey,
+    /// Sugiyama's algorithm, the extended Euclidean algorithm on x<sup>n-k</sup> and S(x) stopped halfway
+    Euclid,
+}
+
+/// A systematic Reed-Solomon (n, k) code
+#[derive(Clone, Debug)]
+pub struct ReedSolomon<GF: GaloisField> {
+    n: usize,
+    k: usize,
+    // The generator has the roots β^fcr, ..., β^(fcr + n - k - 1) for the generator element β
+    fcr: usize,
+    beta: GF,
+    generator: Polynomial<GF>,
+    solver: KeyEquationSolver,
+    // Sorted codeword indices of the parity symbols that are not transmitted
+    punctured: Vec<usize>,
+    // The k × (n - k) parity matrix of the shard codec, computed on first use
+    parity_matrix: std::sync::OnceLock<Matrix<GF>>,
+}
+
+impl<GF: GaloisField> ReedSolomon<GF> {
+    /// Constructs the code with codeword length n and message length k.
+    /// Panics unless 0 < k < n < 2<sup>M</sup>
+    pub fn new(n: usize, k: usize) -> Self {
+        ReedSolomonBuilder::new(n, k).build()
+    }
+
+    /// Starts configuring a code with codeword length n and message length k
+    pub fn builder(n: usize, k: usize) -> ReedSolomonBuilder<GF> {
+        ReedSolomonBuilder::new(n, k)
+    }
+
+    // The code whose generator has the roots α^fcr, ..., α^(fcr + n - k - 1)
+    pub(crate) fn with_first_root(n: usize, k: usize, fcr: usize) -> Self {
+        ReedSolomonBuilder::new(n, k).first_root(fcr).build()
+    }
+
+    /// Punctures the parity symbols at the given codeword indices. They are left out by encode_punctured and
+    /// restored as erasures by decode_punctured, so each costs one symbol of correction capability.
+    /// Panics unless the indices are distinct parity indices, k ≤ p < n
+    pub fn with_puncturing(mut self, positions: &[usize]) -> Self {
+        let mut punctured = positions.to_vec();
+        punctured.sort_unstable();
+        punctured.dedup();
+        if punctured.len() != positions.len()
+            || punctured.iter().any(|&p| p < self.k || p >= self.n)
+        {
+            panic!("Punctured positions must be distinct parity indices");
+        }
+        self.punctured = punctured;
+        self
+    }
+
+    /// The sorted codeword indices of the punctured parity symbols
+    pub fn punctured(&self) -> &[usize] {
+        &self.punctured
+    }
+
+    /// The number of symbols transmitted per codeword, n less the punctured symbols
+    pub fn transmitted_len(&self) -> usize {
+        self.n - self.punctured.len()
+    }
+
+    /// Selects the key equation solver used by decode
+    pub fn with_solver(mut self, solver: KeyEquationSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// The key equation solver used by decode
+    pub fn solver(&self) -> KeyEquationSolver {
+        self.solver
+    }
+
+    /// The first consecutive root fcr: the generator has the roots β<sup>fcr</sup>, ..., β<sup>fcr+n-k-1</sup>
+    pub fn first_root(&self) -> usize {
+        self.fcr
+    }
+
+    /// The generator element β whose powers are the roots of the generator polynomial
+    pub fn generator_element(&self) -> GF {
+        self.beta
+    }
+
+    /// The codeword length n
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The message length k
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The number of parity symbols n - k
+    pub fn parity_len(&self) -> usize {
+        self.n - self.k
+    }
+
+    /// The number of symbol errors that can be corrected, (n - k) / 2
+    pub fn t(&self) -> usize {
+        self.parity_len() / 2
+    }
+
+    /// The generator polynomial
+    pub fn generator_poly(&self) -> &Polynomial<GF> {
+        &self.generator
+    }
+
+    /// Computes the n - k parity symbols of a message. Panics unless the message has k symbols
+    pub fn parity(&self, message: &[GF]) -> Vec<GF> {
+        if message.len() != self.k {
+            panic!("Message length does not match k");
+        }
+
+        // The parity is -(m(x) * x^(n - k) mod g(x)), highest degree first
+        let mut state = PolyRemainderState::new(&self.generator);
+        state.update_slice(message);
+        state.remainder_coeffs().iter().rev().map(|&r| -r).collect()
+    }
+
+    /// Encodes a message into a codeword: the message followed by its parity. Panics unless the message has k symbols
+    pub fn encode(&self, message: &[GF]) -> Vec<GF> {
+        let mut codeword = message.to_vec();
+        codeword.extend(self.parity(message));
+        codeword
+    }
+
+    /// Encodes a message and leaves out the punctured symbols, giving transmitted_len symbols.
+    /// Panics unless the message has k symbols
+    pub fn encode_punctured(&self, message: &[GF]) -> Vec<GF> {
+        self.encode(message)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| self.punctured.binary_search(i).is_err())
+            .map(|(_, c)| c)
+            .collect()
+    }
+
+    /// Decodes a punctured word of transmitted_len symbols in place, treating the punctured symbols as erasures.
+    /// Returns the number of corrected symbols excluding the punctured ones, or None if the errors could not be
+    /// corrected, in which case received is left unchanged. Panics unless received has transmitted_len symbols
+    pub fn decode_punctured(&self, received: &mut [GF]) -> Option<usize> {
+        if received.len() != self.transmitted_len() {
+            panic!("Received word length does not match the transmitted length");
+        }
+
+        let mut full = Vec::with_capacity(self.n);
+        let mut symbols = received.iter();
+        for i in 0..self.n {
+            if self.punctured.binary_search(&i).is_ok() {
+                full.push(GF::ZERO);
+            } else {
+                full.push(*symbols.next().unwrap());
+            }
+        }
+
+        let errata = self.decode_with_erasures(&mut full, &self.punctured)?;
+        let mut corrected = 0;
+        let mut symbols = received.iter_mut();
+        for (i, &c) in full.iter().enumerate() {
+            if self.punctured.binary_search(&i).is_err() {
+                let r = symbols.next().unwrap();
+                if *r != c {
+                    corrected += 1;
+                    *r = c;
+                }
+            }
+        }
+        debug_assert!(corrected <= errata);
+        Some(corrected)
+    }
+
+    /// Computes the n - k syndromes r(β<sup>fcr+j</sup>), where β<sup>fcr</sup> is the first root of the generator. All are zero exactly when received is a codeword.
+    /// Panics unless received has n symbols
+    pub fn syndromes(&self, received: &[GF]) -> Vec<GF> {
+        let mut scratch = DecodeScratch::new();
+        self.syndromes_into(received, &mut scratch);
+        scratch.syndromes
+    }
+
+    // Writes the syndromes of received to scratch and returns whether they are all zero
+    fn syndromes_into(&self, received: &[GF], scratch: &mut DecodeScratch<GF>) -> bool {
+        if received.len() != self.n {
+            panic!("Received word length does not match n");
+        }
+
+        syndromes::syndromes_into(
+            received,
+            self.beta.pow(self.fcr as u128),
+            self.beta,
+            self.parity_len(),
+            &mut scratch.powers,
+            &mut scratch.syndromes,
+        );
+        scratch.syndromes.iter().all(|&s| s == GF::ZERO)
+    }
+
+    /// Whether received is a codeword
+    pub fn is_codeword(&self, received: &[GF]) -> bool {
+        self.syndromes(received).iter().all(|&s| s == GF::ZERO)
+    }
+
+    /// Working space sized for decoding this code with `decode_with_scratch`
+    pub fn scratch(&self) -> DecodeScratch<GF> {
+        DecodeScratch::with_capacity(self.parity_len())
+    }
+
+    /// Corrects up to t symbol errors in place using the selected key equation solver, Chien search and Forney's algorithm.
+    /// Returns the number of corrected symbols, or None if the errors could not be corrected, in which case
+    /// received is left unchanged. Panics unless received has n symbols
+    pub fn decode(&self, received: &mut [GF]) -> Option<usize> {
+        self.decode_with_erasures(received, &[])
+    }
+
+    /// Corrects errors and erasures in place. erasures holds the distinct indices of symbols known to be unreliable,
+    /// whose values are ignored. Decoding succeeds when 2 * errors + erasures ≤ n - k. Returns the number of errata
+    /// located, erasures included, or None if they could not be corrected, in which case received is left unchanged.
+    /// Panics unless received has n symbols and the erasure indices are less than n
+    pub fn decode_with_erasures(&self, received: &mut [GF], erasures: &[usize]) -> Option<usize> {
+        self.decode_with_scratch(received, erasures, &mut self.scratch())
+    }
+
+    /// Decodes like `decode_with_erasures`, keeping the intermediate vectors and polynomials in scratch instead of
+    /// allocating them. With a scratch from `ReedSolomon::scratch` reused across calls, decoding does not allocate.
+    /// The corrected positions and error values are left in `DecodeScratch::errata`. Panics unless received has n symbols and the erasure indices are
+    /// less than n
+    pub fn decode_with_scratch(
+        &self,
+        received: &mut [GF],
+        erasures: &[usize],
+        scratch: &mut DecodeScratch<GF>,
+    ) -> Option<usize> {
+        self.correct_with(received, erasures, scratch)
+            .then_some(scratch.errata.len())
+    }
+
+    /// Corrects errors and erasures in place like `decode_with_erasures`, and reports the corrected positions and
+    /// error values. Panics unless received has n symbols and the erasure indices are less than n
+    pub fn decode_report(&self, received: &mut [GF], erasures: &[usize]) -> DecodeReport<GF> {
+        let mut scratch = self.scratch();
+        let corrected = self.correct_with(received, erasures, &mut scratch);
+        DecodeReport::from_correction(corrected.then_some(scratch.errata))
+    }
+
+    // Corrects received, leaving the (position, error value) pairs in scratch. Returns false if received is
+    // uncorrectable
+    fn correct_with(
+        &self,
+        received: &mut [GF],
+        erasures: &[usize],
+        scratch: &mut DecodeScratch<GF>,
+    ) -> bool {
+        if erasures.iter().any(|&p| p >= self.n) {
+            panic!("Erasure index out of range");
+        }
+        // With n - k erasures there is no room left for errors
+        if erasures.len() >= self.parity_len() {
+            self.correct_erasures(received, erasures, scratch)
+        } else {
+            self.correct_errata(received, erasures, scratch)
+        }
+    }
+
+    fn correct_errata(
+        &self,
+        received: &mut [GF],
+        erasures: &[usize],
+        scratch: &mut DecodeScratch<GF>,
+    ) -> bool {
+        scratch.errata.clear();
+        if self.syndromes_into(received, scratch) {
+            return true;
+        }
+
+        self.erasure_locator(erasures, &mut scratch.erasure_locator);
+        if !self.solve_key_equation(scratch) {
+            return false;
+        }
+        let num_errata = scratch.locator.len() - 1;
+        let num_errors = num_errata - erasures.len();
+        if 2 * num_errors + erasures.len() > self.parity_len() {
+            return false;
+        }
+
+        self.chien_search(&scratch.locator, &mut scratch.positions);
+        if scratch.positions.len() != num_errata {
+            return false;
+        }
+
+        self.correct(received, scratch)
+    }
+
+    /// Decodes a word in place whose only corrupted symbols are the erasures, as when shards of a stored codeword
+    /// are lost. Skips the key equation and the Chien search, so it is faster than `decode_with_erasures`.
+    /// Returns the number of erasures, or None if there are more than n - k or received has errors elsewhere, in
+    /// which case received is left unchanged. Panics unless received has n symbols and the erasure indices are
+    /// less than n
+    pub fn decode_erasures(&self, received: &mut [GF], erasures: &[usize]) -> Option<usize> {
+        if erasures.iter().any(|&p| p >= self.n) {
+            panic!("Erasure index out of range");
+        }
+        let mut scratch = self.scratch();
+        self.correct_erasures(received, erasures, &mut scratch)
+            .then_some(scratch.errata.len())
+    }
+
+    fn correct_erasures(
+        &self,
+        received: &mut [GF],
+        erasures: &[usize],
+        scratch: &mut DecodeScratch<GF>,
+    ) -> bool {
+        scratch.errata.clear();
+        if erasures.len() > self.parity_len() {
+            return false;
+        }
+        if self.syndromes_into(received, scratch) {
+            return true;
+        }
+
+        // The errata locator is the erasure locator Γ(x), so Ω(x) = S(x)Γ(x) mod x^(n - k)
+        self.erasure_locator(erasures, &mut scratch.locator);
+        mul_truncated(
+            &scratch.syndromes,
+            &scratch.locator,
+            self.parity_len(),
+            &mut scratch.evaluator,
+        );
+
+        scratch.positions.clear();
+        scratch.positions.extend_from_slice(erasures);
+        self.correct(received, scratch)
+    }
+
+    // Writes Γ(x) = Π (1 - X x) over the erasure locators X = β^(n - 1 - p) to locator, lowest degree first
+    fn erasure_locator(&self, erasures: &[usize], locator: &mut Vec<GF>) {
+        locator.clear();
+        locator.push(GF::ONE);
+        for &p in erasures {
+            let x = self.beta.pow((self.n - 1 - p) as u128);
+            locator.push(GF::ZERO);
+            for i in (1..locator.len()).rev() {
+                let lower = locator[i - 1];
+                locator[i] -= x * lower;
+            }
+        }
+    }
+
+    // Subtracts the Forney magnitudes of scratch.locator and scratch.evaluator at scratch.positions from received,
+    // provided the result is a codeword, and leaves the subtracted (position, error value) pairs in
+    // scratch.errata. Otherwise received is restored
+    fn correct(&self, received: &mut [GF], scratch: &mut DecodeScratch<GF>) -> bool {
+        // The error at power p has locator X = β^p. With the first root β^fcr the magnitudes from Forney
+        // are scaled by X^(1 - fcr)
+        let DecodeScratch {
+            locator,
+            evaluator,
+            positions,
+            locators,
+            roots,
+            magnitudes,
+            errata,
+            ..
+        } = scratch;
+        locators.clear();
+        locators.extend(
+            positions
+                .iter()
+                .map(|&p| self.beta.pow((self.n - 1 - p) as u128)),
+        );
+        roots.clear();
+        roots.extend(locators.iter().map(|x| x.inverse()));
+        forney_into(locator, evaluator, roots, magnitudes);
+
+        errata.clear();
+        for (i, &p) in positions.iter().enumerate() {
+            let e = magnitudes[i] * locators[i] * roots[i].pow(self.fcr as u128);
+            received[p] -= e;
+            errata.push((p, e));
+        }
+        if !self.syndromes_into(received, scratch) {
+            for &(p, e) in scratch.errata.iter() {
+                received[p] += e;
+            }
+            scratch.errata.clear();
+            return false;
+        }
+        true
+    }
+
+    // Writes the errata locator Λ(x) = σ(x)Γ(x), with Λ(0) = 1, and the evaluator Ω(x) = S(x)Λ(x) mod x^(n - k)
+    // to scratch.locator and scratch.evaluator, where σ(x) locates the errors and Γ(x) = scratch.erasure_locator
+    // the erasures. Returns false if there is no solution
+    fn solve_key_equation(&self, scratch: &mut DecodeScratch<GF>) -> bool {
+        let DecodeScratch {
+            syndromes,
+            erasure_locator,
+            forney_syndromes,
+            locator,
+            evaluator,
+            error_locator,
+            previous,
+            updated,
+            ..
+        } = scratch;
+        let parity_len = self.parity_len();
+        let num_erasures = erasure_locator.len() - 1;
+
+        // The Forney syndromes T(x) = S(x)Γ(x) mod x^(n - k)
+        mul_truncated(syndromes, erasure_locator, parity_len, forney_syndromes);
+
+        match self.solver {
+            KeyEquationSolver::BerlekampMassey => {
+                // T_e, ..., T_(n-k-1) are generated by the LFSR σ(x)
+                forney_syndromes.resize(parity_len, GF::ZERO);
+                berlekamp_massey_into(
+                    &forney_syndromes[num_erasures..],
+                    error_locator,
+                    previous,
+                    updated,
+                );
+            }
+            KeyEquationSolver::Euclid => {
+                // Stopping at deg r < (n - k + e) / 2 bounds deg σ by (n - k - e) / 2. The remainders r and the
+                // cofactors t of S(x) are kept as (previous, evaluator) and (updated, error_locator)
+                let stop = (parity_len + num_erasures).div_ceil(2);
+                previous.clear();
+                previous.resize(parity_len, GF::ZERO);
+                previous.push(GF::ONE);
+                evaluator.clear();
+                evaluator.extend_from_slice(forney_syndromes);
+                updated.clear();
+                error_locator.clear();
+                error_locator.push(GF::ONE);
+                while evaluator.len() > stop {
+                    // Reduces the older remainder by the newer one a leading term at a time, the
+                    // quotient going into the cofactor
+                    while previous.len() >= evaluator.len() {
+                        let shift = previous.len() - evaluator.len();
+                        let q = *previous.last().unwrap() / *evaluator.last().unwrap();
+                        for (j, &c) in evaluator.iter().enumerate() {
+                            previous[j + shift] -= q * c;
+                        }
+                        trim(previous);
+                        if updated.len() < error_locator.len() + shift {
+                            updated.resize(error_locator.len() + shift, GF::ZERO);
+                        }
+                        for (j, &c) in error_locator.iter().enumerate() {
+                            updated[j + shift] -= q * c;
+                        }
+                    }
+                    trim(updated);
+                    core::mem::swap(previous, evaluator);
+                    core::mem::swap(updated, error_locator);
+                }
+                let scale = error_locator.first().copied().unwrap_or(GF::ZERO);
+                if scale == GF::ZERO {
+                    return false;
+                }
+                let scale_inv = scale.inverse();
+                for c in error_locator.iter_mut() {
+                    *c *= scale_inv;
+                }
+            }
+        }
+        mul_truncated(error_locator, erasure_locator, usize::MAX, locator);
+        mul_truncated(syndromes, locator, parity_len, evaluator);
+        true
+    }
+
+    // Writes the indices into the codeword of the errors to positions, i.e. the positions p where
+    // Λ(α^-(n - 1 - p)) = 0
+    fn chien_search(&self, locator: &[GF], positions: &mut Vec<usize>) {
+        let beta_inv = self.beta.inverse();
+        let mut x = GF::ONE;
+        positions.clear();
+        for power in 0..self.n {
+            if eval(locator, x) == GF::ZERO {
+                positions.push(self.n - 1 - power);
+            }
+            x *= beta_inv;
+        }
+    }
+}
+
+/// Configures a `ReedSolomon` code. By default the generator element is α = x and the first consecutive root is
+/// 0. The field polynomial is the POLY of the element type
+#[derive(Clone, Debug)]
+pub struct ReedSolomonBuilder<GF: GaloisField> {
+    n: usize,
+    k: usize,
+    fcr: usize,
+    beta: GF,
+    solver: KeyEquationSolver,
+}
+
+impl<GF: GaloisField> ReedSolomonBuilder<GF> {
+    /// Starts configuring a code with codeword length n and message length k
+    pub fn new(n: usize, k: usize) -> Self {
+        Self {
+            n,
+            k,
+            fcr: 0,
+            beta: GF::ONE.mul_x(),
+            solver: KeyEquationSolver::default(),
+        }
+    }
+
+    /// Sets the first consecutive root: the generator gets the roots β<sup>fcr</sup>, ..., β<sup>fcr+n-k-1</sup>
+    pub fn first_root(mut self, fcr: usize) -> Self {
+        self.fcr = fcr;
+        self
+    }
+
+    /// Sets the generator element β
+    pub fn generator_element(mut self, beta: GF) -> Self {
+        self.beta = beta;
+        self
+    }
+
+    /// Sets the key equation solver used by decode
+    pub fn solver(mut self, solver: KeyEquationSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// Builds the code. Panics unless 0 < k < n < 2<sup>M</sup> and the powers β<sup>0</sup>, ..., β<sup>n-1</sup>
+    /// are distinct, which holds for every n when β is primitive
+    pub fn build(self) -> ReedSolomon<GF> {
+        let (n, k) = (self.n, self.k);
+        if k == 0 || k >= n || n as u128 >= GF::NUM_ELEM {
+            panic!("Reed-Solomon codes need 0 < k < n < 2^M");
+        }
+        let mut power = self.beta;
+        for _ in 1..n {
+            if power == GF::ONE || power == GF::ZERO {
+                panic!("The generator element must have order at least n");
+            }
+            power *= self.beta;
+        }
+
+        let mut generator = Polynomial::one();
+        let mut root = self.beta.pow(self.fcr as u128);
+        for _ in 0..(n - k) {
+            generator *= Polynomial::new(vec![-root, GF::ONE]);
+            root *= self.beta;
+        }
+
+        ReedSolomon {
+            n,
+            k,
+            fcr: self.fcr,
+            beta: self.beta,
+            generator,
+            solver: self.solver,
+            punctured: Vec::new(),
+            parity_matrix: std::sync::OnceLock::new(),
+        }
+    }
+}
+
+/// Returns the connection polynomial Λ(x) = 1 + Λ<sub>1</sub>x + ... + Λ<sub>L</sub>x<sup>L</sup> of the shortest
+/// LFSR generating the sequence, so s<sub>i</sub> + Λ<sub>1</sub>s<sub>i-1</sub> + ... + Λ<sub>L</sub>s<sub>i-L</sub> = 0
+/// for L ≤ i < len. Applied to syndromes this is the error locator. The length L, the linear complexity, can exceed
+/// the degree when Λ<sub>L</sub> = 0. A sequence of length 2L determines an LFSR of length L uniquely
+pub fn berlekamp_massey<GF: GaloisField>(syndromes: &[GF]) -> Polynomial<GF> {
+    let mut locator = Vec::with_capacity(syndromes.len() + 1);
+    let mut previous = Vec::with_capacity(syndromes.len() + 1);
+    let mut updated = Vec::with_capacity(syndromes.len() + 1);
+    berlekamp_massey_into(syndromes, &mut locator, &mut previous, &mut updated);
+    Polynomial::new(locator)
+}
+
+// Berlekamp-Massey writing the connection polynomial to locator, lowest degree first without trailing zeros,
+// with previous and updated as working space
+fn berlekamp_massey_into<GF: GaloisField>(
+    syndromes: &[GF],
+    locator: &mut Vec<GF>,
+    previous: &mut Vec<GF>,
+    updated: &mut Vec<GF>,
+) {
+    locator.clear();
+    locator.push(GF::ONE);
+    previous.clear();
+    previous.push(GF::ONE);
+    let mut length = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = GF::ONE;
+
+    for i in 0..syndromes.len() {
+        let discrepancy = (0..=length.min(locator.len() - 1))
+            .fold(GF::ZERO, |acc, j| acc + locator[j] * syndromes[i - j]);
+        if discrepancy == GF::ZERO {
+            shift += 1;
+            continue;
+        }
+
+        // Λ(x) -= d / b * x^shift * B(x)
+        let scale = discrepancy / previous_discrepancy;
+        updated.clear();
+        updated.extend_from_slice(locator);
+        if updated.len() < previous.len() + shift {
+            updated.resize(previous.len() + shift, GF::ZERO);
+        }
+        for (j, &b) in previous.iter().enumerate() {
+            updated[j + shift] -= scale * b;
+        }
+
+        if 2 * length <= i {
+            length = i + 1 - length;
+            core::mem::swap(previous, locator);
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        core::mem::swap(locator, updated);
+    }
+    trim(locator);
+}
+
+/// Forney's algorithm. Returns -Ω(r) / Λ'(r) for each root r = X<sup>-1</sup> of the locator Λ(x), where X is an
+/// error locator and Ω(x) = S(x)Λ(x) mod x<sup>2t</sup> is the evaluator. With syndromes S<sub>j</sub> = R(α<sup>b+j</sup>)
+/// the error magnitude at X is X<sup>1-b</sup> times the returned value, so the values are the magnitudes
+/// themselves when the first consecutive root is α<sup>1</sup>. Panics if Λ'(r) = 0 for some root
+pub fn forney<GF: GaloisField>(
+    locator: &Polynomial<GF>,
+    evaluator: &Polynomial<GF>,
+    roots: &[GF],
+) -> Vec<GF> {
+    let mut magnitudes = Vec::with_capacity(roots.len());
+    forney_into(locator.coeffs(), evaluator.coeffs(), roots, &mut magnitudes);
+    magnitudes
+}
+
+// Forney's algorithm on coefficients lowest degree first, writing the values to magnitudes
+fn forney_into<GF: GaloisField>(
+    locator: &[GF],
+    evaluator: &[GF],
+    roots: &[GF],
+    magnitudes: &mut Vec<GF>,
+) {
+    magnitudes.clear();
+    magnitudes.extend(
+        roots
+            .iter()
+            .map(|&x| -(eval(evaluator, x) / eval_derivative(locator, x))),
+    );
+}
+
+// Horner's rule on coefficients lowest degree first
+fn eval<GF: GaloisField>(coeffs: &[GF], x: GF) -> GF {
+    coeffs.iter().rev().fold(GF::ZERO, |acc, &c| acc * x + c)
+}
+
+// The formal derivative at x. In characteristic 2 the even powers vanish, so it is Horner's rule in x^2 on the
+// odd coefficients
+fn eval_derivative<GF: GaloisField>(coeffs: &[GF], x: GF) -> GF {
+    let square = x * x;
+    coeffs
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .rev()
+        .fold(GF::ZERO, |acc, &c| acc * square + c)
+}
+
+// Writes a(x)b(x) mod x^len to product, lowest degree first without trailing zeros
+fn mul_truncated<GF: GaloisField>(a: &[GF], b: &[GF], len: usize, product: &mut Vec<GF>) {
+    product.clear();
+    product.resize((a.len() + b.len()).saturating_sub(1).min(len), GF::ZERO);
+    for (i, &x) in a.iter().enumerate().take(product.len()) {
+        for (j, &y) in b.iter().enumerate().take(product.len() - i) {
+            product[i + j] += x * y;
+        }
+    }
+    trim(product);
+}
+
+fn trim<GF: GaloisField>(coeffs: &mut Vec<GF>) {
+    while coeffs.last() == Some(&GF::ZERO) {
+        coeffs.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_symbols(n: usize) -> Vec<GF> {
+        (0..n)
+            .map(|_| GF::new(rand::thread_rng().gen_range(0..=255)))
+            .collect()
+    }
+
+    // Adds non zero errors at count distinct random positions
+    fn corrupt(codeword: &mut [GF], count: usize) -> Vec<usize> {
+        let mut positions: Vec<usize> = (0..codeword.len()).collect();
+        positions.shuffle(&mut rand::thread_rng());
+        positions.truncate(count);
+        for &p in positions.iter() {
+            codeword[p] += GF::new(rand::thread_rng().gen_range(1..=255));
+        }
+        positions
+    }
+
+    #[test]
+    fn qr_code_example() {
+        // Version 1-M "HELLO WORLD" from the QR code specification walkthrough
+        let data: Vec<GF> = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ]
+        .iter()
+        .map(|&v| GF::new(v))
+        .collect();
+        let ecc: Vec<GF> = [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+            .iter()
+            .map(|&v| GF::new(v))
+            .collect();
+
+        let rs = ReedSolomon::<GF>::new(26, 16);
+        assert_eq!(rs.parity(&data), ecc);
+    }
+
+    #[test]
+    fn encode() {
+        let rs = ReedSolomon::<GF>::new(255, 223);
+        assert_eq!(rs.generator_poly().degree(), 32);
+        assert_eq!(rs.t(), 16);
+
+        let message = random_symbols(223);
+        let codeword = rs.encode(&message);
+        assert_eq!(codeword.len(), 255);
+        assert_eq!(codeword[..223], message[..]);
+        assert!(rs.is_codeword(&codeword));
+
+        // The codeword polynomial is a multiple of the generator
+        let c = Polynomial::new(codeword.iter().rev().copied().collect());
+        assert!(c.rem(rs.generator_poly()).is_zero());
+    }
+
+    #[test]
+    fn correct_errors() {
+        for (n, k) in [(255, 223), (15, 11), (26, 16), (7, 5), (100, 60)] {
+            let rs = ReedSolomon::<GF>::new(n, k);
+            for errors in 0..=rs.t() {
+                let message = random_symbols(k);
+                let codeword = rs.encode(&message);
+                let mut received = codeword.clone();
+                corrupt(&mut received, errors);
+
+                assert_eq!(rs.decode(&mut received), Some(errors));
+                assert_eq!(received, codeword);
+            }
+        }
+    }
+
+    #[test]
+    fn too_many_errors() {
+        let rs = ReedSolomon::<GF>::new(255, 223);
+        let mut failures = 0;
+        for _ in 0..20 {
+            let codeword = rs.encode(&random_symbols(223));
+            let mut received = codeword.clone();
+            corrupt(&mut received, 20);
+            let before = received.clone();
+
+            // Beyond t the decoder either gives up without touching the word or finds another codeword
+            match rs.decode(&mut received) {
+                None => {
+                    assert_eq!(received, before);
+                    failures += 1;
+                }
+                Some(_) => assert!(rs.is_codeword(&received)),
+            }
+        }
+        assert!(failures > 0);
+    }
+
+    #[test]
+    fn reused_scratch() {
+        let rs = ReedSolomon::<GF>::new(60, 40);
+        let mut scratch = rs.scratch();
+        for errors in [3, 0, 10, 11, 1] {
+            let codeword = rs.encode(&random_symbols(40));
+            let mut received = codeword.clone();
+            corrupt(&mut received, errors);
+            let before = received.clone();
+            let erasures = [59];
+
+            let mut expected = received.clone();
+            let report = rs.decode_report(&mut expected, &erasures);
+            let decoded = rs.decode_with_scratch(&mut received, &erasures, &mut scratch);
+            assert_eq!(decoded, report.num_corrected());
+            assert_eq!(received, expected);
+            if decoded.is_some() {
+                assert_eq!(received, codeword);
+                let mut errata = scratch.errata().to_vec();
+                errata.sort_unstable_by_key(|&(p, _)| p);
+                let (positions, magnitudes): (Vec<usize>, Vec<GF>) = errata.into_iter().unzip();
+                assert_eq!(positions, report.positions);
+                assert_eq!(magnitudes, report.magnitudes);
+            } else {
+                assert_eq!(received, before);
+            }
+        }
+    }
+
+    // Counts the allocations of each thread, so tests running in parallel do not disturb the count
+    struct CountingAllocator;
+
+    std::thread_local! {
+        static ALLOCATIONS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+    }
+
+    fn allocations() -> usize {
+        ALLOCATIONS.with(|a| a.get())
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(
+            &self,
+            ptr: *mut u8,
+            layout: std::alloc::Layout,
+            new_size: usize,
+        ) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+            std::alloc::System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn scratch_does_not_allocate() {
+        fn check<GF: GaloisField>(n: usize, k: usize, solver: KeyEquationSolver) {
+            let rs = ReedSolomon::<GF>::builder(n, k).solver(solver).build();
+            let random = |len: usize| -> Vec<GF> {
+                (0..len)
+                    .map(|_| GF::from_u128(rand::thread_rng().gen::<u128>() % GF::NUM_ELEM))
+                    .collect()
+            };
+            // Correctable and uncorrectable words, with and without erasures
+            let mut words = Vec::new();
+            for (errors, erasures) in [(0, 0), (1, 0), (3, 4), (5, 0), (0, 10), (2, 6), (9, 0)] {
+                let mut word = rs.encode(&random(k));
+                let mut positions: Vec<usize> = (0..n).collect();
+                positions.shuffle(&mut rand::thread_rng());
+                for &p in positions[..errors + erasures].iter() {
+                    word[p] += GF::ONE;
+                }
+                words.push((word, positions[..erasures].to_vec()));
+            }
+
+            let mut scratch = rs.scratch();
+            let before = allocations();
+            for _ in 0..3 {
+                for (word, erasures) in words.iter_mut() {
+                    let _ = rs.decode_with_scratch(word, erasures, &mut scratch);
+                }
+            }
+            assert_eq!(allocations(), before, "{n} {k} {solver:?}");
+        }
+        for solver in [
+            KeyEquationSolver::BerlekampMassey,
+            KeyEquationSolver::Euclid,
+        ] {
+            check::<GF>(255, 239, solver);
+            check::<GF>(40, 30, solver);
+            check::<gf2::GFu16<0x1_002D>>(1000, 990, solver);
+        }
+    }
+
+    #[test]
+    fn computational_fields() {
+        // GF(2^32) allows codewords of millions of symbols
+        type GF32 = gf2::GFu32<{ crate::polys::GF2_32 }>;
+        let (n, k) = (1 << 20, (1 << 20) - 6);
+        let rs = ReedSolomon::<GF32>::new(n, k);
+        let message: Vec<GF32> = (0..k)
+            .map(|_| GF32::new(rand::thread_rng().gen()))
+            .collect();
+        let codeword = rs.encode(&message);
+        let mut received = codeword.clone();
+        received[0] += GF32::new(0xDEAD_BEEF);
+        received[n / 2] += GF32::ONE;
+        received[n - 1] += GF32::new(0x8000_0000);
+        assert_eq!(rs.decode(&mut received), Some(3));
+        assert_eq!(received, codeword);
+
+        type GF64 = gf2::GFu64<{ crate::polys::GF2_64 }>;
+        let rs = ReedSolomon::<GF64>::builder(300, 260)
+            .solver(KeyEquationSolver::Euclid)
+            .build();
+        let message: Vec<GF64> = (0..260)
+            .map(|_| GF64::new(rand::thread_rng().gen()))
+            .collect();
+        let codeword = rs.encode(&message);
+        let mut received = codeword.clone();
+        for p in [3, 100, 259, 260, 299] {
+            received[p] += GF64::new(rand::thread_rng().gen_range(1..=u64::MAX));
+        }
+        assert_eq!(rs.decode_with_erasures(&mut received, &[7, 8]), Some(7));
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn larger_field() {
+        type GF16 = gf2::GFu16<0x1002d>;
+        let rs = ReedSolomon::<GF16>::new(1000, 900);
+        let message: Vec<GF16> = (0..900)
+            .map(|_| GF16::new(rand::thread_rng().gen_range(0..=0xFFFF)))
+            .collect();
+        let codeword = rs.encode(&message);
+        let mut received = codeword.clone();
+        for p in [0, 17, 500, 899, 900, 999] {
+            received[p] += GF16::new(0x1234);
+        }
+        assert_eq!(rs.decode(&mut received), Some(6));
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn berlekamp_massey_lfsr() {
+        // s_i = s_(i-1) + 3 s_(i-2) comes from Λ(x) = 1 + x + 3x^2 (signs vanish in characteristic 2)
+        let mut s = vec![GF::new(1), GF::new(5)];
+        for i in 2..10 {
+            s.push(s[i - 1] + GF::new(3) * s[i - 2]);
+        }
+        assert_eq!(
+            berlekamp_massey(&s),
+            Polynomial::new(vec![GF::ONE, GF::ONE, GF::new(3)])
+        );
+        assert_eq!(berlekamp_massey(&[GF::ZERO; 4]), Polynomial::one());
+    }
+
+    #[test]
+    fn berlekamp_massey_random_lfsr() {
+        type GF64 = gf2::GFu64<0x2_0000_2001>;
+        let random = || GF64::new(rand::thread_rng().gen_range(1..(1u64 << 33)));
+        for length in 1..8 {
+            let taps: Vec<GF64> = (0..length).map(|_| random()).collect();
+            let mut s: Vec<GF64> = (0..length).map(|_| random()).collect();
+            for i in length..2 * length {
+                let next = (0..length).fold(GF64::ZERO, |acc, j| acc - taps[j] * s[i - 1 - j]);
+                s.push(next);
+            }
+
+            // 2L terms are enough to find the LFSR, which then generates the rest of the sequence
+            let locator = berlekamp_massey(&s);
+            assert!(locator.degree() <= length as isize);
+            let c = locator.coeffs();
+            for i in length..2 * length {
+                let sum = (0..c.len()).fold(GF64::ZERO, |acc, j| acc + c[j] * s[i - j]);
+                assert_eq!(sum, GF64::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn forney_known_locations() {
+        // With the error positions known, e.g. from a failed CRC, n - k errors can be corrected
+        let rs = ReedSolomon::<GF>::new(20, 12);
+        let codeword = rs.encode(&random_symbols(12));
+        let mut received = codeword.clone();
+        let positions = corrupt(&mut received, 8);
+
+        let alpha = GF::ONE.mul_x();
+        let locators: Vec<GF> = positions
+            .iter()
+            .map(|&p| alpha.pow(19 - p as u128))
+            .collect();
+        let locator = locators.iter().fold(Polynomial::one(), |acc, &x| {
+            acc * Polynomial::new(vec![GF::ONE, -x])
+        });
+        let mut evaluator = &Polynomial::new(rs.syndromes(&received)) * &locator;
+        evaluator.truncate(7);
+
+        let roots: Vec<GF> = locators.iter().map(|x| x.inverse()).collect();
+        let magnitudes = forney(&locator, &evaluator, &roots);
+        for ((&p, &x), &e) in positions.iter().zip(locators.iter()).zip(magnitudes.iter()) {
+            // The first root of the generator is α^0
+            received[p] -= x * e;
+        }
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn euclid_solver() {
+        let bm = ReedSolomon::<GF>::new(40, 28);
+        let euclid = ReedSolomon::<GF>::new(40, 28).with_solver(KeyEquationSolver::Euclid);
+        assert_eq!(bm.solver(), KeyEquationSolver::BerlekampMassey);
+        assert_eq!(euclid.solver(), KeyEquationSolver::Euclid);
+
+        // Both solvers agree, also past t where decoding may fail or miscorrect
+        for count in 0..=9 {
+            let codeword = bm.encode(&random_symbols(28));
+            let mut received = codeword.clone();
+            corrupt(&mut received, count);
+            let mut by_bm = received.clone();
+            let mut by_euclid = received.clone();
+            let result = euclid.decode(&mut by_euclid);
+            assert_eq!(bm.decode(&mut by_bm), result);
+            assert_eq!(by_bm, by_euclid);
+            if count <= 6 {
+                assert_eq!(result, Some(count));
+                assert_eq!(by_euclid, codeword);
+            }
+        }
+
+        // An odd number of parity symbols
+        let rs = ReedSolomon::<GF>::new(30, 23).with_solver(KeyEquationSolver::Euclid);
+        let codeword = rs.encode(&random_symbols(23));
+        let mut received = codeword.clone();
+        corrupt(&mut received, 3);
+        assert_eq!(rs.decode(&mut received), Some(3));
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn errors_and_erasures() {
+        for solver in [
+            KeyEquationSolver::BerlekampMassey,
+            KeyEquationSolver::Euclid,
+        ] {
+            let rs = ReedSolomon::<GF>::new(60, 44).with_solver(solver);
+            for erasures in 0..=16 {
+                let errors = (16 - erasures) / 2;
+                let codeword = rs.encode(&random_symbols(44));
+                let mut received = codeword.clone();
+                let mut positions = corrupt(&mut received, errors + erasures);
+                let erased = positions.split_off(errors);
+                // Erased symbols may hold anything, including the right value
+                if let Some(&p) = erased.first() {
+                    received[p] = codeword[p];
+                }
+
+                let located = rs.decode_with_erasures(&mut received, &erased);
+                assert!(located.is_some());
+                assert!(located.unwrap() <= errors + erasures);
+                assert_eq!(received, codeword);
+            }
+
+            // One error too many
+            let codeword = rs.encode(&random_symbols(44));
+            let mut received = codeword.clone();
+            let mut positions = corrupt(&mut received, 10);
+            let erased = positions.split_off(3);
+            let before = received.clone();
+            if rs.decode_with_erasures(&mut received, &erased).is_none() {
+                assert_eq!(received, before);
+            }
+        }
+    }
+
+    #[test]
+    fn report() {
+        let rs = ReedSolomon::<GF>::new(40, 30);
+        let codeword = rs.encode(&random_symbols(30));
+
+        let mut received = codeword.clone();
+        let report = rs.decode_report(&mut received, &[]);
+        assert_eq!(report.status, DecodeStatus::NoErrors);
+        assert_eq!(report.num_corrected(), Some(0));
+
+        received[7] += GF::new(0x11);
+        received[2] += GF::new(0xee);
+        received[30] = codeword[30];
+        let report = rs.decode_report(&mut received, &[30]);
+        assert_eq!(report.status, DecodeStatus::Corrected);
+        assert_eq!(report.positions, vec![2, 7, 30]);
+        assert_eq!(
+            report.magnitudes,
+            vec![GF::new(0xee), GF::new(0x11), GF::ZERO]
+        );
+        assert_eq!(report.num_corrected(), Some(3));
+        assert_eq!(report.num_errors(), 2);
+        assert_eq!(received, codeword);
+
+        let mut received = codeword.clone();
+        for p in [0, 5, 10, 15, 20, 25, 30, 35] {
+            received[p] += GF::new(0x80);
+        }
+        let before = received.clone();
+        let report = rs.decode_report(&mut received, &[]);
+        if report.status == DecodeStatus::Uncorrectable {
+            assert!(report.positions.is_empty());
+            assert_eq!(received, before);
+        }
+    }
+
+    #[test]
+    fn erasures_only() {
+        let rs = ReedSolomon::<GF>::builder(60, 44).first_root(3).build();
+        for erasures in [1, 7, 16] {
+            let codeword = rs.encode(&random_symbols(44));
+            let mut received = codeword.clone();
+            let erased = corrupt(&mut received, erasures);
+            let mut slow = received.clone();
+
+            assert_eq!(rs.decode_erasures(&mut received, &erased), Some(erasures));
+            assert_eq!(received, codeword);
+            assert_eq!(rs.decode_with_erasures(&mut slow, &erased), Some(erasures));
+            assert_eq!(slow, codeword);
+        }
+
+        // An error outside the erasures is detected rather than miscorrected
+        let codeword = rs.encode(&random_symbols(44));
+        let mut received = codeword.clone();
+        let mut positions = corrupt(&mut received, 9);
+        let erased = positions.split_off(1);
+        let before = received.clone();
+        assert_eq!(rs.decode_erasures(&mut received, &erased), None);
+        assert_eq!(received, before);
+    }
+
+    #[test]
+    fn shortened() {
+        // The DVB (204, 188) code is the (255, 239) code with 51 leading zeros left out
+        let short = ReedSolomon::<GF>::new(204, 188);
+        let full = ReedSolomon::<GF>::new(255, 239);
+        let message = random_symbols(188);
+        let mut padded = vec![GF::ZERO; 51];
+        padded.extend_from_slice(&message);
+        assert_eq!(short.encode(&message)[..], full.encode(&padded)[51..]);
+    }
+
+    #[test]
+    fn punctured() {
+        let rs = ReedSolomon::<GF>::new(40, 30).with_puncturing(&[31, 35, 39]);
+        assert_eq!(rs.punctured(), &[31, 35, 39]);
+        assert_eq!(rs.transmitted_len(), 37);
+
+        let message = random_symbols(30);
+        let transmitted = rs.encode_punctured(&message);
+        let codeword = rs.encode(&message);
+        assert_eq!(transmitted[..31], codeword[..31]);
+        assert_eq!(transmitted[31..34], codeword[32..35]);
+
+        // 3 erasures leave room for 3 errors
+        let mut received = transmitted.clone();
+        for p in [0, 17, 36] {
+            received[p] += GF::new(0x5a);
+        }
+        assert_eq!(rs.decode_punctured(&mut received), Some(3));
+        assert_eq!(received, transmitted);
+
+        let mut received = transmitted.clone();
+        for p in [0, 5, 17, 36] {
+            received[p] += GF::new(0x5a);
+        }
+        // Past the capability, so decoding fails or at best miscorrects
+        let _ = rs.decode_punctured(&mut received);
+        assert_ne!(received, transmitted);
+    }
+
+    #[test]
+    fn configured_roots() {
+        // CCSDS: roots β^112, ..., β^143 with β = α^11 in the field of x^8 + x^7 + x^2 + x + 1
+        type Ccsds = gf2_lut::GFu8<0x187>;
+        let beta = Ccsds::new(2).pow(11);
+        let rs = ReedSolomon::<Ccsds>::builder(255, 223)
+            .generator_element(beta)
+            .first_root(112)
+            .build();
+        assert_eq!(rs.first_root(), 112);
+        assert_eq!(rs.generator_element(), beta);
+
+        // The roots are closed under inversion, so the generator is palindromic
+        let g = rs.generator_poly().coeffs();
+        assert!((0..g.len()).all(|i| g[i] == g[g.len() - 1 - i]));
+
+        for errors in [1, 8, 16] {
+            let message: Vec<Ccsds> = (0..223)
+                .map(|_| Ccsds::new(rand::thread_rng().gen()))
+                .collect();
+            let codeword = rs.encode(&message);
+            let mut received = codeword.clone();
+            let mut positions: Vec<usize> = (0..255).collect();
+            positions.shuffle(&mut rand::thread_rng());
+            for &p in positions[..errors].iter() {
+                received[p] += Ccsds::new(rand::thread_rng().gen_range(1..=255));
+            }
+            let mut erased = received.clone();
+            assert_eq!(rs.decode(&mut received), Some(errors));
+            assert_eq!(received, codeword);
+            assert_eq!(
+                rs.decode_with_erasures(&mut erased, &positions[..errors]),
+                Some(errors)
+            );
+            assert_eq!(erased, codeword);
+        }
+
+        for fcr in [1, 5, 200] {
+            let rs = ReedSolomon::<GF>::builder(30, 20)
+                .first_root(fcr)
+                .solver(KeyEquationSolver::Euclid)
+                .build();
+            let codeword = rs.encode(&random_symbols(20));
+            let mut received = codeword.clone();
+            corrupt(&mut received, 5);
+            assert_eq!(rs.decode(&mut received), Some(5));
+            assert_eq!(received, codeword);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn generator_element_of_low_order() {
+        // α^17 has order 15
+        let _ = ReedSolomon::<GF>::builder(20, 10)
+            .generator_element(GF::new(2).pow(17))
+            .build();
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_parameters() {
+        let _ = ReedSolomon::<GF>::new(256, 200);
+    }
+}
@@ -0,0 +1,92 @@
+use super::ReedSolomon;
+use crate::{gf2, gf2_lut, GaloisField};
+
+/// GF(2<sup>8</sup>) with the polynomial x<sup>8</sup> + x<sup>5</sup> + x<sup>3</sup> + x<sup>2</sup> + 1 of Data Matrix
+/// ECC 200
+pub type DataMatrixField = gf2_lut::GFu8<0x12D>;
+
+/// GF(2<sup>4</sup>) with x<sup>4</sup> + x + 1, the field of the Aztec mode message
+pub type AztecModeField = gf2_lut::GFu8<0x13>;
+
+/// GF(2<sup>6</sup>) with x<sup>6</sup> + x + 1, for Aztec symbols of 1 and 2 layers
+pub type AztecField6 = gf2_lut::GFu8<0x43>;
+
+/// GF(2<sup>8</sup>) with x<sup>8</sup> + x<sup>5</sup> + x<sup>3</sup> + x<sup>2</sup> + 1, for Aztec symbols of 3 to 8
+/// layers
+pub type AztecField8 = gf2_lut::GFu8<0x12D>;
+
+/// GF(2<sup>10</sup>) with x<sup>10</sup> + x<sup>3</sup> + 1, for Aztec symbols of 9 to 22 layers
+pub type AztecField10 = gf2::GFu16<0x409>;
+
+/// GF(2<sup>12</sup>) with x<sup>12</sup> + x<sup>6</sup> + x<sup>5</sup> + x<sup>3</sup> + 1, for Aztec symbols of 23 to
+/// 32 layers
+pub type AztecField12 = gf2::GFu16<0x1069>;
+
+/// The Data Matrix ECC 200 code of one block with k data and n - k error correction codewords, whose generator
+/// has the roots α<sup>1</sup>, ..., α<sup>n-k</sup>. Panics unless 0 < k < n < 256
+pub fn data_matrix(n: usize, k: usize) -> ReedSolomon<DataMatrixField> {
+    ReedSolomon::with_first_root(n, k, 1)
+}
+
+/// The code of the Aztec mode message: 2 data and 5 check nibbles for compact symbols, 4 data and 6 check nibbles
+/// for full symbols
+pub fn aztec_mode_message(compact: bool) -> ReedSolomon<AztecModeField> {
+    if compact {
+        ReedSolomon::with_first_root(7, 2, 1)
+    } else {
+        ReedSolomon::with_first_root(10, 4, 1)
+    }
+}
+
+/// The code of an Aztec data message of n codewords, k of them data, over one of the Aztec fields. The generator has
+/// the roots α<sup>1</sup>, ..., α<sup>n-k</sup>. Panics unless 0 < k < n < 2<sup>M</sup>
+pub fn aztec<GF: GaloisField>(n: usize, k: usize) -> ReedSolomon<GF> {
+    ReedSolomon::with_first_root(n, k, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn roundtrip<GF: GaloisField>(rs: &ReedSolomon<GF>) {
+        let alpha = GF::ONE.mul_x();
+        for j in 1..=rs.parity_len() {
+            assert_eq!(rs.generator_poly().eval(alpha.pow(j as u128)), GF::ZERO);
+        }
+
+        let message: Vec<GF> = (0..rs.k())
+            .map(|_| GF::from_u128(rand::thread_rng().gen_range(0..GF::NUM_ELEM)))
+            .collect();
+        let codeword = rs.encode(&message);
+        let mut received = codeword.clone();
+        for i in 0..rs.t() {
+            received[3 * i % rs.n()] += GF::ONE;
+        }
+        assert_eq!(rs.decode(&mut received), Some(rs.t()));
+        assert_eq!(received, codeword);
+    }
+
+    #[test]
+    fn data_matrix_example() {
+        // "123456" in a 10 x 10 symbol (ISO/IEC 16022 annex O)
+        let rs = data_matrix(8, 3);
+        let data: Vec<DataMatrixField> = [142, 164, 186]
+            .iter()
+            .map(|&d| DataMatrixField::new(d))
+            .collect();
+        let ecc: Vec<u8> = rs.parity(&data).iter().map(|c| c.value).collect();
+        assert_eq!(ecc, [114, 25, 5, 88, 102]);
+        roundtrip(&data_matrix(144, 62));
+    }
+
+    #[test]
+    fn aztec_codes() {
+        roundtrip(&aztec_mode_message(true));
+        roundtrip(&aztec_mode_message(false));
+        roundtrip(&aztec::<AztecField6>(40, 20));
+        roundtrip(&aztec::<AztecField8>(200, 120));
+        roundtrip(&aztec::<AztecField10>(1000, 600));
+        roundtrip(&aztec::<AztecField12>(1400, 1000));
+    }
+}
@@ -0,0 +1,118 @@
+use super::ReedSolomon;
+use crate::{gf2_lut, GaloisField};
+
+/// GF(2<sup>8</sup>) with the polynomial x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x<sup>2</sup> + 1 of the DVB
+/// outer code
+pub type DvbField = gf2_lut::GFu8<0x11D>;
+
+/// The length of an MPEG-2 transport stream packet
+pub const TS_PACKET_LEN: usize = 188;
+
+/// The length of a transport stream packet protected by the outer code
+pub const DVB_PACKET_LEN: usize = 204;
+
+/// The RS(204, 188, t = 8) outer code of DVB-T, DVB-S and DVB-C (EN 300 744, EN 300 421), the (255, 239) code with
+/// generator roots λ<sup>0</sup>, ..., λ<sup>15</sup> shortened by 51 bytes. It protects whole transport stream
+/// packets, sync byte included
+#[derive(Clone, Debug)]
+pub struct DvbRs {
+    code: ReedSolomon<DvbField>,
+}
+
+impl DvbRs {
+    /// Constructs the code
+    pub fn new() -> Self {
+        Self {
+            code: ReedSolomon::new(DVB_PACKET_LEN, TS_PACKET_LEN),
+        }
+    }
+
+    /// The underlying Reed-Solomon code
+    pub fn code(&self) -> &ReedSolomon<DvbField> {
+        &self.code
+    }
+
+    /// Appends the 16 parity bytes to a transport stream packet
+    pub fn encode_packet(&self, packet: &[u8; TS_PACKET_LEN]) -> [u8; DVB_PACKET_LEN] {
+        let message: Vec<DvbField> = packet.iter().map(|&b| DvbField::new(b)).collect();
+        let mut out = [0; DVB_PACKET_LEN];
+        out[..TS_PACKET_LEN].copy_from_slice(packet);
+        for (o, p) in out[TS_PACKET_LEN..]
+            .iter_mut()
+            .zip(self.code.parity(&message))
+        {
+            *o = p.value;
+        }
+        out
+    }
+
+    /// Corrects up to 8 byte errors in a protected packet in place. Returns the number of corrected bytes, or None if
+    /// the packet is uncorrectable, in which case it is left unchanged and should be flagged with the transport
+    /// error indicator
+    pub fn decode_packet(&self, packet: &mut [u8; DVB_PACKET_LEN]) -> Option<usize> {
+        let mut received: Vec<DvbField> = packet.iter().map(|&b| DvbField::new(b)).collect();
+        let corrected = self.code.decode(&mut received)?;
+        for (p, r) in packet.iter_mut().zip(received) {
+            *p = r.value;
+        }
+        Some(corrected)
+    }
+}
+
+impl Default for DvbRs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    fn random_packet() -> [u8; TS_PACKET_LEN] {
+        let mut packet = [0; TS_PACKET_LEN];
+        rand::thread_rng().fill(&mut packet[..]);
+        packet[0] = 0x47;
+        packet
+    }
+
+    #[test]
+    fn packets() {
+        let dvb = DvbRs::new();
+        assert_eq!(dvb.code().t(), 8);
+        let alpha = DvbField::new(2);
+        for j in 0..16 {
+            assert_eq!(
+                dvb.code().generator_poly().eval(alpha.pow(j)),
+                DvbField::ZERO
+            );
+        }
+
+        for errors in [0, 1, 8] {
+            let packet = random_packet();
+            let protected = dvb.encode_packet(&packet);
+            assert_eq!(protected[..TS_PACKET_LEN], packet);
+
+            let mut received = protected;
+            let mut positions: Vec<usize> = (0..DVB_PACKET_LEN).collect();
+            positions.shuffle(&mut rand::thread_rng());
+            for &p in positions[..errors].iter() {
+                received[p] ^= rand::thread_rng().gen_range(1..=255);
+            }
+            assert_eq!(dvb.decode_packet(&mut received), Some(errors));
+            assert_eq!(received, protected);
+        }
+    }
+
+    #[test]
+    fn zero_packet() {
+        // The all zero packet is a codeword, and a null packet with the sync byte is not
+        let dvb = DvbRs::default();
+        assert_eq!(dvb.encode_packet(&[0; TS_PACKET_LEN]), [0; DVB_PACKET_LEN]);
+        let mut packet = [0; TS_PACKET_LEN];
+        packet[0] = 0x47;
+        assert_ne!(dvb.encode_packet(&packet)[TS_PACKET_LEN..], [0; 16]);
+    }
+}
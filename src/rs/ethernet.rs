@@ -0,0 +1,101 @@
+use super::ReedSolomon;
+use crate::{gf2, GaloisField};
+
+/// GF(2<sup>10</sup>) with the polynomial x<sup>10</sup> + x<sup>3</sup> + 1 of the IEEE 802.3 RS-FEC
+pub type EthernetFecField = gf2::GFu16<0x409>;
+
+/// The RS(528, 514, t = 7) "KR4" FEC of IEEE 802.3 clause 91, used by 100GBASE-KR4 and -CR4
+pub fn rs_fec_kr4() -> ReedSolomon<EthernetFecField> {
+    ReedSolomon::new(528, 514)
+}
+
+/// The RS(544, 514, t = 15) "KP4" FEC of IEEE 802.3 clauses 91 and 119, used by 100GBASE-KP4 and the PAM4 PHYs
+pub fn rs_fec_kp4() -> ReedSolomon<EthernetFecField> {
+    ReedSolomon::new(544, 514)
+}
+
+/// Packs a serial bit stream into 10 bit symbols. Like the rest of Ethernet the first bit of each group of 10 is the
+/// least significant bit of the symbol. Panics unless the number of bits is a multiple of 10
+pub fn pack_10bit(bits: &[bool]) -> Vec<EthernetFecField> {
+    if !bits.len().is_multiple_of(10) {
+        panic!("The bit stream must hold whole 10 bit symbols");
+    }
+    bits.chunks(10)
+        .map(|chunk| {
+            let value = chunk
+                .iter()
+                .enumerate()
+                .fold(0u16, |acc, (i, &bit)| acc | ((bit as u16) << i));
+            EthernetFecField::new(value)
+        })
+        .collect()
+}
+
+/// Serializes 10 bit symbols into a bit stream, least significant bit first
+pub fn unpack_10bit(symbols: &[EthernetFecField]) -> Vec<bool> {
+    symbols
+        .iter()
+        .flat_map(|s| (0..10).map(move |i| (s.value >> i) & 1 == 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn parameters() {
+        let kr4 = rs_fec_kr4();
+        assert_eq!((kr4.n(), kr4.k(), kr4.t()), (528, 514, 7));
+        let kp4 = rs_fec_kp4();
+        assert_eq!((kp4.n(), kp4.k(), kp4.t()), (544, 514, 15));
+
+        // g(x) = (x - α^0)...(x - α^(2t - 1))
+        let alpha = EthernetFecField::ONE.mul_x();
+        for j in 0..30 {
+            assert_eq!(
+                kp4.generator_poly().eval(alpha.pow(j)),
+                EthernetFecField::ZERO
+            );
+        }
+    }
+
+    #[test]
+    fn packing() {
+        let mut bits = vec![false; 20];
+        bits[0] = true;
+        bits[19] = true;
+        let symbols = pack_10bit(&bits);
+        assert_eq!(
+            symbols,
+            [EthernetFecField::new(1), EthernetFecField::new(0x200)]
+        );
+        assert_eq!(unpack_10bit(&symbols), bits);
+    }
+
+    #[test]
+    fn bit_stream() {
+        // A codeword of KP4 spans 5440 bits, and a burst of 141 bit errors touches at most 15 symbols
+        let kp4 = rs_fec_kp4();
+        let message: Vec<bool> = (0..5140).map(|_| rand::thread_rng().gen()).collect();
+        let codeword = unpack_10bit(&kp4.encode(&pack_10bit(&message)));
+        assert_eq!(codeword.len(), 5440);
+        assert_eq!(codeword[..5140], message[..]);
+
+        let mut received = codeword.clone();
+        let start = 10 * rand::thread_rng().gen_range(0..529) + 9;
+        for bit in received[start..start + 141].iter_mut() {
+            *bit = !*bit;
+        }
+        let mut symbols = pack_10bit(&received);
+        assert_eq!(kp4.decode(&mut symbols), Some(15));
+        assert_eq!(unpack_10bit(&symbols), codeword);
+    }
+
+    #[test]
+    #[should_panic]
+    fn partial_symbol() {
+        let _ = pack_10bit(&[true; 15]);
+    }
+}
@@ -0,0 +1,146 @@
+use super::ReedSolomon;
+use crate::GaloisField;
+
+/// Block interleaving of depth I over a Reed-Solomon code. A buffer holds I codewords symbol by symbol: position
+/// j * I + i has symbol j of codeword i. A burst of up to I * t consecutive symbol errors then hits each codeword at
+/// most t times. The first I * k symbols of an interleaved codeword are the message, in the same order
+#[derive(Clone, Debug)]
+pub struct InterleavedRs<GF: GaloisField> {
+    code: ReedSolomon<GF>,
+    depth: usize,
+}
+
+impl<GF: GaloisField> InterleavedRs<GF> {
+    /// Interleaves depth codewords of code. Panics if depth is zero
+    pub fn new(code: ReedSolomon<GF>, depth: usize) -> Self {
+        if depth == 0 {
+            panic!("The interleaving depth must be at least 1");
+        }
+        Self { code, depth }
+    }
+
+    /// The underlying Reed-Solomon code
+    pub fn code(&self) -> &ReedSolomon<GF> {
+        &self.code
+    }
+
+    /// The interleaving depth I
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The interleaved codeword length I * n
+    pub fn n(&self) -> usize {
+        self.depth * self.code.n()
+    }
+
+    /// The interleaved message length I * k
+    pub fn k(&self) -> usize {
+        self.depth * self.code.k()
+    }
+
+    /// The longest burst of symbol errors that can always be corrected, I * t
+    pub fn burst_len(&self) -> usize {
+        self.depth * (self.code.parity_len() / 2)
+    }
+
+    /// Encodes a message of I * k symbols into an interleaved codeword of I * n symbols. Panics unless the message
+    /// has I * k symbols
+    pub fn encode(&self, message: &[GF]) -> Vec<GF> {
+        if message.len() != self.k() {
+            panic!("Message length does not match I * k");
+        }
+
+        let mut out = message.to_vec();
+        out.resize(self.n(), GF::ZERO);
+        for i in 0..self.depth {
+            let column = self.deinterleave(message, i);
+            for (j, p) in self.code.parity(&column).into_iter().enumerate() {
+                out[(self.code.k() + j) * self.depth + i] = p;
+            }
+        }
+        out
+    }
+
+    /// Corrects the interleaved codewords in place. Returns the total number of corrected symbols, or None if any
+    /// of the codewords could not be corrected, in which case received is left unchanged. Panics unless received
+    /// has I * n symbols
+    pub fn decode(&self, received: &mut [GF]) -> Option<usize> {
+        if received.len() != self.n() {
+            panic!("Received word length does not match I * n");
+        }
+
+        let mut corrected = received.to_vec();
+        let mut total = 0;
+        for i in 0..self.depth {
+            let mut column = self.deinterleave(received, i);
+            total += self.code.decode(&mut column)?;
+            for (j, s) in column.into_iter().enumerate() {
+                corrected[j * self.depth + i] = s;
+            }
+        }
+        received.copy_from_slice(&corrected);
+        Some(total)
+    }
+
+    // The symbols of codeword i
+    fn deinterleave(&self, buffer: &[GF], i: usize) -> Vec<GF> {
+        buffer.iter().skip(i).step_by(self.depth).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_symbols(len: usize) -> Vec<GF> {
+        (0..len)
+            .map(|_| GF::new(rand::thread_rng().gen()))
+            .collect()
+    }
+
+    #[test]
+    fn matches_codewords() {
+        let rs = ReedSolomon::<GF>::new(20, 12);
+        let interleaved = InterleavedRs::new(rs.clone(), 3);
+        let message = random_symbols(36);
+        let encoded = interleaved.encode(&message);
+        assert_eq!(encoded.len(), 60);
+        assert_eq!(encoded[..36], message[..]);
+        for i in 0..3 {
+            let column: Vec<GF> = message.iter().skip(i).step_by(3).copied().collect();
+            let codeword = rs.encode(&column);
+            assert!((0..20).all(|j| encoded[j * 3 + i] == codeword[j]));
+        }
+    }
+
+    #[test]
+    fn burst() {
+        let interleaved = InterleavedRs::new(ReedSolomon::<GF>::new(32, 24), 5);
+        assert_eq!(interleaved.burst_len(), 20);
+        for start in [0, 33, 140] {
+            let codeword = interleaved.encode(&random_symbols(120));
+            let mut received = codeword.clone();
+            for r in received[start..start + 20].iter_mut() {
+                *r += GF::new(rand::thread_rng().gen_range(1..=255));
+            }
+            assert_eq!(interleaved.decode(&mut received), Some(20));
+            assert_eq!(received, codeword);
+        }
+
+        // The burst is too long for one of the codewords
+        let codeword = interleaved.encode(&random_symbols(120));
+        let mut received = codeword.clone();
+        for r in received[..26].iter_mut() {
+            *r += GF::ONE;
+        }
+        let before = received.clone();
+        if interleaved.decode(&mut received).is_none() {
+            assert_eq!(received, before);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use super::shards::mul_add;
+use crate::linalg::Matrix;
+use crate::{gf2_lut, GaloisField};
+
+/// GF(2<sup>16</sup>) with the polynomial x<sup>16</sup> + x<sup>12</sup> + x<sup>3</sup> + x + 1 of PAR2
+pub type Par2Field = gf2_lut::GFu16<0x1100B>;
+
+/// The largest number of input slices PAR2 supports
+pub const PAR2_MAX_INPUTS: usize = 32768;
+
+/// The Reed-Solomon code of the PAR 2.0 specification. Slices are byte buffers of little endian 16 bit words. Input
+/// slice i has the constant c<sub>i</sub> = 2<sup>n<sub>i</sub></sup>, where n<sub>0</sub>, n<sub>1</sub>, ... are
+/// the exponents coprime to 65535 in increasing order, and the recovery slice with exponent e is
+/// Σ c<sub>i</sub><sup>e</sup> * input<sub>i</sub>.
+///
+/// The matrix of this construction is not always invertible, so a recovery may fail even with as many recovery slices
+/// as missing inputs. PAR2 clients then try other recovery slices
+#[derive(Clone, Debug)]
+pub struct Par2 {
+    constants: Vec<Par2Field>,
+}
+
+impl Par2 {
+    /// Sets up the code for num_inputs input slices. Panics unless 0 < num_inputs ≤ 32768
+    pub fn new(num_inputs: usize) -> Self {
+        if num_inputs == 0 || num_inputs > PAR2_MAX_INPUTS {
+            panic!("PAR2 needs between 1 and 32768 input slices");
+        }
+
+        // 65535 = 3 * 5 * 17 * 257
+        let two = Par2Field::new(2);
+        let constants = (1u128..)
+            .filter(|n| [3, 5, 17, 257].iter().all(|p| !n.is_multiple_of(*p)))
+            .take(num_inputs)
+            .map(|n| two.pow(n))
+            .collect();
+        Self { constants }
+    }
+
+    /// The number of input slices
+    pub fn num_inputs(&self) -> usize {
+        self.constants.len()
+    }
+
+    /// The constants c<sub>i</sub> of the input slices
+    pub fn constants(&self) -> &[Par2Field] {
+        &self.constants
+    }
+
+    /// Computes the recovery slice with the given exponent. Panics unless there are num_inputs input slices of equal
+    /// even length
+    pub fn recovery_slice(&self, exponent: u16, inputs: &[&[u8]]) -> Vec<u8> {
+        if inputs.len() != self.num_inputs() {
+            panic!("Number of input slices does not match");
+        }
+        let len = check_slice_len(inputs.iter().map(|s| s.len()));
+
+        let mut recovery = vec![0u8; len];
+        for (&c, input) in self.constants.iter().zip(inputs) {
+            mul_add(c.pow(exponent as u128), input, &mut recovery);
+        }
+        recovery
+    }
+
+    /// Rebuilds missing input slices in place from recovery slices, given as (exponent, slice) pairs. Uses as many
+    /// recovery slices as there are missing inputs, in the given order. Returns false, leaving inputs unchanged, if
+    /// there are too few recovery slices or the chosen ones do not determine the missing inputs. Panics unless there
+    /// are num_inputs entries in inputs and all slices have equal even length
+    pub fn recover(&self, inputs: &mut [Option<Vec<u8>>], recovery: &[(u16, &[u8])]) -> bool {
+        if inputs.len() != self.num_inputs() {
+            panic!("Number of input slices does not match");
+        }
+        let missing: Vec<usize> = (0..inputs.len()).filter(|&i| inputs[i].is_none()).collect();
+        if missing.is_empty() {
+            return true;
+        }
+        if recovery.len() < missing.len() {
+            return false;
+        }
+        let recovery = &recovery[..missing.len()];
+        let len = check_slice_len(
+            inputs
+                .iter()
+                .flatten()
+                .map(|s| s.len())
+                .chain(recovery.iter().map(|(_, s)| s.len())),
+        );
+
+        // Row r of the system is Σ_missing c_i^e_r x_i = recovery_r - Σ_present c_i^e_r input_i
+        let mut system = Matrix::new(missing.len(), missing.len());
+        for (r, &(e, _)) in recovery.iter().enumerate() {
+            for (c, &i) in missing.iter().enumerate() {
+                system.set(r, c, self.constants[i].pow(e as u128));
+            }
+        }
+        let Some(solve) = system.inverse() else {
+            return false;
+        };
+
+        let residuals: Vec<Vec<u8>> = recovery
+            .iter()
+            .map(|&(e, slice)| {
+                let mut residual = slice.to_vec();
+                for (i, input) in inputs.iter().enumerate() {
+                    if let Some(input) = input {
+                        mul_add(self.constants[i].pow(e as u128), input, &mut residual);
+                    }
+                }
+                residual
+            })
+            .collect();
+        for (c, &i) in missing.iter().enumerate() {
+            let mut rebuilt = vec![0u8; len];
+            for (r, residual) in residuals.iter().enumerate() {
+                mul_add(solve.get(c, r), residual, &mut rebuilt);
+            }
+            inputs[i] = Some(rebuilt);
+        }
+        true
+    }
+}
+
+// The common length of the slices, which must be even
+fn check_slice_len(mut lens: impl Iterator<Item = usize>) -> usize {
+    let len = lens.next().unwrap_or(0);
+    if !len.is_multiple_of(2) || lens.any(|l| l != len) {
+        panic!("Slices must have the same even length");
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn random_slices(count: usize, len: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|_| (0..len).map(|_| rand::thread_rng().gen()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn constants() {
+        // The first exponents coprime to 65535 are 1, 2, 4, 7, 8, 11, 13, 14, 16
+        let par2 = Par2::new(9);
+        let values: Vec<u16> = par2.constants().iter().map(|c| c.value).collect();
+        assert_eq!(
+            values,
+            vec![0x2, 0x4, 0x10, 0x80, 0x100, 0x800, 0x2000, 0x4000, 0x100B]
+        );
+    }
+
+    #[test]
+    fn recovery_slices() {
+        let par2 = Par2::new(5);
+        let inputs = random_slices(5, 20);
+        let refs: Vec<&[u8]> = inputs.iter().map(|s| s.as_slice()).collect();
+
+        // Exponent 0 gives the XOR of the inputs
+        let parity = par2.recovery_slice(0, &refs);
+        let xor: Vec<u8> = (0..20)
+            .map(|b| inputs.iter().fold(0, |acc, s| acc ^ s[b]))
+            .collect();
+        assert_eq!(parity, xor);
+
+        // Word w of recovery slice e is Σ c_i^e * word w of input i
+        let recovery = par2.recovery_slice(3, &refs);
+        let word =
+            |s: &[u8], w: usize| Par2Field::new(u16::from_le_bytes([s[2 * w], s[2 * w + 1]]));
+        for w in 0..10 {
+            let expected = (0..5).fold(Par2Field::ZERO, |acc, i| {
+                acc + par2.constants()[i].pow(3) * word(&inputs[i], w)
+            });
+            assert_eq!(word(&recovery, w), expected);
+        }
+    }
+
+    #[test]
+    fn recover() {
+        let par2 = Par2::new(40);
+        let inputs = random_slices(40, 64);
+        let refs: Vec<&[u8]> = inputs.iter().map(|s| s.as_slice()).collect();
+        let recovery: Vec<Vec<u8>> = (0..6).map(|e| par2.recovery_slice(e, &refs)).collect();
+        let pairs: Vec<(u16, &[u8])> = recovery
+            .iter()
+            .enumerate()
+            .map(|(e, s)| (e as u16, s.as_slice()))
+            .collect();
+
+        let mut damaged: Vec<Option<Vec<u8>>> = inputs.iter().cloned().map(Some).collect();
+        for i in [0, 7, 8, 31, 39] {
+            damaged[i] = None;
+        }
+        assert!(!par2.recover(&mut damaged, &pairs[..4]));
+        assert!(damaged[0].is_none());
+        assert!(par2.recover(&mut damaged, &pairs[1..]));
+        let rebuilt: Vec<Vec<u8>> = damaged.into_iter().map(|s| s.unwrap()).collect();
+        assert_eq!(rebuilt, inputs);
+    }
+}
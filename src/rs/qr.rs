@@ -0,0 +1,292 @@
+use super::ReedSolomon;
+use crate::gf2_lut;
+use crate::GaloisField;
+
+/// GF(2<sup>8</sup>) with the polynomial x<sup>8</sup> + x<sup>4</sup> + x<sup>3</sup> + x<sup>2</sup> + 1 of QR codes
+pub type QrField = gf2_lut::GFu8<0x11D>;
+
+/// The error correction level of a QR code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QrEcLevel {
+    /// Recovers about 7% of the codewords
+    L,
+    /// Recovers about 15% of the codewords
+    M,
+    /// Recovers about 25% of the codewords
+    Q,
+    /// Recovers about 30% of the codewords
+    H,
+}
+
+// Error correction codewords per block and number of blocks, indexed by level and then version - 1 (ISO/IEC 18004 table 9)
+const EC_PER_BLOCK: [[u8; 40]; 4] = [
+    [
+        7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30,
+        30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28,
+        28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+    ],
+    [
+        13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30,
+        30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+];
+
+const NUM_BLOCKS: [[u8; 40]; 4] = [
+    [
+        1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13,
+        14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+    ],
+    [
+        1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23,
+        25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+    ],
+    [
+        1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29,
+        34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+    ],
+    [
+        1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35,
+        37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+    ],
+];
+
+/// The Reed-Solomon block structure of a QR code symbol. The data codewords are split into blocks, the shorter
+/// ones first, each block gets its own error correction codewords and the blocks are interleaved codeword by codeword
+#[derive(Clone, Debug)]
+pub struct QrBlocks {
+    num_short: usize,
+    num_long: usize,
+    short_code: ReedSolomon<QrField>,
+    long_code: ReedSolomon<QrField>,
+}
+
+impl QrBlocks {
+    /// The block structure of the given version and error correction level. Panics unless 1 ≤ version ≤ 40
+    pub fn new(version: usize, level: QrEcLevel) -> Self {
+        if !(1..=40).contains(&version) {
+            panic!("QR code versions are 1 to 40");
+        }
+        let ec = EC_PER_BLOCK[level as usize][version - 1] as usize;
+        let blocks = NUM_BLOCKS[level as usize][version - 1] as usize;
+
+        let total = total_codewords(version);
+        let short_len = total / blocks;
+        let num_long = total % blocks;
+        Self {
+            num_short: blocks - num_long,
+            num_long,
+            short_code: ReedSolomon::new(short_len, short_len - ec),
+            long_code: ReedSolomon::new(short_len + 1, short_len + 1 - ec),
+        }
+    }
+
+    /// The number of error correction codewords in each block
+    pub fn ec_codewords_per_block(&self) -> usize {
+        self.short_code.parity_len()
+    }
+
+    /// The number of blocks
+    pub fn num_blocks(&self) -> usize {
+        self.num_short + self.num_long
+    }
+
+    /// The number of data codewords in each block, in order
+    pub fn block_data_lens(&self) -> Vec<usize> {
+        let mut lens = vec![self.short_code.k(); self.num_short];
+        lens.resize(self.num_blocks(), self.long_code.k());
+        lens
+    }
+
+    /// The number of data codewords of the symbol
+    pub fn data_codewords(&self) -> usize {
+        self.num_short * self.short_code.k() + self.num_long * self.long_code.k()
+    }
+
+    /// The number of codewords of the symbol, data and error correction
+    pub fn total_codewords(&self) -> usize {
+        self.num_short * self.short_code.n() + self.num_long * self.long_code.n()
+    }
+
+    /// Splits the data codewords into blocks, appends the error correction codewords and interleaves them into the
+    /// final sequence of codewords. Panics unless data has data_codewords bytes
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        if data.len() != self.data_codewords() {
+            panic!("Data length does not match the number of data codewords");
+        }
+
+        let mut blocks = Vec::with_capacity(self.num_blocks());
+        let mut rest = data;
+        for (b, len) in self.block_data_lens().into_iter().enumerate() {
+            let (block, tail) = rest.split_at(len);
+            let message: Vec<QrField> = block.iter().map(|&d| QrField::new(d)).collect();
+            blocks.push(self.code(b).encode(&message));
+            rest = tail;
+        }
+        self.interleave(&blocks)
+    }
+
+    /// Deinterleaves the codewords read from a symbol, corrects each block and returns the data codewords, or None if
+    /// some block has too many errors. Panics unless codewords has total_codewords bytes
+    pub fn decode(&self, codewords: &[u8]) -> Option<Vec<u8>> {
+        if codewords.len() != self.total_codewords() {
+            panic!("Codeword length does not match the number of codewords");
+        }
+
+        let mut data = Vec::with_capacity(self.data_codewords());
+        for (b, mut block) in self.deinterleave(codewords).into_iter().enumerate() {
+            let code = self.code(b);
+            code.decode(&mut block)?;
+            data.extend(block[..code.k()].iter().map(|c| c.value));
+        }
+        Some(data)
+    }
+
+    fn code(&self, block: usize) -> &ReedSolomon<QrField> {
+        if block < self.num_short {
+            &self.short_code
+        } else {
+            &self.long_code
+        }
+    }
+
+    // Data codewords column by column, the short blocks having no last column, then the error correction codewords
+    fn interleave(&self, blocks: &[Vec<QrField>]) -> Vec<u8> {
+        let ec = self.ec_codewords_per_block();
+        let mut out = Vec::with_capacity(self.total_codewords());
+        for i in 0..self.long_code.k() {
+            for (b, block) in blocks.iter().enumerate() {
+                if i < self.code(b).k() {
+                    out.push(block[i].value);
+                }
+            }
+        }
+        for i in 0..ec {
+            for block in blocks.iter() {
+                out.push(block[block.len() - ec + i].value);
+            }
+        }
+        out
+    }
+
+    fn deinterleave(&self, codewords: &[u8]) -> Vec<Vec<QrField>> {
+        let mut blocks: Vec<Vec<QrField>> = (0..self.num_blocks())
+            .map(|b| Vec::with_capacity(self.code(b).n()))
+            .collect();
+        let mut symbols = codewords.iter().map(|&c| QrField::new(c));
+        for i in 0..self.long_code.k() {
+            for (b, block) in blocks.iter_mut().enumerate() {
+                if i < self.code(b).k() {
+                    block.push(symbols.next().unwrap());
+                }
+            }
+        }
+        for _ in 0..self.ec_codewords_per_block() {
+            for block in blocks.iter_mut() {
+                block.push(symbols.next().unwrap());
+            }
+        }
+        blocks
+    }
+}
+
+// The number of 8 bit codewords that fit in a symbol of the given version, after the function patterns
+fn total_codewords(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        modules -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules / 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    const LEVELS: [QrEcLevel; 4] = [QrEcLevel::L, QrEcLevel::M, QrEcLevel::Q, QrEcLevel::H];
+
+    #[test]
+    fn capacities() {
+        // Data codewords of versions 1, 5, 10 and 40 at levels L, M, Q and H
+        let expected = [
+            (1, [19, 16, 13, 9]),
+            (5, [108, 86, 62, 46]),
+            (10, [274, 216, 154, 122]),
+            (40, [2956, 2334, 1666, 1276]),
+        ];
+        for (version, data) in expected {
+            for (&level, &d) in LEVELS.iter().zip(data.iter()) {
+                assert_eq!(QrBlocks::new(version, level).data_codewords(), d);
+            }
+        }
+
+        let blocks = QrBlocks::new(5, QrEcLevel::Q);
+        assert_eq!(blocks.block_data_lens(), vec![15, 15, 16, 16]);
+        assert_eq!(blocks.ec_codewords_per_block(), 18);
+        assert_eq!(blocks.total_codewords(), 134);
+
+        // Every level of a version fills the same number of codewords
+        for version in 1..=40 {
+            for level in LEVELS {
+                assert_eq!(
+                    QrBlocks::new(version, level).total_codewords(),
+                    total_codewords(version)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hello_world() {
+        // "HELLO WORLD" in alphanumeric mode, version 1-M
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        let blocks = QrBlocks::new(1, QrEcLevel::M);
+        let codewords = blocks.encode(&data);
+        assert_eq!(
+            codewords[16..],
+            [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+        );
+        assert_eq!(blocks.decode(&codewords).unwrap(), data);
+    }
+
+    #[test]
+    fn interleaved_errors() {
+        let blocks = QrBlocks::new(7, QrEcLevel::H);
+        assert_eq!(blocks.num_blocks(), 5);
+        let data: Vec<u8> = (0..blocks.data_codewords())
+            .map(|_| rand::thread_rng().gen())
+            .collect();
+        let codewords = blocks.encode(&data);
+
+        // A burst of 5 * t codewords within the error correction codewords hits each block t times
+        let t = blocks.ec_codewords_per_block() / 2;
+        let mut damaged = codewords.clone();
+        let start = blocks.data_codewords() + rand::thread_rng().gen_range(0..=5 * t);
+        for c in damaged[start..start + 5 * t].iter_mut() {
+            *c ^= 0xA5;
+        }
+        assert_eq!(blocks.decode(&damaged).unwrap(), data);
+
+        let mut positions: Vec<usize> = (0..codewords.len()).collect();
+        positions.shuffle(&mut rand::thread_rng());
+        let mut damaged = codewords.clone();
+        for &p in positions[..codewords.len() / 2].iter() {
+            damaged[p] ^= 0xFF;
+        }
+        assert!(blocks.decode(&damaged).is_none());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::GaloisField;
+
+/// Whether a decoder found a received word clean, corrected it or gave up
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeStatus {
+    /// The received word was a codeword
+    NoErrors,
+    /// Errors were located and corrected
+    Corrected,
+    /// Errors were detected but could not be corrected. The received word was left unchanged
+    Uncorrectable,
+}
+
+/// Diagnostics of a decode, for telemetry and link quality monitoring
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodeReport<GF: GaloisField> {
+    /// The outcome
+    pub status: DecodeStatus,
+    /// The corrected codeword indices in increasing order, erasures included. Empty unless corrected
+    pub positions: Vec<usize>,
+    /// The error value e at each position: the received symbol was the codeword symbol plus e. Erasures that held
+    /// the right value have e = 0, and bit errors of binary codes have e = 1
+    pub magnitudes: Vec<GF>,
+}
+
+impl<GF: GaloisField> DecodeReport<GF> {
+    // The report of a correction given as (position, error value) pairs, or of a failure
+    pub(crate) fn from_correction(correction: Option<Vec<(usize, GF)>>) -> Self {
+        let Some(mut errata) = correction else {
+            return Self {
+                status: DecodeStatus::Uncorrectable,
+                positions: Vec::new(),
+                magnitudes: Vec::new(),
+            };
+        };
+        errata.sort_by_key(|&(p, _)| p);
+        let status = if errata.is_empty() {
+            DecodeStatus::NoErrors
+        } else {
+            DecodeStatus::Corrected
+        };
+        let (positions, magnitudes) = errata.into_iter().unzip();
+        Self {
+            status,
+            positions,
+            magnitudes,
+        }
+    }
+
+    /// The number of corrected symbols, or None if the word was uncorrectable
+    pub fn num_corrected(&self) -> Option<usize> {
+        match self.status {
+            DecodeStatus::Uncorrectable => None,
+            _ => Some(self.positions.len()),
+        }
+    }
+
+    /// The number of corrected symbols whose error value was nonzero
+    pub fn num_errors(&self) -> usize {
+        self.magnitudes.iter().filter(|&&e| e != GF::ZERO).count()
+    }
+}
@@ -0,0 +1,67 @@
+use crate::GaloisField;
+
+/// Reusable working space for `ReedSolomon::decode_with_scratch`. The syndromes, the key equation solver, the
+/// Chien search and Forney's algorithm keep their vectors and polynomials here, so a decoder handling many words
+/// allocates them once instead of per word. Once the buffers have grown to the size of the code, decoding does
+/// not allocate at all
+#[derive(Clone, Debug)]
+pub struct DecodeScratch<GF: GaloisField> {
+    pub(super) syndromes: Vec<GF>,
+    // The roots the syndromes are evaluated at
+    pub(super) powers: Vec<GF>,
+    pub(super) positions: Vec<usize>,
+    // The error locators X of positions and their inverses, the roots of the errata locator
+    pub(super) locators: Vec<GF>,
+    pub(super) roots: Vec<GF>,
+    pub(super) magnitudes: Vec<GF>,
+    // The (position, error value) pairs of the last correction
+    pub(super) errata: Vec<(usize, GF)>,
+    // The polynomials of the key equation, lowest degree first: the erasure locator Γ(x), the Forney syndromes
+    // T(x), the errata locator Λ(x) and the evaluator Ω(x)
+    pub(super) erasure_locator: Vec<GF>,
+    pub(super) forney_syndromes: Vec<GF>,
+    pub(super) locator: Vec<GF>,
+    pub(super) evaluator: Vec<GF>,
+    // The error locator σ(x) and the working space of the solvers
+    pub(super) error_locator: Vec<GF>,
+    pub(super) previous: Vec<GF>,
+    pub(super) updated: Vec<GF>,
+}
+
+impl<GF: GaloisField> DecodeScratch<GF> {
+    /// Empty buffers, which grow to the size of the code on the first decode
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Buffers with room for decoding a code with parity_len = n - k parity symbols without reallocating
+    pub fn with_capacity(parity_len: usize) -> Self {
+        Self {
+            syndromes: Vec::with_capacity(parity_len),
+            powers: Vec::with_capacity(parity_len),
+            positions: Vec::with_capacity(parity_len),
+            locators: Vec::with_capacity(parity_len),
+            roots: Vec::with_capacity(parity_len),
+            magnitudes: Vec::with_capacity(parity_len),
+            errata: Vec::with_capacity(parity_len),
+            erasure_locator: Vec::with_capacity(parity_len + 1),
+            forney_syndromes: Vec::with_capacity(parity_len + 1),
+            locator: Vec::with_capacity(parity_len + 1),
+            evaluator: Vec::with_capacity(parity_len + 1),
+            error_locator: Vec::with_capacity(parity_len + 1),
+            previous: Vec::with_capacity(parity_len + 1),
+            updated: Vec::with_capacity(parity_len + 1),
+        }
+    }
+
+    /// The (position, error value) pairs corrected by the last successful decode, in the order they were located
+    pub fn errata(&self) -> &[(usize, GF)] {
+        &self.errata
+    }
+}
+
+impl<GF: GaloisField> Default for DecodeScratch<GF> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,385 @@
+use super::ReedSolomon;
+use crate::linalg::Matrix;
+use crate::simd::{self, NibbleTables};
+use crate::GaloisField;
+
+// Shards are byte buffers holding one symbol of each codeword per byte over GF(2^8), or per little endian group of 2,
+// 4 or 8 bytes over GF(2^16), GF(2^32) and GF(2^64). A data shard whose length is not a multiple of the symbol size
+// ends in part of a symbol whose missing high bytes are zero, and the parity shards are rounded up to whole symbols
+impl<GF: GaloisField> ReedSolomon<GF> {
+    /// Computes n - k parity shards from k equally long data shards. Symbol b of every shard forms one codeword, data
+    /// shards first. Over GF(2<sup>8</sup>) a symbol is a byte, and over GF(2<sup>16</sup>) a little endian pair of
+    /// bytes, which allows up to 65535 shards. GF(2<sup>32</sup>) and GF(2<sup>64</sup>) take little endian symbols
+    /// of 4 and 8 bytes. The parity shards have the data length rounded up to whole symbols. Panics unless there
+    /// are k shards of equal length and the field is GF(2<sup>8</sup>), GF(2<sup>16</sup>), GF(2<sup>32</sup>) or
+    /// GF(2<sup>64</sup>)
+    pub fn encode_shards(&self, data: &[&[u8]]) -> Vec<Vec<u8>> {
+        let symbol_bytes = Self::symbol_bytes();
+        if data.len() != self.k {
+            panic!("Number of data shards does not match k");
+        }
+        let len = data[0].len();
+        if data.iter().any(|d| d.len() != len) {
+            panic!("Shards must have the same length");
+        }
+
+        let p = self.parity_matrix();
+        (0..self.parity_len())
+            .map(|j| {
+                let mut parity = vec![0u8; len.next_multiple_of(symbol_bytes)];
+                for (i, shard) in data.iter().enumerate() {
+                    mul_add(p.get(i, j), shard, &mut parity);
+                }
+                parity
+            })
+            .collect()
+    }
+
+    /// Updates the n - k parity shards in place after data shard index changed from old_shard to new_shard. The code
+    /// is linear, so only the difference of the shards times row index of the parity matrix is added, without reading
+    /// the other data shards. Panics unless index < k, there are n - k parity shards, the shards have the lengths
+    /// `encode_shards` gives and the field is one `encode_shards` takes
+    pub fn update_parity(
+        &self,
+        old_shard: &[u8],
+        new_shard: &[u8],
+        index: usize,
+        parity: &mut [Vec<u8>],
+    ) {
+        let symbol_bytes = Self::symbol_bytes();
+        if index >= self.k {
+            panic!("Data shard index out of range");
+        }
+        if parity.len() != self.parity_len() {
+            panic!("Number of parity shards does not match n - k");
+        }
+        let len = old_shard.len();
+        let parity_len = len.next_multiple_of(symbol_bytes);
+        if new_shard.len() != len || parity.iter().any(|p| p.len() != parity_len) {
+            panic!("Shards must have the same length");
+        }
+
+        let delta: Vec<u8> = old_shard
+            .iter()
+            .zip(new_shard.iter())
+            .map(|(&a, &b)| a ^ b)
+            .collect();
+        for (p, &c) in parity.iter_mut().zip(self.parity_matrix().row(index)) {
+            mul_add(c, &delta, p);
+        }
+    }
+
+    /// Rebuilds missing shards in place. shards holds the n data and parity shards in order, with None for the
+    /// missing ones. Returns false, leaving shards unchanged, if fewer than k shards are present. Rebuilt data
+    /// shards get the length of the present ones, or whole symbols if none is present. Panics unless there are n
+    /// shards, the present ones have the lengths `encode_shards` gives and the field is one `encode_shards` takes
+    pub fn reconstruct(&self, shards: &mut [Option<Vec<u8>>]) -> bool {
+        let symbol_bytes = Self::symbol_bytes();
+        if shards.len() != self.n {
+            panic!("Number of shards does not match n");
+        }
+        let present: Vec<usize> = (0..self.n).filter(|&i| shards[i].is_some()).collect();
+        if present.len() < self.k {
+            return false;
+        }
+        if present.len() == self.n {
+            return true;
+        }
+        let shard_len = |i: usize| shards[i].as_ref().unwrap().len();
+        let parity_len = shard_len(present[0]).next_multiple_of(symbol_bytes);
+        let data_len = match present.iter().find(|&&i| i < self.k) {
+            Some(&i) => shard_len(i),
+            None => parity_len,
+        };
+        if present.iter().any(|&i| {
+            let expected = if i < self.k { data_len } else { parity_len };
+            shard_len(i) != expected
+        }) || data_len.next_multiple_of(symbol_bytes) != parity_len
+        {
+            panic!("Shards must have the same length");
+        }
+
+        // A codeword is m * G with G = [I | P]. Any k columns of G are invertible, so the message follows
+        // from the first k present shards
+        let used = &present[..self.k];
+        let mut sub = Matrix::new(self.k, self.k);
+        for (c, &col) in used.iter().enumerate() {
+            for r in 0..self.k {
+                sub.set(r, c, self.generator_entry(r, col));
+            }
+        }
+        let decode = sub
+            .inverse()
+            .expect("Columns of a Reed-Solomon generator matrix are independent");
+
+        // Missing shard j is Σ_c (G_S^-1 * G)[c][j] * shard used[c]. Only the columns of G_S^-1 * G of the
+        // missing shards are needed: column j < k of G is a unit vector, the others are columns of P
+        let missing: Vec<usize> = (0..self.n).filter(|&i| shards[i].is_none()).collect();
+        for &j in missing.iter() {
+            let mut rebuilt = vec![0u8; parity_len];
+            for (c, &col) in used.iter().enumerate() {
+                let coefficient = if j < self.k {
+                    decode.get(c, j)
+                } else {
+                    (0..self.k).fold(GF::ZERO, |acc, r| {
+                        acc + decode.get(c, r) * self.generator_entry(r, j)
+                    })
+                };
+                mul_add(coefficient, shards[col].as_ref().unwrap(), &mut rebuilt);
+            }
+            if j < self.k {
+                rebuilt.truncate(data_len);
+            }
+            shards[j] = Some(rebuilt);
+        }
+        true
+    }
+
+    // Entry (row, col) of the k × n systematic generator matrix [I | P], without building it
+    fn generator_entry(&self, row: usize, col: usize) -> GF {
+        if col >= self.k {
+            self.parity_matrix().get(row, col - self.k)
+        } else if row == col {
+            GF::ONE
+        } else {
+            GF::ZERO
+        }
+    }
+
+    // Row i is the parity of the i-th unit message. Computed once per code, as it takes k encodes
+    fn parity_matrix(&self) -> &Matrix<GF> {
+        self.parity_matrix.get_or_init(|| {
+            let mut p = Matrix::new(self.k, self.parity_len());
+            let mut unit = vec![GF::ZERO; self.k];
+            for r in 0..self.k {
+                unit[r] = GF::ONE;
+                for (c, v) in self.parity(&unit).into_iter().enumerate() {
+                    p.set(r, c, v);
+                }
+                unit[r] = GF::ZERO;
+            }
+            p
+        })
+    }
+
+    // The number of bytes per symbol
+    pub(crate) fn symbol_bytes() -> usize {
+        match GF::M {
+            8 => 1,
+            16 => 2,
+            32 => 4,
+            64 => 8,
+            _ => panic!("Shards need GF(2^8), GF(2^16), GF(2^32) or GF(2^64)"),
+        }
+    }
+}
+
+// output += c * input symbol by symbol, with input zero extended to the length of output. Over GF(2^8) this is the
+// simd kernel. Over the wider fields the products of c with every byte value at every byte of a symbol are
+// tabulated first, and the product with a symbol is the sum of those with its bytes
+pub(crate) fn mul_add<GF: GaloisField>(c: GF, input: &[u8], output: &mut [u8]) {
+    if c == GF::ZERO {
+        return;
+    }
+    if GF::M == 8 {
+        simd::mul_add_slice_tables(&NibbleTables::new(c), &mut output[..input.len()], input);
+        return;
+    }
+
+    let symbol_bytes = ReedSolomon::<GF>::symbol_bytes();
+    let tables: Vec<Vec<u64>> = (0..symbol_bytes)
+        .map(|i| {
+            (0..256u128)
+                .map(|b| (c * GF::from_u128(b << (8 * i))).to_u128() as u64)
+                .collect()
+        })
+        .collect();
+    for (o, i) in output
+        .chunks_exact_mut(symbol_bytes)
+        .zip(input.chunks(symbol_bytes))
+    {
+        let product = i
+            .iter()
+            .zip(tables.iter())
+            .fold(0u64, |acc, (&b, table)| acc ^ table[b as usize]);
+        for (o, p) in o.iter_mut().zip(product.to_le_bytes()) {
+            *o ^= p;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn random_shards(count: usize, len: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|_| (0..len).map(|_| rand::thread_rng().gen()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn matches_codewords() {
+        let rs = ReedSolomon::<GF>::new(14, 10);
+        let data = random_shards(10, 37);
+        let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        let parity = rs.encode_shards(&refs);
+        assert_eq!(parity.len(), 4);
+
+        for b in 0..37 {
+            let message: Vec<GF> = data.iter().map(|d| GF::new(d[b])).collect();
+            let expected: Vec<u8> = rs.parity(&message).iter().map(|p| p.value).collect();
+            let actual: Vec<u8> = parity.iter().map(|p| p[b]).collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn update_parity() {
+        let rs = ReedSolomon::<GF>::new(10, 6);
+        let mut data = random_shards(6, 50);
+        let mut parity = rs.encode_shards(&data.iter().map(|d| &d[..]).collect::<Vec<_>>());
+        for index in [0, 3, 5] {
+            let mut new_shard = vec![0u8; 50];
+            rand::thread_rng().fill(&mut new_shard[..]);
+            rs.update_parity(&data[index], &new_shard, index, &mut parity);
+            data[index] = new_shard;
+            let refs: Vec<&[u8]> = data.iter().map(|d| &d[..]).collect();
+            assert_eq!(parity, rs.encode_shards(&refs));
+        }
+    }
+
+    #[test]
+    fn reconstruct() {
+        let rs = ReedSolomon::<GF>::new(12, 8);
+        for _ in 0..20 {
+            let data = random_shards(8, 100);
+            let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+            let mut all = data.clone();
+            all.extend(rs.encode_shards(&refs));
+
+            // Lose up to n - k shards anywhere
+            let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+            let mut indices: Vec<usize> = (0..12).collect();
+            indices.shuffle(&mut rand::thread_rng());
+            let lost = rand::thread_rng().gen_range(0..=4);
+            for &i in indices[..lost].iter() {
+                shards[i] = None;
+            }
+
+            assert!(rs.reconstruct(&mut shards));
+            let rebuilt: Vec<Vec<u8>> = shards.into_iter().map(|s| s.unwrap()).collect();
+            assert_eq!(rebuilt, all);
+        }
+    }
+
+    #[test]
+    fn too_few_shards() {
+        let rs = ReedSolomon::<GF>::new(6, 4);
+        let data = random_shards(4, 10);
+        let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        let parity = rs.encode_shards(&refs);
+
+        let mut shards: Vec<Option<Vec<u8>>> = data
+            .iter()
+            .chain(parity.iter())
+            .cloned()
+            .map(Some)
+            .collect();
+        shards[0] = None;
+        shards[2] = None;
+        shards[5] = None;
+        let before = shards.clone();
+        assert!(!rs.reconstruct(&mut shards));
+        assert_eq!(shards, before);
+    }
+
+    #[test]
+    fn wide_field() {
+        // 300 shards need GF(2^16)
+        type Wide = gf2::GFu16<0x1100B>;
+        let rs = ReedSolomon::<Wide>::new(300, 280);
+        for len in [64, 33] {
+            let data = random_shards(280, len);
+            let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+            let parity = rs.encode_shards(&refs);
+            assert!(parity.iter().all(|p| p.len() == len.next_multiple_of(2)));
+
+            for b in 0..len.div_ceil(2) {
+                let symbol = |d: &[u8]| {
+                    Wide::new(d[2 * b] as u16 | (*d.get(2 * b + 1).unwrap_or(&0) as u16) << 8)
+                };
+                let message: Vec<Wide> = data.iter().map(|d| symbol(d)).collect();
+                let expected = rs.parity(&message);
+                let actual: Vec<Wide> = parity.iter().map(|p| symbol(p)).collect();
+                assert_eq!(actual, expected);
+            }
+
+            let mut all = data.clone();
+            all.extend(parity);
+            let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+            for i in [0, 5, 100, 279, 280, 290, 299] {
+                shards[i] = None;
+            }
+            assert!(rs.reconstruct(&mut shards));
+            let rebuilt: Vec<Vec<u8>> = shards.into_iter().map(|s| s.unwrap()).collect();
+            assert_eq!(rebuilt, all);
+        }
+    }
+
+    fn check_symbols<Wide: GaloisField>(n: usize, k: usize) {
+        let symbol_bytes = ReedSolomon::<Wide>::symbol_bytes();
+        let rs = ReedSolomon::<Wide>::new(n, k);
+        for len in [64, 37] {
+            let data = random_shards(k, len);
+            let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+            let parity = rs.encode_shards(&refs);
+            assert!(parity
+                .iter()
+                .all(|p| p.len() == len.next_multiple_of(symbol_bytes)));
+
+            for b in 0..len.div_ceil(symbol_bytes) {
+                let symbol = |d: &[u8]| {
+                    let bytes = &d[symbol_bytes * b..d.len().min(symbol_bytes * (b + 1))];
+                    Wide::from_u128(
+                        bytes
+                            .iter()
+                            .rev()
+                            .fold(0u128, |acc, &byte| acc << 8 | byte as u128),
+                    )
+                };
+                let message: Vec<Wide> = data.iter().map(|d| symbol(d)).collect();
+                let actual: Vec<Wide> = parity.iter().map(|p| symbol(p)).collect();
+                assert_eq!(actual, rs.parity(&message));
+            }
+
+            let mut all = data.clone();
+            all.extend(parity);
+            let mut shards: Vec<Option<Vec<u8>>> = all.iter().cloned().map(Some).collect();
+            for i in [0, 2, k - 1, k, n - 1] {
+                shards[i] = None;
+            }
+            assert!(rs.reconstruct(&mut shards));
+            let rebuilt: Vec<Vec<u8>> = shards.into_iter().map(|s| s.unwrap()).collect();
+            assert_eq!(rebuilt, all);
+        }
+    }
+
+    #[test]
+    fn wider_fields() {
+        check_symbols::<gf2::GFu32<{ crate::polys::GF2_32 }>>(20, 14);
+        check_symbols::<gf2::GFu64<{ crate::polys::GF2_64 }>>(20, 14);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unequal_lengths() {
+        let rs = ReedSolomon::<GF>::new(4, 2);
+        let _ = rs.encode_shards(&[&[1, 2, 3], &[4, 5]]);
+    }
+}
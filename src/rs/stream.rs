@@ -0,0 +1,146 @@
+use super::ReedSolomon;
+use crate::poly::PolyRemainderState;
+use crate::GaloisField;
+
+/// Encodes a byte stream incrementally into consecutive codewords of a code over GF(2<sup>8</sup>). Every k input
+/// bytes are passed through followed by their n - k parity bytes. Only the division state of the current codeword
+/// is kept, so arbitrarily long streams are encoded in constant memory.
+///
+/// A final block of fewer than k bytes is encoded as a shortened codeword: its parity is that of the message
+/// preceded by zeros, so it decodes after padding the front of the block with zeros
+///
+/// ```
+/// use galois_field_2pm::{GaloisField, gf2_lut};
+/// use galois_field_2pm::rs::ReedSolomon;
+///
+/// type GF = gf2_lut::GFu8<0x11d>;
+/// let rs = ReedSolomon::<GF>::new(10, 6);
+/// let mut encoder = rs.stream_encoder();
+/// let mut out = encoder.update(b"Hello, ");
+/// out.extend(encoder.update(b"world"));
+/// out.extend(encoder.finish());
+/// // 12 message bytes: two full blocks of 6 bytes plus 4 parity bytes each
+/// assert_eq!(out.len(), 20);
+/// assert_eq!(out[..6], b"Hello,"[..]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamEncoder<'a, GF: GaloisField> {
+    code: &'a ReedSolomon<GF>,
+    state: PolyRemainderState<GF>,
+    // The number of message bytes fed into the current codeword
+    fed: usize,
+}
+
+impl<GF: GaloisField> ReedSolomon<GF> {
+    /// Starts encoding a byte stream. Panics if the field is not GF(2<sup>8</sup>)
+    pub fn stream_encoder(&self) -> StreamEncoder<'_, GF> {
+        if GF::M != 8 {
+            panic!("Byte streams need GF(2^8)");
+        }
+        StreamEncoder {
+            code: self,
+            state: PolyRemainderState::new(&self.generator),
+            fed: 0,
+        }
+    }
+}
+
+impl<GF: GaloisField> StreamEncoder<'_, GF> {
+    /// Feeds more of the stream. Returns the encoded bytes that are ready: the input itself with the parity of every
+    /// codeword completed by it inserted after its last message byte
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        let k = self.code.k();
+        let mut out =
+            Vec::with_capacity(data.len() + (data.len() / k + 1) * self.code.parity_len());
+        let mut rest = data;
+        while !rest.is_empty() {
+            let take = rest.len().min(k - self.fed);
+            let (chunk, tail) = rest.split_at(take);
+            for &b in chunk {
+                self.state.update(GF::from_u128(b as u128));
+            }
+            out.extend_from_slice(chunk);
+            self.fed += take;
+            rest = tail;
+            if self.fed == k {
+                self.emit_parity(&mut out);
+            }
+        }
+        out
+    }
+
+    /// Ends the stream. Returns the parity of the final partial codeword, or nothing if the stream ended on a
+    /// codeword boundary
+    pub fn finish(mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        if self.fed > 0 {
+            self.emit_parity(&mut out);
+        }
+        out
+    }
+
+    // The parity is the negated remainder, highest degree first
+    fn emit_parity(&mut self, out: &mut Vec<u8>) {
+        out.extend(
+            self.state
+                .remainder_coeffs()
+                .iter()
+                .rev()
+                .map(|&r| (-r).to_u128() as u8),
+        );
+        self.state.reset();
+        self.fed = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf2_lut;
+    use rand::Rng;
+
+    type GF = gf2_lut::GFu8<0x11d>;
+
+    fn to_symbols(bytes: &[u8]) -> Vec<GF> {
+        bytes.iter().map(|&b| GF::new(b)).collect()
+    }
+
+    #[test]
+    fn matches_encode() {
+        let rs = ReedSolomon::<GF>::new(40, 30);
+        let mut data = vec![0u8; 1000];
+        rand::thread_rng().fill(&mut data[..]);
+
+        // Feed the stream in random pieces
+        let mut encoder = rs.stream_encoder();
+        let mut out = Vec::new();
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let take = rand::thread_rng().gen_range(0..=rest.len().min(70));
+            out.extend(encoder.update(&rest[..take]));
+            rest = &rest[take..];
+        }
+        out.extend(encoder.finish());
+        assert_eq!(out.len(), 1000 + 34 * 10);
+
+        for (block, message) in out.chunks(40).zip(data.chunks(30)) {
+            if message.len() == 30 {
+                assert_eq!(to_symbols(block), rs.encode(&to_symbols(message)));
+            } else {
+                // The final shortened block decodes once padded with zeros
+                let mut padded = vec![GF::ZERO; 30 - message.len()];
+                padded.extend(to_symbols(block));
+                assert!(rs.is_codeword(&padded));
+            }
+        }
+    }
+
+    #[test]
+    fn block_boundary() {
+        let rs = ReedSolomon::<GF>::new(12, 8);
+        let mut encoder = rs.stream_encoder();
+        let out = encoder.update(&[7; 16]);
+        assert_eq!(out.len(), 24);
+        assert!(encoder.finish().is_empty());
+    }
+}
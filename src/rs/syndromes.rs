@@ -0,0 +1,214 @@
+use crate::gf2_lut::GaloisFieldLut;
+use crate::simd::{self, NibbleTables};
+use crate::GaloisField;
+
+// Symbols the simd path of syndromes_into processes at once, and the longest word it takes
+const LANES: usize = 32;
+const MAX_SIMD_LEN: usize = 256;
+
+/// Computes the count syndromes S<sub>j</sub> = r(α<sup>fcr+j</sup>) of received, stored highest degree first,
+/// with α = x. All are zero exactly when received is a codeword of a Reed-Solomon code with first consecutive
+/// root α<sup>fcr</sup> and count parity symbols. Over fields with M ≤ 8 words of 32 to 256 symbols go through
+/// the `simd` kernels
+pub fn syndromes<GF: GaloisField>(received: &[GF], count: usize, fcr: usize) -> Vec<GF> {
+    let alpha = GF::ONE.mul_x();
+    syndromes_at(received, alpha.pow(fcr as u128), alpha, count)
+}
+
+// The count values r(first * step^j)
+pub(crate) fn syndromes_at<GF: GaloisField>(
+    received: &[GF],
+    first: GF,
+    step: GF,
+    count: usize,
+) -> Vec<GF> {
+    let mut syndromes = Vec::with_capacity(count);
+    syndromes_into(
+        received,
+        first,
+        step,
+        count,
+        &mut Vec::with_capacity(count),
+        &mut syndromes,
+    );
+    syndromes
+}
+
+// The same, writing the syndromes to syndromes and the roots first * step^j to roots
+pub(crate) fn syndromes_into<GF: GaloisField>(
+    received: &[GF],
+    first: GF,
+    step: GF,
+    count: usize,
+    roots: &mut Vec<GF>,
+    syndromes: &mut Vec<GF>,
+) {
+    roots.clear();
+    let mut root = first;
+    for _ in 0..count {
+        roots.push(root);
+        root *= step;
+    }
+
+    syndromes.clear();
+    if GF::M <= 8 && (LANES..=MAX_SIMD_LEN).contains(&received.len()) {
+        syndromes_simd(received, roots, syndromes);
+    } else {
+        syndromes_horner(received, roots, syndromes);
+    }
+}
+
+// One pass over received runs Horner's rule for every syndrome at once
+fn syndromes_horner<GF: GaloisField>(received: &[GF], roots: &[GF], syndromes: &mut Vec<GF>) {
+    syndromes.resize(roots.len(), GF::ZERO);
+    for &r in received {
+        for (s, &root) in syndromes.iter_mut().zip(roots.iter()) {
+            *s = *s * root + r;
+        }
+    }
+}
+
+// Over fields with M ≤ 8 symbols are bytes, and Horner's rule runs on LANES interleaved subsequences of received
+// at once. With q = root^LANES every lane accumulates acc = q * acc + r block by block, a multiplication by a
+// constant that the simd kernels do for the whole block. The syndrome is then Σ root^(LANES - 1 - l) acc[l].
+// Each root takes its own pass over the at most MAX_SIMD_LEN bytes, with its own tables
+fn syndromes_simd<GF: GaloisField>(received: &[GF], roots: &[GF], syndromes: &mut Vec<GF>) {
+    // Leading zeros do not change the value, so the word is padded to whole blocks in front
+    let padded = received.len().next_multiple_of(LANES);
+    let mut bytes = [0u8; MAX_SIMD_LEN];
+    let bytes = &mut bytes[..padded];
+    for (b, r) in bytes[padded - received.len()..].iter_mut().zip(received) {
+        *b = r.to_u128() as u8;
+    }
+
+    for &root in roots {
+        let tables = NibbleTables::new(root.pow(LANES as u128));
+        let mut acc = [0u8; LANES];
+        for block in bytes.chunks_exact(LANES) {
+            simd::mul_slice_tables(&tables, &mut acc);
+            for (a, &b) in acc.iter_mut().zip(block) {
+                *a ^= b;
+            }
+        }
+        syndromes.push(
+            acc.iter()
+                .fold(GF::ZERO, |s, &a| s * root + GF::from_u128(a as u128)),
+        );
+    }
+}
+
+/// The same as `syndromes`, but with log and exp tables in place of multiplications: each non zero symbol
+/// r<sub>i</sub> = α<sup>l</sup> at power p adds α<sup>l + (fcr+j)p</sup> to S<sub>j</sub>
+pub fn syndromes_lut<GF: GaloisFieldLut>(received: &[GF], count: usize, fcr: usize) -> Vec<GF> {
+    let order = (GF::NUM_ELEM - 1) as usize;
+    let mut syndromes = vec![GF::ZERO; count];
+    for (i, &r) in received.iter().enumerate() {
+        if r == GF::ZERO {
+            continue;
+        }
+        let power = (received.len() - 1 - i) % order;
+        let step = power * (fcr % order) % order;
+        let mut exponent = r.log_alpha() as usize + step;
+        for s in syndromes.iter_mut() {
+            *s += GF::alpha_pow((exponent % order) as isize);
+            exponent = exponent % order + power;
+        }
+    }
+    syndromes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut};
+    use rand::Rng;
+
+    #[test]
+    fn matches_evaluation() {
+        type GF = gf2_lut::GFu8<0x11d>;
+        let received: Vec<GF> = (0..255)
+            .map(|_| GF::new(rand::thread_rng().gen()))
+            .collect();
+        let poly: Vec<GF> = received.iter().rev().copied().collect();
+        let poly = crate::poly::Polynomial::new(poly);
+
+        for fcr in [0, 1, 112, 300] {
+            let s = syndromes(&received, 16, fcr);
+            assert_eq!(s, syndromes_lut(&received, 16, fcr));
+            for (j, &s) in s.iter().enumerate() {
+                assert_eq!(s, poly.eval(GF::new(2).pow((fcr + j) as u128)));
+            }
+        }
+        assert!(syndromes(&received, 0, 0).is_empty());
+        assert_eq!(syndromes::<GF>(&[], 4, 1), vec![GF::ZERO; 4]);
+    }
+
+    fn check_simd<GF: GaloisField>() {
+        for len in [0, 1, 31, 32, 33, 64, 100, 255, 256, 257] {
+            let received: Vec<GF> = (0..len)
+                .map(|_| GF::from_u128(rand::thread_rng().gen::<u128>() % GF::NUM_ELEM))
+                .collect();
+            let poly = crate::poly::Polynomial::new(received.iter().rev().copied().collect());
+            let alpha = GF::ONE.mul_x();
+            for fcr in [0, 1, 3] {
+                let s = syndromes(&received, 10, fcr);
+                for (j, &s) in s.iter().enumerate() {
+                    assert_eq!(s, poly.eval(alpha.pow((fcr + j) as u128)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simd_path() {
+        check_simd::<gf2::GFu8<0x11D>>();
+        check_simd::<gf2_lut::GFu8<0x11D>>();
+        check_simd::<crate::gf2_rev::GFu8<0x11B>>();
+        check_simd::<gf2::GFu8<0x13>>();
+        check_simd::<gf2::GFu16<0x1_002D>>();
+    }
+
+    #[test]
+    fn simd_matches_horner() {
+        // Both paths on the same words, including lengths syndromes_into sends to the other path
+        fn check<GF: GaloisField>() {
+            let alpha = GF::ONE.mul_x();
+            let roots: Vec<GF> = (0..16).map(|j| alpha.pow(j + 1)).collect();
+            for len in [1, 31, 32, 33, 200, 256, 300] {
+                let received: Vec<GF> = (0..len)
+                    .map(|_| GF::from_u128(rand::thread_rng().gen::<u128>() % GF::NUM_ELEM))
+                    .collect();
+                let (mut simd, mut horner) = (Vec::new(), Vec::new());
+                syndromes_horner(&received, &roots, &mut horner);
+                if len <= MAX_SIMD_LEN {
+                    syndromes_simd(&received, &roots, &mut simd);
+                    assert_eq!(simd, horner);
+                }
+                let mut powers = Vec::new();
+                let mut dispatched = Vec::new();
+                syndromes_into(&received, roots[0], alpha, 16, &mut powers, &mut dispatched);
+                assert_eq!(dispatched, horner);
+            }
+        }
+        check::<gf2::GFu8<0x11D>>();
+        check::<crate::gf2_rev::GFu8<0x11B>>();
+        check::<gf2::GFu8<0x13>>();
+    }
+
+    #[test]
+    fn codewords() {
+        type GF = gf2::GFu16<0x1002D>;
+        let rs = crate::rs::ReedSolomon::<GF>::new(300, 280);
+        let message: Vec<GF> = (0..280)
+            .map(|_| GF::new(rand::thread_rng().gen()))
+            .collect();
+        let codeword = rs.encode(&message);
+        assert!(syndromes(&codeword, 20, 0).iter().all(|&s| s == GF::ZERO));
+
+        type Lut = gf2_lut::GFu16<0x1002D>;
+        let codeword: Vec<Lut> = codeword.iter().map(|s| Lut::new(s.value)).collect();
+        assert!(syndromes_lut(&codeword, 20, 0)
+            .iter()
+            .all(|&s| s == Lut::ZERO));
+    }
+}
@@ -0,0 +1,256 @@
+//! Additive and multiplicative scramblers.
+//!
+//! An additive (synchronous) scrambler XORs the data with the output of an LFSR started from a fixed state, so
+//! scrambling and descrambling are the same operation. A multiplicative (self synchronizing) scrambler divides the
+//! data by a polynomial 1 + c<sub>1</sub>x + ... + c<sub>L</sub>x<sup>L</sup>, feeding back its own output, and the
+//! descrambler multiplies it back. The descrambler recovers after L correct bits whatever its starting state.
+//!
+//! Standards differ in which bit of a byte goes first, so both scramblers take a `BitOrder`. The presets reproduce
+//! the sequences of their standards.
+//!
+//! ```
+//! use galois_field_2pm::scrambler;
+//!
+//! let mut data = *b"hello world";
+//! let mut whitening = scrambler::pn9();
+//! whitening.apply(&mut data);
+//! assert_ne!(&data, b"hello world");
+//!
+//! whitening.reset();
+//! whitening.apply(&mut data);
+//! assert_eq!(&data, b"hello world");
+//! ```
+
+use crate::lfsr::{FibonacciLfsr, GaloisLfsr, Lfsr};
+
+/// The order bits of a byte go through a scrambler
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BitOrder {
+    /// Bit 0 first
+    LsbFirst,
+    /// Bit 7 first
+    MsbFirst,
+}
+
+/// An additive scrambler XORing data with the output of an LFSR
+#[derive(Clone, Debug)]
+pub struct AdditiveScrambler<L: Lfsr + Clone> {
+    initial: L,
+    lfsr: L,
+    order: BitOrder,
+}
+
+impl<L: Lfsr + Clone> AdditiveScrambler<L> {
+    /// Constructs the scrambler from an LFSR in its starting state
+    pub fn new(lfsr: L, order: BitOrder) -> Self {
+        Self {
+            initial: lfsr.clone(),
+            lfsr,
+            order,
+        }
+    }
+
+    /// The bit order
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// The LFSR in its current state
+    pub fn lfsr(&self) -> &L {
+        &self.lfsr
+    }
+
+    /// The next keystream byte in the bit order
+    pub fn next_byte(&mut self) -> u8 {
+        let b = self.lfsr.next_byte();
+        match self.order {
+            BitOrder::LsbFirst => b,
+            BitOrder::MsbFirst => b.reverse_bits(),
+        }
+    }
+
+    /// Scrambles or descrambles data in place
+    pub fn apply(&mut self, data: &mut [u8]) {
+        for b in data.iter_mut() {
+            *b ^= self.next_byte();
+        }
+    }
+
+    /// Restarts the LFSR from its starting state
+    pub fn reset(&mut self) {
+        self.lfsr = self.initial.clone();
+    }
+}
+
+/// A self synchronizing scrambler for the polynomial 1 + c<sub>1</sub>x + ... + c<sub>L</sub>x<sup>L</sup>. Each
+/// scrambled bit is y<sub>n</sub> = x<sub>n</sub> + c<sub>1</sub>y<sub>n-1</sub> + ... + c<sub>L</sub>y<sub>n-L</sub>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultiplicativeScrambler {
+    degree: u32,
+    taps: u128,
+    // Bit i - 1 holds y_(n-i), the last L scrambled bits
+    state: u128,
+    order: BitOrder,
+}
+
+impl MultiplicativeScrambler {
+    /// Constructs the scrambler for poly with an all zero history. Panics unless poly has a constant term and
+    /// degree between 1 and 127
+    pub fn new(poly: u128, order: BitOrder) -> Self {
+        let degree = crate::calc_degree(poly);
+        if degree < 1 || poly & 1 == 0 {
+            panic!("Polynomial must have a constant term and degree at least 1");
+        }
+        Self {
+            degree: degree as u32,
+            taps: poly >> 1,
+            state: 0,
+            order,
+        }
+    }
+
+    /// The bit order
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    // Runs the bits of data through the feedback. The history takes the scrambled bits, which are the outputs when
+    // scrambling and the inputs when descrambling
+    fn run(&mut self, data: &mut [u8], descramble: bool) {
+        let mask = u128::MAX >> (128 - self.degree);
+        for b in data.iter_mut() {
+            let mut out = 0;
+            for i in 0..8 {
+                let shift = match self.order {
+                    BitOrder::LsbFirst => i,
+                    BitOrder::MsbFirst => 7 - i,
+                };
+                let x = (*b >> shift) & 1;
+                let y = x ^ ((self.state & self.taps).count_ones() & 1) as u8;
+                let scrambled = if descramble { x } else { y };
+                self.state = ((self.state << 1) | scrambled as u128) & mask;
+                out |= y << shift;
+            }
+            *b = out;
+        }
+    }
+
+    /// Scrambles data in place
+    pub fn scramble(&mut self, data: &mut [u8]) {
+        self.run(data, false);
+    }
+
+    /// Descrambles data in place
+    pub fn descramble(&mut self, data: &mut [u8]) {
+        self.run(data, true);
+    }
+
+    /// Clears the history
+    pub fn reset(&mut self) {
+        self.state = 0;
+    }
+}
+
+/// The DVB energy dispersal PRBS 1 + x<sup>14</sup> + x<sup>15</sup> of EN 300 421, started from the register
+/// 100101010000000 and applied MSB first. DVB does not scramble the sync bytes, which is left to the caller
+pub fn dvb() -> AdditiveScrambler<FibonacciLfsr> {
+    // The register outputs its feedback bit, so the equivalent state holds the first 15 outputs, which satisfy
+    // s(n+15) = s(n+1) + s(n)
+    AdditiveScrambler::new(FibonacciLfsr::new(0x8003, 0x6FC0), BitOrder::MsbFirst)
+}
+
+/// The PN9 whitening x<sup>9</sup> + x<sup>5</sup> + 1 of IEEE 802.15.4 SUN FSK and many sub GHz radios, started
+/// from all ones and applied LSB first
+pub fn pn9() -> AdditiveScrambler<FibonacciLfsr> {
+    AdditiveScrambler::new(FibonacciLfsr::new(0x221, 0x1FF), BitOrder::LsbFirst)
+}
+
+/// The Bluetooth LE data whitening x<sup>7</sup> + x<sup>4</sup> + 1 for a channel index below 64, applied LSB
+/// first. Position 0 of the register starts at 1 and positions 1 to 6 hold the channel index, most significant bit
+/// first
+pub fn ble_whitening(channel: u8) -> AdditiveScrambler<GaloisLfsr> {
+    if channel >= 64 {
+        panic!("Channel index must be less than 64");
+    }
+    let state = ((channel.reverse_bits() >> 1) | 1) as u128;
+    AdditiveScrambler::new(GaloisLfsr::new(0x91, state), BitOrder::LsbFirst)
+}
+
+/// The scrambler x<sup>16</sup> + x<sup>5</sup> + x<sup>4</sup> + x<sup>3</sup> + 1 of PCIe 1.x and 2.x and USB 3
+/// 8b/10b, started from FFFF and applied LSB first. The links skip K symbols and restart on COM, which is left to
+/// the caller
+pub fn pcie() -> AdditiveScrambler<GaloisLfsr> {
+    AdditiveScrambler::new(GaloisLfsr::new(0x1_0039, 0xFFFF), BitOrder::LsbFirst)
+}
+
+/// The self synchronizing 64b/66b scrambler 1 + x<sup>39</sup> + x<sup>58</sup> of 10GBASE-R, applied LSB first
+pub fn ethernet_64b66b() -> MultiplicativeScrambler {
+    MultiplicativeScrambler::new((1 << 58) | (1 << 39) | 1, BitOrder::LsbFirst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn keystream<L: Lfsr + Clone>(mut scrambler: AdditiveScrambler<L>, len: usize) -> Vec<u8> {
+        let mut data = vec![0; len];
+        scrambler.apply(&mut data);
+        data
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!(keystream(dvb(), 4), [0x03, 0xF6, 0x08, 0x34]);
+        assert_eq!(keystream(pn9(), 6), [0xFF, 0xE1, 0x1D, 0x9A, 0xED, 0x85]);
+        assert_eq!(
+            keystream(pcie(), 8),
+            [0xFF, 0x17, 0xC0, 0x14, 0xB2, 0xE7, 0x02, 0x82]
+        );
+    }
+
+    #[test]
+    fn ble_register() {
+        // The register of the specification: position 6 is output and fed back into positions 0 and 4
+        for channel in [0, 17, 37, 39] {
+            let mut reg: Vec<u8> = (0..7)
+                .map(|i| if i == 0 { 1 } else { (channel >> (6 - i)) & 1 })
+                .collect();
+            let expected: Vec<u8> = (0..10)
+                .map(|_| {
+                    (0..8).fold(0, |acc, i| {
+                        let out = reg[6];
+                        reg.rotate_right(1);
+                        reg[4] ^= out;
+                        acc | (out << i)
+                    })
+                })
+                .collect();
+            assert_eq!(keystream(ble_whitening(channel), 10), expected);
+        }
+    }
+
+    #[test]
+    fn self_synchronizing() {
+        let data: Vec<u8> = (0..100).map(|_| rand::thread_rng().gen()).collect();
+        for order in [BitOrder::LsbFirst, BitOrder::MsbFirst] {
+            let mut scrambled = data.clone();
+            let mut scrambler = MultiplicativeScrambler::new(0x4009, order);
+            scrambler.scramble(&mut scrambled[..40]);
+            scrambler.scramble(&mut scrambled[40..]);
+            assert_ne!(scrambled, data);
+
+            let mut descrambled = scrambled.clone();
+            MultiplicativeScrambler::new(0x4009, order).descramble(&mut descrambled);
+            assert_eq!(descrambled, data);
+        }
+
+        // A descrambler with the wrong history recovers once the scrambled bits fill it
+        let mut scrambled = data.clone();
+        ethernet_64b66b().scramble(&mut scrambled);
+        let mut descrambler = ethernet_64b66b();
+        descrambler.descramble(&mut [0xA5; 3]);
+        descrambler.descramble(&mut scrambled);
+        assert_eq!(scrambled[8..], data[8..]);
+    }
+}
@@ -0,0 +1,288 @@
+//! Vectorized multiplication of byte slices by a constant in GF(2<sup>8</sup>) and smaller fields.
+//!
+//! Multiplying by a constant c is linear over GF(2), so the product of a byte splits into the products of its two
+//! nibbles: c * b = lo\[b & 0xF\] + hi\[b >> 4\] with two 16 entry tables. A byte shuffle instruction (`vpshufb` on
+//! x86_64, `TBL` on AArch64) looks up 16 or 32 nibbles at once, which is the kernel behind fast Reed-Solomon and
+//! erasure coding.
+//! Being linear, the product is also an 8x8 bit matrix, which GFNI (`GF2P8AFFINEQB`) applies to 64 bytes at once for
+//! any polynomial. GFNI multiplies two slices element by element (`GF2P8MULB`) in the field of 0x11B only.
+//! The kernel is picked from the features of the CPU, detected at runtime with the `std` feature, and falls back
+//! to the tables one byte at a time.
+//!
+//! Each byte of a slice is one element, so the functions work on the shards of `erasure` directly and on any field
+//! stored in a u8.
+//!
+//! ```
+//! use galois_field_2pm::{GaloisField, gf2};
+//! use galois_field_2pm::simd;
+//!
+//! type GF = gf2::GFu8<0x11D>;
+//! let c = GF::new(0x53);
+//! let mut data: Vec<u8> = (0..=255).collect();
+//! simd::mul_slice(c, &mut data);
+//! assert_eq!(data[0xCA], (c * GF::new(0xCA)).value);
+//! ```
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+#[cfg(target_arch = "x86_64")]
+mod x86_64;
+
+use crate::GaloisField;
+
+/// The products of a constant with every low and every high nibble
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NibbleTables {
+    /// lo\[i\] = c * i
+    pub lo: [u8; 16],
+    /// hi\[i\] = c * (i << 4)
+    pub hi: [u8; 16],
+}
+
+impl NibbleTables {
+    /// The tables of c. Nibbles beyond the field, which are never valid elements, map to 0. Panics if M > 8
+    pub fn new<GF: GaloisField>(c: GF) -> Self {
+        if GF::M > 8 {
+            panic!("Field must have at most 8 bits");
+        }
+        let product = |b: u8| {
+            let b = GF::from_u128(b as u128);
+            if b.validate() {
+                (c * b).to_u128() as u8
+            } else {
+                0
+            }
+        };
+        Self {
+            lo: core::array::from_fn(|i| product(i as u8)),
+            hi: core::array::from_fn(|i| product((i as u8) << 4)),
+        }
+    }
+
+    /// The bit matrix of c in the layout of `GF2P8AFFINEQB`: byte 7 - i holds the input bits that output bit i sums
+    pub fn matrix(&self) -> u64 {
+        let columns: [u8; 8] = core::array::from_fn(|j| {
+            if j < 4 {
+                self.lo[1 << j]
+            } else {
+                self.hi[1 << (j - 4)]
+            }
+        });
+        (0..8).fold(0, |matrix, i| {
+            let row = (0..8).fold(0u64, |row, j| row | (((columns[j] >> i) & 1) as u64) << j);
+            matrix | row << (8 * (7 - i))
+        })
+    }
+
+    /// c * b for a byte b
+    pub fn mul(&self, b: u8) -> u8 {
+        self.lo[(b & 0xF) as usize] ^ self.hi[(b >> 4) as usize]
+    }
+}
+
+// Runs the widest kernel the CPU supports over a prefix of data and returns its length
+#[cfg(target_arch = "x86_64")]
+fn mul_kernel(tables: &NibbleTables, data: &mut [u8]) -> usize {
+    x86_64::mul_slice(tables, data)
+}
+
+#[cfg(target_arch = "aarch64")]
+fn mul_kernel(tables: &NibbleTables, data: &mut [u8]) -> usize {
+    aarch64::mul_slice(tables, data)
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn mul_kernel(_: &NibbleTables, _: &mut [u8]) -> usize {
+    0
+}
+
+// The same for dst += c * src
+#[cfg(target_arch = "x86_64")]
+fn mul_add_kernel(tables: &NibbleTables, dst: &mut [u8], src: &[u8]) -> usize {
+    x86_64::mul_add_slice(tables, dst, src)
+}
+
+#[cfg(target_arch = "aarch64")]
+fn mul_add_kernel(tables: &NibbleTables, dst: &mut [u8], src: &[u8]) -> usize {
+    aarch64::mul_add_slice(tables, dst, src)
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn mul_add_kernel(_: &NibbleTables, _: &mut [u8], _: &[u8]) -> usize {
+    0
+}
+
+// Runs the widest kernel for the field of x^8 + x^4 + x^3 + x + 1 over a prefix of dst and returns its length
+#[cfg(target_arch = "x86_64")]
+fn mul_slices_aes_kernel(dst: &mut [u8], src: &[u8]) -> usize {
+    x86_64::mul_slices_aes(dst, src)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn mul_slices_aes_kernel(_: &mut [u8], _: &[u8]) -> usize {
+    0
+}
+
+/// Multiplies every element of data by c in place
+pub fn mul_slice<GF: GaloisField<StorageType = u8>>(c: GF, data: &mut [u8]) {
+    mul_slice_tables(&NibbleTables::new(c), data);
+}
+
+/// Multiplies every element of data in place by the constant of tables, for reusing the tables across slices
+pub fn mul_slice_tables(tables: &NibbleTables, data: &mut [u8]) {
+    let done = mul_kernel(tables, data);
+    for b in data[done..].iter_mut() {
+        *b = tables.mul(*b);
+    }
+}
+
+/// dst += c * src element by element. Panics if the lengths differ
+pub fn mul_add_slice<GF: GaloisField<StorageType = u8>>(c: GF, dst: &mut [u8], src: &[u8]) {
+    mul_add_slice_tables(&NibbleTables::new(c), dst, src);
+}
+
+/// dst += c * src element by element with the constant of tables. Panics if the lengths differ
+pub fn mul_add_slice_tables(tables: &NibbleTables, dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "Slices must have the same length");
+    let done = mul_add_kernel(tables, dst, src);
+    for (d, &s) in dst[done..].iter_mut().zip(src[done..].iter()) {
+        *d ^= tables.mul(s);
+    }
+}
+
+/// Multiplies dst by src element by element. Panics if the lengths differ
+pub fn mul_slices<GF: GaloisField<StorageType = u8>>(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "Slices must have the same length");
+    let done = if GF::POLY == 0x11B && GF::NATURAL_REPR {
+        mul_slices_aes_kernel(dst, src)
+    } else {
+        0
+    };
+    for (d, &s) in dst[done..].iter_mut().zip(src[done..].iter()) {
+        *d = (GF::new(*d) * GF::new(s)).to_u128() as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, gf2_lut, gf2_rev};
+    use rand::Rng;
+
+    fn check<GF: GaloisField<StorageType = u8>>() {
+        for len in [0, 1, 15, 16, 17, 31, 32, 33, 50, 100, 1000] {
+            let c = GF::new(rand::thread_rng().gen::<u8>() & GF::MASK);
+            let data: Vec<u8> = (0..len)
+                .map(|_| rand::thread_rng().gen::<u8>() & GF::MASK)
+                .collect();
+            let expected: Vec<u8> = data
+                .iter()
+                .map(|&b| (c * GF::new(b)).to_u128() as u8)
+                .collect();
+
+            // Unaligned starts exercise the kernels and the tail
+            for start in 0..len.min(3) {
+                let mut out = data.clone();
+                mul_slice(c, &mut out[start..]);
+                assert_eq!(out[start..], expected[start..]);
+                assert_eq!(out[..start], data[..start]);
+
+                let mut out = data.clone();
+                mul_add_slice(c, &mut out[start..], &data[..len - start]);
+                for i in start..len {
+                    assert_eq!(out[i], data[i] ^ expected[i - start]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_field() {
+        check::<gf2::GFu8<0x11D>>();
+        check::<gf2::GFu8<0x11B>>();
+        check::<gf2_lut::GFu8<0x11D>>();
+        check::<gf2_rev::GFu8<0x11B>>();
+        check::<gf2::GFu8<0x13>>();
+    }
+
+    fn check_slices<GF: GaloisField<StorageType = u8>>() {
+        for len in [0, 1, 16, 17, 64, 65, 100, 1000] {
+            let random = || -> Vec<u8> {
+                (0..len)
+                    .map(|_| rand::thread_rng().gen::<u8>() & GF::MASK)
+                    .collect()
+            };
+            let (mut dst, src) = (random(), random());
+            let expected: Vec<u8> = dst
+                .iter()
+                .zip(src.iter())
+                .map(|(&a, &b)| (GF::new(a) * GF::new(b)).to_u128() as u8)
+                .collect();
+            mul_slices::<GF>(&mut dst, &src);
+            assert_eq!(dst, expected);
+        }
+    }
+
+    #[test]
+    fn slices() {
+        check_slices::<gf2::GFu8<0x11B>>();
+        check_slices::<gf2_lut::GFu8<0x11D>>();
+        check_slices::<gf2_rev::GFu8<0x11B>>();
+    }
+
+    #[test]
+    fn reflected_slices() {
+        // The reflected bytes of the AES field are not products for the GF2P8MULB kernel
+        type Rev = gf2_rev::GFu8<0x11B>;
+        type Natural = gf2::GFu8<0x11B>;
+        const { assert!(!Rev::NATURAL_REPR && Natural::NATURAL_REPR) };
+        let a: Vec<Natural> = (0..100)
+            .map(|_| Natural::new(rand::thread_rng().gen()))
+            .collect();
+        let b: Vec<Natural> = (0..100)
+            .map(|_| Natural::new(rand::thread_rng().gen()))
+            .collect();
+        let mut dst: Vec<u8> = a.iter().map(|&x| Rev::from(x).value).collect();
+        let src: Vec<u8> = b.iter().map(|&x| Rev::from(x).value).collect();
+        mul_slices::<Rev>(&mut dst, &src);
+        for ((&d, &x), &y) in dst.iter().zip(a.iter()).zip(b.iter()) {
+            assert_eq!(Natural::from(Rev::new(d)), x * y);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn slices_length() {
+        mul_slices::<gf2::GFu8<0x11B>>(&mut [1, 2], &[3]);
+    }
+
+    #[test]
+    fn matrix() {
+        // The matrix of 1 is the identity
+        let tables = NibbleTables::new(gf2::GFu8::<0x11B>::ONE);
+        assert_eq!(tables.matrix(), 0x0102_0408_1020_4080);
+        for c in 0..=255 {
+            let tables = NibbleTables::new(gf2::GFu8::<0x11D>::new(c));
+            let matrix = tables.matrix();
+            for b in 0..=255u8 {
+                let product = (0..8).fold(0, |out, i| {
+                    let row = (matrix >> (8 * (7 - i))) as u8;
+                    out | (((row & b).count_ones() & 1) as u8) << i
+                });
+                assert_eq!(product, tables.mul(b));
+            }
+        }
+    }
+
+    #[test]
+    fn tables() {
+        let tables = NibbleTables::new(gf2::GFu8::<0x11D>::new(2));
+        assert_eq!(tables.lo[1], 2);
+        assert_eq!(tables.hi[8], 0x1D);
+        // x^3 + x + 1 has no elements above 7
+        let tables = NibbleTables::new(gf2::GFu8::<0xB>::ONE);
+        assert_eq!(tables.lo[7], 7);
+        assert_eq!(tables.lo[8], 0);
+        assert_eq!(tables.hi, [0; 16]);
+    }
+}
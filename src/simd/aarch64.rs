@@ -0,0 +1,87 @@
+// NEON table lookup kernels
+use super::NibbleTables;
+use core::arch::aarch64::*;
+
+pub(super) fn mul_slice(tables: &NibbleTables, data: &mut [u8]) -> usize {
+    let ptr = data.as_mut_ptr();
+    unsafe { mul::<false>(tables, ptr, ptr, data.len()) }
+}
+
+// dst += c * src over a prefix, dst and src have the same length
+pub(super) fn mul_add_slice(tables: &NibbleTables, dst: &mut [u8], src: &[u8]) -> usize {
+    unsafe { mul::<true>(tables, src.as_ptr(), dst.as_mut_ptr(), dst.len()) }
+}
+
+// dst = c * src, or dst += c * src when ADD, over a prefix of len bytes. src may equal dst
+unsafe fn mul<const ADD: bool>(
+    tables: &NibbleTables,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    // NEON is part of every AArch64 target that Rust supports with std
+    if cfg!(target_feature = "neon") {
+        mul_neon::<ADD>(tables, src, dst, len)
+    } else {
+        0
+    }
+}
+
+// Only call when the CPU has NEON
+#[target_feature(enable = "neon")]
+unsafe fn mul_neon<const ADD: bool>(
+    tables: &NibbleTables,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    let lo = vld1q_u8(tables.lo.as_ptr());
+    let hi = vld1q_u8(tables.hi.as_ptr());
+    let nibble = vdupq_n_u8(0x0F);
+    let product = |x| {
+        veorq_u8(
+            vqtbl1q_u8(lo, vandq_u8(x, nibble)),
+            vqtbl1q_u8(hi, vshrq_n_u8::<4>(x)),
+        )
+    };
+
+    // Two registers per iteration hide the latency of the lookups
+    let done = len - len % 32;
+    for i in (0..done).step_by(32) {
+        let mut y0 = product(vld1q_u8(src.add(i)));
+        let mut y1 = product(vld1q_u8(src.add(i + 16)));
+        if ADD {
+            y0 = veorq_u8(y0, vld1q_u8(dst.add(i)));
+            y1 = veorq_u8(y1, vld1q_u8(dst.add(i + 16)));
+        }
+        vst1q_u8(dst.add(i), y0);
+        vst1q_u8(dst.add(i + 16), y1);
+    }
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, GaloisField};
+    use rand::Rng;
+
+    #[test]
+    fn neon_kernel() {
+        type GF = gf2::GFu8<0x11D>;
+        let tables = NibbleTables::new(GF::new(rand::thread_rng().gen()));
+        let data: Vec<u8> = (0..1000).map(|_| rand::thread_rng().gen()).collect();
+        let expected: Vec<u8> = data.iter().map(|&b| tables.mul(b)).collect();
+
+        let mut out = data.clone();
+        let done = mul_slice(&tables, &mut out);
+        assert_eq!(done, 992);
+        assert_eq!(out[..done], expected[..done]);
+
+        let mut out = data.clone();
+        let done = mul_add_slice(&tables, &mut out, &data);
+        for i in 0..done {
+            assert_eq!(out[i], data[i] ^ expected[i]);
+        }
+    }
+}
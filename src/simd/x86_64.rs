@@ -0,0 +1,372 @@
+// GFNI affine and SSSE3/AVX2 byte shuffle kernels
+use super::NibbleTables;
+use core::arch::x86_64::*;
+
+// Whether the CPU has feature, known at compile time or detected at runtime with std
+macro_rules! has_feature {
+    ($feature:tt) => {
+        cfg!(target_feature = $feature)
+            || (cfg!(feature = "std") && std::arch::is_x86_feature_detected!($feature))
+    };
+}
+
+// The kernels the CPU can run
+#[derive(Clone, Copy, Debug)]
+struct Features {
+    gfni512: bool,
+    gfni256: bool,
+    gfni: bool,
+    avx2: bool,
+    ssse3: bool,
+}
+
+impl Features {
+    fn detect() -> Self {
+        Self {
+            gfni512: has_feature!("gfni") && has_feature!("avx512f"),
+            gfni256: has_feature!("gfni") && has_feature!("avx") && has_feature!("avx2"),
+            gfni: has_feature!("gfni"),
+            avx2: has_feature!("avx2"),
+            ssse3: has_feature!("ssse3"),
+        }
+    }
+}
+
+// Detected once, like the carry-less multiply of gf2::clmul
+#[inline]
+fn features() -> Features {
+    static FEATURES: std::sync::OnceLock<Features> = std::sync::OnceLock::new();
+    *FEATURES.get_or_init(Features::detect)
+}
+
+pub(super) fn mul_slice(tables: &NibbleTables, data: &mut [u8]) -> usize {
+    let ptr = data.as_mut_ptr();
+    unsafe { mul::<false>(features(), tables, ptr, ptr, data.len()) }
+}
+
+// dst += c * src over a prefix, dst and src have the same length
+pub(super) fn mul_add_slice(tables: &NibbleTables, dst: &mut [u8], src: &[u8]) -> usize {
+    unsafe {
+        mul::<true>(
+            features(),
+            tables,
+            src.as_ptr(),
+            dst.as_mut_ptr(),
+            dst.len(),
+        )
+    }
+}
+
+// dst = c * src, or dst += c * src when ADD, over a prefix of len bytes with the best kernel in
+// features. src may equal dst
+unsafe fn mul<const ADD: bool>(
+    features: Features,
+    tables: &NibbleTables,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    if features.gfni512 {
+        mul_gfni512::<ADD>(tables.matrix(), src, dst, len)
+    } else if features.gfni256 {
+        mul_gfni256::<ADD>(tables.matrix(), src, dst, len)
+    } else if features.gfni {
+        mul_gfni128::<ADD>(tables.matrix(), src, dst, len)
+    } else if features.avx2 {
+        mul_avx2::<ADD>(tables, src, dst, len)
+    } else if features.ssse3 {
+        mul_ssse3::<ADD>(tables, src, dst, len)
+    } else {
+        0
+    }
+}
+
+// Element-wise products in the field of x^8 + x^4 + x^3 + x + 1
+pub(super) fn mul_slices_aes(dst: &mut [u8], src: &[u8]) -> usize {
+    mul_slices_aes_with(features(), dst, src)
+}
+
+fn mul_slices_aes_with(features: Features, dst: &mut [u8], src: &[u8]) -> usize {
+    if features.gfni512 {
+        unsafe { mul_slices_gfni512(dst, src) }
+    } else if features.gfni {
+        unsafe { mul_slices_gfni128(dst, src) }
+    } else {
+        0
+    }
+}
+
+// Only call when the CPU has GFNI and AVX-512
+#[target_feature(enable = "gfni,avx512f")]
+unsafe fn mul_gfni512<const ADD: bool>(
+    matrix: u64,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    let a = _mm512_set1_epi64(matrix as i64);
+    let done = len - len % 64;
+    for i in (0..done).step_by(64) {
+        let y =
+            _mm512_gf2p8affine_epi64_epi8::<0>(_mm512_loadu_si512(src.add(i) as *const __m512i), a);
+        let ptr = dst.add(i) as *mut __m512i;
+        let y = if ADD {
+            _mm512_xor_si512(y, _mm512_loadu_si512(ptr))
+        } else {
+            y
+        };
+        _mm512_storeu_si512(ptr, y);
+    }
+    done + mul_gfni128::<ADD>(matrix, src.add(done), dst.add(done), len - done)
+}
+
+// Only call when the CPU has GFNI and AVX2
+#[target_feature(enable = "gfni,avx,avx2")]
+unsafe fn mul_gfni256<const ADD: bool>(
+    matrix: u64,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    let a = _mm256_set1_epi64x(matrix as i64);
+    let done = len - len % 32;
+    for i in (0..done).step_by(32) {
+        let y =
+            _mm256_gf2p8affine_epi64_epi8::<0>(_mm256_loadu_si256(src.add(i) as *const __m256i), a);
+        let ptr = dst.add(i) as *mut __m256i;
+        let y = if ADD {
+            _mm256_xor_si256(y, _mm256_loadu_si256(ptr))
+        } else {
+            y
+        };
+        _mm256_storeu_si256(ptr, y);
+    }
+    done + mul_gfni128::<ADD>(matrix, src.add(done), dst.add(done), len - done)
+}
+
+// Only call when the CPU has GFNI
+#[target_feature(enable = "gfni")]
+unsafe fn mul_gfni128<const ADD: bool>(
+    matrix: u64,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    let a = _mm_set1_epi64x(matrix as i64);
+    let done = len - len % 16;
+    for i in (0..done).step_by(16) {
+        let y = _mm_gf2p8affine_epi64_epi8::<0>(_mm_loadu_si128(src.add(i) as *const __m128i), a);
+        let ptr = dst.add(i) as *mut __m128i;
+        let y = if ADD {
+            _mm_xor_si128(y, _mm_loadu_si128(ptr))
+        } else {
+            y
+        };
+        _mm_storeu_si128(ptr, y);
+    }
+    done
+}
+
+// Only call when the CPU has GFNI and AVX-512
+#[target_feature(enable = "gfni,avx512f")]
+unsafe fn mul_slices_gfni512(dst: &mut [u8], src: &[u8]) -> usize {
+    let done = dst.len() - dst.len() % 64;
+    for (d, s) in dst[..done].chunks_exact_mut(64).zip(src.chunks_exact(64)) {
+        let ptr = d.as_mut_ptr() as *mut __m512i;
+        let x = _mm512_loadu_si512(ptr);
+        let y = _mm512_loadu_si512(s.as_ptr() as *const __m512i);
+        _mm512_storeu_si512(ptr, _mm512_gf2p8mul_epi8(x, y));
+    }
+    done + mul_slices_gfni128(&mut dst[done..], &src[done..])
+}
+
+// Only call when the CPU has GFNI
+#[target_feature(enable = "gfni")]
+unsafe fn mul_slices_gfni128(dst: &mut [u8], src: &[u8]) -> usize {
+    let done = dst.len() - dst.len() % 16;
+    for (d, s) in dst[..done].chunks_exact_mut(16).zip(src.chunks_exact(16)) {
+        let ptr = d.as_mut_ptr() as *mut __m128i;
+        let x = _mm_loadu_si128(ptr);
+        let y = _mm_loadu_si128(s.as_ptr() as *const __m128i);
+        _mm_storeu_si128(ptr, _mm_gf2p8mul_epi8(x, y));
+    }
+    done
+}
+
+// Only call when the CPU has SSSE3
+#[target_feature(enable = "ssse3")]
+unsafe fn mul_ssse3<const ADD: bool>(
+    tables: &NibbleTables,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    let lo = _mm_loadu_si128(tables.lo.as_ptr() as *const __m128i);
+    let hi = _mm_loadu_si128(tables.hi.as_ptr() as *const __m128i);
+    let nibble = _mm_set1_epi8(0x0F);
+
+    let done = len - len % 16;
+    for i in (0..done).step_by(16) {
+        let x = _mm_loadu_si128(src.add(i) as *const __m128i);
+        let low = _mm_shuffle_epi8(lo, _mm_and_si128(x, nibble));
+        let high = _mm_shuffle_epi8(hi, _mm_and_si128(_mm_srli_epi64(x, 4), nibble));
+        let ptr = dst.add(i) as *mut __m128i;
+        let y = _mm_xor_si128(low, high);
+        let y = if ADD {
+            _mm_xor_si128(y, _mm_loadu_si128(ptr))
+        } else {
+            y
+        };
+        _mm_storeu_si128(ptr, y);
+    }
+    done
+}
+
+// Only call when the CPU has AVX2
+#[target_feature(enable = "avx2")]
+unsafe fn mul_avx2<const ADD: bool>(
+    tables: &NibbleTables,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> usize {
+    let lo = _mm256_broadcastsi128_si256(_mm_loadu_si128(tables.lo.as_ptr() as *const __m128i));
+    let hi = _mm256_broadcastsi128_si256(_mm_loadu_si128(tables.hi.as_ptr() as *const __m128i));
+    let nibble = _mm256_set1_epi8(0x0F);
+
+    let done = len - len % 32;
+    for i in (0..done).step_by(32) {
+        let x = _mm256_loadu_si256(src.add(i) as *const __m256i);
+        let low = _mm256_shuffle_epi8(lo, _mm256_and_si256(x, nibble));
+        let high = _mm256_shuffle_epi8(hi, _mm256_and_si256(_mm256_srli_epi64(x, 4), nibble));
+        let ptr = dst.add(i) as *mut __m256i;
+        let y = _mm256_xor_si256(low, high);
+        let y = if ADD {
+            _mm256_xor_si256(y, _mm256_loadu_si256(ptr))
+        } else {
+            y
+        };
+        _mm256_storeu_si256(ptr, y);
+    }
+    done + mul_ssse3::<ADD>(tables, src.add(done), dst.add(done), len - done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gf2, GaloisField};
+    use rand::Rng;
+
+    type Kernel = unsafe fn(&NibbleTables, *const u8, *mut u8, usize) -> usize;
+
+    #[test]
+    fn every_kernel() {
+        type GF = gf2::GFu8<0x11D>;
+        let tables = NibbleTables::new(GF::new(rand::thread_rng().gen()));
+        let src: Vec<u8> = (0..1000).map(|_| rand::thread_rng().gen()).collect();
+        let dst: Vec<u8> = (0..1000).map(|_| rand::thread_rng().gen()).collect();
+        let products: Vec<u8> = src.iter().map(|&b| tables.mul(b)).collect();
+
+        let features = features();
+        let kernels: [(bool, Kernel, Kernel); 5] = [
+            (
+                features.gfni512,
+                |t, s, d, l| unsafe { mul_gfni512::<false>(t.matrix(), s, d, l) },
+                |t, s, d, l| unsafe { mul_gfni512::<true>(t.matrix(), s, d, l) },
+            ),
+            (
+                features.gfni256,
+                |t, s, d, l| unsafe { mul_gfni256::<false>(t.matrix(), s, d, l) },
+                |t, s, d, l| unsafe { mul_gfni256::<true>(t.matrix(), s, d, l) },
+            ),
+            (
+                features.gfni,
+                |t, s, d, l| unsafe { mul_gfni128::<false>(t.matrix(), s, d, l) },
+                |t, s, d, l| unsafe { mul_gfni128::<true>(t.matrix(), s, d, l) },
+            ),
+            (features.avx2, mul_avx2::<false>, mul_avx2::<true>),
+            (features.ssse3, mul_ssse3::<false>, mul_ssse3::<true>),
+        ];
+        for (_, scale, mul_add) in kernels.iter().filter(|(supported, _, _)| *supported) {
+            let mut out = src.clone();
+            let done = unsafe { scale(&tables, out.as_ptr(), out.as_mut_ptr(), out.len()) };
+            assert_eq!(out[..done], products[..done]);
+
+            let mut out = dst.clone();
+            let done = unsafe { mul_add(&tables, src.as_ptr(), out.as_mut_ptr(), out.len()) };
+            for i in 0..done {
+                assert_eq!(out[i], dst[i] ^ products[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn every_tier() {
+        type GF = gf2::GFu8<0x11D>;
+        type Aes = gf2::GFu8<0x11B>;
+        let tables = NibbleTables::new(GF::new(rand::thread_rng().gen()));
+        let src: Vec<u8> = (0..1000).map(|_| rand::thread_rng().gen()).collect();
+        let dst: Vec<u8> = (0..1000).map(|_| rand::thread_rng().gen()).collect();
+        let products: Vec<u8> = src.iter().map(|&b| tables.mul(b)).collect();
+        let aes: Vec<u8> = src
+            .iter()
+            .zip(&dst)
+            .map(|(&a, &b)| (Aes::new(a) * Aes::new(b)).value)
+            .collect();
+
+        // One tier at a time, so the dispatch runs exactly that kernel
+        let detected = features();
+        let none = Features {
+            gfni512: false,
+            gfni256: false,
+            gfni: false,
+            avx2: false,
+            ssse3: false,
+        };
+        let tiers = [
+            (
+                detected.gfni512,
+                Features {
+                    gfni512: true,
+                    ..none
+                },
+            ),
+            (
+                detected.gfni256,
+                Features {
+                    gfni256: true,
+                    ..none
+                },
+            ),
+            (detected.gfni, Features { gfni: true, ..none }),
+            (detected.avx2, Features { avx2: true, ..none }),
+            (
+                detected.ssse3,
+                Features {
+                    ssse3: true,
+                    ..none
+                },
+            ),
+            (true, none),
+        ];
+        for (_, tier) in tiers.iter().filter(|(supported, _)| *supported) {
+            let mut out = src.clone();
+            let ptr = out.as_mut_ptr();
+            let done = unsafe { mul::<false>(*tier, &tables, ptr, ptr, out.len()) };
+            let scalar = !(tier.gfni512 || tier.gfni256 || tier.gfni || tier.avx2 || tier.ssse3);
+            assert_eq!(done == 0, scalar, "{tier:?}");
+            assert_eq!(out[..done], products[..done], "{tier:?}");
+
+            let mut out = dst.clone();
+            let done =
+                unsafe { mul::<true>(*tier, &tables, src.as_ptr(), out.as_mut_ptr(), out.len()) };
+            for i in 0..done {
+                assert_eq!(out[i], dst[i] ^ products[i], "{tier:?}");
+            }
+
+            let mut out = dst.clone();
+            let done = mul_slices_aes_with(*tier, &mut out, &src);
+            assert_eq!(out[..done], aes[..done], "{tier:?}");
+        }
+    }
+}
@@ -0,0 +1,628 @@
+//! A versioned, self-describing binary container for data produced by this crate.
+//!
+//! Every container names its field, so bytes written today can be checked and decoded by later versions of the
+//! crate or by other implementations. Version 1 is laid out as follows, with all integers little endian:
+//!
+//! | Offset      | Size | Content                                                   |
+//! |-------------|------|-----------------------------------------------------------|
+//! | 0           | 4    | Magic `GF2M`                                              |
+//! | 4           | 1    | Format version, 1                                         |
+//! | 5           | 1    | `Kind` of the payload                                     |
+//! | 6           | 1    | Field degree M                                            |
+//! | 7           | 1    | Number of parameters p                                    |
+//! | 8           | 16   | Field polynomial POLY as a u128                           |
+//! | 24          | 16p  | Parameters of the kind, each a u128                       |
+//! | 24 + 16p    | 8    | Payload length L in bytes as a u64                        |
+//! | 32 + 16p    | L    | Payload                                                   |
+//! | 32 + 16p + L| 4    | CRC-32 (as in zip) of all the preceding bytes             |
+//!
+//! Field elements in a payload take ⌈M/8⌉ bytes each, little endian, in the polynomial basis: bit i is the
+//! coefficient of x<sup>i</sup> whatever the representation of the element type. The kinds are
+//!
+//! - `Kind::Elements`: no parameters, the payload is the elements
+//! - `Kind::Polynomial`: no parameters, the payload is the coefficients, lowest degree first
+//! - `Kind::Codeword`: parameters n, k, the first consecutive root and the generator element of a `ReedSolomon`
+//!   code, the payload is the n codeword symbols
+//! - `Kind::Shard`: parameters n, k and the shard index of an `ErasureCode`, the payload is the shard bytes
+//!
+//! The crate has no secret sharing, so there is no kind for shares yet. New kinds take the next free number and
+//! older parsers report them as `WireError::UnknownKind`. Parsers reject version 0 and containers of a newer
+//! version, so a format change that old readers cannot follow bumps `VERSION`. Lengths are checked before they
+//! are used, so a length field pointing past the end of the bytes, even one that overflows, is
+//! `WireError::Truncated` and the work of parsing is bounded by the number of bytes.
+//!
+//! ```
+//! use galois_field_2pm::{gf2, wire, GaloisField};
+//! use galois_field_2pm::rs::ReedSolomon;
+//!
+//! type GF = gf2::GFu8<0x11D>;
+//! let rs = ReedSolomon::<GF>::new(10, 6);
+//! let codeword = rs.encode(&[GF::new(1), GF::new(2), GF::new(3), GF::new(4), GF::new(5), GF::new(6)]);
+//!
+//! let bytes = wire::encode_codeword(&rs, &codeword);
+//! let (parsed_rs, parsed) = wire::parse_codeword::<GF>(&bytes).unwrap();
+//! assert_eq!(parsed, codeword);
+//! assert_eq!(parsed_rs.k(), 6);
+//!
+//! // The field is part of the container
+//! assert_eq!(
+//!     wire::parse_codeword::<gf2::GFu8<0x12B>>(&bytes).unwrap_err(),
+//!     wire::WireError::FieldMismatch,
+//! );
+//! ```
+
+use core::fmt;
+
+use crate::crc::{Crc, CRC_32};
+use crate::poly::Polynomial;
+use crate::rs::ReedSolomon;
+use crate::GaloisField;
+
+/// The magic bytes every container starts with
+pub const MAGIC: [u8; 4] = *b"GF2M";
+
+/// The format version written by this crate and the newest one it parses
+pub const VERSION: u8 = 1;
+
+// The bytes before the parameters, and those around the payload
+const HEADER_LEN: usize = 24;
+const LENGTH_LEN: usize = 8;
+const CRC_LEN: usize = 4;
+
+/// What a container holds
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Kind {
+    /// A sequence of field elements
+    Elements = 1,
+    /// The coefficients of a `Polynomial`, lowest degree first
+    Polynomial = 2,
+    /// A Reed-Solomon codeword together with its code
+    Codeword = 3,
+    /// One shard of an erasure code
+    Shard = 4,
+}
+
+impl Kind {
+    fn from_u8(kind: u8) -> Option<Self> {
+        match kind {
+            1 => Some(Kind::Elements),
+            2 => Some(Kind::Polynomial),
+            3 => Some(Kind::Codeword),
+            4 => Some(Kind::Shard),
+            _ => None,
+        }
+    }
+}
+
+/// The error returned when parsing a container fails
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WireError {
+    /// The bytes end before the container does
+    Truncated,
+    /// The bytes do not start with `MAGIC`
+    BadMagic,
+    /// The container has version 0 or a version newer than `VERSION`
+    UnsupportedVersion(u8),
+    /// The kind byte is not a known `Kind`
+    UnknownKind(u8),
+    /// The CRC-32 does not match the contents
+    Checksum,
+    /// The container holds a different kind than asked for
+    WrongKind(Kind),
+    /// M or POLY differ from those of the field asked for
+    FieldMismatch,
+    /// The parameters or the payload are not valid for the kind
+    InvalidPayload,
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Truncated => write!(f, "container is truncated"),
+            WireError::BadMagic => write!(f, "container does not start with the magic bytes"),
+            WireError::UnsupportedVersion(v) => write!(f, "unsupported container version {}", v),
+            WireError::UnknownKind(k) => write!(f, "unknown container kind {}", k),
+            WireError::Checksum => write!(f, "container checksum mismatch"),
+            WireError::WrongKind(k) => write!(f, "container holds {:?}", k),
+            WireError::FieldMismatch => write!(f, "container field does not match"),
+            WireError::InvalidPayload => write!(f, "invalid container parameters or payload"),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+/// A parsed container: its kind, field, parameters and raw payload
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Container {
+    /// What the payload holds
+    pub kind: Kind,
+    /// The field degree M
+    pub m: u8,
+    /// The field polynomial POLY
+    pub poly: u128,
+    /// The parameters of the kind
+    pub params: Vec<u128>,
+    /// The payload bytes
+    pub payload: Vec<u8>,
+}
+
+impl Container {
+    /// Writes the container in the current format version. Panics if there are more than 255 parameters
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.params.len() > u8::MAX as usize {
+            panic!("A container has at most 255 parameters");
+        }
+        let mut bytes = Vec::with_capacity(
+            HEADER_LEN + 16 * self.params.len() + LENGTH_LEN + self.payload.len() + CRC_LEN,
+        );
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&[VERSION, self.kind as u8, self.m, self.params.len() as u8]);
+        bytes.extend_from_slice(&self.poly.to_le_bytes());
+        for p in self.params.iter() {
+            bytes.extend_from_slice(&p.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.payload.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.payload);
+        let crc = Crc::new(CRC_32).checksum(&bytes);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    /// Parses a container. Bytes after its end are ignored
+    pub fn parse(bytes: &[u8]) -> Result<Self, WireError> {
+        if bytes.len() < 4 || bytes[..4] != MAGIC {
+            return Err(if bytes.len() < 4 && MAGIC.starts_with(bytes) {
+                WireError::Truncated
+            } else {
+                WireError::BadMagic
+            });
+        }
+        let header = bytes.get(..HEADER_LEN).ok_or(WireError::Truncated)?;
+        if header[4] == 0 || header[4] > VERSION {
+            return Err(WireError::UnsupportedVersion(header[4]));
+        }
+        let kind = Kind::from_u8(header[5]).ok_or(WireError::UnknownKind(header[5]))?;
+        let m = header[6];
+        let num_params = header[7] as usize;
+        let poly = u128::from_le_bytes(header[8..24].try_into().unwrap());
+
+        let params_end = HEADER_LEN + 16 * num_params;
+        let params = bytes
+            .get(HEADER_LEN..params_end)
+            .ok_or(WireError::Truncated)?
+            .chunks_exact(16)
+            .map(|p| u128::from_le_bytes(p.try_into().unwrap()))
+            .collect();
+        let payload_start = params_end
+            .checked_add(LENGTH_LEN)
+            .ok_or(WireError::Truncated)?;
+        let length = bytes
+            .get(params_end..payload_start)
+            .ok_or(WireError::Truncated)?;
+        let length = usize::try_from(u64::from_le_bytes(length.try_into().unwrap()))
+            .map_err(|_| WireError::Truncated)?;
+        let payload_end = payload_start
+            .checked_add(length)
+            .ok_or(WireError::Truncated)?;
+        let crc_end = payload_end
+            .checked_add(CRC_LEN)
+            .ok_or(WireError::Truncated)?;
+        let crc = bytes
+            .get(payload_end..crc_end)
+            .ok_or(WireError::Truncated)?;
+        if Crc::new(CRC_32).checksum(&bytes[..payload_end])
+            != u32::from_le_bytes(crc.try_into().unwrap())
+        {
+            return Err(WireError::Checksum);
+        }
+
+        Ok(Self {
+            kind,
+            m,
+            poly,
+            params,
+            payload: bytes[payload_start..payload_end].to_vec(),
+        })
+    }
+
+    // A container of the field GF
+    fn new<GF: GaloisField>(kind: Kind, params: Vec<u128>, payload: Vec<u8>) -> Self {
+        Self {
+            kind,
+            m: GF::M as u8,
+            poly: GF::POLY,
+            params,
+            payload,
+        }
+    }
+
+    // Parses a container of the given kind over the field GF with num_params parameters
+    fn parse_as<GF: GaloisField>(
+        bytes: &[u8],
+        kind: Kind,
+        num_params: usize,
+    ) -> Result<Self, WireError> {
+        let container = Self::parse(bytes)?;
+        if container.kind != kind {
+            return Err(WireError::WrongKind(container.kind));
+        }
+        if container.m as u128 != GF::M || container.poly != GF::POLY {
+            return Err(WireError::FieldMismatch);
+        }
+        if container.params.len() != num_params {
+            return Err(WireError::InvalidPayload);
+        }
+        Ok(container)
+    }
+}
+
+/// Writes field elements as a `Kind::Elements` container
+pub fn encode_elements<GF: GaloisField>(elements: &[GF]) -> Vec<u8> {
+    Container::new::<GF>(Kind::Elements, Vec::new(), element_bytes(elements)).to_bytes()
+}
+
+/// Parses a `Kind::Elements` container over the field GF
+pub fn parse_elements<GF: GaloisField>(bytes: &[u8]) -> Result<Vec<GF>, WireError> {
+    let container = Container::parse_as::<GF>(bytes, Kind::Elements, 0)?;
+    elements_from_bytes(&container.payload)
+}
+
+/// Writes a polynomial as a `Kind::Polynomial` container
+pub fn encode_polynomial<GF: GaloisField>(poly: &Polynomial<GF>) -> Vec<u8> {
+    Container::new::<GF>(Kind::Polynomial, Vec::new(), element_bytes(poly.coeffs())).to_bytes()
+}
+
+/// Parses a `Kind::Polynomial` container over the field GF
+pub fn parse_polynomial<GF: GaloisField>(bytes: &[u8]) -> Result<Polynomial<GF>, WireError> {
+    let container = Container::parse_as::<GF>(bytes, Kind::Polynomial, 0)?;
+    Ok(Polynomial::new(elements_from_bytes(&container.payload)?))
+}
+
+/// Writes a codeword of the Reed-Solomon code as a `Kind::Codeword` container. Panics if the codeword does not
+/// have n symbols
+pub fn encode_codeword<GF: GaloisField>(rs: &ReedSolomon<GF>, codeword: &[GF]) -> Vec<u8> {
+    if codeword.len() != rs.n() {
+        panic!("Codeword length does not match n");
+    }
+    let params = vec![
+        rs.n() as u128,
+        rs.k() as u128,
+        rs.first_root() as u128,
+        natural(rs.generator_element()),
+    ];
+    Container::new::<GF>(Kind::Codeword, params, element_bytes(codeword)).to_bytes()
+}
+
+/// Parses a `Kind::Codeword` container over the field GF into the Reed-Solomon code and the codeword
+pub fn parse_codeword<GF: GaloisField>(
+    bytes: &[u8],
+) -> Result<(ReedSolomon<GF>, Vec<GF>), WireError> {
+    let container = Container::parse_as::<GF>(bytes, Kind::Codeword, 4)?;
+    let p = &container.params;
+    let (n, k) = (p[0], p[1]);
+    let fcr = usize::try_from(p[2]).map_err(|_| WireError::InvalidPayload)?;
+    if k == 0 || k >= n || n >= GF::NUM_ELEM || p[3] >= GF::NUM_ELEM {
+        return Err(WireError::InvalidPayload);
+    }
+
+    // The payload bounds n before the order of the generator element is checked in n steps
+    let codeword = elements_from_bytes(&container.payload)?;
+    if codeword.len() as u128 != n {
+        return Err(WireError::InvalidPayload);
+    }
+    let (n, k) = (n as usize, k as usize);
+    let beta = from_natural::<GF>(p[3]);
+    let mut power = beta;
+    for _ in 1..n {
+        if power == GF::ONE || power == GF::ZERO {
+            return Err(WireError::InvalidPayload);
+        }
+        power *= beta;
+    }
+    let rs = ReedSolomon::builder(n, k)
+        .first_root(fcr)
+        .generator_element(beta)
+        .build();
+    Ok((rs, codeword))
+}
+
+/// One shard of an erasure code as stored in a `Kind::Shard` container
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shard {
+    /// The number of shards of the code
+    pub n: usize,
+    /// The number of data shards of the code
+    pub k: usize,
+    /// The index of the shard, data shards first
+    pub index: usize,
+    /// The shard bytes
+    pub data: Vec<u8>,
+}
+
+/// Writes shard index of an `ErasureCode` with n shards of which k hold data as a `Kind::Shard` container over the
+/// field GF of the code, such as `erasure::Raid6Field` for `Raid6`. Panics unless index < n and k ≤ n
+pub fn encode_shard<GF: GaloisField>(n: usize, k: usize, index: usize, shard: &[u8]) -> Vec<u8> {
+    if index >= n || k > n {
+        panic!("Shard parameters need index < n and k <= n");
+    }
+    let params = vec![n as u128, k as u128, index as u128];
+    Container::new::<GF>(Kind::Shard, params, shard.to_vec()).to_bytes()
+}
+
+/// Parses a `Kind::Shard` container over the field GF
+pub fn parse_shard<GF: GaloisField>(bytes: &[u8]) -> Result<Shard, WireError> {
+    let container = Container::parse_as::<GF>(bytes, Kind::Shard, 3)?;
+    let p = &container.params;
+    let (n, k, index) = (p[0], p[1], p[2]);
+    if index >= n || k > n || n > usize::MAX as u128 {
+        return Err(WireError::InvalidPayload);
+    }
+    Ok(Shard {
+        n: n as usize,
+        k: k as usize,
+        index: index as usize,
+        data: container.payload,
+    })
+}
+
+// The element in the polynomial basis, bit i the coefficient of x^i
+fn natural<GF: GaloisField>(e: GF) -> u128 {
+    if GF::NATURAL_REPR {
+        return e.to_u128();
+    }
+    e.to_coefficients()
+        .iter()
+        .rev()
+        .fold(0, |acc, &c| acc << 1 | c as u128)
+}
+
+fn from_natural<GF: GaloisField>(value: u128) -> GF {
+    if GF::NATURAL_REPR {
+        return GF::from_u128(value);
+    }
+    let coeffs: Vec<bool> = (0..GF::M).map(|i| value >> i & 1 == 1).collect();
+    GF::from_coefficients(&coeffs)
+}
+
+fn element_len<GF: GaloisField>() -> usize {
+    GF::M.div_ceil(8) as usize
+}
+
+fn element_bytes<GF: GaloisField>(elements: &[GF]) -> Vec<u8> {
+    let len = element_len::<GF>();
+    elements
+        .iter()
+        .flat_map(|&e| natural(e).to_le_bytes().into_iter().take(len))
+        .collect()
+}
+
+fn elements_from_bytes<GF: GaloisField>(bytes: &[u8]) -> Result<Vec<GF>, WireError> {
+    let len = element_len::<GF>();
+    if !bytes.len().is_multiple_of(len) {
+        return Err(WireError::InvalidPayload);
+    }
+    bytes
+        .chunks_exact(len)
+        .map(|chunk| {
+            let mut value = [0u8; 16];
+            value[..len].copy_from_slice(chunk);
+            let value = u128::from_le_bytes(value);
+            if value >= GF::NUM_ELEM {
+                return Err(WireError::InvalidPayload);
+            }
+            Ok(from_natural(value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erasure::{ErasureCode, Raid6, Raid6Field};
+    use crate::{gf2, gf2_lut, gf2_rev};
+    use rand::Rng;
+
+    fn random<GF: GaloisField>(len: usize) -> Vec<GF> {
+        (0..len)
+            .map(|_| GF::from_u128(rand::thread_rng().gen::<u128>() % GF::NUM_ELEM))
+            .collect()
+    }
+
+    #[test]
+    fn layout() {
+        type GF = gf2::GFu16<0x1_100B>;
+        let bytes = encode_elements(&[GF::new(0x1234), GF::new(0xABCD)]);
+        let mut expected = b"GF2M".to_vec();
+        expected.extend_from_slice(&[1, 1, 16, 0]);
+        expected.extend_from_slice(&0x1_100Bu128.to_le_bytes());
+        expected.extend_from_slice(&4u64.to_le_bytes());
+        expected.extend_from_slice(&[0x34, 0x12, 0xCD, 0xAB]);
+        let crc = Crc::new(CRC_32).checksum(&expected);
+        expected.extend_from_slice(&crc.to_le_bytes());
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn elements() {
+        fn check<GF: GaloisField>() {
+            for len in [0, 1, 17] {
+                let elements = random::<GF>(len);
+                assert_eq!(
+                    parse_elements::<GF>(&encode_elements(&elements)).unwrap(),
+                    elements
+                );
+            }
+        }
+        check::<gf2::GFu8<0x13>>();
+        check::<gf2::GFu16<0x1_002D>>();
+        check::<gf2_lut::GFu8<0x11D>>();
+        check::<gf2::GFu32<0x1_0040_0007>>();
+        check::<gf2::GFu128<0x87>>();
+    }
+
+    #[test]
+    fn representation_independent() {
+        // The reflected types write the same bytes as the natural ones
+        let natural = random::<gf2::GFu8<0x11B>>(20);
+        let reflected: Vec<gf2_rev::GFu8<0x11B>> = natural.iter().map(|&e| e.into()).collect();
+        let bytes = encode_elements(&reflected);
+        assert_eq!(bytes, encode_elements(&natural));
+        assert_eq!(
+            parse_elements::<gf2_rev::GFu8<0x11B>>(&bytes).unwrap(),
+            reflected
+        );
+    }
+
+    #[test]
+    fn polynomial() {
+        type GF = gf2_lut::GFu8<0x11D>;
+        let poly = Polynomial::new(random::<GF>(9));
+        assert_eq!(
+            parse_polynomial::<GF>(&encode_polynomial(&poly)).unwrap(),
+            poly
+        );
+        assert_eq!(
+            parse_polynomial::<GF>(&encode_elements(poly.coeffs())).unwrap_err(),
+            WireError::WrongKind(Kind::Elements)
+        );
+    }
+
+    #[test]
+    fn codeword() {
+        type GF = gf2::GFu16<0x1_002D>;
+        let beta = GF::new(2).pow(3);
+        let rs = ReedSolomon::builder(40, 30)
+            .first_root(1)
+            .generator_element(beta)
+            .build();
+        let codeword = rs.encode(&random::<GF>(30));
+        let (parsed, word) = parse_codeword::<GF>(&encode_codeword(&rs, &codeword)).unwrap();
+        assert_eq!(word, codeword);
+        assert_eq!((parsed.n(), parsed.k(), parsed.first_root()), (40, 30, 1));
+        assert_eq!(parsed.generator_element(), beta);
+        assert_eq!(parsed.generator_poly(), rs.generator_poly());
+    }
+
+    #[test]
+    fn shard() {
+        let code = Raid6::new(4);
+        let data: Vec<Vec<u8>> = (0..4).map(|i| vec![i as u8; 10]).collect();
+        let refs: Vec<&[u8]> = data.iter().map(|d| d.as_slice()).collect();
+        let q = code.encode_shards(&refs).pop().unwrap();
+        let bytes = encode_shard::<Raid6Field>(6, 4, 5, &q);
+        let shard = parse_shard::<Raid6Field>(&bytes).unwrap();
+        assert_eq!((shard.n, shard.k, shard.index), (6, 4, 5));
+        assert_eq!(shard.data, q);
+    }
+
+    #[test]
+    fn errors() {
+        type GF = gf2::GFu8<0x11D>;
+        let bytes = encode_elements(&random::<GF>(5));
+        assert!(Container::parse(&bytes).is_ok());
+        for end in 0..bytes.len() {
+            assert_eq!(
+                Container::parse(&bytes[..end]).unwrap_err(),
+                WireError::Truncated
+            );
+        }
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(Container::parse(&trailing).is_ok());
+
+        let corrupt = |at: usize, value: u8| {
+            let mut b = bytes.clone();
+            b[at] = value;
+            Container::parse(&b).unwrap_err()
+        };
+        assert_eq!(corrupt(0, b'X'), WireError::BadMagic);
+        assert_eq!(corrupt(4, 0), WireError::UnsupportedVersion(0));
+        assert_eq!(corrupt(4, 2), WireError::UnsupportedVersion(2));
+        assert_eq!(corrupt(5, 9), WireError::UnknownKind(9));
+        assert_eq!(corrupt(35, 0), WireError::Checksum);
+
+        assert_eq!(
+            parse_elements::<gf2::GFu8<0x11B>>(&bytes).unwrap_err(),
+            WireError::FieldMismatch
+        );
+        assert_eq!(
+            parse_elements::<gf2::GFu16<0x1_002D>>(&bytes).unwrap_err(),
+            WireError::FieldMismatch
+        );
+
+        // Values of M bits or more are not elements
+        let too_wide = Container {
+            kind: Kind::Elements,
+            m: 4,
+            poly: 0x13,
+            params: Vec::new(),
+            payload: vec![0x10],
+        };
+        assert_eq!(
+            parse_elements::<gf2::GFu8<0x13>>(&too_wide.to_bytes()).unwrap_err(),
+            WireError::InvalidPayload
+        );
+    }
+
+    #[test]
+    fn hostile_lengths() {
+        type GF = gf2::GFu8<0x11D>;
+        let bytes = encode_elements(&random::<GF>(5));
+        let with_length = |length: u64| {
+            let mut b = bytes.clone();
+            b[HEADER_LEN..HEADER_LEN + LENGTH_LEN].copy_from_slice(&length.to_le_bytes());
+            Container::parse(&b).unwrap_err()
+        };
+        // Lengths past the end, including those that overflow the offsets of the payload and the CRC
+        let end = (bytes.len() - HEADER_LEN - LENGTH_LEN) as u64;
+        for length in [
+            end - CRC_LEN as u64 + 1,
+            end,
+            u64::MAX,
+            u64::MAX - 3,
+            u64::MAX - 40,
+        ] {
+            assert_eq!(with_length(length), WireError::Truncated);
+        }
+        for _ in 0..1000 {
+            let length = rand::thread_rng().gen::<u64>() >> rand::thread_rng().gen_range(0..64);
+            if length != 5 {
+                let error = with_length(length);
+                assert!(
+                    error == WireError::Truncated || error == WireError::Checksum,
+                    "{length}"
+                );
+            }
+        }
+
+        // Random bytes after a valid header never panic
+        for _ in 0..1000 {
+            let mut b = bytes[..HEADER_LEN].to_vec();
+            let len = rand::thread_rng().gen_range(0..64);
+            b.extend((0..len).map(|_| rand::thread_rng().gen::<u8>()));
+            let _ = Container::parse(&b);
+        }
+    }
+
+    #[test]
+    fn oversized_codeword() {
+        // n fits the field but not the payload, which is rejected before the generator order is checked in n steps
+        type GF = gf2::GFu32<0x1_0040_0007>;
+        let container = |n: u128| Container {
+            kind: Kind::Codeword,
+            m: 32,
+            poly: GF::POLY,
+            params: vec![n, 1, 0, 2],
+            payload: element_bytes(&random::<GF>(10)),
+        };
+        for n in [11, 0xFFFF_FFFE, GF::NUM_ELEM, u128::MAX] {
+            assert_eq!(
+                parse_codeword::<GF>(&container(n).to_bytes()).unwrap_err(),
+                WireError::InvalidPayload
+            );
+        }
+        assert!(parse_codeword::<GF>(&container(10).to_bytes()).is_ok());
+    }
+}